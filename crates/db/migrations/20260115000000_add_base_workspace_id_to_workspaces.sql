@@ -0,0 +1,4 @@
+-- Stacked workspaces: a workspace can be based on another workspace's branch.
+-- Children are rebased/retargeted when the base workspace moves or is merged.
+ALTER TABLE workspaces ADD COLUMN base_workspace_id BLOB REFERENCES workspaces(id) ON DELETE SET NULL;
+CREATE INDEX idx_workspaces_base_workspace_id ON workspaces(base_workspace_id);
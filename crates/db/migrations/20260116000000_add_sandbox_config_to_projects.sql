@@ -0,0 +1,2 @@
+-- Per-project process sandbox settings (JSON-encoded SandboxConfig)
+ALTER TABLE projects ADD COLUMN sandbox_config TEXT;
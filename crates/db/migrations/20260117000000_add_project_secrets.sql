@@ -0,0 +1,13 @@
+-- Per-project secrets, encrypted at rest (AES-256-GCM, base64 nonce||ciphertext).
+-- Values are injected into execution environments and never returned by the API.
+CREATE TABLE project_secrets (
+    id              BLOB PRIMARY KEY,
+    project_id      BLOB NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    name            TEXT NOT NULL,
+    encrypted_value TEXT NOT NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    UNIQUE (project_id, name)
+);
+
+CREATE INDEX idx_project_secrets_project_id ON project_secrets(project_id);
@@ -0,0 +1,12 @@
+-- Disk usage and pinning for workspace worktrees, used by the cleanup policies.
+-- Sizes are in bytes and refreshed by the periodic cleanup run.
+CREATE TABLE workspace_storage (
+    workspace_id     BLOB PRIMARY KEY REFERENCES workspaces(id) ON DELETE CASCADE,
+    pinned           BOOLEAN NOT NULL DEFAULT FALSE,
+    total_bytes      INTEGER,
+    dependency_bytes INTEGER,
+    build_bytes      INTEGER,
+    measured_at      TEXT,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
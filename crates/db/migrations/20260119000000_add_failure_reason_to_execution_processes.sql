@@ -0,0 +1,3 @@
+-- Why an execution process failed, when it was stopped for a known reason
+-- rather than exiting with an error on its own (e.g. by the watchdog).
+ALTER TABLE execution_processes ADD COLUMN failure_reason TEXT;
@@ -0,0 +1,3 @@
+-- Forked sessions: a session can continue the conversation of another
+-- session as of one of its coding agent turns, in a new workspace.
+ALTER TABLE sessions ADD COLUMN forked_from_process_id BLOB REFERENCES execution_processes(id) ON DELETE SET NULL;
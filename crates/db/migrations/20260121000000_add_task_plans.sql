@@ -0,0 +1,18 @@
+-- Plan-first attempts: a read-only planning turn produces a plan that must be
+-- approved, and may be edited, before the implementation turn starts.
+CREATE TABLE task_plans (
+    id                   BLOB PRIMARY KEY,
+    task_id              BLOB NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    workspace_id         BLOB NOT NULL UNIQUE REFERENCES workspaces(id) ON DELETE CASCADE,
+    execution_process_id BLOB REFERENCES execution_processes(id) ON DELETE SET NULL,
+    content              TEXT NOT NULL DEFAULT '',
+    status               TEXT NOT NULL DEFAULT 'planning'
+                            CHECK (status IN ('planning', 'pending_approval', 'approved')),
+    approval_id          TEXT,
+    approved_at          TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE INDEX idx_task_plans_task_id ON task_plans(task_id);
+CREATE INDEX idx_task_plans_approval_id ON task_plans(approval_id);
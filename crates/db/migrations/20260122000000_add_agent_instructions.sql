@@ -0,0 +1,3 @@
+-- Instructions given to every coding agent run in the project, whichever executor runs it
+ALTER TABLE projects ADD COLUMN agent_instructions TEXT;
+ALTER TABLE project_repos ADD COLUMN agent_instructions TEXT;
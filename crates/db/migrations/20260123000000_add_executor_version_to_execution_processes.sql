@@ -0,0 +1,3 @@
+-- Version of the coding agent CLI that ran the process, so log normalization
+-- issues can be traced back to CLI releases.
+ALTER TABLE execution_processes ADD COLUMN executor_version TEXT;
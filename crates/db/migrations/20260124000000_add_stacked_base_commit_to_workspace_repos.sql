@@ -0,0 +1,4 @@
+-- Commit of the base workspace's branch a stacked workspace was last rebased
+-- onto, so rebases after the base branch is rewritten only replay the child's
+-- own commits.
+ALTER TABLE workspace_repos ADD COLUMN stacked_base_commit TEXT;
@@ -0,0 +1,3 @@
+-- Task status last reported to the workspace a sub-task was created from, so
+-- each status is only reported once.
+ALTER TABLE workspaces ADD COLUMN parent_report_status TEXT;
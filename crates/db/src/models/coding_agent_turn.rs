@@ -45,6 +45,26 @@ impl CodingAgentTurn {
         .await
     }
 
+    /// Latest non-empty summary produced by a coding agent in a workspace
+    pub async fn find_latest_summary_for_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let summary = sqlx::query_scalar!(
+            r#"SELECT cat.summary as "summary!"
+               FROM coding_agent_turns cat
+               JOIN execution_processes ep ON ep.id = cat.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               WHERE s.workspace_id = $1 AND cat.summary IS NOT NULL
+               ORDER BY cat.created_at DESC
+               LIMIT 1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(summary)
+    }
+
     pub async fn find_by_agent_session_id(
         pool: &SqlitePool,
         agent_session_id: &str,
@@ -57,6 +57,40 @@ pub enum ExecutionProcessRunReason {
     DevServer,
 }
 
+/// Why a process failed, when the cause is known
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, TS)]
+#[sqlx(
+    type_name = "execution_process_failure_reason",
+    rename_all = "snake_case"
+)]
+#[serde(rename_all = "snake_case")]
+#[ts(use_ts_enum)]
+pub enum ExecutionProcessFailureReason {
+    /// Ran longer than the profile's maximum duration
+    TimeLimitExceeded,
+    /// Produced no output for longer than the profile's idle timeout
+    Stalled,
+    /// Repeated the same tool call more often than the profile allows
+    RepeatedToolCalls,
+    /// The provider rejected requests over its rate limit
+    RateLimited,
+    /// The provider was too busy to answer
+    Overloaded,
+    /// The agent's credentials were invalid or expired
+    AuthExpired,
+    /// The agent could not reach the provider
+    Network,
+    /// The conversation no longer fits in the model's context window
+    ContextLengthExceeded,
+}
+
+impl ExecutionProcessFailureReason {
+    /// Whether the run is retried automatically after this failure
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::RateLimited | Self::Overloaded | Self::Network)
+    }
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct ExecutionProcess {
     pub id: Uuid,
@@ -66,6 +100,9 @@ pub struct ExecutionProcess {
     pub executor_action: sqlx::types::Json<ExecutorActionField>,
     pub status: ExecutionProcessStatus,
     pub exit_code: Option<i64>,
+    pub failure_reason: Option<ExecutionProcessFailureReason>,
+    /// Version of the coding agent CLI, detected when the process started
+    pub executor_version: Option<String>,
     /// dropped: true if this process is excluded from the current
     /// history view (due to restore/trimming). Hidden from logs/timeline;
     /// still listed in the Processes tab.
@@ -91,7 +128,7 @@ pub struct UpdateExecutionProcess {
     pub completed_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExecutionContext {
     pub execution_process: ExecutionProcess,
     pub session: Session,
@@ -131,6 +168,8 @@ impl ExecutionProcess {
                     ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                     ep.status as "status!: ExecutionProcessStatus",
                     ep.exit_code,
+                    ep.failure_reason as "failure_reason: ExecutionProcessFailureReason",
+                    ep.executor_version,
                     ep.dropped as "dropped!: bool",
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
@@ -205,6 +244,8 @@ impl ExecutionProcess {
                     ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                     ep.status as "status!: ExecutionProcessStatus",
                     ep.exit_code,
+                    ep.failure_reason as "failure_reason: ExecutionProcessFailureReason",
+                    ep.executor_version,
                     ep.dropped as "dropped!: bool",
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
@@ -232,6 +273,8 @@ impl ExecutionProcess {
                       ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                       ep.status          as "status!: ExecutionProcessStatus",
                       ep.exit_code,
+                      ep.failure_reason as "failure_reason: ExecutionProcessFailureReason",
+                      ep.executor_version,
                       ep.dropped as "dropped!: bool",
                       ep.started_at      as "started_at!: DateTime<Utc>",
                       ep.completed_at    as "completed_at?: DateTime<Utc>",
@@ -259,6 +302,8 @@ impl ExecutionProcess {
                     ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                     ep.status as "status!: ExecutionProcessStatus",
                     ep.exit_code,
+                    ep.failure_reason as "failure_reason: ExecutionProcessFailureReason",
+                    ep.executor_version,
                     ep.dropped as "dropped!: bool",
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
@@ -279,6 +324,8 @@ impl ExecutionProcess {
             ExecutionProcess,
             r#"SELECT ep.id as "id!: Uuid", ep.session_id as "session_id!: Uuid", ep.run_reason as "run_reason!: ExecutionProcessRunReason", ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                       ep.status as "status!: ExecutionProcessStatus", ep.exit_code,
+                      ep.failure_reason as "failure_reason: ExecutionProcessFailureReason",
+                      ep.executor_version,
                       ep.dropped as "dropped!: bool", ep.started_at as "started_at!: DateTime<Utc>", ep.completed_at as "completed_at?: DateTime<Utc>", ep.created_at as "created_at!: DateTime<Utc>", ep.updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep
                JOIN sessions s ON ep.session_id = s.id
@@ -326,6 +373,8 @@ impl ExecutionProcess {
             ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
             ep.status as "status!: ExecutionProcessStatus",
             ep.exit_code,
+            ep.failure_reason as "failure_reason: ExecutionProcessFailureReason",
+            ep.executor_version,
             ep.dropped as "dropped!: bool",
             ep.started_at as "started_at!: DateTime<Utc>",
             ep.completed_at as "completed_at?: DateTime<Utc>",
@@ -370,7 +419,43 @@ impl ExecutionProcess {
 
         tracing::info!("Latest coding agent turn session id: {:?}", row);
 
-        Ok(row.and_then(|r| r.agent_session_id))
+        if let Some(agent_session_id) = row.and_then(|r| r.agent_session_id) {
+            return Ok(Some(agent_session_id));
+        }
+
+        // A forked session continues the agent session of the turn it was forked from
+        let forked_agent_session_id = sqlx::query_scalar!(
+            r#"SELECT cat.agent_session_id
+               FROM sessions s
+               JOIN coding_agent_turns cat ON cat.execution_process_id = s.forked_from_process_id
+               WHERE s.id = $1"#,
+            session_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(forked_agent_session_id.flatten())
+    }
+
+    /// Coding agent turn a forked session was forked from
+    async fn find_fork_point(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let forked_from_process_id = sqlx::query_scalar!(
+            r#"SELECT forked_from_process_id AS "forked_from_process_id: Uuid"
+               FROM sessions
+               WHERE id = $1"#,
+            session_id
+        )
+        .fetch_optional(pool)
+        .await?
+        .flatten();
+
+        match forked_from_process_id {
+            Some(process_id) => Self::find_by_id(pool, process_id).await,
+            None => Ok(None),
+        }
     }
 
     /// Find latest execution process by session and run reason
@@ -388,6 +473,8 @@ impl ExecutionProcess {
                     ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                     ep.status as "status!: ExecutionProcessStatus",
                     ep.exit_code,
+                    ep.failure_reason as "failure_reason: ExecutionProcessFailureReason",
+                    ep.executor_version,
                     ep.dropped as "dropped!: bool",
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
@@ -418,6 +505,8 @@ impl ExecutionProcess {
                     ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                     ep.status as "status!: ExecutionProcessStatus",
                     ep.exit_code,
+                    ep.failure_reason as "failure_reason: ExecutionProcessFailureReason",
+                    ep.executor_version,
                     ep.dropped as "dropped!: bool",
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
@@ -516,6 +605,42 @@ impl ExecutionProcess {
         Ok(())
     }
 
+    pub async fn set_executor_version(
+        pool: &SqlitePool,
+        id: Uuid,
+        executor_version: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE execution_processes
+               SET executor_version = $1
+               WHERE id = $2"#,
+            executor_version,
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_failure_reason(
+        pool: &SqlitePool,
+        id: Uuid,
+        failure_reason: ExecutionProcessFailureReason,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE execution_processes
+               SET failure_reason = $1
+               WHERE id = $2"#,
+            failure_reason,
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub fn executor_action(&self) -> Result<&ExecutorAction, anyhow::Error> {
         match &self.executor_action.0 {
             ExecutorActionField::ExecutorAction(action) => Ok(action),
@@ -571,6 +696,26 @@ impl ExecutionProcess {
         Ok(result.flatten())
     }
 
+    /// Commit a fork of this turn starts the repository from: where the turn
+    /// left it, or where the session's previous turn did if nothing was recorded
+    pub async fn fork_commit(
+        &self,
+        pool: &SqlitePool,
+        repo_id: Uuid,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let recorded = ExecutionProcessRepoState::find_by_execution_process_id(pool, self.id)
+            .await?
+            .into_iter()
+            .find(|state| state.repo_id == repo_id)
+            .and_then(|state| state.after_head_commit.or(state.before_head_commit));
+        match recorded {
+            Some(commit) => Ok(Some(commit)),
+            None => {
+                Self::find_prev_after_head_commit(pool, self.session_id, self.id, repo_id).await
+            }
+        }
+    }
+
     /// Get the parent Session for this execution process
     pub async fn parent_session(&self, pool: &SqlitePool) -> Result<Option<Session>, sqlx::Error> {
         Session::find_by_id(pool, self.session_id).await
@@ -644,6 +789,8 @@ impl ExecutionProcess {
                     ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                     ep.status as "status!: ExecutionProcessStatus",
                     ep.exit_code,
+                    ep.failure_reason as "failure_reason: ExecutionProcessFailureReason",
+                    ep.executor_version,
                     ep.dropped as "dropped!: bool",
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
@@ -656,7 +803,14 @@ impl ExecutionProcess {
             ExecutionProcessRunReason::CodingAgent
         )
         .fetch_optional(pool)
-        .await?
+        .await?;
+
+        // A forked session uses the profile of the turn it was forked from until
+        // it has run a turn of its own
+        let latest_execution_process = match latest_execution_process {
+            Some(process) => Some(process),
+            None => Self::find_fork_point(pool, session_id).await?,
+        }
         .ok_or_else(|| {
             ExecutionProcessError::ValidationError(
                 "Couldn't find initial coding agent process, has it run yet?".to_string(),
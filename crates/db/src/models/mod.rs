@@ -6,10 +6,13 @@ pub mod image;
 pub mod merge;
 pub mod project;
 pub mod project_repo;
+pub mod project_secret;
 pub mod repo;
 pub mod scratch;
 pub mod session;
 pub mod tag;
 pub mod task;
+pub mod task_plan;
 pub mod workspace;
 pub mod workspace_repo;
+pub mod workspace_storage;
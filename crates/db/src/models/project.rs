@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
+use executors::sandbox::SandboxConfig;
 use serde::{Deserialize, Serialize};
-use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, types::Json};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
@@ -25,6 +26,10 @@ pub struct Project {
     pub dev_script_working_dir: Option<String>,
     pub default_agent_working_dir: Option<String>,
     pub remote_project_id: Option<Uuid>,
+    #[ts(type = "SandboxConfig | null")]
+    pub sandbox_config: Option<Json<SandboxConfig>>,
+    /// Instructions given to every coding agent run in the project
+    pub agent_instructions: Option<String>,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -43,6 +48,14 @@ pub struct UpdateProject {
     pub dev_script: Option<String>,
     pub dev_script_working_dir: Option<String>,
     pub default_agent_working_dir: Option<String>,
+    /// Leaves the existing sandbox settings untouched when omitted
+    #[serde(default)]
+    #[ts(optional)]
+    pub sandbox_config: Option<SandboxConfig>,
+    /// Leaves the existing instructions untouched when omitted; empty clears them
+    #[serde(default)]
+    #[ts(optional)]
+    pub agent_instructions: Option<String>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -75,6 +88,8 @@ impl Project {
                       dev_script_working_dir,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      sandbox_config as "sandbox_config: Json<SandboxConfig>",
+                      agent_instructions,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -92,6 +107,8 @@ impl Project {
             SELECT p.id as "id!: Uuid", p.name, p.dev_script, p.dev_script_working_dir,
                    p.default_agent_working_dir,
                    p.remote_project_id as "remote_project_id: Uuid",
+                   p.sandbox_config as "sandbox_config: Json<SandboxConfig>",
+                   p.agent_instructions,
                    p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>"
             FROM projects p
             WHERE p.id IN (
@@ -117,6 +134,8 @@ impl Project {
                       dev_script_working_dir,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      sandbox_config as "sandbox_config: Json<SandboxConfig>",
+                      agent_instructions,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -136,6 +155,8 @@ impl Project {
                       dev_script_working_dir,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      sandbox_config as "sandbox_config: Json<SandboxConfig>",
+                      agent_instructions,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -158,6 +179,8 @@ impl Project {
                       dev_script_working_dir,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      sandbox_config as "sandbox_config: Json<SandboxConfig>",
+                      agent_instructions,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -188,6 +211,8 @@ impl Project {
                           dev_script_working_dir,
                           default_agent_working_dir,
                           remote_project_id as "remote_project_id: Uuid",
+                          sandbox_config as "sandbox_config: Json<SandboxConfig>",
+                          agent_instructions,
                           created_at as "created_at!: DateTime<Utc>",
                           updated_at as "updated_at!: DateTime<Utc>""#,
             project_id,
@@ -210,11 +235,21 @@ impl Project {
         let dev_script = payload.dev_script.clone();
         let dev_script_working_dir = payload.dev_script_working_dir.clone();
         let default_agent_working_dir = payload.default_agent_working_dir.clone();
+        let sandbox_config = payload
+            .sandbox_config
+            .clone()
+            .map(Json)
+            .or(existing.sandbox_config);
+        let agent_instructions = match &payload.agent_instructions {
+            Some(instructions) => Some(instructions.trim().to_string()).filter(|s| !s.is_empty()),
+            None => existing.agent_instructions,
+        };
 
         sqlx::query_as!(
             Project,
             r#"UPDATE projects
-               SET name = $2, dev_script = $3, dev_script_working_dir = $4, default_agent_working_dir = $5
+               SET name = $2, dev_script = $3, dev_script_working_dir = $4, default_agent_working_dir = $5, sandbox_config = $6,
+                   agent_instructions = $7
                WHERE id = $1
                RETURNING id as "id!: Uuid",
                          name,
@@ -222,6 +257,8 @@ impl Project {
                          dev_script_working_dir,
                          default_agent_working_dir,
                          remote_project_id as "remote_project_id: Uuid",
+                         sandbox_config as "sandbox_config: Json<SandboxConfig>",
+                         agent_instructions,
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -229,6 +266,8 @@ impl Project {
             dev_script,
             dev_script_working_dir,
             default_agent_working_dir,
+            sandbox_config,
+            agent_instructions,
         )
         .fetch_one(pool)
         .await
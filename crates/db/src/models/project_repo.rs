@@ -28,6 +28,8 @@ pub struct ProjectRepo {
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
     pub parallel_setup_script: bool,
+    /// Instructions for coding agents working in this repository
+    pub agent_instructions: Option<String>,
 }
 
 /// ProjectRepo with the associated repo name (for script execution in worktrees)
@@ -41,6 +43,8 @@ pub struct ProjectRepoWithName {
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
     pub parallel_setup_script: bool,
+    /// Instructions for coding agents working in this repository
+    pub agent_instructions: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, TS)]
@@ -56,6 +60,10 @@ pub struct UpdateProjectRepo {
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
     pub parallel_setup_script: Option<bool>,
+    /// Leaves the existing instructions untouched when omitted; empty clears them
+    #[serde(default)]
+    #[ts(optional)]
+    pub agent_instructions: Option<String>,
 }
 
 impl ProjectRepo {
@@ -71,7 +79,8 @@ impl ProjectRepo {
                       setup_script,
                       cleanup_script,
                       copy_files,
-                      parallel_setup_script as "parallel_setup_script!: bool"
+                      parallel_setup_script as "parallel_setup_script!: bool",
+                      agent_instructions
                FROM project_repos
                WHERE project_id = $1"#,
             project_id
@@ -92,7 +101,8 @@ impl ProjectRepo {
                       setup_script,
                       cleanup_script,
                       copy_files,
-                      parallel_setup_script as "parallel_setup_script!: bool"
+                      parallel_setup_script as "parallel_setup_script!: bool",
+                      agent_instructions
                FROM project_repos
                WHERE repo_id = $1"#,
             repo_id
@@ -114,7 +124,8 @@ impl ProjectRepo {
                       pr.setup_script,
                       pr.cleanup_script,
                       pr.copy_files,
-                      pr.parallel_setup_script as "parallel_setup_script!: bool"
+                      pr.parallel_setup_script as "parallel_setup_script!: bool",
+                      pr.agent_instructions
                FROM project_repos pr
                JOIN repos r ON r.id = pr.repo_id
                WHERE pr.project_id = $1
@@ -160,7 +171,8 @@ impl ProjectRepo {
                       setup_script,
                       cleanup_script,
                       copy_files,
-                      parallel_setup_script as "parallel_setup_script!: bool"
+                      parallel_setup_script as "parallel_setup_script!: bool",
+                      agent_instructions
                FROM project_repos
                WHERE project_id = $1 AND repo_id = $2"#,
             project_id,
@@ -235,7 +247,8 @@ impl ProjectRepo {
                          setup_script,
                          cleanup_script,
                          copy_files,
-                         parallel_setup_script as "parallel_setup_script!: bool""#,
+                         parallel_setup_script as "parallel_setup_script!: bool",
+                         agent_instructions"#,
             id,
             project_id,
             repo_id
@@ -259,6 +272,10 @@ impl ProjectRepo {
         let parallel_setup_script = payload
             .parallel_setup_script
             .unwrap_or(existing.parallel_setup_script);
+        let agent_instructions = match &payload.agent_instructions {
+            Some(instructions) => Some(instructions.trim().to_string()).filter(|s| !s.is_empty()),
+            None => existing.agent_instructions,
+        };
 
         sqlx::query_as!(
             ProjectRepo,
@@ -266,19 +283,22 @@ impl ProjectRepo {
                SET setup_script = $1,
                    cleanup_script = $2,
                    copy_files = $3,
-                   parallel_setup_script = $4
-               WHERE project_id = $5 AND repo_id = $6
+                   parallel_setup_script = $4,
+                   agent_instructions = $5
+               WHERE project_id = $6 AND repo_id = $7
                RETURNING id as "id!: Uuid",
                          project_id as "project_id!: Uuid",
                          repo_id as "repo_id!: Uuid",
                          setup_script,
                          cleanup_script,
                          copy_files,
-                         parallel_setup_script as "parallel_setup_script!: bool""#,
+                         parallel_setup_script as "parallel_setup_script!: bool",
+                         agent_instructions"#,
             setup_script,
             cleanup_script,
             copy_files,
             parallel_setup_script,
+            agent_instructions,
             project_id,
             repo_id
         )
@@ -20,6 +20,8 @@ pub struct Session {
     pub id: Uuid,
     pub workspace_id: Uuid,
     pub executor: Option<String>,
+    /// Coding agent turn this session's conversation was forked from
+    pub forked_from_process_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -36,6 +38,7 @@ impl Session {
             r#"SELECT id AS "id!: Uuid",
                       workspace_id AS "workspace_id!: Uuid",
                       executor,
+                      forked_from_process_id AS "forked_from_process_id: Uuid",
                       created_at AS "created_at!: DateTime<Utc>",
                       updated_at AS "updated_at!: DateTime<Utc>"
                FROM sessions
@@ -55,6 +58,7 @@ impl Session {
             r#"SELECT id AS "id!: Uuid",
                       workspace_id AS "workspace_id!: Uuid",
                       executor,
+                      forked_from_process_id AS "forked_from_process_id: Uuid",
                       created_at AS "created_at!: DateTime<Utc>",
                       updated_at AS "updated_at!: DateTime<Utc>"
                FROM sessions
@@ -76,6 +80,7 @@ impl Session {
             r#"SELECT id AS "id!: Uuid",
                       workspace_id AS "workspace_id!: Uuid",
                       executor,
+                      forked_from_process_id AS "forked_from_process_id: Uuid",
                       created_at AS "created_at!: DateTime<Utc>",
                       updated_at AS "updated_at!: DateTime<Utc>"
                FROM sessions
@@ -101,6 +106,7 @@ impl Session {
                RETURNING id AS "id!: Uuid",
                          workspace_id AS "workspace_id!: Uuid",
                          executor,
+                         forked_from_process_id AS "forked_from_process_id: Uuid",
                          created_at AS "created_at!: DateTime<Utc>",
                          updated_at AS "updated_at!: DateTime<Utc>""#,
             id,
@@ -110,4 +116,32 @@ impl Session {
         .fetch_one(pool)
         .await?)
     }
+
+    /// Create a session that continues the conversation of another session as
+    /// of the given coding agent turn
+    pub async fn create_fork(
+        pool: &SqlitePool,
+        data: &CreateSession,
+        id: Uuid,
+        workspace_id: Uuid,
+        forked_from_process_id: Uuid,
+    ) -> Result<Self, SessionError> {
+        Ok(sqlx::query_as!(
+            Session,
+            r#"INSERT INTO sessions (id, workspace_id, executor, forked_from_process_id)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id AS "id!: Uuid",
+                         workspace_id AS "workspace_id!: Uuid",
+                         executor,
+                         forked_from_process_id AS "forked_from_process_id: Uuid",
+                         created_at AS "created_at!: DateTime<Utc>",
+                         updated_at AS "updated_at!: DateTime<Utc>""#,
+            id,
+            workspace_id,
+            data.executor,
+            forked_from_process_id
+        )
+        .fetch_one(pool)
+        .await?)
+    }
 }
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "task_plan_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TaskPlanStatus {
+    /// The agent is working out the plan, without changing any files
+    Planning,
+    /// The plan is waiting for a human to approve it
+    PendingApproval,
+    /// The plan was approved and handed to the implementation turn
+    Approved,
+}
+
+/// Plan produced by the planning turn of a plan-first attempt
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskPlan {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub workspace_id: Uuid,
+    /// Planning turn that produced the current content
+    pub execution_process_id: Option<Uuid>,
+    pub content: String,
+    pub status: TaskPlanStatus,
+    /// Approval the plan is waiting on, answered through the approvals API
+    pub approval_id: Option<String>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateTaskPlan {
+    pub content: String,
+}
+
+impl TaskPlan {
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        workspace_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskPlan,
+            r#"INSERT INTO task_plans (id, task_id, workspace_id)
+               VALUES ($1, $2, $3)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         execution_process_id as "execution_process_id: Uuid",
+                         content,
+                         status as "status!: TaskPlanStatus",
+                         approval_id,
+                         approved_at as "approved_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            workspace_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskPlan,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      execution_process_id as "execution_process_id: Uuid",
+                      content,
+                      status as "status!: TaskPlanStatus",
+                      approval_id,
+                      approved_at as "approved_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_plans
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_approval_id(
+        pool: &SqlitePool,
+        approval_id: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskPlan,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      execution_process_id as "execution_process_id: Uuid",
+                      content,
+                      status as "status!: TaskPlanStatus",
+                      approval_id,
+                      approved_at as "approved_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_plans
+               WHERE approval_id = $1"#,
+            approval_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Record the plan produced by a planning turn and wait for its approval
+    pub async fn submit(
+        pool: &SqlitePool,
+        id: Uuid,
+        execution_process_id: Uuid,
+        content: &str,
+        approval_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        let status = TaskPlanStatus::PendingApproval;
+        sqlx::query!(
+            r#"UPDATE task_plans
+               SET execution_process_id = $2, content = $3, status = $4, approval_id = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            execution_process_id,
+            content,
+            status,
+            approval_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Replace the approval a pending plan waits on, e.g. after a restart
+    pub async fn update_approval_id(
+        pool: &SqlitePool,
+        id: Uuid,
+        approval_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_plans
+               SET approval_id = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            approval_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_content(
+        pool: &SqlitePool,
+        id: Uuid,
+        content: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_plans
+               SET content = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            content
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: TaskPlanStatus,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_plans
+               SET status = $2,
+                   approval_id = CASE WHEN $2 = 'pending_approval' THEN approval_id END,
+                   approved_at = CASE WHEN $2 = 'approved' THEN datetime('now', 'subsec') END,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            status
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ use uuid::Uuid;
 
 use super::{
     project::Project,
-    task::Task,
+    task::{Task, TaskStatus},
     workspace_repo::{RepoWithTargetBranch, WorkspaceRepo},
 };
 
@@ -51,6 +51,8 @@ pub struct Workspace {
     pub container_ref: Option<String>,
     pub branch: String,
     pub agent_working_dir: Option<String>,
+    /// Workspace whose branch this workspace is stacked on, if any
+    pub base_workspace_id: Option<Uuid>,
     pub setup_completed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -91,6 +93,8 @@ pub struct WorkspaceContext {
 pub struct CreateWorkspace {
     pub branch: String,
     pub agent_working_dir: Option<String>,
+    #[serde(default)]
+    pub base_workspace_id: Option<Uuid>,
 }
 
 impl Workspace {
@@ -111,6 +115,7 @@ impl Workspace {
                               container_ref,
                               branch,
                               agent_working_dir,
+                              base_workspace_id AS "base_workspace_id: Uuid",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
@@ -129,6 +134,7 @@ impl Workspace {
                               container_ref,
                               branch,
                               agent_working_dir,
+                              base_workspace_id AS "base_workspace_id: Uuid",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
@@ -157,6 +163,7 @@ impl Workspace {
                        w.container_ref,
                        w.branch,
                        w.agent_working_dir,
+                       w.base_workspace_id AS "base_workspace_id: Uuid",
                        w.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        w.created_at        AS "created_at!: DateTime<Utc>",
                        w.updated_at        AS "updated_at!: DateTime<Utc>"
@@ -243,6 +250,7 @@ impl Workspace {
                        container_ref,
                        branch,
                        agent_working_dir,
+                       base_workspace_id AS "base_workspace_id: Uuid",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
@@ -262,6 +270,7 @@ impl Workspace {
                        container_ref,
                        branch,
                        agent_working_dir,
+                       base_workspace_id AS "base_workspace_id: Uuid",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
@@ -273,6 +282,79 @@ impl Workspace {
         .await
     }
 
+    /// Find workspaces stacked directly on top of the given workspace
+    pub async fn find_stacked_children(
+        pool: &SqlitePool,
+        base_workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"SELECT  id                AS "id!: Uuid",
+                       task_id           AS "task_id!: Uuid",
+                       container_ref,
+                       branch,
+                       agent_working_dir,
+                       base_workspace_id AS "base_workspace_id: Uuid",
+                       setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       created_at        AS "created_at!: DateTime<Utc>",
+                       updated_at        AS "updated_at!: DateTime<Utc>"
+               FROM    workspaces
+               WHERE   base_workspace_id = $1
+               ORDER BY created_at ASC"#,
+            base_workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Detach a workspace from its base once the base has been merged
+    pub async fn clear_base_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE workspaces SET base_workspace_id = NULL, updated_at = datetime('now') WHERE id = ?",
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record that the sub-task status `status` is being reported to the parent
+    /// workspace. Returns false if that status was already reported.
+    pub async fn claim_parent_report(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        status: &TaskStatus,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE workspaces SET parent_report_status = $1 WHERE id = $2 AND parent_report_status IS NOT $1",
+            status,
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Undo [`Workspace::claim_parent_report`] after the report could not be
+    /// delivered, so the next attempt sends it
+    pub async fn release_parent_report(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        status: &TaskStatus,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE workspaces SET parent_report_status = NULL WHERE id = $1 AND parent_report_status = $2",
+            workspace_id,
+            status
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn container_ref_exists(
         pool: &SqlitePool,
         container_ref: &str,
@@ -287,53 +369,6 @@ impl Workspace {
         Ok(result.exists)
     }
 
-    /// Find workspaces that are expired (72+ hours since last activity) and eligible for cleanup
-    pub async fn find_expired_for_cleanup(
-        pool: &SqlitePool,
-    ) -> Result<Vec<Workspace>, sqlx::Error> {
-        sqlx::query_as!(
-            Workspace,
-            r#"
-            SELECT
-                w.id as "id!: Uuid",
-                w.task_id as "task_id!: Uuid",
-                w.container_ref,
-                w.branch as "branch!",
-                w.agent_working_dir,
-                w.setup_completed_at as "setup_completed_at: DateTime<Utc>",
-                w.created_at as "created_at!: DateTime<Utc>",
-                w.updated_at as "updated_at!: DateTime<Utc>"
-            FROM workspaces w
-            LEFT JOIN sessions s ON w.id = s.workspace_id
-            LEFT JOIN execution_processes ep ON s.id = ep.session_id AND ep.completed_at IS NOT NULL
-            WHERE w.container_ref IS NOT NULL
-                AND w.id NOT IN (
-                    SELECT DISTINCT s2.workspace_id
-                    FROM sessions s2
-                    JOIN execution_processes ep2 ON s2.id = ep2.session_id
-                    WHERE ep2.completed_at IS NULL
-                )
-            GROUP BY w.id, w.container_ref, w.updated_at
-            HAVING datetime('now', '-72 hours') > datetime(
-                MAX(
-                    CASE
-                        WHEN ep.completed_at IS NOT NULL THEN ep.completed_at
-                        ELSE w.updated_at
-                    END
-                )
-            )
-            ORDER BY MAX(
-                CASE
-                    WHEN ep.completed_at IS NOT NULL THEN ep.completed_at
-                    ELSE w.updated_at
-                END
-            ) ASC
-            "#
-        )
-        .fetch_all(pool)
-        .await
-    }
-
     pub async fn create(
         pool: &SqlitePool,
         data: &CreateWorkspace,
@@ -342,20 +377,28 @@ impl Workspace {
     ) -> Result<Self, WorkspaceError> {
         Ok(sqlx::query_as!(
             Workspace,
-            r#"INSERT INTO workspaces (id, task_id, container_ref, branch, agent_working_dir, setup_completed_at)
-               VALUES ($1, $2, $3, $4, $5, $6)
-               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, agent_working_dir, setup_completed_at as "setup_completed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO workspaces (id, task_id, container_ref, branch, agent_working_dir, base_workspace_id, setup_completed_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, agent_working_dir, base_workspace_id as "base_workspace_id: Uuid", setup_completed_at as "setup_completed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             task_id,
             Option::<String>::None,
             data.branch,
             data.agent_working_dir,
+            data.base_workspace_id,
             Option::<DateTime<Utc>>::None
         )
         .fetch_one(pool)
         .await?)
     }
 
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM workspaces WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn update_branch_name(
         pool: &SqlitePool,
         workspace_id: Uuid,
@@ -194,6 +194,40 @@ impl WorkspaceRepo {
         Ok(())
     }
 
+    /// Commit of the base workspace's branch that a stacked workspace's branch
+    /// was last rebased onto
+    pub async fn find_stacked_base_commit(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let commit = sqlx::query_scalar!(
+            "SELECT stacked_base_commit FROM workspace_repos WHERE workspace_id = $1 AND repo_id = $2",
+            workspace_id,
+            repo_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(commit.flatten())
+    }
+
+    pub async fn set_stacked_base_commit(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        commit: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE workspace_repos SET stacked_base_commit = $1, updated_at = datetime('now') WHERE workspace_id = $2 AND repo_id = $3",
+            commit,
+            workspace_id,
+            repo_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn update_target_branch_for_children_of_workspace(
         pool: &SqlitePool,
         parent_workspace_id: Uuid,
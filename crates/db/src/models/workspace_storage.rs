@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// Measured disk usage of a workspace's worktrees
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkspaceDiskUsage {
+    pub total_bytes: i64,
+    /// Installed dependencies such as `node_modules` and virtualenvs
+    pub dependency_bytes: i64,
+    /// Build output such as `target` and `dist`
+    pub build_bytes: i64,
+}
+
+/// A workspace with its task, storage state and last activity, as listed on
+/// the storage dashboard and considered by the cleanup policies
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceStorageEntry {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub task_title: String,
+    pub task_status: TaskStatus,
+    pub branch: String,
+    pub container_ref: Option<String>,
+    pub pinned: bool,
+    #[ts(type = "number | null")]
+    pub total_bytes: Option<i64>,
+    #[ts(type = "number | null")]
+    pub dependency_bytes: Option<i64>,
+    #[ts(type = "number | null")]
+    pub build_bytes: Option<i64>,
+    pub measured_at: Option<DateTime<Utc>>,
+    pub last_activity_at: DateTime<Utc>,
+    pub has_running_process: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct PinWorkspace {
+    pub pinned: bool,
+}
+
+pub struct WorkspaceStorage;
+
+impl WorkspaceStorage {
+    /// All workspaces, most recently active first
+    pub async fn find_all_entries(
+        pool: &SqlitePool,
+    ) -> Result<Vec<WorkspaceStorageEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceStorageEntry,
+            r#"SELECT
+                w.id                    AS "workspace_id!: Uuid",
+                w.task_id               AS "task_id!: Uuid",
+                t.project_id            AS "project_id!: Uuid",
+                t.title                 AS "task_title!",
+                t.status                AS "task_status!: TaskStatus",
+                w.branch                AS "branch!",
+                w.container_ref,
+                COALESCE(ws.pinned, FALSE) AS "pinned!: bool",
+                ws.total_bytes          AS "total_bytes: i64",
+                ws.dependency_bytes     AS "dependency_bytes: i64",
+                ws.build_bytes          AS "build_bytes: i64",
+                ws.measured_at          AS "measured_at: DateTime<Utc>",
+                MAX(
+                    w.updated_at,
+                    COALESCE(
+                        (SELECT MAX(ep.completed_at)
+                           FROM sessions s
+                           JOIN execution_processes ep ON ep.session_id = s.id
+                          WHERE s.workspace_id = w.id),
+                        w.updated_at
+                    )
+                )                       AS "last_activity_at!: DateTime<Utc>",
+                EXISTS (
+                    SELECT 1
+                      FROM sessions s
+                      JOIN execution_processes ep ON ep.session_id = s.id
+                     WHERE s.workspace_id = w.id
+                       AND ep.completed_at IS NULL
+                )                       AS "has_running_process!: bool",
+                w.created_at            AS "created_at!: DateTime<Utc>"
+               FROM workspaces w
+               JOIN tasks t ON t.id = w.task_id
+               LEFT JOIN workspace_storage ws ON ws.workspace_id = w.id
+               ORDER BY "last_activity_at!: DateTime<Utc>" DESC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn set_pinned(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        pinned: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO workspace_storage (workspace_id, pinned)
+               VALUES ($1, $2)
+               ON CONFLICT(workspace_id) DO UPDATE SET
+                   pinned = excluded.pinned,
+                   updated_at = datetime('now', 'subsec')"#,
+            workspace_id,
+            pinned
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn record_usage(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        usage: &WorkspaceDiskUsage,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO workspace_storage (workspace_id, total_bytes, dependency_bytes, build_bytes, measured_at)
+               VALUES ($1, $2, $3, $4, datetime('now', 'subsec'))
+               ON CONFLICT(workspace_id) DO UPDATE SET
+                   total_bytes = excluded.total_bytes,
+                   dependency_bytes = excluded.dependency_bytes,
+                   build_bytes = excluded.build_bytes,
+                   measured_at = excluded.measured_at,
+                   updated_at = datetime('now', 'subsec')"#,
+            workspace_id,
+            usage.total_bytes,
+            usage.dependency_bytes,
+            usage.build_bytes
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Forget measured usage once the worktrees are removed; the pin is kept
+    pub async fn clear_usage(pool: &SqlitePool, workspace_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE workspace_storage
+               SET total_bytes = NULL,
+                   dependency_bytes = NULL,
+                   build_bytes = NULL,
+                   measured_at = NULL,
+                   updated_at = datetime('now', 'subsec')
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -12,7 +12,7 @@ use db::{
     },
 };
 use executors::executors::ExecutorError;
-use futures::{StreamExt, TryStreamExt};
+use futures::{FutureExt, StreamExt, TryStreamExt};
 use git2::Error as Git2Error;
 use serde_json::Value;
 use services::services::{
@@ -27,10 +27,11 @@ use services::services::{
     filesystem_watcher::FilesystemWatcherError,
     git::{GitService, GitServiceError},
     image::{ImageError, ImageService},
-    pr_monitor::PrMonitorService,
+    pr_monitor::{PrMergedHook, PrMonitorService},
     project::ProjectService,
     queued_message::QueuedMessageService,
     repo::RepoService,
+    secrets::{SecretsError, SecretsService},
     share::SharePublisher,
     worktree_manager::WorktreeError,
 };
@@ -71,6 +72,8 @@ pub enum DeploymentError {
     Event(#[from] EventError),
     #[error(transparent)]
     Config(#[from] ConfigError),
+    #[error(transparent)]
+    Secrets(#[from] SecretsError),
     #[error("Remote client not configured")]
     RemoteClientNotConfigured,
     #[error(transparent)]
@@ -109,6 +112,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn queued_message_service(&self) -> &QueuedMessageService;
 
+    fn secrets(&self) -> &SecretsService;
+
     fn auth_context(&self) -> &AuthContext;
 
     fn share_publisher(&self) -> Result<SharePublisher, RemoteClientNotConfigured>;
@@ -133,7 +138,27 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                 analytics_service: analytics_service.clone(),
             });
         let publisher = self.share_publisher().ok();
-        PrMonitorService::spawn(db, analytics, publisher).await
+        let notification_service = self.container().notification_service().clone();
+        // Merged sub-tasks report back to the workspace they were created from
+        let deployment = self.clone();
+        let on_merged: PrMergedHook = Arc::new(move |workspace| {
+            let deployment = deployment.clone();
+            async move {
+                deployment
+                    .container()
+                    .notify_parent_workspace(&workspace)
+                    .await
+            }
+            .boxed()
+        });
+        PrMonitorService::spawn(
+            db,
+            analytics,
+            publisher,
+            notification_service,
+            Some(on_merged),
+        )
+        .await
     }
 
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
@@ -24,6 +24,10 @@ pub struct CodingAgentFollowUpRequest {
     /// If None, uses the container_ref directory directly.
     #[serde(default)]
     pub working_dir: Option<String>,
+    /// Project instructions given to the agent, filled in when the run starts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub instructions: Option<String>,
 }
 
 impl CodingAgentFollowUpRequest {
@@ -62,9 +66,10 @@ impl Executable for CodingAgentFollowUpRequest {
             ))?;
 
         agent.use_approvals(approvals.clone());
+        let env = env.clone().with_instructions(self.instructions.clone());
 
         agent
-            .spawn_follow_up(&effective_dir, &self.prompt, &self.session_id, env)
+            .spawn_follow_up(&effective_dir, &self.prompt, &self.session_id, &env)
             .await
     }
 }
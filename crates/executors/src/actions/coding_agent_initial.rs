@@ -23,6 +23,10 @@ pub struct CodingAgentInitialRequest {
     /// If None, uses the container_ref directory directly.
     #[serde(default)]
     pub working_dir: Option<String>,
+    /// Project instructions given to the agent, filled in when the run starts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub instructions: Option<String>,
 }
 
 impl CodingAgentInitialRequest {
@@ -56,7 +60,8 @@ impl Executable for CodingAgentInitialRequest {
             ))?;
 
         agent.use_approvals(approvals.clone());
+        let env = env.clone().with_instructions(self.instructions.clone());
 
-        agent.spawn(&effective_dir, &self.prompt, env).await
+        agent.spawn(&effective_dir, &self.prompt, &env).await
     }
 }
@@ -12,7 +12,10 @@ use crate::{
     },
     approvals::ExecutorApprovalService,
     env::ExecutionEnv,
-    executors::{BaseCodingAgent, ExecutorError, SpawnedChild},
+    executors::{BaseCodingAgent, CodingAgent, ExecutorError, SpawnedChild},
+    failure::RetryPolicy,
+    profile::{ExecutorConfigs, ExecutorProfileId},
+    watchdog::WatchdogConfig,
 };
 pub mod coding_agent_follow_up;
 pub mod coding_agent_initial;
@@ -63,6 +66,54 @@ impl ExecutorAction {
             ExecutorActionType::ScriptRequest(_) => None,
         }
     }
+
+    pub fn executor_profile_id(&self) -> Option<&ExecutorProfileId> {
+        match self.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                Some(&request.executor_profile_id)
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                Some(&request.executor_profile_id)
+            }
+            ExecutorActionType::ScriptRequest(_) => None,
+        }
+    }
+
+    /// Set the project instructions of a coding agent request; other actions
+    /// are returned unchanged
+    pub fn with_instructions(mut self, instructions: Option<String>) -> Self {
+        match &mut self.typ {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                request.instructions = instructions;
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                request.instructions = instructions;
+            }
+            ExecutorActionType::ScriptRequest(_) => {}
+        }
+        self
+    }
+
+    /// The coding agent's profile, for coding agent requests
+    pub fn coding_agent(&self) -> Option<CodingAgent> {
+        ExecutorConfigs::get_cached().get_coding_agent(self.executor_profile_id()?)
+    }
+
+    /// Watchdog limits from the coding agent's profile, if any are set
+    pub fn watchdog(&self) -> Option<WatchdogConfig> {
+        self.coding_agent()?
+            .cmd_overrides()?
+            .watchdog
+            .clone()
+            .filter(WatchdogConfig::is_enabled)
+    }
+
+    /// Retry policy from the coding agent's profile, or the default one
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.coding_agent()
+            .and_then(|agent| agent.cmd_overrides()?.retry.clone())
+            .unwrap_or_default()
+    }
 }
 
 #[async_trait]
@@ -3,7 +3,6 @@ use std::{path::Path, sync::Arc};
 use async_trait::async_trait;
 use command_group::AsyncCommandGroup;
 use serde::{Deserialize, Serialize};
-use tokio::process::Command;
 use ts_rs::TS;
 use workspace_utils::shell::get_shell_command;
 
@@ -53,7 +52,7 @@ impl Executable for ScriptRequest {
         };
 
         let (shell_cmd, shell_arg) = get_shell_command();
-        let mut command = Command::new(shell_cmd);
+        let mut command = env.command(shell_cmd)?;
         command
             .kill_on_drop(true)
             .stdin(std::process::Stdio::null())
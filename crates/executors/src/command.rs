@@ -6,7 +6,10 @@ use thiserror::Error;
 use ts_rs::TS;
 use workspace_utils::shell::resolve_executable_path;
 
-use crate::executors::ExecutorError;
+use crate::{
+    executors::ExecutorError, failure::RetryPolicy, sandbox::SandboxConfig,
+    watchdog::WatchdogConfig,
+};
 
 #[derive(Debug, Error)]
 pub enum CommandBuildError {
@@ -58,6 +61,24 @@ pub struct CmdOverrides {
     )]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub env: Option<HashMap<String, String>>,
+    #[schemars(
+        title = "Process Sandbox",
+        description = "Restrict filesystem and network access for the executor process (Linux only)"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_sandbox: Option<SandboxConfig>,
+    #[schemars(
+        title = "Watchdog",
+        description = "Stop the agent when it runs too long, stops producing output or repeats the same tool call"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watchdog: Option<WatchdogConfig>,
+    #[schemars(
+        title = "Retry Policy",
+        description = "Retry runs that fail because of rate limits, provider overload or network errors"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
@@ -1,19 +1,53 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ffi::OsStr};
 
 use tokio::process::Command;
 
-use crate::command::CmdOverrides;
+use crate::{
+    command::CmdOverrides,
+    executors::ExecutorError,
+    sandbox::{SandboxConfig, SandboxPolicy},
+};
 
 /// Environment variables to inject into executor processes
 #[derive(Debug, Clone, Default)]
 pub struct ExecutionEnv {
     pub vars: HashMap<String, String>,
+    /// Sandbox applied to processes built via [`ExecutionEnv::command`]
+    pub sandbox: SandboxPolicy,
+    /// Project instructions for the coding agent, passed on by each executor
+    /// through the channel that suits it best
+    pub instructions: Option<String>,
 }
 
 impl ExecutionEnv {
     pub fn new() -> Self {
         Self {
             vars: HashMap::new(),
+            sandbox: SandboxPolicy::default(),
+            instructions: None,
+        }
+    }
+
+    /// Return a new env with the given sandbox policy
+    pub fn with_sandbox(mut self, sandbox: SandboxPolicy) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
+    /// Return a new env carrying the given agent instructions
+    pub fn with_instructions(mut self, instructions: Option<String>) -> Self {
+        self.instructions = instructions;
+        self
+    }
+
+    /// Prefix the prompt with the agent instructions, for executors that have
+    /// no better way to receive them
+    pub fn prompt_with_instructions(&self, prompt: &str) -> String {
+        match &self.instructions {
+            Some(instructions) => format!(
+                "<project_instructions>\n{instructions}\n</project_instructions>\n\n{prompt}"
+            ),
+            None => prompt.to_string(),
         }
     }
 
@@ -34,8 +68,11 @@ impl ExecutionEnv {
         self
     }
 
-    /// Return a new env with profile env from CmdOverrides merged in.
-    pub fn with_profile(self, cmd: &CmdOverrides) -> Self {
+    /// Return a new env with profile env and sandbox settings from CmdOverrides merged in.
+    pub fn with_profile(mut self, cmd: &CmdOverrides) -> Self {
+        if let Some(ref profile_sandbox) = cmd.process_sandbox {
+            self.merge_sandbox(profile_sandbox);
+        }
         if let Some(ref profile_env) = cmd.env {
             self.with_overrides(profile_env)
         } else {
@@ -43,6 +80,16 @@ impl ExecutionEnv {
         }
     }
 
+    /// Layer sandbox settings on top of the current policy
+    pub fn merge_sandbox(&mut self, config: &SandboxConfig) {
+        self.sandbox.config.merge(config);
+    }
+
+    /// Create a command for `program`, sandboxed when the policy requires it
+    pub fn command(&self, program: impl AsRef<OsStr>) -> Result<Command, ExecutorError> {
+        self.sandbox.command(program)
+    }
+
     /// Apply all environment variables to a Command
     pub fn apply_to_command(&self, command: &mut Command) {
         for (key, value) in &self.vars {
@@ -75,4 +122,16 @@ mod tests {
         assert_eq!(merged.vars.get("FOO").unwrap(), "profile"); // overrides
         assert_eq!(merged.vars.get("BAR").unwrap(), "profile");
     }
+
+    #[test]
+    fn instructions_prefix_prompt() {
+        let env = ExecutionEnv::default();
+        assert_eq!(env.prompt_with_instructions("Fix the bug"), "Fix the bug");
+
+        let env = env.with_instructions(Some("Use pnpm".to_string()));
+        assert_eq!(
+            env.prompt_with_instructions("Fix the bug"),
+            "<project_instructions>\nUse pnpm\n</project_instructions>\n\nFix the bug"
+        );
+    }
 }
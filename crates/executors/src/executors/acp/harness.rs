@@ -9,7 +9,7 @@ use agent_client_protocol as proto;
 use agent_client_protocol::Agent as _;
 use command_group::{AsyncCommandGroup, AsyncGroupChild};
 use futures::StreamExt;
-use tokio::{io::AsyncWriteExt, process::Command, sync::mpsc};
+use tokio::{io::AsyncWriteExt, sync::mpsc};
 use tokio_util::{
     compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt},
     io::ReaderStream,
@@ -23,6 +23,7 @@ use crate::{
     command::{CmdOverrides, CommandParts},
     env::ExecutionEnv,
     executors::{ExecutorError, ExecutorExitResult, SpawnedChild, acp::AcpEvent},
+    logs::utils::SteerMessage,
 };
 
 /// Reusable harness for ACP-based conns (Gemini, Qwen, etc.)
@@ -78,7 +79,8 @@ impl AcpAgentHarness {
         approvals: Option<std::sync::Arc<dyn ExecutorApprovalService>>,
     ) -> Result<SpawnedChild, ExecutorError> {
         let (program_path, args) = command_parts.into_resolved().await?;
-        let mut command = Command::new(program_path);
+        let env = env.clone().with_profile(cmd_overrides);
+        let mut command = env.command(program_path)?;
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -88,13 +90,12 @@ impl AcpAgentHarness {
             .args(&args)
             .env("NODE_NO_WARNINGS", "1");
 
-        env.clone()
-            .with_profile(cmd_overrides)
-            .apply_to_command(&mut command);
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
         let (exit_tx, exit_rx) = tokio::sync::oneshot::channel::<ExecutorExitResult>();
+        let (steer_tx, steer_rx) = mpsc::unbounded_channel::<String>();
         Self::bootstrap_acp_connection(
             &mut child,
             current_dir.to_path_buf(),
@@ -105,6 +106,7 @@ impl AcpAgentHarness {
             self.model.clone(),
             self.mode.clone(),
             approvals,
+            steer_rx,
         )
         .await?;
 
@@ -112,6 +114,7 @@ impl AcpAgentHarness {
             child,
             exit_signal: Some(exit_rx),
             interrupt_sender: None,
+            steer_sender: Some(steer_tx),
         })
     }
 
@@ -127,7 +130,8 @@ impl AcpAgentHarness {
         approvals: Option<std::sync::Arc<dyn ExecutorApprovalService>>,
     ) -> Result<SpawnedChild, ExecutorError> {
         let (program_path, args) = command_parts.into_resolved().await?;
-        let mut command = Command::new(program_path);
+        let env = env.clone().with_profile(cmd_overrides);
+        let mut command = env.command(program_path)?;
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -137,13 +141,12 @@ impl AcpAgentHarness {
             .args(&args)
             .env("NODE_NO_WARNINGS", "1");
 
-        env.clone()
-            .with_profile(cmd_overrides)
-            .apply_to_command(&mut command);
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
         let (exit_tx, exit_rx) = tokio::sync::oneshot::channel::<ExecutorExitResult>();
+        let (steer_tx, steer_rx) = mpsc::unbounded_channel::<String>();
         Self::bootstrap_acp_connection(
             &mut child,
             current_dir.to_path_buf(),
@@ -154,6 +157,7 @@ impl AcpAgentHarness {
             self.model.clone(),
             self.mode.clone(),
             approvals,
+            steer_rx,
         )
         .await?;
 
@@ -161,6 +165,7 @@ impl AcpAgentHarness {
             child,
             exit_signal: Some(exit_rx),
             interrupt_sender: None,
+            steer_sender: Some(steer_tx),
         })
     }
 
@@ -175,6 +180,7 @@ impl AcpAgentHarness {
         model: Option<String>,
         mode: Option<String>,
         approvals: Option<std::sync::Arc<dyn ExecutorApprovalService>>,
+        mut steer_rx: mpsc::UnboundedReceiver<String>,
     ) -> Result<(), ExecutorError> {
         // Take child's stdio for ACP wiring
         let orig_stdout = child.inner().stdout.take().ok_or_else(|| {
@@ -419,6 +425,31 @@ impl AcpAgentHarness {
                             }
                         });
 
+                        // Deliver messages sent while running by cancelling the
+                        // current turn; the prompt loop below sends them next
+                        let steer_log_tx = log_tx.clone();
+                        let steer_feedback_handle = client_feedback_handle.clone();
+                        let sm_for_steer = session_manager.clone();
+                        let sess_id_for_steer = display_session_id.clone();
+                        let conn_for_steer = conn.clone();
+                        let acp_session_id_for_steer = acp_session_id.clone();
+                        tokio::task::spawn_local(async move {
+                            while let Some(message) = steer_rx.recv().await {
+                                let _ = steer_log_tx.send(SteerMessage::new(&message).raw());
+                                let _ = sm_for_steer.append_raw_line(
+                                    &sess_id_for_steer,
+                                    &serde_json::to_string(&serde_json::json!({ "user": message }))
+                                        .unwrap_or_default(),
+                                );
+                                steer_feedback_handle.enqueue_feedback(message).await;
+                                let _ = conn_for_steer
+                                    .cancel(proto::CancelNotification::new(proto::SessionId::new(
+                                        acp_session_id_for_steer.clone(),
+                                    )))
+                                    .await;
+                            }
+                        });
+
                         // Save prompt to session
                         let _ = session_manager.append_raw_line(
                             &display_session_id,
@@ -18,7 +18,7 @@ use crate::{
         ActionType, FileChange, NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
         TodoItem, ToolResult, ToolResultValueType, ToolStatus as LogToolStatus,
         stderr_processor::normalize_stderr_logs,
-        utils::{ConversationPatch, EntryIndexProvider},
+        utils::{ConversationPatch, EntryIndexProvider, SteerMessage},
     },
 };
 
@@ -39,6 +39,14 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
 
         let mut stdout_lines = msg_store.stdout_lines_stream();
         while let Some(Ok(line)) = stdout_lines.next().await {
+            if let Some(steer_message) = SteerMessage::parse(&line) {
+                msg_store.push_patch(ConversationPatch::add_normalized_entry(
+                    entry_index.next(),
+                    steer_message.to_normalized_entry(),
+                ));
+                continue;
+            }
+
             if let Some(parsed) = AcpEventParser::parse_line(&line) {
                 tracing::trace!("Parsed ACP line: {:?}", parsed);
                 match parsed {
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use command_group::AsyncCommandGroup;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::io::AsyncWriteExt;
 use ts_rs::TS;
 use workspace_utils::msg_store::MsgStore;
 
@@ -16,6 +16,7 @@ use crate::{
         claude::{ClaudeLogProcessor, HistoryStrategy},
     },
     logs::{stderr_processor::normalize_stderr_logs, utils::EntryIndexProvider},
+    version::{self, VersionCommand},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
@@ -33,9 +34,13 @@ pub struct Amp {
 }
 
 impl Amp {
+    pub fn base_command() -> &'static str {
+        "npx -y @sourcegraph/amp@0.0.1764777697-g907e30"
+    }
+
     fn build_command_builder(&self) -> CommandBuilder {
-        let mut builder = CommandBuilder::new("npx -y @sourcegraph/amp@0.0.1764777697-g907e30")
-            .params(["--execute", "--stream-json"]);
+        let mut builder =
+            CommandBuilder::new(Self::base_command()).params(["--execute", "--stream-json"]);
         if self.dangerously_allow_all.unwrap_or(false) {
             builder = builder.extend_params(["--dangerously-allow-all"]);
         }
@@ -54,9 +59,12 @@ impl StandardCodingAgentExecutor for Amp {
         let command_parts = self.build_command_builder().build_initial()?;
         let (executable_path, args) = command_parts.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_prompt(&env.prompt_with_instructions(prompt));
 
-        let mut command = Command::new(executable_path);
+        let env = env.clone().with_profile(&self.cmd);
+        let mut command = env.command(executable_path)?;
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -65,9 +73,7 @@ impl StandardCodingAgentExecutor for Amp {
             .current_dir(current_dir)
             .args(&args);
 
-        env.clone()
-            .with_profile(&self.cmd)
-            .apply_to_command(&mut command);
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
@@ -87,6 +93,8 @@ impl StandardCodingAgentExecutor for Amp {
         session_id: &str,
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
+        let env = env.clone().with_profile(&self.cmd);
+
         // 1) Fork the thread synchronously to obtain new thread id
         let builder = self.build_command_builder();
         let fork_line = builder.build_follow_up(&[
@@ -95,14 +103,15 @@ impl StandardCodingAgentExecutor for Amp {
             session_id.to_string(),
         ])?;
         let (fork_program, fork_args) = fork_line.into_resolved().await?;
-        let fork_output = Command::new(fork_program)
+        let mut fork_command = env.command(fork_program)?;
+        fork_command
             .kill_on_drop(true)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .current_dir(current_dir)
-            .args(&fork_args)
-            .output()
-            .await?;
+            .args(&fork_args);
+        env.apply_to_command(&mut fork_command);
+        let fork_output = fork_command.output().await?;
         let stdout_str = String::from_utf8_lossy(&fork_output.stdout);
         let new_thread_id = stdout_str
             .lines()
@@ -129,7 +138,7 @@ impl StandardCodingAgentExecutor for Amp {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(continue_program);
+        let mut command = env.command(continue_program)?;
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -138,9 +147,7 @@ impl StandardCodingAgentExecutor for Amp {
             .current_dir(current_dir)
             .args(&continue_args);
 
-        env.clone()
-            .with_profile(&self.cmd)
-            .apply_to_command(&mut command);
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
@@ -172,4 +179,8 @@ impl StandardCodingAgentExecutor for Amp {
     fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
         dirs::home_dir().map(|home| home.join(".config").join("amp").join("settings.json"))
     }
+
+    fn version_command(&self) -> Option<VersionCommand> {
+        Some(version::version_command(Self::base_command(), &self.cmd))
+    }
 }
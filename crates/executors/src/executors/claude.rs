@@ -10,7 +10,6 @@ use command_group::AsyncCommandGroup;
 use futures::StreamExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tokio::process::Command;
 use ts_rs::TS;
 use workspace_utils::{
     approvals::ApprovalStatus, diff::create_unified_diff, log_msg::LogMsg, msg_store::MsgStore,
@@ -30,13 +29,15 @@ use crate::{
         AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
         codex::client::LogWriter,
     },
+    failure::{self, AgentFailure},
     logs::{
         ActionType, FileChange, NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
         TodoItem, ToolStatus,
         stderr_processor::normalize_stderr_logs,
-        utils::{EntryIndexProvider, patch::ConversationPatch},
+        utils::{EntryIndexProvider, SteerMessage, patch::ConversationPatch},
     },
     stdout_dup::create_stdout_pipe_writer,
+    version::{self, TestedVersions, VersionCommand},
 };
 
 fn base_command(claude_code_router: bool) -> &'static str {
@@ -207,11 +208,49 @@ impl StandardCodingAgentExecutor for ClaudeCode {
         normalize_stderr_logs(msg_store, entry_index_provider);
     }
 
+    /// API errors such as rate limits end the run with an error result
+    /// rather than on stderr
+    fn classify_failure(&self, msg_store: &MsgStore) -> Option<AgentFailure> {
+        let stdout = msg_store
+            .get_history()
+            .into_iter()
+            .filter_map(|msg| match msg {
+                LogMsg::Stdout(s) => Some(s),
+                _ => None,
+            })
+            .collect::<String>();
+        stdout
+            .lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str::<ClaudeJson>(line).ok())
+            .find_map(|json| match json {
+                ClaudeJson::Result {
+                    is_error: Some(true),
+                    result: Some(serde_json::Value::String(result)),
+                    ..
+                } => failure::classify(&result),
+                _ => None,
+            })
+            .or_else(|| failure::classify_output(msg_store))
+    }
+
     // MCP configuration methods
     fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
         dirs::home_dir().map(|home| home.join(".claude.json"))
     }
 
+    fn version_command(&self) -> Option<VersionCommand> {
+        // The router wraps the CLI and doesn't report the CLI's version
+        if self.claude_code_router.unwrap_or(false) && self.cmd.base_command_override.is_none() {
+            return None;
+        }
+        Some(version::version_command(base_command(false), &self.cmd))
+    }
+
+    fn tested_versions(&self) -> Option<TestedVersions> {
+        Some(TestedVersions::new("2.0.0", "2.1.0"))
+    }
+
     fn get_availability_info(&self) -> AvailabilityInfo {
         let auth_file_path = dirs::home_dir().map(|home| home.join(".claude.json"));
 
@@ -238,10 +277,15 @@ impl ClaudeCode {
         command_parts: CommandParts,
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (program_path, args) = command_parts.into_resolved().await?;
+        let (program_path, mut args) = command_parts.into_resolved().await?;
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        // Project instructions go in the system prompt, on every turn
+        if let Some(instructions) = &env.instructions {
+            args.extend(["--append-system-prompt".to_string(), instructions.clone()]);
+        }
 
-        let mut command = Command::new(program_path);
+        let env = env.clone().with_profile(&self.cmd);
+        let mut command = env.command(program_path)?;
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -250,9 +294,7 @@ impl ClaudeCode {
             .current_dir(current_dir)
             .args(&args);
 
-        env.clone()
-            .with_profile(&self.cmd)
-            .apply_to_command(&mut command);
+        env.apply_to_command(&mut command);
 
         // Remove ANTHROPIC_API_KEY if disable_api_key is enabled
         if self.disable_api_key.unwrap_or(false) {
@@ -275,6 +317,7 @@ impl ClaudeCode {
 
         // Create interrupt channel for graceful shutdown
         let (interrupt_tx, interrupt_rx) = tokio::sync::oneshot::channel::<()>();
+        let (steer_tx, mut steer_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
 
         // Spawn task to handle the SDK client with control protocol
         let prompt_clone = combined_prompt.clone();
@@ -304,6 +347,26 @@ impl ClaudeCode {
                 let _ = log_writer
                     .log_raw(&format!("Error: Failed to send prompt - {e}"))
                     .await;
+                return;
+            }
+
+            // Claude queues messages sent mid-turn and picks them up as soon as
+            // it can. Holding the peer keeps stdin open, so stop once the
+            // session has finished.
+            loop {
+                let message = tokio::select! {
+                    message = steer_rx.recv() => match message {
+                        Some(message) => message,
+                        None => break,
+                    },
+                    _ = protocol_peer.finished() => break,
+                };
+                match protocol_peer.send_user_message(message.clone()).await {
+                    Ok(()) => {
+                        let _ = log_writer.log_raw(&SteerMessage::new(message).raw()).await;
+                    }
+                    Err(e) => tracing::warn!("Failed to deliver message to Claude: {e}"),
+                }
             }
         });
 
@@ -311,6 +374,7 @@ impl ClaudeCode {
             child,
             exit_signal: None,
             interrupt_sender: Some(interrupt_tx),
+            steer_sender: Some(steer_tx),
         })
     }
 }
@@ -394,6 +458,15 @@ impl ClaudeLogProcessor {
                         continue;
                     }
 
+                    if let Some(steer_message) = SteerMessage::parse(trimmed) {
+                        let patch = ConversationPatch::add_normalized_entry(
+                            entry_index_provider.next(),
+                            steer_message.to_normalized_entry(),
+                        );
+                        msg_store.push_patch(patch);
+                        continue;
+                    }
+
                     match serde_json::from_str::<ClaudeJson>(trimmed) {
                         Ok(claude_json) => {
                             // Extract session ID if present
@@ -2018,6 +2091,9 @@ mod tests {
                 base_command_override: None,
                 additional_params: None,
                 env: None,
+                process_sandbox: None,
+                watchdog: None,
+                retry: None,
             },
             approvals_service: None,
             disable_api_key: None,
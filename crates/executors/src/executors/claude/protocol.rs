@@ -6,6 +6,7 @@ use tokio::{
     process::{ChildStdin, ChildStdout},
     sync::{Mutex, oneshot},
 };
+use tokio_util::sync::CancellationToken;
 
 use super::types::{CLIMessage, ControlRequestType, ControlResponseMessage, ControlResponseType};
 use crate::executors::{
@@ -20,6 +21,10 @@ use crate::executors::{
 #[derive(Clone)]
 pub struct ProtocolPeer {
     stdin: Arc<Mutex<ChildStdin>>,
+    /// User messages sent whose result hasn't arrived yet, `None` once the
+    /// session has finished
+    pending_turns: Arc<std::sync::Mutex<Option<usize>>>,
+    finished: CancellationToken,
 }
 
 impl ProtocolPeer {
@@ -31,6 +36,8 @@ impl ProtocolPeer {
     ) -> Self {
         let peer = Self {
             stdin: Arc::new(Mutex::new(stdin)),
+            pending_turns: Arc::new(std::sync::Mutex::new(Some(0))),
+            finished: CancellationToken::new(),
         };
 
         let reader_peer = peer.clone();
@@ -38,6 +45,7 @@ impl ProtocolPeer {
             if let Err(e) = reader_peer.read_loop(stdout, client, interrupt_rx).await {
                 tracing::error!("Protocol reader loop error: {}", e);
             }
+            reader_peer.finish();
         });
 
         peer
@@ -77,7 +85,9 @@ impl ProtocolPeer {
                                 Ok(CLIMessage::ControlResponse { .. }) => {}
                                 Ok(CLIMessage::Result(_)) => {
                                     client.on_non_control(line).await?;
-                                    break;
+                                    if self.complete_turn() {
+                                        break;
+                                    }
                                 }
                                 _ => {
                                     client.on_non_control(line).await?;
@@ -189,10 +199,43 @@ impl ProtocolPeer {
     }
 
     pub async fn send_user_message(&self, content: String) -> Result<(), ExecutorError> {
+        match self.pending_turns.lock().unwrap().as_mut() {
+            Some(pending) => *pending += 1,
+            None => {
+                return Err(ExecutorError::Io(std::io::Error::other(
+                    "Claude session has already finished",
+                )));
+            }
+        }
         let message = Message::new_user(content);
         self.send_json(&message).await
     }
 
+    /// Record a turn's result, returning whether the session is finished
+    fn complete_turn(&self) -> bool {
+        let mut pending_turns = self.pending_turns.lock().unwrap();
+        match pending_turns.as_mut() {
+            Some(pending) if *pending > 1 => {
+                *pending -= 1;
+                false
+            }
+            _ => {
+                *pending_turns = None;
+                true
+            }
+        }
+    }
+
+    fn finish(&self) {
+        *self.pending_turns.lock().unwrap() = None;
+        self.finished.cancel();
+    }
+
+    /// Resolves once the session has finished and no more messages are accepted
+    pub async fn finished(&self) {
+        self.finished.cancelled().await
+    }
+
     pub async fn initialize(&self, hooks: Option<serde_json::Value>) -> Result<(), ExecutorError> {
         self.send_json(&SDKControlRequest::new(SDKControlRequestType::Initialize {
             hooks,
@@ -19,7 +19,6 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use strum_macros::AsRefStr;
-use tokio::process::Command;
 use ts_rs::TS;
 use workspace_utils::msg_store::MsgStore;
 
@@ -38,7 +37,9 @@ use crate::{
         StandardCodingAgentExecutor,
         codex::{jsonrpc::ExitSignalSender, normalize_logs::Error},
     },
+    logs::utils::SteerMessage,
     stdout_dup::create_stdout_pipe_writer,
+    version::{self, TestedVersions, VersionCommand},
 };
 
 /// Sandbox policy modes for Codex
@@ -179,6 +180,14 @@ impl StandardCodingAgentExecutor for Codex {
         dirs::home_dir().map(|home| home.join(".codex").join("config.toml"))
     }
 
+    fn version_command(&self) -> Option<VersionCommand> {
+        Some(version::version_command(Self::base_command(), &self.cmd))
+    }
+
+    fn tested_versions(&self) -> Option<TestedVersions> {
+        Some(TestedVersions::new("0.77.0", "0.78.0"))
+    }
+
     fn get_availability_info(&self) -> AvailabilityInfo {
         if let Some(timestamp) = dirs::home_dir()
             .and_then(|home| std::fs::metadata(home.join(".codex").join("auth.json")).ok())
@@ -223,7 +232,11 @@ impl Codex {
         apply_overrides(builder, &self.cmd)
     }
 
-    fn build_new_conversation_params(&self, cwd: &Path) -> NewConversationParams {
+    fn build_new_conversation_params(
+        &self,
+        cwd: &Path,
+        instructions: Option<&str>,
+    ) -> NewConversationParams {
         let sandbox = match self.sandbox.as_ref() {
             None | Some(SandboxMode::Auto) => Some(CodexSandboxMode::WorkspaceWrite), // match the Auto preset in codex
             Some(SandboxMode::ReadOnly) => Some(CodexSandboxMode::ReadOnly),
@@ -254,7 +267,19 @@ impl Codex {
             include_apply_patch_tool: self.include_apply_patch_tool,
             model_provider: self.model_provider.clone(),
             compact_prompt: self.compact_prompt.clone(),
-            developer_instructions: self.developer_instructions.clone(),
+            developer_instructions: Self::developer_instructions(
+                self.developer_instructions.as_deref(),
+                instructions,
+            ),
+        }
+    }
+
+    /// Project instructions are passed as developer instructions, after the
+    /// profile's own
+    fn developer_instructions(profile: Option<&str>, project: Option<&str>) -> Option<String> {
+        match (profile, project) {
+            (Some(profile), Some(project)) => Some(format!("{profile}\n\n{project}")),
+            (profile, project) => profile.or(project).map(str::to_string),
         }
     }
 
@@ -302,7 +327,8 @@ impl Codex {
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
         let (program_path, args) = command_parts.into_resolved().await?;
 
-        let mut process = Command::new(program_path);
+        let env = env.clone().with_profile(&self.cmd);
+        let mut process = env.command(program_path)?;
         process
             .kill_on_drop(true)
             .stdin(std::process::Stdio::piped())
@@ -314,9 +340,7 @@ impl Codex {
             .env("NO_COLOR", "1")
             .env("RUST_LOG", "error");
 
-        env.clone()
-            .with_profile(&self.cmd)
-            .apply_to_command(&mut process);
+        env.apply_to_command(&mut process);
 
         let mut child = process.group_spawn()?;
 
@@ -329,8 +353,9 @@ impl Codex {
 
         let new_stdout = create_stdout_pipe_writer(&mut child)?;
         let (exit_signal_tx, exit_signal_rx) = tokio::sync::oneshot::channel();
+        let (steer_tx, steer_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
 
-        let params = self.build_new_conversation_params(current_dir);
+        let params = self.build_new_conversation_params(current_dir, env.instructions.as_deref());
         let resume_session = resume_session.map(|s| s.to_string());
         let auto_approve = matches!(
             (&self.sandbox, &self.ask_for_approval),
@@ -350,6 +375,7 @@ impl Codex {
                 exit_signal_tx.clone(),
                 approvals,
                 auto_approve,
+                steer_rx,
             )
             .await
             {
@@ -390,6 +416,7 @@ impl Codex {
             child,
             exit_signal: Some(exit_signal_rx),
             interrupt_sender: None,
+            steer_sender: Some(steer_tx),
         })
     }
 
@@ -404,8 +431,9 @@ impl Codex {
         exit_signal_tx: ExitSignalSender,
         approvals: Option<Arc<dyn ExecutorApprovalService>>,
         auto_approve: bool,
+        mut steer_rx: tokio::sync::mpsc::UnboundedReceiver<String>,
     ) -> Result<(), ExecutorError> {
-        let client = AppServerClient::new(log_writer, approvals, auto_approve);
+        let client = AppServerClient::new(log_writer.clone(), approvals, auto_approve);
         let rpc_peer =
             JsonRpcPeer::spawn(child_stdin, child_stdout, client.clone(), exit_signal_tx);
         client.connect(rpc_peer);
@@ -416,16 +444,11 @@ impl Codex {
                 "Codex authentication required".to_string(),
             ));
         }
-        match resume_session {
+        let conversation_id = match resume_session {
             None => {
                 let params = conversation_params;
                 let response = client.new_conversation(params).await?;
-                let conversation_id = response.conversation_id;
-                client.register_session(&conversation_id).await?;
-                client.add_conversation_listener(conversation_id).await?;
-                client
-                    .send_user_message(conversation_id, combined_prompt)
-                    .await?;
+                response.conversation_id
             }
             Some(session_id) => {
                 let (rollout_path, _forked_session_id) =
@@ -440,13 +463,24 @@ impl Codex {
                     rollout_path.display(),
                     response
                 );
-                let conversation_id = response.conversation_id;
-                client.register_session(&conversation_id).await?;
-                client.add_conversation_listener(conversation_id).await?;
-                client
-                    .send_user_message(conversation_id, combined_prompt)
-                    .await?;
+                response.conversation_id
             }
+        };
+        client.register_session(&conversation_id).await?;
+        client.add_conversation_listener(conversation_id).await?;
+        client
+            .send_user_message(conversation_id, combined_prompt)
+            .await?;
+
+        // Messages sent while a turn is running are added to it; the sender
+        // is dropped once the process exits
+        while let Some(message) = steer_rx.recv().await {
+            client
+                .send_user_message(conversation_id, message.clone())
+                .await?;
+            log_writer
+                .log_raw(&SteerMessage::new(message).raw())
+                .await?;
         }
         Ok(())
     }
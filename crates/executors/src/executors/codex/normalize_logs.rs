@@ -39,7 +39,7 @@ use crate::{
         ToolStatus,
         stderr_processor::normalize_stderr_logs,
         utils::{
-            ConversationPatch, EntryIndexProvider,
+            ConversationPatch, EntryIndexProvider, SteerMessage,
             patch::{add_normalized_entry, replace_normalized_entry, upsert_normalized_entry},
         },
     },
@@ -367,6 +367,15 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
         let mut stdout_lines = msg_store.stdout_lines_stream();
 
         while let Some(Ok(line)) = stdout_lines.next().await {
+            if let Some(steer_message) = SteerMessage::parse(&line) {
+                add_normalized_entry(
+                    &msg_store,
+                    &entry_index,
+                    steer_message.to_normalized_entry(),
+                );
+                continue;
+            }
+
             if let Ok(error) = serde_json::from_str::<Error>(&line) {
                 add_normalized_entry(&msg_store, &entry_index, error.to_normalized_entry());
                 continue;
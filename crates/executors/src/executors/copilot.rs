@@ -14,7 +14,6 @@ use serde::{Deserialize, Serialize};
 use tokio::{
     fs,
     io::AsyncWriteExt,
-    process::Command,
     time::{interval, timeout},
 };
 use ts_rs::TS;
@@ -32,6 +31,7 @@ use crate::{
         stderr_processor::normalize_stderr_logs, utils::EntryIndexProvider,
     },
     stdout_dup::{self, StdoutAppender},
+    version::{self, TestedVersions, VersionCommand},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
@@ -55,8 +55,12 @@ pub struct Copilot {
 }
 
 impl Copilot {
+    pub fn base_command() -> &'static str {
+        "npx -y @github/copilot@0.0.367"
+    }
+
     fn build_command_builder(&self, log_dir: &str) -> CommandBuilder {
-        let mut builder = CommandBuilder::new("npx -y @github/copilot@0.0.367").params([
+        let mut builder = CommandBuilder::new(Self::base_command()).params([
             "--no-color",
             "--log-level",
             "debug",
@@ -110,9 +114,12 @@ impl StandardCodingAgentExecutor for Copilot {
             .build_initial()?;
         let (program_path, args) = command_parts.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_prompt(&env.prompt_with_instructions(prompt));
 
-        let mut command = Command::new(program_path);
+        let env = env.clone().with_profile(&self.cmd);
+        let mut command = env.command(program_path)?;
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -122,9 +129,7 @@ impl StandardCodingAgentExecutor for Copilot {
             .args(&args)
             .env("NODE_NO_WARNINGS", "1");
 
-        env.clone()
-            .with_profile(&self.cmd)
-            .apply_to_command(&mut command);
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
@@ -155,7 +160,8 @@ impl StandardCodingAgentExecutor for Copilot {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(program_path);
+        let env = env.clone().with_profile(&self.cmd);
+        let mut command = env.command(program_path)?;
 
         command
             .kill_on_drop(true)
@@ -166,9 +172,7 @@ impl StandardCodingAgentExecutor for Copilot {
             .args(&args)
             .env("NODE_NO_WARNINGS", "1");
 
-        env.clone()
-            .with_profile(&self.cmd)
-            .apply_to_command(&mut command);
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
@@ -215,6 +219,14 @@ impl StandardCodingAgentExecutor for Copilot {
         dirs::home_dir().map(|home| home.join(".copilot").join("mcp-config.json"))
     }
 
+    fn version_command(&self) -> Option<VersionCommand> {
+        Some(version::version_command(Self::base_command(), &self.cmd))
+    }
+
+    fn tested_versions(&self) -> Option<TestedVersions> {
+        Some(TestedVersions::new("0.0.367", "0.1.0"))
+    }
+
     fn get_availability_info(&self) -> AvailabilityInfo {
         let mcp_config_found = self
             .default_mcp_config_path()
@@ -6,7 +6,7 @@ use command_group::AsyncCommandGroup;
 use futures::StreamExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::io::AsyncWriteExt;
 use ts_rs::TS;
 use workspace_utils::{
     diff::{create_unified_diff, normalize_unified_diff},
@@ -27,6 +27,7 @@ use crate::{
         plain_text_processor::PlainTextLogProcessor,
         utils::{ConversationPatch, EntryIndexProvider},
     },
+    version::{self, VersionCommand},
 };
 
 mod mcp;
@@ -83,9 +84,12 @@ impl StandardCodingAgentExecutor for CursorAgent {
 
         let (executable_path, args) = command_parts.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_prompt(&env.prompt_with_instructions(prompt));
 
-        let mut command = Command::new(executable_path);
+        let env = env.clone().with_profile(&self.cmd);
+        let mut command = env.command(executable_path)?;
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -94,9 +98,7 @@ impl StandardCodingAgentExecutor for CursorAgent {
             .current_dir(current_dir)
             .args(&args);
 
-        env.clone()
-            .with_profile(&self.cmd)
-            .apply_to_command(&mut command);
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
@@ -124,7 +126,8 @@ impl StandardCodingAgentExecutor for CursorAgent {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(executable_path);
+        let env = env.clone().with_profile(&self.cmd);
+        let mut command = env.command(executable_path)?;
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -133,9 +136,7 @@ impl StandardCodingAgentExecutor for CursorAgent {
             .current_dir(current_dir)
             .args(&args);
 
-        env.clone()
-            .with_profile(&self.cmd)
-            .apply_to_command(&mut command);
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
@@ -490,6 +491,10 @@ impl StandardCodingAgentExecutor for CursorAgent {
         dirs::home_dir().map(|home| home.join(".cursor").join("mcp.json"))
     }
 
+    fn version_command(&self) -> Option<VersionCommand> {
+        Some(version::version_command(Self::base_command(), &self.cmd))
+    }
+
     fn get_availability_info(&self) -> AvailabilityInfo {
         let binary_found = resolve_executable_path_blocking(Self::base_command()).is_some();
         if !binary_found {
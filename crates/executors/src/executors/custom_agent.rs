@@ -5,7 +5,7 @@ use command_group::AsyncCommandGroup;
 use derivative::Derivative;
 use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::io::AsyncWriteExt;
 use ts_rs::TS;
 use workspace_utils::msg_store::MsgStore;
 
@@ -77,7 +77,8 @@ pub struct CustomAgentBaseAgentSchema {
     pub DROID: Option<super::droid::Droid>,
 }
 
-/// Deserializer that validates base_agent is not a CustomAgent (prevents recursion)
+/// Deserializer that validates base_agent is not a CustomAgent (prevents recursion), a Replay
+/// or the built-in OpenAI-compatible agent, neither of which runs a CLI
 fn deserialize_base_agent<'de, D>(deserializer: D) -> Result<Option<Box<CodingAgent>>, D::Error>
 where
     D: Deserializer<'de>,
@@ -89,6 +90,16 @@ where
                 "Custom agent cannot be based on another Custom agent",
             ));
         }
+        if matches!(boxed.as_ref(), CodingAgent::Replay(_)) {
+            return Err(serde::de::Error::custom(
+                "Custom agent cannot be based on a Replay agent",
+            ));
+        }
+        if matches!(boxed.as_ref(), CodingAgent::OpenaiCompatible(_)) {
+            return Err(serde::de::Error::custom(
+                "Custom agent cannot be based on the built-in OpenAI-compatible agent",
+            ));
+        }
     }
     Ok(agent)
 }
@@ -140,6 +151,12 @@ impl CustomAgent {
                 // This case is prevented by deserialize_base_agent validation
                 unreachable!("Custom agent cannot be based on Custom agent")
             }
+            Some(CodingAgent::Replay(_)) => {
+                unreachable!("Custom agent cannot be based on Replay agent")
+            }
+            Some(CodingAgent::OpenaiCompatible(_)) => {
+                unreachable!("Custom agent cannot be based on OpenAI-compatible agent")
+            }
             None => "npx -y @anthropic-ai/claude-code@2.0.76",
         }
     }
@@ -242,6 +259,12 @@ impl CustomAgent {
             Some(CodingAgent::CustomAgent(_)) => {
                 unreachable!("Custom agent cannot be based on Custom agent")
             }
+            Some(CodingAgent::Replay(_)) => {
+                unreachable!("Custom agent cannot be based on Replay agent")
+            }
+            Some(CodingAgent::OpenaiCompatible(_)) => {
+                unreachable!("Custom agent cannot be based on OpenAI-compatible agent")
+            }
         }
 
         // Apply any additional overrides from the custom agent itself
@@ -271,7 +294,8 @@ impl StandardCodingAgentExecutor for CustomAgent {
             let command_parts = command_builder.build_initial()?;
             let (executable_path, args) = command_parts.into_resolved().await?;
 
-            let mut command = Command::new(executable_path);
+            let env = env.clone().with_profile(&self.cmd);
+            let mut command = env.command(executable_path)?;
             command
                 .kill_on_drop(true)
                 .stdin(Stdio::piped())
@@ -280,9 +304,7 @@ impl StandardCodingAgentExecutor for CustomAgent {
                 .current_dir(current_dir)
                 .args(&args);
 
-            env.clone()
-                .with_profile(&self.cmd)
-                .apply_to_command(&mut command);
+            env.apply_to_command(&mut command);
 
             let mut child = command.group_spawn()?;
 
@@ -302,7 +324,9 @@ impl StandardCodingAgentExecutor for CustomAgent {
             let approvals_clone = self.approvals_service.clone();
             let (interrupt_tx, interrupt_rx) = tokio::sync::oneshot::channel::<()>();
 
-            let prompt_clone = self.append_prompt.combine_prompt(prompt);
+            let prompt_clone = self
+                .append_prompt
+                .combine_prompt(&env.prompt_with_instructions(prompt));
 
             tokio::spawn(async move {
                 let log_writer = LogWriter::new(new_stdout);
@@ -334,6 +358,7 @@ impl StandardCodingAgentExecutor for CustomAgent {
                 child,
                 exit_signal: None,
                 interrupt_sender: Some(interrupt_tx),
+                steer_sender: None,
             })
         } else {
             // For non-ClaudeCode base agents, use simple stdin piping
@@ -341,9 +366,12 @@ impl StandardCodingAgentExecutor for CustomAgent {
             let command_parts = command_builder.build_initial()?;
             let (executable_path, args) = command_parts.into_resolved().await?;
 
-            let combined_prompt = self.append_prompt.combine_prompt(prompt);
+            let combined_prompt = self
+                .append_prompt
+                .combine_prompt(&env.prompt_with_instructions(prompt));
 
-            let mut command = Command::new(executable_path);
+            let env = env.clone().with_profile(&self.cmd);
+            let mut command = env.command(executable_path)?;
             command
                 .kill_on_drop(true)
                 .stdin(Stdio::piped())
@@ -352,9 +380,7 @@ impl StandardCodingAgentExecutor for CustomAgent {
                 .current_dir(current_dir)
                 .args(&args);
 
-            env.clone()
-                .with_profile(&self.cmd)
-                .apply_to_command(&mut command);
+            env.apply_to_command(&mut command);
 
             let mut child = command.group_spawn()?;
 
@@ -387,7 +413,8 @@ impl StandardCodingAgentExecutor for CustomAgent {
             ])?;
             let (executable_path, args) = command_parts.into_resolved().await?;
 
-            let mut command = Command::new(executable_path);
+            let env = env.clone().with_profile(&self.cmd);
+            let mut command = env.command(executable_path)?;
             command
                 .kill_on_drop(true)
                 .stdin(Stdio::piped())
@@ -396,9 +423,7 @@ impl StandardCodingAgentExecutor for CustomAgent {
                 .current_dir(current_dir)
                 .args(&args);
 
-            env.clone()
-                .with_profile(&self.cmd)
-                .apply_to_command(&mut command);
+            env.apply_to_command(&mut command);
 
             let mut child = command.group_spawn()?;
 
@@ -448,6 +473,7 @@ impl StandardCodingAgentExecutor for CustomAgent {
                 child,
                 exit_signal: None,
                 interrupt_sender: Some(interrupt_tx),
+                steer_sender: None,
             })
         } else {
             // For non-ClaudeCode base agents, use simple stdin piping
@@ -460,7 +486,8 @@ impl StandardCodingAgentExecutor for CustomAgent {
 
             let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-            let mut command = Command::new(executable_path);
+            let env = env.clone().with_profile(&self.cmd);
+            let mut command = env.command(executable_path)?;
             command
                 .kill_on_drop(true)
                 .stdin(Stdio::piped())
@@ -469,9 +496,7 @@ impl StandardCodingAgentExecutor for CustomAgent {
                 .current_dir(current_dir)
                 .args(&args);
 
-            env.clone()
-                .with_profile(&self.cmd)
-                .apply_to_command(&mut command);
+            env.apply_to_command(&mut command);
 
             let mut child = command.group_spawn()?;
 
@@ -523,6 +548,12 @@ impl StandardCodingAgentExecutor for CustomAgent {
                 // Prevented by deserialize_base_agent validation
                 unreachable!("Custom agent cannot be based on Custom agent")
             }
+            Some(CodingAgent::Replay(_)) => {
+                unreachable!("Custom agent cannot be based on Replay agent")
+            }
+            Some(CodingAgent::OpenaiCompatible(_)) => {
+                unreachable!("Custom agent cannot be based on OpenAI-compatible agent")
+            }
         }
     }
 
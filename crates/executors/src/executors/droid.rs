@@ -5,7 +5,7 @@ use command_group::AsyncCommandGroup;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use strum_macros::AsRefStr;
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::io::AsyncWriteExt;
 use ts_rs::TS;
 use workspace_utils::msg_store::MsgStore;
 
@@ -14,6 +14,7 @@ use crate::{
     env::ExecutionEnv,
     executors::{AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
     logs::utils::EntryIndexProvider,
+    version::{self, VersionCommand},
 };
 
 pub mod normalize_logs;
@@ -114,7 +115,8 @@ async fn spawn_droid(
 ) -> Result<SpawnedChild, ExecutorError> {
     let (program_path, args) = command_parts.into_resolved().await?;
 
-    let mut command = Command::new(program_path);
+    let env = env.clone().with_profile(cmd_overrides);
+    let mut command = env.command(program_path)?;
     command
         .kill_on_drop(true)
         .stdin(Stdio::piped())
@@ -123,9 +125,7 @@ async fn spawn_droid(
         .current_dir(current_dir)
         .args(args);
 
-    env.clone()
-        .with_profile(cmd_overrides)
-        .apply_to_command(&mut command);
+    env.apply_to_command(&mut command);
 
     let mut child = command.group_spawn()?;
 
@@ -146,7 +146,9 @@ impl StandardCodingAgentExecutor for Droid {
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let droid_command = self.build_command_builder().build_initial()?;
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_prompt(&env.prompt_with_instructions(prompt));
 
         spawn_droid(droid_command, &combined_prompt, current_dir, env, &self.cmd).await
     }
@@ -182,4 +184,8 @@ impl StandardCodingAgentExecutor for Droid {
     fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
         dirs::home_dir().map(|home| home.join(".factory").join("mcp.json"))
     }
+
+    fn version_command(&self) -> Option<VersionCommand> {
+        Some(version::version_command("droid", &self.cmd))
+    }
 }
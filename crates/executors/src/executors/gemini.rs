@@ -15,6 +15,7 @@ use crate::{
     executors::{
         AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
     },
+    version::{self, TestedVersions, VersionCommand},
 };
 
 #[derive(Derivative, Clone, Serialize, Deserialize, TS, JsonSchema)]
@@ -35,8 +36,12 @@ pub struct Gemini {
 }
 
 impl Gemini {
+    pub fn base_command() -> &'static str {
+        "npx -y @google/gemini-cli@0.22.5"
+    }
+
     fn build_command_builder(&self) -> CommandBuilder {
-        let mut builder = CommandBuilder::new("npx -y @google/gemini-cli@0.22.5");
+        let mut builder = CommandBuilder::new(Self::base_command());
 
         if let Some(model) = &self.model {
             builder = builder.extend_params(["--model", model.as_str()]);
@@ -66,7 +71,9 @@ impl StandardCodingAgentExecutor for Gemini {
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let harness = AcpAgentHarness::new();
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_prompt(&env.prompt_with_instructions(prompt));
         let gemini_command = self.build_command_builder().build_initial()?;
         let approvals = if self.yolo.unwrap_or(false) {
             None
@@ -121,6 +128,14 @@ impl StandardCodingAgentExecutor for Gemini {
         dirs::home_dir().map(|home| home.join(".gemini").join("settings.json"))
     }
 
+    fn version_command(&self) -> Option<VersionCommand> {
+        Some(version::version_command(Self::base_command(), &self.cmd))
+    }
+
+    fn tested_versions(&self) -> Option<TestedVersions> {
+        Some(TestedVersions::new("0.22.0", "0.23.0"))
+    }
+
     fn get_availability_info(&self) -> AvailabilityInfo {
         if let Some(timestamp) = dirs::home_dir()
             .and_then(|home| std::fs::metadata(home.join(".gemini").join("oauth_creds.json")).ok())
@@ -15,13 +15,16 @@ use workspace_utils::msg_store::MsgStore;
 use crate::{
     actions::ExecutorAction,
     approvals::ExecutorApprovalService,
-    command::CommandBuildError,
+    command::{CmdOverrides, CommandBuildError},
     env::ExecutionEnv,
     executors::{
         amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot, cursor::CursorAgent,
-        custom_agent::CustomAgent, droid::Droid, gemini::Gemini, opencode::Opencode, qwen::QwenCode,
+        custom_agent::CustomAgent, droid::Droid, gemini::Gemini,
+        openai_compatible::OpenaiCompatible, opencode::Opencode, qwen::QwenCode, replay::Replay,
     },
+    failure::{self, AgentFailure},
     mcp_config::McpConfig,
+    version::{self, AgentVersionInfo, TestedVersions, VersionCommand},
 };
 
 pub mod acp;
@@ -33,8 +36,10 @@ pub mod cursor;
 pub mod custom_agent;
 pub mod droid;
 pub mod gemini;
+pub mod openai_compatible;
 pub mod opencode;
 pub mod qwen;
+pub mod replay;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -43,6 +48,8 @@ pub enum BaseAgentCapability {
     SessionFork,
     /// Agent requires a setup script before it can run (e.g., login, installation)
     SetupHelper,
+    /// Agent accepts user messages while it is running
+    Steer,
 }
 
 #[derive(Debug, Error)]
@@ -71,6 +78,8 @@ pub enum ExecutorError {
     SetupHelperNotSupported,
     #[error("Auth required: {0}")]
     AuthRequired(String),
+    #[error("Sandbox unavailable: {0}")]
+    SandboxUnavailable(String),
 }
 
 #[enum_dispatch]
@@ -106,6 +115,8 @@ pub enum CodingAgent {
     #[strum_discriminants(serde(rename = "CUSTOM"))]
     #[strum_discriminants(strum(serialize = "CUSTOM"))]
     CustomAgent,
+    Replay,
+    OpenaiCompatible,
 }
 
 impl CodingAgent {
@@ -155,25 +166,47 @@ impl CodingAgent {
         }
     }
 
+    /// Command overrides from the profile, for executors that run a command
+    pub fn cmd_overrides(&self) -> Option<&CmdOverrides> {
+        match self {
+            Self::ClaudeCode(agent) => Some(&agent.cmd),
+            Self::Amp(agent) => Some(&agent.cmd),
+            Self::Gemini(agent) => Some(&agent.cmd),
+            Self::Codex(agent) => Some(&agent.cmd),
+            Self::Opencode(agent) => Some(&agent.cmd),
+            Self::CursorAgent(agent) => Some(&agent.cmd),
+            Self::QwenCode(agent) => Some(&agent.cmd),
+            Self::Copilot(agent) => Some(&agent.cmd),
+            Self::Droid(agent) => Some(&agent.cmd),
+            Self::CustomAgent(agent) => Some(&agent.cmd),
+            Self::OpenaiCompatible(agent) => Some(&agent.cmd),
+            Self::Replay(_) => None,
+        }
+    }
+
     pub fn supports_mcp(&self) -> bool {
         self.default_mcp_config_path().is_some()
     }
 
     pub fn capabilities(&self) -> Vec<BaseAgentCapability> {
         match self {
+            Self::Amp(_) | Self::Droid(_) | Self::CustomAgent(_) => {
+                vec![BaseAgentCapability::SessionFork]
+            }
             Self::ClaudeCode(_)
-            | Self::Amp(_)
             | Self::Gemini(_)
             | Self::QwenCode(_)
-            | Self::Droid(_)
             | Self::Opencode(_)
-            | Self::CustomAgent(_) => vec![BaseAgentCapability::SessionFork],
+            | Self::OpenaiCompatible(_) => {
+                vec![BaseAgentCapability::SessionFork, BaseAgentCapability::Steer]
+            }
             Self::Codex(_) => vec![
                 BaseAgentCapability::SessionFork,
                 BaseAgentCapability::SetupHelper,
+                BaseAgentCapability::Steer,
             ],
             Self::CursorAgent(_) => vec![BaseAgentCapability::SetupHelper],
-            Self::Copilot(_) => vec![],
+            Self::Copilot(_) | Self::Replay(_) => vec![],
         }
     }
 }
@@ -216,6 +249,17 @@ pub trait StandardCodingAgentExecutor {
     ) -> Result<SpawnedChild, ExecutorError>;
     fn normalize_logs(&self, _raw_logs_event_store: Arc<MsgStore>, _worktree_path: &Path);
 
+    /// Normalize the logs of a follow-up run. Only differs from
+    /// [`Self::normalize_logs`] for executors that spawn follow-ups differently.
+    fn normalize_follow_up_logs(&self, raw_logs_event_store: Arc<MsgStore>, worktree_path: &Path) {
+        self.normalize_logs(raw_logs_event_store, worktree_path)
+    }
+
+    /// Recognise why a run failed from its output
+    fn classify_failure(&self, msg_store: &MsgStore) -> Option<AgentFailure> {
+        failure::classify_output(msg_store)
+    }
+
     // MCP configuration methods
     fn default_mcp_config_path(&self) -> Option<std::path::PathBuf>;
 
@@ -223,6 +267,34 @@ pub trait StandardCodingAgentExecutor {
         Err(ExecutorError::SetupHelperNotSupported)
     }
 
+    /// Command that prints the installed CLI's version
+    fn version_command(&self) -> Option<VersionCommand> {
+        None
+    }
+
+    /// CLI versions the executor's protocol handling and log normalization
+    /// were tested with
+    fn tested_versions(&self) -> Option<TestedVersions> {
+        None
+    }
+
+    /// Installed version as last detected. Never waits for the CLI: on a cache
+    /// miss detection starts in the background and the version is unknown
+    /// until it finishes.
+    fn get_version_info(&self) -> AgentVersionInfo {
+        let installed = match self.version_command() {
+            Some(command) => match version::cached_version(&command) {
+                Some(installed) => installed,
+                None => {
+                    version::detect_version_in_background(command);
+                    None
+                }
+            },
+            None => None,
+        };
+        AgentVersionInfo::new(installed, self.tested_versions())
+    }
+
     fn get_availability_info(&self) -> AvailabilityInfo {
         let config_files_found = self
             .default_mcp_config_path()
@@ -255,6 +327,10 @@ pub type ExecutorExitSignal = tokio::sync::oneshot::Receiver<ExecutorExitResult>
 /// When sent, the executor should attempt to interrupt gracefully before being killed.
 pub type InterruptSender = tokio::sync::oneshot::Sender<()>;
 
+/// Sender for user messages to deliver to a running executor.
+/// Only executors with [`BaseAgentCapability::Steer`] provide one.
+pub type SteerSender = tokio::sync::mpsc::UnboundedSender<String>;
+
 #[derive(Debug)]
 pub struct SpawnedChild {
     pub child: AsyncGroupChild,
@@ -262,6 +338,8 @@ pub struct SpawnedChild {
     pub exit_signal: Option<ExecutorExitSignal>,
     /// Container → Executor: signals when container wants to interrupt
     pub interrupt_sender: Option<InterruptSender>,
+    /// Container → Executor: user messages sent while the executor is running
+    pub steer_sender: Option<SteerSender>,
 }
 
 impl From<AsyncGroupChild> for SpawnedChild {
@@ -270,6 +348,7 @@ impl From<AsyncGroupChild> for SpawnedChild {
             child,
             exit_signal: None,
             interrupt_sender: None,
+            steer_sender: None,
         }
     }
 }
@@ -0,0 +1,296 @@
+//! Built-in coding agent for OpenAI-compatible chat completion endpoints.
+//!
+//! Unlike the other executors there is no vendor CLI: the agent loop runs in
+//! process and talks to any `/chat/completions` endpoint, such as llama.cpp or
+//! Ollama serving a local model. A placeholder process stands in for the
+//! agent so the container can treat it like any other run; the loop writes
+//! [`AgentEvent`]s to its stdout, which `normalize_logs` turns into entries.
+pub mod agent;
+pub mod client;
+pub mod session;
+pub mod tools;
+
+use std::{collections::HashMap, path::Path, process::Stdio, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use command_group::AsyncCommandGroup;
+use derivative::Derivative;
+use futures::StreamExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use workspace_utils::msg_store::MsgStore;
+
+use self::{
+    agent::{AgentRun, EventWriter},
+    client::{ChatClient, ChatMessage},
+    session::SessionStore,
+};
+use crate::{
+    approvals::ExecutorApprovalService,
+    command::CmdOverrides,
+    env::ExecutionEnv,
+    executors::{
+        AppendPrompt, AvailabilityInfo, ExecutorError, ExecutorExitResult, SpawnedChild,
+        StandardCodingAgentExecutor,
+    },
+    logs::{
+        NormalizedEntry, NormalizedEntryType,
+        stderr_processor::normalize_stderr_logs,
+        utils::{
+            EntryIndexProvider,
+            patch::{add_normalized_entry, replace_normalized_entry},
+        },
+    },
+    stdout_dup::create_stdout_pipe_writer,
+};
+
+const SESSION_NAMESPACE: &str = "openai_compatible_sessions";
+
+#[derive(Derivative, Clone, Serialize, Deserialize, TS, JsonSchema)]
+#[derivative(Debug, PartialEq)]
+pub struct OpenaiCompatible {
+    #[serde(default)]
+    pub append_prompt: AppendPrompt,
+    /// Base URL of the API, including the version prefix, e.g. `http://localhost:11434/v1`
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Environment variable holding the API key, for endpoints that require one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// Model requests per run before giving up, defaults to 50
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_turns: Option<u32>,
+    /// Timeout for commands the agent runs, defaults to 300 seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_timeout_secs: Option<u32>,
+    /// Auto-approve file edits and commands
+    #[serde(default = "default_to_true")]
+    pub auto_approve: bool,
+    #[serde(flatten)]
+    pub cmd: CmdOverrides,
+    #[serde(skip)]
+    #[ts(skip)]
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub approvals: Option<Arc<dyn ExecutorApprovalService>>,
+}
+
+/// Written by the agent loop to stdout, one JSON object per line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AgentEvent {
+    SessionStarted {
+        session_id: String,
+    },
+    /// A new entry, or the new state of the earlier entry with the same id
+    Entry {
+        id: usize,
+        entry: NormalizedEntry,
+    },
+}
+
+fn default_base_url() -> String {
+    "http://localhost:11434/v1".to_string()
+}
+
+fn default_to_true() -> bool {
+    true
+}
+
+impl OpenaiCompatible {
+    fn api_key(&self, env: &ExecutionEnv) -> Option<String> {
+        let name = self.api_key_env.as_ref()?;
+        env.vars
+            .get(name)
+            .cloned()
+            .or_else(|| std::env::var(name).ok())
+    }
+
+    async fn spawn_agent(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        history: Vec<ChatMessage>,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let env = env.clone().with_profile(&self.cmd);
+        let store = SessionStore::new(SESSION_NAMESPACE).map_err(ExecutorError::Io)?;
+
+        // The agent runs in this process; `cat` only stands in for it and
+        // idles until the exit signal stops it
+        let mut command = env.command("cat")?;
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir);
+        let mut child = command.group_spawn()?;
+        let stdin = child.inner().stdin.take();
+        let stdout = create_stdout_pipe_writer(&mut child)?;
+        let (steer_tx, steer_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+
+        let run = AgentRun {
+            client: ChatClient::new(&self.base_url, self.model.clone(), self.api_key(&env)),
+            store,
+            session_id: uuid::Uuid::new_v4().to_string(),
+            messages: history,
+            current_dir: current_dir.to_path_buf(),
+            env,
+            approvals: if self.auto_approve {
+                None
+            } else {
+                self.approvals.clone()
+            },
+            max_turns: self.max_turns.unwrap_or(50),
+            command_timeout: Duration::from_secs(u64::from(
+                self.command_timeout_secs.unwrap_or(300),
+            )),
+            steer_rx,
+        };
+        let prompt = self.append_prompt.combine_prompt(prompt);
+
+        let (exit_tx, exit_rx) = tokio::sync::oneshot::channel::<ExecutorExitResult>();
+        let (interrupt_tx, interrupt_rx) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(async move {
+            let mut events = EventWriter::new(stdout);
+            let result = run.run(&mut events, prompt, interrupt_rx).await;
+            events.flush().await;
+            drop(events);
+            let _ = exit_tx.send(result);
+            drop(stdin);
+        });
+
+        Ok(SpawnedChild {
+            child,
+            exit_signal: Some(exit_rx),
+            interrupt_sender: Some(interrupt_tx),
+            steer_sender: Some(steer_tx),
+        })
+    }
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for OpenaiCompatible {
+    fn use_approvals(&mut self, approvals: Arc<dyn ExecutorApprovalService>) {
+        self.approvals = Some(approvals);
+    }
+
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        self.spawn_agent(current_dir, prompt, Vec::new(), env).await
+    }
+
+    /// Continues from the stored transcript under a new session id, so the
+    /// original session can still be resumed
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let history = SessionStore::new(SESSION_NAMESPACE)
+            .and_then(|store| store.load(session_id))
+            .map_err(|e| {
+                ExecutorError::FollowUpNotSupported(format!(
+                    "Failed to load session {session_id}: {e}"
+                ))
+            })?;
+        self.spawn_agent(current_dir, prompt, history, env).await
+    }
+
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, _worktree_path: &Path) {
+        let entry_index = EntryIndexProvider::start_from(&msg_store);
+        normalize_stderr_logs(msg_store.clone(), entry_index.clone());
+
+        tokio::spawn(async move {
+            // Event entry id -> conversation index
+            let mut indices: HashMap<usize, usize> = HashMap::new();
+            let mut stored_session_id = false;
+
+            let mut stdout_lines = msg_store.stdout_lines_stream();
+            while let Some(Ok(line)) = stdout_lines.next().await {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<AgentEvent>(line) {
+                    Ok(AgentEvent::SessionStarted { session_id }) => {
+                        if !stored_session_id {
+                            msg_store.push_session_id(session_id);
+                            stored_session_id = true;
+                        }
+                    }
+                    Ok(AgentEvent::Entry { id, entry }) => match indices.get(&id) {
+                        Some(&index) => replace_normalized_entry(&msg_store, index, entry),
+                        None => {
+                            let index = add_normalized_entry(&msg_store, &entry_index, entry);
+                            indices.insert(id, index);
+                        }
+                    },
+                    Err(_) => {
+                        add_normalized_entry(
+                            &msg_store,
+                            &entry_index,
+                            NormalizedEntry {
+                                timestamp: None,
+                                entry_type: NormalizedEntryType::SystemMessage,
+                                content: line.to_string(),
+                                metadata: None,
+                            },
+                        );
+                    }
+                }
+            }
+        });
+    }
+
+    fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+
+    /// Nothing to install; whether the endpoint is reachable is only known
+    /// once a run starts
+    fn get_availability_info(&self) -> AvailabilityInfo {
+        AvailabilityInfo::InstallationFound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::{ActionType, ToolStatus};
+
+    #[test]
+    fn test_agent_event_roundtrip() {
+        let event = AgentEvent::Entry {
+            id: 3,
+            entry: NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::ToolUse {
+                    tool_name: "run_command".to_string(),
+                    action_type: ActionType::CommandRun {
+                        command: "cargo test".to_string(),
+                        result: None,
+                    },
+                    status: ToolStatus::Created,
+                },
+                content: "cargo test".to_string(),
+                metadata: None,
+            },
+        };
+        let line = serde_json::to_string(&event).unwrap();
+        assert!(line.starts_with(r#"{"type":"entry","id":3"#));
+        assert!(matches!(
+            serde_json::from_str::<AgentEvent>(&line).unwrap(),
+            AgentEvent::Entry { id: 3, .. }
+        ));
+    }
+}
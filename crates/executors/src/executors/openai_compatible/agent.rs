@@ -0,0 +1,350 @@
+//! The agent loop: ask the model for the next step, run the tools it calls and
+//! feed their output back until it answers without calling any.
+
+use std::{path::PathBuf, pin::Pin, sync::Arc, time::Duration};
+
+use futures::FutureExt;
+use serde_json::Value;
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt},
+    sync::{mpsc, oneshot},
+};
+use workspace_utils::approvals::ApprovalStatus;
+
+use super::{
+    AgentEvent,
+    client::{ChatClient, ChatMessage, ChatToolCall},
+    session::SessionStore,
+    tools::{self, PreparedTool, ToolCall},
+};
+use crate::{
+    approvals::{ExecutorApprovalService, ToolCallMetadata},
+    env::ExecutionEnv,
+    executors::ExecutorExitResult,
+    logs::{ActionType, NormalizedEntry, NormalizedEntryError, NormalizedEntryType, ToolStatus},
+};
+
+/// Writes [`AgentEvent`]s as JSON lines to the placeholder process's stdout
+pub struct EventWriter {
+    writer: Pin<Box<dyn AsyncWrite + Send>>,
+    next_id: usize,
+}
+
+impl EventWriter {
+    pub fn new(writer: impl AsyncWrite + Send + 'static) -> Self {
+        Self {
+            writer: Box::pin(writer),
+            next_id: 0,
+        }
+    }
+
+    pub async fn emit(&mut self, event: &AgentEvent) {
+        let Ok(mut line) = serde_json::to_string(event) else {
+            return;
+        };
+        line.push('\n');
+        if let Err(e) = self.writer.write_all(line.as_bytes()).await {
+            tracing::warn!("Failed to write agent event: {e}");
+        }
+    }
+
+    /// Emit a new entry, returning the id used to update it
+    async fn add(&mut self, entry: NormalizedEntry) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.emit(&AgentEvent::Entry { id, entry }).await;
+        id
+    }
+
+    async fn update(&mut self, id: usize, entry: NormalizedEntry) {
+        self.emit(&AgentEvent::Entry { id, entry }).await;
+    }
+
+    async fn error(&mut self, message: String) {
+        self.add(NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ErrorMessage {
+                error_type: NormalizedEntryError::Other,
+            },
+            content: message,
+            metadata: None,
+        })
+        .await;
+    }
+
+    pub async fn flush(&mut self) {
+        let _ = self.writer.flush().await;
+    }
+}
+
+pub struct AgentRun {
+    pub client: ChatClient,
+    pub store: SessionStore,
+    pub session_id: String,
+    /// Conversation so far, without the system prompt
+    pub messages: Vec<ChatMessage>,
+    pub current_dir: PathBuf,
+    pub env: ExecutionEnv,
+    pub approvals: Option<Arc<dyn ExecutorApprovalService>>,
+    pub max_turns: u32,
+    pub command_timeout: Duration,
+    /// User messages sent while the agent is running
+    pub steer_rx: mpsc::UnboundedReceiver<String>,
+}
+
+impl AgentRun {
+    fn system_prompt(&self) -> String {
+        let prompt = format!(
+            "You are a coding agent working in the directory {}. Use the tools to inspect \
+             and change files and to run commands; paths are relative to that directory. \
+             Make the changes the user asks for, check them where you can, then reply \
+             with a short summary of what you did.",
+            self.current_dir.display()
+        );
+        match &self.env.instructions {
+            Some(instructions) => {
+                format!("{prompt}\n\nFollow these project instructions:\n{instructions}")
+            }
+            None => prompt,
+        }
+    }
+
+    fn save(&self) {
+        if let Err(e) = self.store.save(&self.session_id, &self.messages) {
+            tracing::warn!("Failed to save session {}: {e}", self.session_id);
+        }
+    }
+
+    /// Add the user messages that arrived since the last turn to the
+    /// conversation, returning whether there were any
+    async fn take_steer_messages(&mut self, events: &mut EventWriter) -> bool {
+        let mut received = false;
+        while let Ok(message) = self.steer_rx.try_recv() {
+            events
+                .add(NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::UserMessage,
+                    content: message.clone(),
+                    metadata: None,
+                })
+                .await;
+            self.messages.push(ChatMessage::User { content: message });
+            received = true;
+        }
+        received
+    }
+
+    pub async fn run(
+        mut self,
+        events: &mut EventWriter,
+        prompt: String,
+        interrupt_rx: oneshot::Receiver<()>,
+    ) -> ExecutorExitResult {
+        let mut interrupt_rx = interrupt_rx.fuse();
+        let tools = tools::definitions();
+
+        events
+            .emit(&AgentEvent::SessionStarted {
+                session_id: self.session_id.clone(),
+            })
+            .await;
+        self.messages.push(ChatMessage::User { content: prompt });
+        self.save();
+
+        for _ in 0..self.max_turns {
+            self.take_steer_messages(events).await;
+            let mut request = vec![ChatMessage::System {
+                content: self.system_prompt(),
+            }];
+            request.extend(self.messages.iter().cloned());
+
+            let reply = tokio::select! {
+                reply = self.client.complete(&request, &tools) => reply,
+                _ = &mut interrupt_rx => return ExecutorExitResult::Success,
+            };
+            let (content, mut tool_calls) = match reply {
+                Ok(ChatMessage::Assistant {
+                    content,
+                    tool_calls,
+                }) => (content, tool_calls),
+                Ok(_) => unreachable!("ChatClient only returns assistant messages"),
+                Err(e) => {
+                    events.error(e).await;
+                    return ExecutorExitResult::Failure;
+                }
+            };
+            for call in tool_calls.iter_mut().filter(|call| call.id.is_empty()) {
+                call.id = format!("call_{}", uuid::Uuid::new_v4().simple());
+            }
+
+            if let Some(text) = content.as_deref().map(str::trim)
+                && !text.is_empty()
+            {
+                events
+                    .add(NormalizedEntry {
+                        timestamp: None,
+                        entry_type: NormalizedEntryType::AssistantMessage,
+                        content: text.to_string(),
+                        metadata: None,
+                    })
+                    .await;
+            }
+            self.messages.push(ChatMessage::Assistant {
+                content,
+                tool_calls: tool_calls.clone(),
+            });
+
+            if tool_calls.is_empty() {
+                // A message sent while the model was answering still needs a reply
+                if self.take_steer_messages(events).await {
+                    self.save();
+                    continue;
+                }
+                self.save();
+                return ExecutorExitResult::Success;
+            }
+
+            let mut interrupted = false;
+            for call in &tool_calls {
+                // Every call needs an answer or the transcript can't be resumed
+                let output = if interrupted {
+                    "Cancelled by the user".to_string()
+                } else {
+                    tokio::select! {
+                        output = self.run_tool(events, call) => output,
+                        _ = &mut interrupt_rx => {
+                            interrupted = true;
+                            "Cancelled by the user".to_string()
+                        }
+                    }
+                };
+                self.messages.push(ChatMessage::Tool {
+                    tool_call_id: call.id.clone(),
+                    content: output,
+                });
+            }
+            self.save();
+            if interrupted {
+                return ExecutorExitResult::Success;
+            }
+        }
+
+        events
+            .error(format!(
+                "Stopped after {} turns without a final answer",
+                self.max_turns
+            ))
+            .await;
+        ExecutorExitResult::Failure
+    }
+
+    /// Run a tool call, reporting it as a tool use entry, and return the output
+    /// for the model
+    async fn run_tool(&self, events: &mut EventWriter, call: &ChatToolCall) -> String {
+        let tool_name = call.function.name.clone();
+        let metadata = serde_json::to_value(ToolCallMetadata {
+            tool_call_id: call.id.clone(),
+        })
+        .ok();
+        let tool_entry =
+            |action_type: ActionType, status: ToolStatus, content: String| NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::ToolUse {
+                    tool_name: tool_name.clone(),
+                    action_type,
+                    status,
+                },
+                content,
+                metadata: metadata.clone(),
+            };
+
+        let arguments = call.function.parsed_arguments();
+        let prepared = match &arguments {
+            Ok(arguments) => match ToolCall::parse(&tool_name, arguments.clone()) {
+                Ok(tool_call) => tool_call.prepare(&self.current_dir).await,
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e.clone()),
+        };
+        let prepared = match prepared {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                let action_type = ActionType::Other {
+                    description: tool_name.clone(),
+                };
+                events
+                    .add(tool_entry(action_type, ToolStatus::Failed, e.clone()))
+                    .await;
+                return e;
+            }
+        };
+
+        let mut entry = tool_entry(
+            prepared.action_type(),
+            ToolStatus::Created,
+            describe(&prepared),
+        );
+        let id = events.add(entry.clone()).await;
+
+        if prepared.needs_approval()
+            && let Some(approvals) = &self.approvals
+        {
+            events.flush().await;
+            let input = arguments.unwrap_or(Value::Null);
+            match approvals
+                .request_tool_approval(&tool_name, input, &call.id)
+                .await
+            {
+                Ok(ApprovalStatus::Approved) => {}
+                Ok(status) => {
+                    let tool_status =
+                        ToolStatus::from_approval_status(&status).unwrap_or(ToolStatus::Failed);
+                    if let Some(denied) = entry.with_tool_status(tool_status) {
+                        events.update(id, denied).await;
+                    }
+                    return match status {
+                        ApprovalStatus::Denied {
+                            reason: Some(reason),
+                        } => format!("The user denied this tool call: {reason}"),
+                        ApprovalStatus::TimedOut => {
+                            "Approval for this tool call timed out".to_string()
+                        }
+                        _ => "The user denied this tool call".to_string(),
+                    };
+                }
+                Err(e) => {
+                    if let Some(failed) = entry.with_tool_status(ToolStatus::Failed) {
+                        events.update(id, failed).await;
+                    }
+                    return format!("Approval request failed: {e}");
+                }
+            }
+        }
+
+        let outcome = prepared
+            .run(&self.current_dir, &self.env, self.command_timeout)
+            .await;
+        if let Some(action_type) = outcome.action_type {
+            entry = tool_entry(action_type, ToolStatus::Created, entry.content);
+        }
+        let status = if outcome.success {
+            ToolStatus::Success
+        } else {
+            ToolStatus::Failed
+        };
+        if let Some(done) = entry.with_tool_status(status) {
+            events.update(id, done).await;
+        }
+        outcome.output
+    }
+}
+
+fn describe(prepared: &PreparedTool) -> String {
+    match prepared {
+        PreparedTool::ReadFile { path, .. }
+        | PreparedTool::WriteFile { path, .. }
+        | PreparedTool::EditFile { path, .. } => path.clone(),
+        PreparedTool::Search { pattern, .. } => pattern.clone(),
+        PreparedTool::RunCommand { command } => command.clone(),
+    }
+}
@@ -0,0 +1,179 @@
+//! Minimal client for the `/chat/completions` endpoint of OpenAI-compatible
+//! servers such as llama.cpp, Ollama and vLLM.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// Local models can take a while to answer on modest hardware
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "role", rename_all = "lowercase")]
+pub enum ChatMessage {
+    System {
+        content: String,
+    },
+    User {
+        content: String,
+    },
+    Assistant {
+        #[serde(default)]
+        content: Option<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tool_calls: Vec<ChatToolCall>,
+    },
+    Tool {
+        tool_call_id: String,
+        content: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatToolCall {
+    /// Some servers leave this empty; the agent assigns one before use
+    #[serde(default)]
+    pub id: String,
+    #[serde(rename = "type", default = "default_tool_call_type")]
+    pub call_type: String,
+    pub function: ChatFunctionCall,
+}
+
+fn default_tool_call_type() -> String {
+    "function".to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatFunctionCall {
+    pub name: String,
+    /// A JSON-encoded string per the OpenAI API, though some servers send an object
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+impl ChatFunctionCall {
+    pub fn parsed_arguments(&self) -> Result<Value, String> {
+        match &self.arguments {
+            Value::String(s) if s.trim().is_empty() => Ok(json!({})),
+            Value::String(s) => serde_json::from_str(s)
+                .map_err(|e| format!("Arguments for `{}` are not valid JSON: {e}", self.name)),
+            Value::Null => Ok(json!({})),
+            other => Ok(other.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+pub struct ChatClient {
+    http: reqwest::Client,
+    base_url: String,
+    model: Option<String>,
+    api_key: Option<String>,
+}
+
+impl ChatClient {
+    pub fn new(base_url: &str, model: Option<String>, api_key: Option<String>) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model,
+            api_key,
+        }
+    }
+
+    /// Request the next assistant message for the conversation
+    pub async fn complete(
+        &self,
+        messages: &[ChatMessage],
+        tools: &Value,
+    ) -> Result<ChatMessage, String> {
+        let mut body = json!({
+            "messages": messages,
+            "tools": tools,
+            "stream": false,
+        });
+        if let Some(model) = &self.model {
+            body["model"] = json!(model);
+        }
+
+        let mut request = self
+            .http
+            .post(format!("{}/chat/completions", self.base_url))
+            .json(&body);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Request to {} failed: {e}", self.base_url))?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("Model endpoint returned {status}: {text}"));
+        }
+
+        let response: ChatCompletionResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Unexpected response from model endpoint: {e}"))?;
+        match response.choices.into_iter().next().map(|c| c.message) {
+            Some(message @ ChatMessage::Assistant { .. }) => Ok(message),
+            Some(_) | None => Err("Model endpoint returned no assistant message".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let response: ChatCompletionResponse = serde_json::from_str(
+            r#"{
+                "choices": [{
+                    "index": 0,
+                    "message": {
+                        "role": "assistant",
+                        "content": null,
+                        "tool_calls": [{
+                            "id": "call_1",
+                            "type": "function",
+                            "function": { "name": "read_file", "arguments": "{\"path\":\"README.md\"}" }
+                        }]
+                    },
+                    "finish_reason": "tool_calls"
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let ChatMessage::Assistant {
+            content,
+            tool_calls,
+        } = &response.choices[0].message
+        else {
+            panic!("Expected an assistant message");
+        };
+        assert_eq!(content, &None);
+        assert_eq!(
+            tool_calls[0].function.parsed_arguments().unwrap(),
+            json!({ "path": "README.md" })
+        );
+    }
+}
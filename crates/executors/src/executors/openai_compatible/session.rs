@@ -0,0 +1,45 @@
+use std::{
+    fs,
+    io::{self, Result},
+    path::PathBuf,
+};
+
+use super::client::ChatMessage;
+
+/// Persists conversation transcripts so follow-ups can resume them
+pub struct SessionStore {
+    base_dir: PathBuf,
+}
+
+impl SessionStore {
+    pub fn new(namespace: &str) -> Result<Self> {
+        let mut vk_dir = dirs::home_dir()
+            .ok_or_else(|| io::Error::other("Could not determine home directory"))?
+            .join(".vibe-kanban");
+
+        if cfg!(debug_assertions) {
+            vk_dir = vk_dir.join("dev");
+        }
+
+        let base_dir = vk_dir.join(namespace);
+        fs::create_dir_all(&base_dir)?;
+
+        Ok(Self { base_dir })
+    }
+
+    fn session_file_path(&self, session_id: &str) -> PathBuf {
+        self.base_dir.join(format!("{session_id}.json"))
+    }
+
+    /// Load the transcript of a session, without its system prompt
+    pub fn load(&self, session_id: &str) -> Result<Vec<ChatMessage>> {
+        let content = fs::read_to_string(self.session_file_path(session_id))?;
+        serde_json::from_str(&content).map_err(io::Error::other)
+    }
+
+    /// Replace the stored transcript of a session
+    pub fn save(&self, session_id: &str, messages: &[ChatMessage]) -> Result<()> {
+        let content = serde_json::to_string(messages).map_err(io::Error::other)?;
+        fs::write(self.session_file_path(session_id), content)
+    }
+}
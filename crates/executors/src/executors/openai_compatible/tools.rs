@@ -0,0 +1,652 @@
+//! Tools offered to the model, and how each maps to a normalized action.
+
+use std::{
+    path::{Component, Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use workspace_utils::diff::create_unified_diff;
+
+use crate::{
+    env::ExecutionEnv,
+    logs::{ActionType, CommandExitStatus, CommandRunResult, FileChange},
+};
+
+/// Output beyond this many characters is cut before it's sent back to the model
+const MAX_OUTPUT_CHARS: usize = 20_000;
+
+/// Lines returned by `read_file` when no limit is given
+const DEFAULT_READ_LIMIT: usize = 2_000;
+
+/// Matches returned by `search`
+const MAX_SEARCH_MATCHES: usize = 200;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "name", content = "arguments", rename_all = "snake_case")]
+pub enum ToolCall {
+    ReadFile {
+        path: String,
+        /// 1-based line to start from
+        #[serde(default)]
+        offset: Option<usize>,
+        #[serde(default)]
+        limit: Option<usize>,
+    },
+    WriteFile {
+        path: String,
+        content: String,
+    },
+    EditFile {
+        path: String,
+        old_string: String,
+        new_string: String,
+    },
+    Search {
+        pattern: String,
+        #[serde(default)]
+        path: Option<String>,
+    },
+    RunCommand {
+        command: String,
+    },
+}
+
+/// JSON schema of every tool, in the `tools` format of the chat completions API
+pub fn definitions() -> Value {
+    json!([
+        {
+            "type": "function",
+            "function": {
+                "name": "read_file",
+                "description": "Read a file in the working directory. Lines are prefixed with their line number.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string", "description": "Path relative to the working directory" },
+                        "offset": { "type": "integer", "description": "1-based line to start reading from" },
+                        "limit": { "type": "integer", "description": "Maximum number of lines to read" }
+                    },
+                    "required": ["path"]
+                }
+            }
+        },
+        {
+            "type": "function",
+            "function": {
+                "name": "write_file",
+                "description": "Create a file, or overwrite it with new content.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string", "description": "Path relative to the working directory" },
+                        "content": { "type": "string" }
+                    },
+                    "required": ["path", "content"]
+                }
+            }
+        },
+        {
+            "type": "function",
+            "function": {
+                "name": "edit_file",
+                "description": "Replace an exact snippet in a file. `old_string` must appear exactly once; include surrounding lines to make it unique.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string", "description": "Path relative to the working directory" },
+                        "old_string": { "type": "string" },
+                        "new_string": { "type": "string" }
+                    },
+                    "required": ["path", "old_string", "new_string"]
+                }
+            }
+        },
+        {
+            "type": "function",
+            "function": {
+                "name": "search",
+                "description": "Search file contents with a regular expression. Returns matching lines as `path:line:text`.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "pattern": { "type": "string" },
+                        "path": { "type": "string", "description": "Directory or file to search, defaults to the working directory" }
+                    },
+                    "required": ["pattern"]
+                }
+            }
+        },
+        {
+            "type": "function",
+            "function": {
+                "name": "run_command",
+                "description": "Run a shell command in the working directory and return its combined output.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "command": { "type": "string" }
+                    },
+                    "required": ["command"]
+                }
+            }
+        }
+    ])
+}
+
+/// A tool call checked against the worktree and ready to run
+#[derive(Debug, Clone)]
+pub enum PreparedTool {
+    ReadFile {
+        path: String,
+        offset: usize,
+        limit: usize,
+    },
+    /// Full new content of the file
+    WriteFile {
+        path: String,
+        content: String,
+        change: FileChange,
+    },
+    /// Applied to the file as it is when the edit runs, after any approval;
+    /// `change` is the diff against the file as it was when prepared
+    EditFile {
+        path: String,
+        old_string: String,
+        new_string: String,
+        change: FileChange,
+    },
+    Search {
+        pattern: String,
+        path: String,
+    },
+    RunCommand {
+        command: String,
+    },
+}
+
+/// Result of running a tool
+#[derive(Debug, Clone)]
+pub struct ToolOutcome {
+    /// Sent back to the model
+    pub output: String,
+    pub success: bool,
+    /// Set when running the tool adds detail to its action, e.g. command output
+    pub action_type: Option<ActionType>,
+}
+
+impl ToolOutcome {
+    fn failed(output: impl Into<String>) -> Self {
+        Self {
+            output: output.into(),
+            success: false,
+            action_type: None,
+        }
+    }
+}
+
+impl ToolCall {
+    /// Parse a tool call from the name and arguments returned by the model
+    pub fn parse(name: &str, arguments: Value) -> Result<Self, String> {
+        serde_json::from_value(json!({ "name": name, "arguments": arguments }))
+            .map_err(|e| format!("Invalid call to tool `{name}`: {e}"))
+    }
+
+    /// Resolve paths and compute edits without changing anything on disk
+    pub async fn prepare(self, current_dir: &Path) -> Result<PreparedTool, String> {
+        match self {
+            ToolCall::ReadFile {
+                path,
+                offset,
+                limit,
+            } => {
+                resolve_path(current_dir, &path)?;
+                Ok(PreparedTool::ReadFile {
+                    path,
+                    offset: offset.unwrap_or(1).max(1),
+                    limit: limit.unwrap_or(DEFAULT_READ_LIMIT),
+                })
+            }
+            ToolCall::WriteFile { path, content } => {
+                resolve_path(current_dir, &path)?;
+                Ok(PreparedTool::WriteFile {
+                    path,
+                    change: FileChange::Write {
+                        content: content.clone(),
+                    },
+                    content,
+                })
+            }
+            ToolCall::EditFile {
+                path,
+                old_string,
+                new_string,
+            } => {
+                let full_path = resolve_path(current_dir, &path)?;
+                let original = tokio::fs::read_to_string(&full_path)
+                    .await
+                    .map_err(|e| format!("Failed to read {path}: {e}"))?;
+                let content = apply_edit(&original, &old_string, &new_string)
+                    .map_err(|e| format!("Failed to edit {path}: {e}"))?;
+                Ok(PreparedTool::EditFile {
+                    change: FileChange::Edit {
+                        unified_diff: create_unified_diff(&path, &original, &content),
+                        has_line_numbers: true,
+                    },
+                    path,
+                    old_string,
+                    new_string,
+                })
+            }
+            ToolCall::Search { pattern, path } => {
+                let path = path.unwrap_or_else(|| ".".to_string());
+                resolve_path(current_dir, &path)?;
+                Ok(PreparedTool::Search { pattern, path })
+            }
+            ToolCall::RunCommand { command } => Ok(PreparedTool::RunCommand { command }),
+        }
+    }
+}
+
+impl PreparedTool {
+    /// Whether running the tool changes the worktree, and so needs approval
+    pub fn needs_approval(&self) -> bool {
+        matches!(
+            self,
+            PreparedTool::WriteFile { .. }
+                | PreparedTool::EditFile { .. }
+                | PreparedTool::RunCommand { .. }
+        )
+    }
+
+    pub fn action_type(&self) -> ActionType {
+        match self {
+            PreparedTool::ReadFile { path, .. } => ActionType::FileRead { path: path.clone() },
+            PreparedTool::WriteFile { path, change, .. }
+            | PreparedTool::EditFile { path, change, .. } => ActionType::FileEdit {
+                path: path.clone(),
+                changes: vec![change.clone()],
+            },
+            PreparedTool::Search { pattern, .. } => ActionType::Search {
+                query: pattern.clone(),
+            },
+            PreparedTool::RunCommand { command } => ActionType::CommandRun {
+                command: command.clone(),
+                result: None,
+            },
+        }
+    }
+
+    pub async fn run(
+        &self,
+        current_dir: &Path,
+        env: &ExecutionEnv,
+        command_timeout: Duration,
+    ) -> ToolOutcome {
+        match self {
+            PreparedTool::ReadFile {
+                path,
+                offset,
+                limit,
+            } => {
+                let result = match resolve_path(current_dir, path) {
+                    Ok(full_path) => tokio::fs::read_to_string(&full_path)
+                        .await
+                        .map_err(|e| format!("Failed to read {path}: {e}")),
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(content) => ToolOutcome {
+                        output: number_lines(&content, *offset, *limit),
+                        success: true,
+                        action_type: None,
+                    },
+                    Err(e) => ToolOutcome::failed(e),
+                }
+            }
+            PreparedTool::WriteFile { path, content, .. } => {
+                let result = async {
+                    let full_path = resolve_path(current_dir, path)?;
+                    if let Some(parent) = full_path.parent() {
+                        tokio::fs::create_dir_all(parent)
+                            .await
+                            .map_err(|e| e.to_string())?;
+                    }
+                    tokio::fs::write(&full_path, content)
+                        .await
+                        .map_err(|e| e.to_string())
+                }
+                .await;
+                match result {
+                    Ok(()) => ToolOutcome {
+                        output: format!("Wrote {path}"),
+                        success: true,
+                        action_type: None,
+                    },
+                    Err(e) => ToolOutcome::failed(format!("Failed to write {path}: {e}")),
+                }
+            }
+            PreparedTool::EditFile {
+                path,
+                old_string,
+                new_string,
+                ..
+            } => {
+                // The file may have changed while the edit waited for approval
+                let result = async {
+                    let full_path = resolve_path(current_dir, path)?;
+                    let original = tokio::fs::read_to_string(&full_path)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    let content = apply_edit(&original, old_string, new_string)?;
+                    tokio::fs::write(&full_path, content)
+                        .await
+                        .map_err(|e| e.to_string())
+                }
+                .await;
+                match result {
+                    Ok(()) => ToolOutcome {
+                        output: format!("Edited {path}"),
+                        success: true,
+                        action_type: None,
+                    },
+                    Err(e) => ToolOutcome::failed(format!("Failed to edit {path}: {e}")),
+                }
+            }
+            PreparedTool::Search { pattern, path } => {
+                let args = [
+                    "-rnI",
+                    "--exclude-dir=.git",
+                    "--exclude-dir=node_modules",
+                    "--exclude-dir=target",
+                    "-E",
+                    "-e",
+                    pattern.as_str(),
+                    "--",
+                    path.as_str(),
+                ];
+                match run_process(env, "grep", &args, current_dir, command_timeout).await {
+                    // grep exits with 1 when nothing matched
+                    Ok((Some(1), _)) => ToolOutcome {
+                        output: "No matches found".to_string(),
+                        success: true,
+                        action_type: None,
+                    },
+                    Ok((Some(0), output)) => {
+                        let total = output.lines().count();
+                        let mut matches = output
+                            .lines()
+                            .take(MAX_SEARCH_MATCHES)
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        if total > MAX_SEARCH_MATCHES {
+                            matches.push_str(&format!(
+                                "\n... {} more matches, narrow the search",
+                                total - MAX_SEARCH_MATCHES
+                            ));
+                        }
+                        ToolOutcome {
+                            output: truncate_output(matches),
+                            success: true,
+                            action_type: None,
+                        }
+                    }
+                    Ok((_, output)) => ToolOutcome::failed(truncate_output(output)),
+                    Err(e) => ToolOutcome::failed(e),
+                }
+            }
+            PreparedTool::RunCommand { command } => {
+                let (exit_code, output) = match run_process(
+                    env,
+                    "sh",
+                    &["-c", command.as_str()],
+                    current_dir,
+                    command_timeout,
+                )
+                .await
+                {
+                    Ok(result) => result,
+                    Err(e) => (None, e),
+                };
+                let output = truncate_output(output);
+                ToolOutcome {
+                    success: exit_code == Some(0),
+                    action_type: Some(ActionType::CommandRun {
+                        command: command.clone(),
+                        result: Some(CommandRunResult {
+                            exit_status: exit_code.map(|code| CommandExitStatus::ExitCode { code }),
+                            output: Some(output.clone()),
+                        }),
+                    }),
+                    output: match exit_code {
+                        Some(code) => format!("Exit code: {code}\n{output}"),
+                        None => output,
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// Resolve `path` inside `current_dir`, rejecting paths that escape it,
+/// including through symlinks. Absolute paths are accepted when they point
+/// into `current_dir`.
+pub fn resolve_path(current_dir: &Path, path: &str) -> Result<PathBuf, String> {
+    let requested = Path::new(path);
+    let relative = if requested.is_absolute() {
+        requested
+            .strip_prefix(current_dir)
+            .map_err(|_| format!("Path is outside the working directory: {path}"))?
+    } else {
+        requested
+    };
+
+    let is_contained = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if !is_contained {
+        return Err(format!("Path is outside the working directory: {path}"));
+    }
+    let full_path = current_dir.join(relative);
+
+    // Files that don't exist yet are checked through their deepest existing
+    // ancestor, which is where they would be created
+    let root = std::fs::canonicalize(current_dir)
+        .map_err(|e| format!("Failed to resolve the working directory: {e}"))?;
+    let existing = full_path
+        .ancestors()
+        .find(|ancestor| ancestor.symlink_metadata().is_ok())
+        .unwrap_or(current_dir);
+    let resolved =
+        std::fs::canonicalize(existing).map_err(|e| format!("Failed to resolve {path}: {e}"))?;
+    if !resolved.starts_with(&root) {
+        return Err(format!("Path is outside the working directory: {path}"));
+    }
+    Ok(full_path)
+}
+
+/// Replace the single occurrence of `old_string` in `content`
+fn apply_edit(content: &str, old_string: &str, new_string: &str) -> Result<String, String> {
+    if old_string.is_empty() {
+        return Err("old_string must not be empty".to_string());
+    }
+    match content.matches(old_string).count() {
+        0 => Err("old_string was not found".to_string()),
+        1 => Ok(content.replacen(old_string, new_string, 1)),
+        n => Err(format!(
+            "old_string appears {n} times, include more context to make it unique"
+        )),
+    }
+}
+
+fn number_lines(content: &str, offset: usize, limit: usize) -> String {
+    let total = content.lines().count();
+    let mut output = content
+        .lines()
+        .enumerate()
+        .skip(offset - 1)
+        .take(limit)
+        .map(|(i, line)| format!("{}\t{line}", i + 1))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let last = (offset - 1).saturating_add(limit).min(total);
+    if last < total {
+        output.push_str(&format!(
+            "\n... {} more lines, read from offset {}",
+            total - last,
+            last + 1
+        ));
+    }
+    truncate_output(output)
+}
+
+fn truncate_output(mut output: String) -> String {
+    if output.len() > MAX_OUTPUT_CHARS {
+        let mut end = MAX_OUTPUT_CHARS;
+        while !output.is_char_boundary(end) {
+            end -= 1;
+        }
+        output.truncate(end);
+        output.push_str("\n... output truncated");
+    }
+    output
+}
+
+/// Run a process to completion, returning its exit code and combined output
+async fn run_process(
+    env: &ExecutionEnv,
+    program: &str,
+    args: &[&str],
+    current_dir: &Path,
+    timeout: Duration,
+) -> Result<(Option<i32>, String), String> {
+    let mut command = env
+        .command(program)
+        .map_err(|e| format!("Failed to run {program}: {e}"))?;
+    command
+        .kill_on_drop(true)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .current_dir(current_dir)
+        .args(args);
+    env.apply_to_command(&mut command);
+
+    let output = match tokio::time::timeout(timeout, command.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return Err(format!("Failed to run {program}: {e}")),
+        Err(_) => {
+            return Err(format!(
+                "Command timed out after {} seconds",
+                timeout.as_secs()
+            ));
+        }
+    };
+
+    let mut combined = String::from_utf8_lossy(&output.stdout).into_owned();
+    combined.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok((output.status.code(), combined))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tool_call() {
+        let call = ToolCall::parse(
+            "edit_file",
+            json!({ "path": "src/lib.rs", "old_string": "a", "new_string": "b" }),
+        )
+        .unwrap();
+        assert!(matches!(call, ToolCall::EditFile { path, .. } if path == "src/lib.rs"));
+
+        assert!(ToolCall::parse("delete_everything", json!({})).is_err());
+        assert!(ToolCall::parse("write_file", json!({ "path": "a" })).is_err());
+    }
+
+    #[test]
+    fn test_apply_edit() {
+        assert_eq!(apply_edit("a b c", "b", "x").unwrap(), "a x c");
+        assert!(apply_edit("a b c", "z", "x").is_err());
+        assert!(apply_edit("a a", "a", "x").is_err());
+        assert!(apply_edit("a", "", "x").is_err());
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        let dir = workspace.path();
+        assert_eq!(
+            resolve_path(dir, "repo/src/main.rs").unwrap(),
+            dir.join("repo/src/main.rs")
+        );
+        assert_eq!(
+            resolve_path(dir, &dir.join("repo/a.rs").to_string_lossy()).unwrap(),
+            dir.join("repo/a.rs")
+        );
+        assert!(resolve_path(dir, "../outside").is_err());
+        assert!(resolve_path(dir, "/etc/passwd").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_path_rejects_symlinks_out_of_worktree() {
+        let outside = tempfile::TempDir::new().unwrap();
+        let workspace = tempfile::TempDir::new().unwrap();
+        let dir = workspace.path();
+        std::os::unix::fs::symlink(outside.path(), dir.join("escape")).unwrap();
+        std::os::unix::fs::symlink(outside.path().join("missing"), dir.join("dangling")).unwrap();
+        std::fs::create_dir(dir.join("src")).unwrap();
+        std::os::unix::fs::symlink(dir.join("src"), dir.join("alias")).unwrap();
+
+        assert!(resolve_path(dir, "escape/secret").is_err());
+        assert!(resolve_path(dir, "escape").is_err());
+        assert!(resolve_path(dir, "dangling").is_err());
+        assert!(resolve_path(dir, "alias/new.rs").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_edit_applies_to_file_as_it_is_when_run() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        let dir = workspace.path();
+        std::fs::write(dir.join("lib.rs"), "fn a() {}\n").unwrap();
+
+        let prepared = ToolCall::EditFile {
+            path: "lib.rs".to_string(),
+            old_string: "fn a()".to_string(),
+            new_string: "fn b()".to_string(),
+        }
+        .prepare(dir)
+        .await
+        .unwrap();
+        assert!(prepared.needs_approval());
+        assert_eq!(
+            std::fs::read_to_string(dir.join("lib.rs")).unwrap(),
+            "fn a() {}\n"
+        );
+
+        // Changed while the edit waited for approval
+        std::fs::write(dir.join("lib.rs"), "fn a() {}\nfn c() {}\n").unwrap();
+        let outcome = prepared
+            .run(dir, &ExecutionEnv::new(), Duration::from_secs(5))
+            .await;
+        assert!(outcome.success);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("lib.rs")).unwrap(),
+            "fn b() {}\nfn c() {}\n"
+        );
+    }
+
+    #[test]
+    fn test_number_lines() {
+        let content = "one\ntwo\nthree\n";
+        assert_eq!(
+            number_lines(content, 2, 1),
+            "2\ttwo\n... 1 more lines, read from offset 3"
+        );
+        assert_eq!(number_lines(content, 1, 10), "1\tone\n2\ttwo\n3\tthree");
+    }
+}
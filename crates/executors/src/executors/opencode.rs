@@ -15,6 +15,7 @@ use crate::{
         AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
         acp::AcpAgentHarness,
     },
+    version::{self, TestedVersions, VersionCommand},
 };
 
 #[derive(Derivative, Clone, Serialize, Deserialize, TS, JsonSchema)]
@@ -38,8 +39,12 @@ pub struct Opencode {
 }
 
 impl Opencode {
+    pub fn base_command() -> &'static str {
+        "npx -y opencode-ai@1.1.3"
+    }
+
     fn build_command_builder(&self) -> CommandBuilder {
-        let builder = CommandBuilder::new("npx -y opencode-ai@1.1.3").extend_params(["acp"]);
+        let builder = CommandBuilder::new(Self::base_command()).extend_params(["acp"]);
         apply_overrides(builder, &self.cmd)
     }
 
@@ -60,7 +65,9 @@ impl StandardCodingAgentExecutor for Opencode {
         prompt: &str,
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_prompt(&env.prompt_with_instructions(prompt));
 
         let mut harness = Self::harness();
         if let Some(model) = &self.model {
@@ -138,6 +145,14 @@ impl StandardCodingAgentExecutor for Opencode {
         }
     }
 
+    fn version_command(&self) -> Option<VersionCommand> {
+        Some(version::version_command(Self::base_command(), &self.cmd))
+    }
+
+    fn tested_versions(&self) -> Option<TestedVersions> {
+        Some(TestedVersions::new("1.1.0", "1.2.0"))
+    }
+
     fn get_availability_info(&self) -> AvailabilityInfo {
         let mcp_config_found = self
             .default_mcp_config_path()
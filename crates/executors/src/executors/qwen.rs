@@ -15,6 +15,7 @@ use crate::{
         AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
         gemini::AcpAgentHarness,
     },
+    version::{self, TestedVersions, VersionCommand},
 };
 
 #[derive(Derivative, Clone, Serialize, Deserialize, TS, JsonSchema)]
@@ -33,8 +34,12 @@ pub struct QwenCode {
 }
 
 impl QwenCode {
+    pub fn base_command() -> &'static str {
+        "npx -y @qwen-code/qwen-code@0.2.1"
+    }
+
     fn build_command_builder(&self) -> CommandBuilder {
-        let mut builder = CommandBuilder::new("npx -y @qwen-code/qwen-code@0.2.1");
+        let mut builder = CommandBuilder::new(Self::base_command());
 
         if self.yolo.unwrap_or(false) {
             builder = builder.extend_params(["--yolo"]);
@@ -57,7 +62,9 @@ impl StandardCodingAgentExecutor for QwenCode {
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let qwen_command = self.build_command_builder().build_initial()?;
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_prompt(&env.prompt_with_instructions(prompt));
         let harness = AcpAgentHarness::with_session_namespace("qwen_sessions");
         let approvals = if self.yolo.unwrap_or(false) {
             None
@@ -113,6 +120,14 @@ impl StandardCodingAgentExecutor for QwenCode {
         dirs::home_dir().map(|home| home.join(".qwen").join("settings.json"))
     }
 
+    fn version_command(&self) -> Option<VersionCommand> {
+        Some(version::version_command(Self::base_command(), &self.cmd))
+    }
+
+    fn tested_versions(&self) -> Option<TestedVersions> {
+        Some(TestedVersions::new("0.2.0", "0.3.0"))
+    }
+
     fn get_availability_info(&self) -> AvailabilityInfo {
         let mcp_config_found = self
             .default_mcp_config_path()
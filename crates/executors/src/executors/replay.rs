@@ -0,0 +1,401 @@
+//! Replays a recorded agent session instead of running an agent CLI.
+//!
+//! A fixture holds the agent whose log normalizer applies, the raw
+//! stdout/stderr of the run, file edits to apply to the worktree and the exit
+//! code. Replaying one exercises process spawning, the exit monitor, commits
+//! and log normalization without the real agent installed.
+
+use std::{
+    path::{Component, Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use command_group::AsyncCommandGroup;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use tokio::io::AsyncWriteExt;
+use ts_rs::TS;
+use workspace_utils::msg_store::MsgStore;
+
+use crate::{
+    env::ExecutionEnv,
+    executors::{
+        AvailabilityInfo, CodingAgent, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+    },
+    stdout_dup::create_stdout_pipe_writer,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct Replay {
+    /// Path to the fixture replayed for the initial request
+    pub fixture: String,
+    /// Fixture replayed for follow-ups; defaults to `fixture`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub follow_up_fixture: Option<String>,
+    /// Honor recorded `wait` steps instead of replaying as fast as possible
+    #[serde(default)]
+    pub honor_timing: bool,
+}
+
+/// Rejects fixtures recorded from a replay, which would recurse in `normalize_logs`
+fn deserialize_recorded_agent<'de, D>(deserializer: D) -> Result<CodingAgent, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let agent = CodingAgent::deserialize(deserializer)?;
+    if matches!(agent, CodingAgent::Replay(_)) {
+        return Err(serde::de::Error::custom(
+            "Replay fixture cannot be recorded from a replay",
+        ));
+    }
+    Ok(agent)
+}
+
+/// A recorded session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayFixture {
+    /// Agent whose log normalizer processes the replayed output
+    #[serde(deserialize_with = "deserialize_recorded_agent")]
+    pub agent: CodingAgent,
+    pub steps: Vec<ReplayStep>,
+    #[serde(default)]
+    pub exit_code: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplayStep {
+    /// Written to stdout verbatim, so lines need their trailing newline
+    Stdout {
+        text: String,
+    },
+    /// Written to stderr verbatim
+    Stderr {
+        text: String,
+    },
+    /// Create or overwrite a file, relative to the workspace directory
+    WriteFile {
+        path: String,
+        content: String,
+    },
+    /// Delete a file, relative to the workspace directory
+    DeleteFile {
+        path: String,
+    },
+    Wait {
+        ms: u64,
+    },
+}
+
+impl ReplayFixture {
+    pub async fn load(path: &Path) -> Result<Self, ExecutorError> {
+        let content = tokio::fs::read_to_string(path).await.map_err(|e| {
+            ExecutorError::Io(std::io::Error::new(
+                e.kind(),
+                format!("Failed to read replay fixture {}: {e}", path.display()),
+            ))
+        })?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, ExecutorError> {
+        Ok(serde_json::from_str(content)?)
+    }
+}
+
+/// Resolve a fixture path inside `current_dir`, rejecting paths that escape it
+fn resolve_edit_path(current_dir: &Path, path: &str) -> std::io::Result<PathBuf> {
+    let relative = Path::new(path);
+    let is_contained = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if !is_contained || path.is_empty() {
+        return Err(std::io::Error::other(format!(
+            "Replay edit path must be relative to the workspace: {path}"
+        )));
+    }
+    Ok(current_dir.join(relative))
+}
+
+/// Normalize replayed output with the normalizer of the agent `fixture_path`
+/// was recorded from
+fn normalize_fixture_logs(fixture_path: &str, msg_store: Arc<MsgStore>, worktree_path: &Path) {
+    let fixture = std::fs::read_to_string(fixture_path)
+        .map_err(ExecutorError::Io)
+        .and_then(|content| ReplayFixture::parse(&content));
+
+    match fixture {
+        Ok(fixture) => fixture.agent.normalize_logs(msg_store, worktree_path),
+        Err(e) => tracing::error!("Failed to load replay fixture {fixture_path}: {e}"),
+    }
+}
+
+impl Replay {
+    async fn spawn_fixture(
+        &self,
+        fixture_path: &str,
+        current_dir: &Path,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let fixture = ReplayFixture::load(Path::new(fixture_path)).await?;
+
+        // A shell stands in for the agent: whatever is written to its stdin
+        // comes out on its stderr, and it exits with the recorded code once
+        // stdin is closed
+        let mut command = env.command("sh")?;
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir)
+            .args([
+                "-c",
+                r#"cat >&2; exit "$1""#,
+                "replay",
+                &fixture.exit_code.to_string(),
+            ]);
+
+        env.apply_to_command(&mut command);
+
+        let mut child = command.group_spawn()?;
+        let mut stderr = child.inner().stdin.take().ok_or_else(|| {
+            ExecutorError::Io(std::io::Error::other("Replay process missing stdin"))
+        })?;
+        let mut stdout = create_stdout_pipe_writer(&mut child)?;
+
+        let current_dir = current_dir.to_path_buf();
+        let honor_timing = self.honor_timing;
+        tokio::spawn(async move {
+            for step in fixture.steps {
+                let result = match step {
+                    ReplayStep::Stdout { text } => stdout.write_all(text.as_bytes()).await,
+                    ReplayStep::Stderr { text } => stderr.write_all(text.as_bytes()).await,
+                    ReplayStep::WriteFile { path, content } => {
+                        async {
+                            let path = resolve_edit_path(&current_dir, &path)?;
+                            if let Some(parent) = path.parent() {
+                                tokio::fs::create_dir_all(parent).await?;
+                            }
+                            tokio::fs::write(&path, content).await
+                        }
+                        .await
+                    }
+                    ReplayStep::DeleteFile { path } => {
+                        async {
+                            let path = resolve_edit_path(&current_dir, &path)?;
+                            tokio::fs::remove_file(&path).await
+                        }
+                        .await
+                    }
+                    ReplayStep::Wait { ms } => {
+                        if honor_timing {
+                            tokio::time::sleep(Duration::from_millis(ms)).await;
+                        }
+                        Ok(())
+                    }
+                };
+
+                if let Err(e) = result {
+                    tracing::warn!("Replay step failed: {e}");
+                    let _ = stderr
+                        .write_all(format!("Replay step failed: {e}\n").as_bytes())
+                        .await;
+                }
+            }
+
+            let _ = stdout.flush().await;
+            drop(stdout);
+            // Closing stdin lets the shell exit with the recorded code
+            let _ = stderr.shutdown().await;
+        });
+
+        Ok(child.into())
+    }
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Replay {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        _prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        self.spawn_fixture(&self.fixture, current_dir, env).await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        _prompt: &str,
+        _session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let fixture = self.follow_up_fixture.as_deref().unwrap_or(&self.fixture);
+        self.spawn_fixture(fixture, current_dir, env).await
+    }
+
+    /// Delegates to the recorded agent's normalizer
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, worktree_path: &Path) {
+        normalize_fixture_logs(&self.fixture, msg_store, worktree_path);
+    }
+
+    /// Follow-ups replay a different fixture, whose agent may differ
+    fn normalize_follow_up_logs(&self, msg_store: Arc<MsgStore>, worktree_path: &Path) {
+        let fixture = self.follow_up_fixture.as_deref().unwrap_or(&self.fixture);
+        normalize_fixture_logs(fixture, msg_store, worktree_path);
+    }
+
+    fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+
+    fn get_availability_info(&self) -> AvailabilityInfo {
+        if Path::new(&self.fixture).exists() {
+            AvailabilityInfo::InstallationFound
+        } else {
+            AvailabilityInfo::NotFound
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use futures::StreamExt;
+    use workspace_utils::log_msg::LogMsg;
+
+    use super::*;
+    use crate::logs::utils::patch::extract_normalized_entry_from_patch;
+
+    const RECORDED_FIXTURE: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/replay/claude_code_session.json"
+    );
+    const RECORDED_FIXTURE_GOLDEN: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/replay/claude_code_session.golden.json"
+    ));
+
+    /// A msg store holding the fixture's output, as a finished replay leaves it
+    fn replayed_output(fixture_path: &str) -> Arc<MsgStore> {
+        let fixture = ReplayFixture::parse(&std::fs::read_to_string(fixture_path).unwrap())
+            .expect("Should parse recorded fixture");
+        let msg_store = Arc::new(MsgStore::new());
+        for step in fixture.steps {
+            match step {
+                ReplayStep::Stdout { text } => msg_store.push_stdout(text),
+                ReplayStep::Stderr { text } => msg_store.push_stderr(text),
+                _ => {}
+            }
+        }
+        msg_store.push_finished();
+        msg_store
+    }
+
+    /// Final type, tool status and content of each normalized entry, read
+    /// once the normalizer goes quiet
+    async fn normalized_entries(msg_store: &MsgStore) -> Vec<serde_json::Value> {
+        let mut entries = BTreeMap::new();
+        let mut stream = msg_store.history_plus_stream();
+        while let Ok(Some(Ok(msg))) =
+            tokio::time::timeout(Duration::from_millis(500), stream.next()).await
+        {
+            if let LogMsg::JsonPatch(patch) = msg
+                && let Some((index, entry)) = extract_normalized_entry_from_patch(&patch)
+            {
+                entries.insert(index, entry);
+            }
+        }
+
+        entries
+            .into_values()
+            .map(|entry| {
+                let entry_type = serde_json::to_value(&entry.entry_type).unwrap();
+                let mut summary = serde_json::json!({
+                    "type": entry_type["type"],
+                    "content": entry.content,
+                });
+                if let Some(status) = entry_type.get("status") {
+                    summary["status"] = status["status"].clone();
+                }
+                summary
+            })
+            .collect()
+    }
+
+    fn golden() -> Vec<serde_json::Value> {
+        serde_json::from_str(RECORDED_FIXTURE_GOLDEN).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_recorded_fixture_matches_golden() {
+        let replay = Replay {
+            fixture: RECORDED_FIXTURE.to_string(),
+            follow_up_fixture: None,
+            honor_timing: false,
+        };
+        let msg_store = replayed_output(RECORDED_FIXTURE);
+
+        replay.normalize_logs(msg_store.clone(), Path::new("/tmp/replay"));
+
+        assert_eq!(normalized_entries(&msg_store).await, golden());
+    }
+
+    #[tokio::test]
+    async fn test_follow_up_normalizes_follow_up_fixture() {
+        // Only the follow-up fixture exists, so normalizing with the initial
+        // fixture's agent would produce nothing
+        let replay = Replay {
+            fixture: "missing-initial-fixture.json".to_string(),
+            follow_up_fixture: Some(RECORDED_FIXTURE.to_string()),
+            honor_timing: false,
+        };
+        let msg_store = replayed_output(RECORDED_FIXTURE);
+
+        replay.normalize_follow_up_logs(msg_store.clone(), Path::new("/tmp/replay"));
+
+        assert_eq!(normalized_entries(&msg_store).await, golden());
+    }
+
+    #[test]
+    fn test_parse_fixture() {
+        let fixture = ReplayFixture::parse(
+            r#"{
+                "agent": { "CLAUDE_CODE": {} },
+                "steps": [
+                    { "type": "stdout", "text": "{\"type\":\"system\"}\n" },
+                    { "type": "write_file", "path": "src/lib.rs", "content": "fn main() {}\n" },
+                    { "type": "wait", "ms": 10 }
+                ],
+                "exit_code": 1
+            }"#,
+        )
+        .expect("Should parse fixture");
+        assert!(matches!(fixture.agent, CodingAgent::ClaudeCode(_)));
+        assert_eq!(fixture.steps.len(), 3);
+        assert_eq!(fixture.exit_code, 1);
+
+        let nested = ReplayFixture::parse(
+            r#"{ "agent": { "REPLAY": { "fixture": "a.json" } }, "steps": [] }"#,
+        );
+        assert!(nested.is_err());
+    }
+
+    #[test]
+    fn test_resolve_edit_path() {
+        let dir = Path::new("/workspace");
+        assert_eq!(
+            resolve_edit_path(dir, "repo/src/main.rs").unwrap(),
+            dir.join("repo/src/main.rs")
+        );
+        assert!(resolve_edit_path(dir, "../outside").is_err());
+        assert!(resolve_edit_path(dir, "/etc/passwd").is_err());
+        assert!(resolve_edit_path(dir, "").is_err());
+    }
+}
@@ -0,0 +1,193 @@
+//! Why a coding agent run failed, and when to try it again.
+//!
+//! Executors recognise common provider failures in their output; the
+//! container records the cause on the execution process and, for transient
+//! causes, re-runs the turn after a backoff according to the profile's
+//! [`RetryPolicy`].
+
+use std::{sync::LazyLock, time::Duration};
+
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use workspace_utils::{log_msg::LogMsg, msg_store::MsgStore};
+
+use crate::logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch};
+
+/// Cause of a failed run, as recognised from the agent's output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentFailure {
+    RateLimited,
+    Overloaded,
+    AuthExpired,
+    Network,
+    ContextLengthExceeded,
+}
+
+impl AgentFailure {
+    /// Whether trying again later can be expected to succeed
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::RateLimited | Self::Overloaded | Self::Network)
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::RateLimited => "the provider's rate limit was reached",
+            Self::Overloaded => "the provider is overloaded",
+            Self::AuthExpired => "the agent's credentials are invalid or expired",
+            Self::Network => "the agent could not reach the provider",
+            Self::ContextLengthExceeded => "the conversation exceeds the model's context window",
+        }
+    }
+}
+
+/// Checked in order, so the more specific causes win
+static PATTERNS: LazyLock<Vec<(AgentFailure, Regex)>> = LazyLock::new(|| {
+    [
+        (
+            AgentFailure::ContextLengthExceeded,
+            r"context[ _-]?(length|window)[ _-]?(exceeded|too long)|maximum context length|exceeds? the context window|prompt is too long|input is too long|too many input tokens",
+        ),
+        (
+            AgentFailure::AuthExpired,
+            r"\b(status|error|code)[: ]*401\b|401 unauthori[sz]ed|authentication[ _]error|invalid[ _]api[ _]key|(token|credentials|session) (has )?expired|please run /login|not logged in|login required",
+        ),
+        (
+            AgentFailure::Overloaded,
+            r"overloaded|\b(status|error|code)[: ]*(529|503)\b|service unavailable",
+        ),
+        (
+            AgentFailure::RateLimited,
+            r"rate[ _-]?limit|too many requests|\b(status|error|code)[: ]*429\b|quota exceeded|resource[ _]exhausted|usage limit",
+        ),
+        (
+            AgentFailure::Network,
+            r"econnreset|econnrefused|etimedout|enotfound|eai_again|socket hang up|connection (reset|refused|closed)|network error|fetch failed|error sending request|dns error|getaddrinfo",
+        ),
+    ]
+    .into_iter()
+    .map(|(failure, pattern)| {
+        let regex = Regex::new(&format!("(?i){pattern}")).expect("valid regex");
+        (failure, regex)
+    })
+    .collect()
+});
+
+/// Recognise a failure cause in an error message
+pub fn classify(text: &str) -> Option<AgentFailure> {
+    PATTERNS
+        .iter()
+        .find(|(_, regex)| regex.is_match(text))
+        .map(|(failure, _)| *failure)
+}
+
+/// Recognise a failure cause in a run's stderr and error entries, latest first.
+/// Regular output is skipped since it echoes file contents and command output.
+pub fn classify_output(msg_store: &MsgStore) -> Option<AgentFailure> {
+    let history = msg_store.get_history();
+    let stderr = history
+        .iter()
+        .filter_map(|msg| match msg {
+            LogMsg::Stderr(s) => Some(s.as_str()),
+            _ => None,
+        })
+        .collect::<String>();
+    let errors = history
+        .iter()
+        .filter_map(|msg| match msg {
+            LogMsg::JsonPatch(patch) => extract_normalized_entry_from_patch(patch),
+            _ => None,
+        })
+        .filter(|(_, entry)| matches!(entry.entry_type, NormalizedEntryType::ErrorMessage { .. }))
+        .map(|(_, entry)| entry.content)
+        .collect::<Vec<_>>();
+
+    errors
+        .iter()
+        .rev()
+        .map(String::as_str)
+        .chain(stderr.lines().rev())
+        .find_map(classify)
+}
+
+/// Retries for runs that fail for transient reasons such as rate limits.
+/// Unset fields use the defaults; set `max_attempts` to 0 to disable.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema, Default)]
+pub struct RetryPolicy {
+    #[schemars(
+        title = "Maximum Attempts",
+        description = "Retries before the failure is reported, defaults to 3. Set to 0 to disable"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+    #[schemars(
+        title = "Initial Backoff (seconds)",
+        description = "Wait before the first retry, doubled for each further retry. Defaults to 30"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_backoff_secs: Option<u64>,
+    #[schemars(
+        title = "Maximum Backoff (seconds)",
+        description = "Longest wait between retries, defaults to 600"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_backoff_secs: Option<u64>,
+}
+
+impl RetryPolicy {
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts.unwrap_or(3)
+    }
+
+    /// Wait before the given retry, counting from 1
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let initial = self.initial_backoff_secs.unwrap_or(30);
+        let max = self.max_backoff_secs.unwrap_or(600);
+        let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+        Duration::from_secs(initial.saturating_mul(factor).min(max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            classify(r#"API Error: 529 {"type":"error","error":{"type":"overloaded_error"}}"#),
+            Some(AgentFailure::Overloaded)
+        );
+        assert_eq!(
+            classify("stream error: exceeded retry limit, last status: 429 Too Many Requests"),
+            Some(AgentFailure::RateLimited)
+        );
+        assert_eq!(
+            classify("OAuth token has expired. Please run /login"),
+            Some(AgentFailure::AuthExpired)
+        );
+        assert_eq!(
+            classify("Prompt is too long"),
+            Some(AgentFailure::ContextLengthExceeded)
+        );
+        assert_eq!(
+            classify("request failed: error sending request for url"),
+            Some(AgentFailure::Network)
+        );
+        assert_eq!(classify("error[E0425]: cannot find value `x`"), None);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy {
+            initial_backoff_secs: Some(10),
+            max_backoff_secs: Some(35),
+            ..Default::default()
+        };
+        assert_eq!(policy.backoff(1), Duration::from_secs(10));
+        assert_eq!(policy.backoff(2), Duration::from_secs(20));
+        assert_eq!(policy.backoff(3), Duration::from_secs(35));
+        assert_eq!(policy.max_attempts(), 3);
+    }
+}
@@ -67,6 +67,7 @@ pub struct NormalizedConversation {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum NormalizedEntryError {
     SetupRequired,
+    SandboxViolation,
     Other,
 }
 
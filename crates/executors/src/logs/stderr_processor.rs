@@ -17,7 +17,7 @@ use super::{
     NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
     plain_text_processor::PlainTextLogProcessor,
 };
-use crate::logs::utils::EntryIndexProvider;
+use crate::{logs::utils::EntryIndexProvider, sandbox};
 
 /// Standard stderr log normalizer that uses PlainTextLogProcessor to stream error logs.
 ///
@@ -42,13 +42,20 @@ pub fn normalize_stderr_logs(msg_store: Arc<MsgStore>, entry_index_provider: Ent
 
         // Create a processor with time-based emission for stderr
         let mut processor = PlainTextLogProcessor::builder()
-            .normalized_entry_producer(Box::new(|content: String| NormalizedEntry {
-                timestamp: None,
-                entry_type: NormalizedEntryType::ErrorMessage {
-                    error_type: NormalizedEntryError::Other,
-                },
-                content: strip_ansi_escapes::strip_str(&content),
-                metadata: None,
+            .normalized_entry_producer(Box::new(|content: String| {
+                let content = strip_ansi_escapes::strip_str(&content);
+                // Surface sandbox denials distinctly so the UI can explain them
+                let error_type = if content.lines().any(sandbox::is_violation) {
+                    NormalizedEntryError::SandboxViolation
+                } else {
+                    NormalizedEntryError::Other
+                };
+                NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::ErrorMessage { error_type },
+                    content,
+                    metadata: None,
+                }
             }))
             .time_gap(Duration::from_secs(2)) // Break messages if they are 2 seconds apart
             .index_provider(entry_index_provider)
@@ -33,10 +33,9 @@ impl EntryIndexProvider {
     }
 
     /// Create a provider starting from the maximum existing normalized-entry index
-    /// observed in prior JSON patches in `MsgStore`.
+    /// observed in prior JSON patches in `MsgStore`. Providers for the same store
+    /// share one counter, so entries added outside the normalizer get fresh indices.
     pub fn start_from(msg_store: &MsgStore) -> Self {
-        let provider = EntryIndexProvider::new();
-
         let max_index: Option<usize> = msg_store
             .get_history()
             .iter()
@@ -58,8 +57,9 @@ impl EntryIndexProvider {
             .max();
 
         let start_at = max_index.map_or(0, |n| n.saturating_add(1));
-        provider.0.store(start_at, Ordering::Relaxed);
-        provider
+        let counter = msg_store.entry_index();
+        counter.fetch_max(start_at, Ordering::Relaxed);
+        Self(counter)
     }
 }
 
@@ -110,4 +110,19 @@ mod tests {
         provider.next();
         assert_eq!(provider.current(), 2);
     }
+
+    #[test]
+    fn test_start_from_shares_counter_per_store() {
+        let msg_store = MsgStore::new();
+        let normalizer = EntryIndexProvider::start_from(&msg_store);
+        assert_eq!(normalizer.next(), 0);
+        assert_eq!(normalizer.next(), 1);
+
+        let other = EntryIndexProvider::start_from(&msg_store);
+        assert_eq!(other.next(), 2);
+        assert_eq!(normalizer.next(), 3);
+
+        let fresh = EntryIndexProvider::start_from(&MsgStore::new());
+        assert_eq!(fresh.next(), 0);
+    }
 }
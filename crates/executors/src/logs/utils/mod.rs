@@ -2,6 +2,8 @@
 
 pub mod entry_index;
 pub mod patch;
+pub mod steer;
 
 pub use entry_index::EntryIndexProvider;
 pub use patch::ConversationPatch;
+pub use steer::SteerMessage;
@@ -1,13 +1,18 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
 
+use futures::{Stream, StreamExt};
 use json_patch::Patch;
 use serde::{Deserialize, Serialize};
 use serde_json::{from_value, json, to_value};
 use ts_rs::TS;
-use workspace_utils::{diff::Diff, msg_store::MsgStore};
+use workspace_utils::{diff::Diff, log_msg::LogMsg, msg_store::MsgStore};
 
 use crate::logs::{NormalizedEntry, utils::EntryIndexProvider};
 
+/// Normalizers of stored logs run in the background with no completion signal,
+/// so collection also stops once their output has been quiet this long
+const NORMALIZE_IDLE_TIMEOUT: Duration = Duration::from_millis(500);
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, TS)]
 #[serde(rename_all = "lowercase")]
 enum PatchOperation {
@@ -175,3 +180,59 @@ pub fn replace_normalized_entry(
 ) {
     upsert_normalized_entry(msg_store, index, normalized_entry, false);
 }
+
+/// Current state of each entry of a normalized log stream, in conversation
+/// order. Reads until `LogMsg::Finished`, or until the stream goes quiet.
+pub async fn collect_normalized_entries(
+    mut stream: impl Stream<Item = Result<LogMsg, std::io::Error>> + Unpin,
+) -> Vec<NormalizedEntry> {
+    // Later patches replace earlier versions of an entry
+    let mut entries = BTreeMap::new();
+    while let Ok(Some(msg)) = tokio::time::timeout(NORMALIZE_IDLE_TIMEOUT, stream.next()).await {
+        match msg {
+            Ok(LogMsg::JsonPatch(patch)) => {
+                if let Some((index, entry)) = extract_normalized_entry_from_patch(&patch) {
+                    entries.insert(index, entry);
+                }
+            }
+            Ok(LogMsg::Finished) => break,
+            _ => {}
+        }
+    }
+    entries.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::NormalizedEntryType;
+
+    fn message(content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::AssistantMessage,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collect_normalized_entries_until_finished() {
+        let msg_store = MsgStore::new();
+        msg_store.push_patch(ConversationPatch::add_normalized_entry(
+            1,
+            message("second"),
+        ));
+        msg_store.push_patch(ConversationPatch::add_normalized_entry(0, message("draft")));
+        msg_store.push_patch(ConversationPatch::replace(0, message("first")));
+        msg_store.push_finished();
+        msg_store.push_patch(ConversationPatch::add_normalized_entry(2, message("late")));
+
+        let contents: Vec<String> = collect_normalized_entries(msg_store.history_plus_stream())
+            .await
+            .into_iter()
+            .map(|entry| entry.content)
+            .collect();
+        assert_eq!(contents, vec!["first", "second"]);
+    }
+}
@@ -0,0 +1,56 @@
+//! Marker for user messages delivered to an agent while it is running.
+//!
+//! Agents don't reliably echo these back, so the executor writes a marker line
+//! to its log stdout when it delivers one and the normalizer turns the marker
+//! into a user message entry.
+
+use serde::{Deserialize, Serialize};
+
+use crate::logs::{NormalizedEntry, NormalizedEntryType};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SteerMessage {
+    pub steer_message: String,
+}
+
+impl SteerMessage {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            steer_message: message.into(),
+        }
+    }
+
+    /// The log line to write when the message is delivered
+    pub fn raw(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Parse a log line written by [`SteerMessage::raw`]
+    pub fn parse(line: &str) -> Option<Self> {
+        if !line.starts_with(r#"{"steer_message":"#) {
+            return None;
+        }
+        serde_json::from_str(line).ok()
+    }
+
+    pub fn to_normalized_entry(&self) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::UserMessage,
+            content: self.steer_message.clone(),
+            metadata: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let message = SteerMessage::new("Use the \"fast\" path instead");
+        assert_eq!(SteerMessage::parse(&message.raw()), Some(message));
+        assert_eq!(SteerMessage::parse(r#"{"type":"result"}"#), None);
+    }
+}
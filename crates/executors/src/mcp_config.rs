@@ -299,7 +299,9 @@ impl CodingAgent {
             CodingAgent::Codex(_) => Codex,
             CodingAgent::Opencode(_) => Opencode,
             CodingAgent::Copilot(..) => Copilot,
-            CodingAgent::CustomAgent(_) => Passthrough,
+            CodingAgent::CustomAgent(_)
+            | CodingAgent::Replay(_)
+            | CodingAgent::OpenaiCompatible(_) => Passthrough,
         };
 
         let canonical = PRECONFIGURED_MCP_SERVERS.clone();
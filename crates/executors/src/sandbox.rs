@@ -0,0 +1,201 @@
+//! Optional Linux sandbox for executor and script processes.
+//!
+//! When enabled, processes are launched through bubblewrap (`bwrap`): the host
+//! filesystem is mounted read-only, the workspace worktrees plus an allow-list
+//! are bind-mounted writable, and networking can be cut off with a fresh
+//! network namespace.
+
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+use ts_rs::TS;
+use workspace_utils::shell::resolve_executable_path_blocking;
+
+use crate::executors::ExecutorError;
+
+/// Sandbox settings. Configured per project and overridable per executor
+/// profile; unset fields fall through to the lower-precedence layer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema, Default)]
+pub struct SandboxConfig {
+    #[schemars(
+        title = "Enable Sandbox",
+        description = "Run processes inside a bubblewrap sandbox (Linux only)"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[schemars(
+        title = "Allow Network",
+        description = "Allow network access from inside the sandbox"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_network: Option<bool>,
+    #[schemars(
+        title = "Writable Paths",
+        description = "Additional paths that stay writable inside the sandbox (e.g. ~/.cache)"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub writable_paths: Option<Vec<String>>,
+}
+
+impl SandboxConfig {
+    /// Layer `other` on top of `self`. Set fields in `other` win; writable
+    /// paths are accumulated.
+    pub fn merge(&mut self, other: &SandboxConfig) {
+        if other.enabled.is_some() {
+            self.enabled = other.enabled;
+        }
+        if other.allow_network.is_some() {
+            self.allow_network = other.allow_network;
+        }
+        if let Some(paths) = &other.writable_paths {
+            self.writable_paths
+                .get_or_insert_with(Vec::new)
+                .extend(paths.iter().cloned());
+        }
+    }
+}
+
+/// Sandbox settings resolved for a single execution
+#[derive(Debug, Clone, Default)]
+pub struct SandboxPolicy {
+    pub config: SandboxConfig,
+    /// Directories that are always writable (the workspace and its worktrees)
+    pub workspace_roots: Vec<PathBuf>,
+}
+
+impl SandboxPolicy {
+    pub fn new(config: SandboxConfig, workspace_roots: Vec<PathBuf>) -> Self {
+        Self {
+            config,
+            workspace_roots,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled.unwrap_or(false)
+    }
+
+    /// Build a command for `program`, wrapped in bubblewrap when the sandbox
+    /// is enabled. Arguments added afterwards are passed to `program`.
+    pub fn command(&self, program: impl AsRef<OsStr>) -> Result<Command, ExecutorError> {
+        if !self.is_enabled() {
+            return Ok(Command::new(program));
+        }
+
+        // Fail closed: an enabled sandbox must never silently run unsandboxed
+        if !cfg!(target_os = "linux") {
+            return Err(ExecutorError::SandboxUnavailable(
+                "sandboxing is only supported on Linux".to_string(),
+            ));
+        }
+        let bwrap = resolve_executable_path_blocking("bwrap").ok_or_else(|| {
+            ExecutorError::SandboxUnavailable("bubblewrap (`bwrap`) is not installed".to_string())
+        })?;
+
+        let mut command = Command::new(bwrap);
+        command.args(self.bwrap_args()).arg("--").arg(program);
+        Ok(command)
+    }
+
+    fn bwrap_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = [
+            "--die-with-parent",
+            "--new-session",
+            "--ro-bind",
+            "/",
+            "/",
+            "--dev",
+            "/dev",
+            "--proc",
+            "/proc",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        let extra = self
+            .config
+            .writable_paths
+            .iter()
+            .flatten()
+            .map(|p| expand_home(p));
+        let writable = self
+            .workspace_roots
+            .iter()
+            .cloned()
+            .chain(std::iter::once(std::env::temp_dir()))
+            .chain(extra);
+        for path in writable {
+            // bwrap refuses to bind paths that don't exist
+            if path.exists() {
+                let path = path.to_string_lossy().to_string();
+                args.extend(["--bind".to_string(), path.clone(), path]);
+            }
+        }
+
+        if !self.config.allow_network.unwrap_or(true) {
+            args.push("--unshare-net".to_string());
+        }
+        args
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|| Path::new(path).to_path_buf()),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Heuristically detect output caused by the sandbox blocking an operation
+pub fn is_violation(line: &str) -> bool {
+    line.starts_with("bwrap:") || line.contains("Read-only file system") || line.contains("EROFS")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_settings_override_project_settings() {
+        let mut config = SandboxConfig {
+            enabled: Some(true),
+            allow_network: Some(true),
+            writable_paths: Some(vec!["/a".to_string()]),
+        };
+        config.merge(&SandboxConfig {
+            enabled: None,
+            allow_network: Some(false),
+            writable_paths: Some(vec!["/b".to_string()]),
+        });
+
+        assert_eq!(config.enabled, Some(true));
+        assert_eq!(config.allow_network, Some(false));
+        assert_eq!(
+            config.writable_paths,
+            Some(vec!["/a".to_string(), "/b".to_string()])
+        );
+    }
+
+    #[test]
+    fn disabling_network_unshares_namespace() {
+        let policy = SandboxPolicy::new(
+            SandboxConfig {
+                enabled: Some(true),
+                allow_network: Some(false),
+                writable_paths: None,
+            },
+            vec![],
+        );
+        let args = policy.bwrap_args();
+        assert!(args.contains(&"--unshare-net".to_string()));
+        assert!(args.windows(3).any(|w| w == ["--ro-bind", "/", "/"]));
+    }
+}
@@ -0,0 +1,291 @@
+//! Installed versions of coding agent CLIs.
+//!
+//! Vendor CLIs change their stream formats between releases, so each executor
+//! declares the range of versions its protocol handling and log normalization
+//! were tested with. The availability check reports the installed version and
+//! warns when it is outside that range, and every coding agent execution
+//! process records the version it ran with. The CLI runs with the profile's
+//! environment, and at most once at a time per command.
+
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    sync::{Arc, LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use ts_rs::TS;
+
+use crate::{
+    command::{CmdOverrides, CommandBuilder},
+    env::ExecutionEnv,
+};
+
+/// npx may have to fetch the package before the CLI can print its version
+const DETECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Detected versions are reused for a while instead of running the CLI for
+/// every execution
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+static VERSION_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d+\.\d+(?:\.\d+)?(?:-[0-9A-Za-z.]+)?").unwrap());
+
+static DETECTED: LazyLock<Mutex<HashMap<String, (Instant, Option<String>)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Held while a command's version is being detected, so concurrent callers
+/// wait for that run instead of starting another npx download
+static DETECTING: LazyLock<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Versions an executor was tested with: from `min` up to, but not including,
+/// `below`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestedVersions {
+    pub min: &'static str,
+    pub below: &'static str,
+}
+
+impl TestedVersions {
+    pub const fn new(min: &'static str, below: &'static str) -> Self {
+        Self { min, below }
+    }
+
+    pub fn contains(&self, version: &str) -> bool {
+        compare(version, self.min) != Ordering::Less
+            && compare(version, self.below) == Ordering::Less
+    }
+}
+
+impl fmt::Display for TestedVersions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, ">={}, <{}", self.min, self.below)
+    }
+}
+
+/// Installed version of an executor's CLI, checked against the versions it was
+/// tested with
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct AgentVersionInfo {
+    /// Version the CLI reported, if it could be run
+    pub installed: Option<String>,
+    /// Versions the executor was tested with, e.g. ">=2.0.0, <2.1.0"
+    pub tested_range: Option<String>,
+    /// Set when the installed version is outside the tested range
+    pub warning: Option<String>,
+}
+
+impl AgentVersionInfo {
+    pub fn new(installed: Option<String>, tested: Option<TestedVersions>) -> Self {
+        let warning = match (&installed, tested) {
+            (Some(version), Some(tested)) if !tested.contains(version) => Some(format!(
+                "Version {version} is outside the tested range {tested}, so its output \
+                 may not be displayed correctly"
+            )),
+            _ => None,
+        };
+        Self {
+            installed,
+            tested_range: tested.map(|tested| tested.to_string()),
+            warning,
+        }
+    }
+}
+
+/// `--version` for an executor's CLI, with the environment the profile runs
+/// it with
+#[derive(Debug, Clone)]
+pub struct VersionCommand {
+    command: CommandBuilder,
+    env: ExecutionEnv,
+}
+
+impl VersionCommand {
+    /// Cache key: the command line and the environment it runs with
+    fn key(&self) -> Option<String> {
+        let parts = self.command.build_initial().ok()?;
+        let mut vars = self.env.vars.iter().collect::<Vec<_>>();
+        vars.sort();
+        Some(format!("{parts:?} {vars:?}"))
+    }
+}
+
+/// `--version` for an executor's base command, or for the profile's override
+/// of it
+pub fn version_command(base: &str, cmd: &CmdOverrides) -> VersionCommand {
+    VersionCommand {
+        command: CommandBuilder::new(cmd.base_command_override.as_deref().unwrap_or(base))
+            .params(["--version"]),
+        env: ExecutionEnv::new().with_profile(cmd),
+    }
+}
+
+/// The version detected for a command within the cache lifetime, without
+/// running it. `None` if it has to be detected.
+pub fn cached_version(command: &VersionCommand) -> Option<Option<String>> {
+    let key = command.key()?;
+    cached(&key)
+}
+
+fn cached(key: &str) -> Option<Option<String>> {
+    DETECTED
+        .lock()
+        .unwrap()
+        .get(key)
+        .filter(|(detected_at, _)| detected_at.elapsed() < CACHE_TTL)
+        .map(|(_, version)| version.clone())
+}
+
+/// Run a version command and return the version it prints
+pub async fn detect_version(command: &VersionCommand) -> Option<String> {
+    let key = command.key()?;
+    if let Some(version) = cached(&key) {
+        return version;
+    }
+
+    let lock = DETECTING
+        .lock()
+        .unwrap()
+        .entry(key.clone())
+        .or_default()
+        .clone();
+    let _detecting = lock.lock().await;
+    // Another caller may have detected it while this one waited
+    if let Some(version) = cached(&key) {
+        return version;
+    }
+
+    let version = run_version_command(command).await;
+    DETECTED
+        .lock()
+        .unwrap()
+        .insert(key, (Instant::now(), version.clone()));
+    version
+}
+
+/// Detect a command's version without waiting for it, for a later
+/// [`cached_version`]. The handle resolves to the detected version.
+pub fn detect_version_in_background(command: VersionCommand) -> JoinHandle<Option<String>> {
+    tokio::spawn(async move { detect_version(&command).await })
+}
+
+async fn run_version_command(command: &VersionCommand) -> Option<String> {
+    let (program, args) = command
+        .command
+        .build_initial()
+        .ok()?
+        .into_resolved()
+        .await
+        .ok()?;
+    let mut process = tokio::process::Command::new(program);
+    process.args(args).kill_on_drop(true);
+    command.env.apply_to_command(&mut process);
+    let output = tokio::time::timeout(DETECT_TIMEOUT, process.output())
+        .await
+        .ok()?
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_version(&String::from_utf8_lossy(&output.stdout))
+        .or_else(|| parse_version(&String::from_utf8_lossy(&output.stderr)))
+}
+
+/// The first version number in a CLI's `--version` output
+pub fn parse_version(output: &str) -> Option<String> {
+    VERSION_PATTERN
+        .find(output)
+        .map(|version| version.as_str().to_string())
+}
+
+/// Compare dotted version numbers; pre-release suffixes are ignored
+fn compare(a: &str, b: &str) -> Ordering {
+    let parts = |version: &str| {
+        version
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse::<u64>().unwrap_or(0))
+            .collect::<Vec<_>>()
+    };
+    let (a, b) = (parts(a), parts(b));
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            parse_version("2.0.76 (Claude Code)").as_deref(),
+            Some("2.0.76")
+        );
+        assert_eq!(
+            parse_version("codex-cli 0.77.0\n").as_deref(),
+            Some("0.77.0")
+        );
+        assert_eq!(
+            parse_version("0.0.1764777697-g907e30").as_deref(),
+            Some("0.0.1764777697-g907e30")
+        );
+        assert_eq!(parse_version("command not found"), None);
+    }
+
+    #[test]
+    fn test_tested_versions() {
+        let tested = TestedVersions::new("2.0.0", "2.1.0");
+        assert!(tested.contains("2.0.0"));
+        assert!(tested.contains("2.0.76"));
+        assert!(!tested.contains("2.1.0"));
+        assert!(!tested.contains("1.9.12"));
+        assert!(!tested.contains("10.0.0"));
+
+        let info = AgentVersionInfo::new(Some("2.1.3".to_string()), Some(tested));
+        assert_eq!(info.tested_range.as_deref(), Some(">=2.0.0, <2.1.0"));
+        assert!(info.warning.is_some());
+        assert_eq!(
+            AgentVersionInfo::new(Some("2.0.5".to_string()), Some(tested)).warning,
+            None
+        );
+        assert_eq!(AgentVersionInfo::new(None, Some(tested)).warning, None);
+    }
+
+    #[test]
+    fn test_version_command_runs_with_profile_env() {
+        let base_command_override = Some("npx -y @acme/cli@1.2.3".to_string());
+        let cmd = CmdOverrides {
+            base_command_override: base_command_override.clone(),
+            env: Some(HashMap::from([(
+                "HTTPS_PROXY".to_string(),
+                "http://proxy:8080".to_string(),
+            )])),
+            ..Default::default()
+        };
+        let command = version_command("acme", &cmd);
+        assert_eq!(
+            command.env.vars.get("HTTPS_PROXY").map(String::as_str),
+            Some("http://proxy:8080")
+        );
+
+        // Profiles with different environments are detected separately
+        let without_env = version_command(
+            "acme",
+            &CmdOverrides {
+                base_command_override,
+                ..Default::default()
+            },
+        );
+        assert_ne!(command.key(), without_env.key());
+    }
+}
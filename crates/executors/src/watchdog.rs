@@ -0,0 +1,344 @@
+//! Limits enforced on running executor processes.
+//!
+//! The container watches each coding agent run against its profile's limits:
+//! total run time, time without new output, and the same tool call being made
+//! over and over. [`watch`] resolves with the limit that was hit, if any.
+
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use futures::StreamExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use ts_rs::TS;
+use workspace_utils::{log_msg::LogMsg, msg_store::MsgStore};
+
+use crate::logs::{
+    NormalizedEntry, NormalizedEntryType, ToolStatus,
+    utils::patch::extract_normalized_entry_from_patch,
+};
+
+/// Watchdog limits for an executor profile. Unset limits are not enforced.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema, Default)]
+pub struct WatchdogConfig {
+    #[schemars(
+        title = "Maximum Duration (seconds)",
+        description = "Stop the agent once it has been running this long"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_duration_secs: Option<u64>,
+    #[schemars(
+        title = "Idle Timeout (seconds)",
+        description = "Stop the agent after this long without new output, unless it is waiting for an approval"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_timeout_secs: Option<u64>,
+    #[schemars(
+        title = "Repeated Tool Call Limit",
+        description = "Stop the agent once it makes the same tool call this many times in a row"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_repeated_tool_calls: Option<u32>,
+}
+
+impl WatchdogConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.max_duration_secs.is_some()
+            || self.idle_timeout_secs.is_some()
+            || self.max_repeated_tool_calls.is_some()
+    }
+}
+
+/// The limit a run exceeded
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchdogTrigger {
+    TimeLimit { limit: Duration },
+    Idle { limit: Duration },
+    RepeatedToolCalls { tool_name: String, count: u32 },
+}
+
+impl WatchdogTrigger {
+    /// Explanation shown in the conversation
+    pub fn message(&self) -> String {
+        match self {
+            Self::TimeLimit { limit } => format!(
+                "The agent was stopped after running for {}, the maximum for this profile.",
+                format_duration(*limit)
+            ),
+            Self::Idle { limit } => format!(
+                "The agent was stopped after {} without any output. It may have been waiting on an interactive prompt.",
+                format_duration(*limit)
+            ),
+            Self::RepeatedToolCalls { tool_name, count } => format!(
+                "The agent was stopped after making the same `{tool_name}` call {count} times in a row."
+            ),
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        1 => "1 second".to_string(),
+        60 => "1 minute".to_string(),
+        s if s >= 60 && s % 60 == 0 => format!("{} minutes", s / 60),
+        s => format!("{s} seconds"),
+    }
+}
+
+/// Counts consecutive identical tool calls. Each entry is counted once, when
+/// it first appears; later updates to its status are ignored.
+#[derive(Debug)]
+pub struct RepeatedToolCallDetector {
+    limit: u32,
+    seen: HashSet<usize>,
+    last_call: Option<(String, String)>,
+    count: u32,
+}
+
+impl RepeatedToolCallDetector {
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            seen: HashSet::new(),
+            last_call: None,
+            count: 0,
+        }
+    }
+
+    pub fn observe(&mut self, index: usize, entry: &NormalizedEntry) -> Option<WatchdogTrigger> {
+        let NormalizedEntryType::ToolUse { tool_name, .. } = &entry.entry_type else {
+            return None;
+        };
+        if !self.seen.insert(index) {
+            return None;
+        }
+
+        let call = (tool_name.clone(), entry.content.clone());
+        if self.last_call.as_ref() == Some(&call) {
+            self.count += 1;
+        } else {
+            self.last_call = Some(call);
+            self.count = 1;
+        }
+
+        (self.limit > 0 && self.count >= self.limit).then(|| WatchdogTrigger::RepeatedToolCalls {
+            tool_name: tool_name.clone(),
+            count: self.count,
+        })
+    }
+}
+
+/// Watch a run's output until one of the configured limits is exceeded.
+/// Never resolves if no limit is hit.
+pub async fn watch(config: WatchdogConfig, msg_store: Arc<MsgStore>) -> WatchdogTrigger {
+    let started = Instant::now();
+    let max_duration = config.max_duration_secs.map(Duration::from_secs);
+    let idle_timeout = config.idle_timeout_secs.map(Duration::from_secs);
+    let mut detector = config
+        .max_repeated_tool_calls
+        .map(RepeatedToolCallDetector::new);
+
+    // Tool calls waiting for approval; the agent is expected to be quiet meanwhile
+    let mut pending_approvals: HashSet<usize> = HashSet::new();
+    let mut last_output = Instant::now();
+    let mut stream = msg_store.history_plus_stream();
+
+    loop {
+        let time_limit_at = max_duration.map(|limit| started + limit);
+        let idle_at = idle_timeout
+            .filter(|_| pending_approvals.is_empty())
+            .map(|limit| last_output + limit);
+        let next_check = [time_limit_at, idle_at].into_iter().flatten().min();
+        let sleep = async {
+            match next_check {
+                Some(at) => tokio::time::sleep_until(at).await,
+                None => std::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            msg = stream.next() => match msg {
+                Some(Ok(LogMsg::Finished)) | None => {
+                    // The process is exiting; the exit monitor takes it from here
+                    return std::future::pending().await;
+                }
+                Some(Ok(LogMsg::JsonPatch(patch))) => {
+                    last_output = Instant::now();
+                    let Some((index, entry)) = extract_normalized_entry_from_patch(&patch) else {
+                        continue;
+                    };
+                    if let NormalizedEntryType::ToolUse { status, .. } = &entry.entry_type {
+                        if matches!(status, ToolStatus::PendingApproval { .. }) {
+                            pending_approvals.insert(index);
+                        } else {
+                            pending_approvals.remove(&index);
+                        }
+                    }
+                    if let Some(detector) = detector.as_mut()
+                        && let Some(trigger) = detector.observe(index, &entry)
+                    {
+                        return trigger;
+                    }
+                }
+                Some(Ok(_)) => last_output = Instant::now(),
+                Some(Err(_)) => {}
+            },
+            _ = sleep => {
+                if let (Some(at), Some(limit)) = (time_limit_at, max_duration)
+                    && Instant::now() >= at
+                {
+                    return WatchdogTrigger::TimeLimit { limit };
+                }
+                if let Some(limit) = idle_timeout
+                    && pending_approvals.is_empty()
+                    && last_output.elapsed() >= limit
+                {
+                    return WatchdogTrigger::Idle { limit };
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::logs::{ActionType, utils::patch::ConversationPatch};
+
+    fn command(command: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ToolUse {
+                tool_name: "bash".to_string(),
+                action_type: ActionType::CommandRun {
+                    command: command.to_string(),
+                    result: None,
+                },
+                status: ToolStatus::Created,
+            },
+            content: command.to_string(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_repeated_tool_calls() {
+        let mut detector = RepeatedToolCallDetector::new(3);
+        assert_eq!(detector.observe(0, &command("npm test")), None);
+        assert_eq!(detector.observe(1, &command("npm test")), None);
+        // Status updates to an entry already seen don't count
+        assert_eq!(detector.observe(1, &command("npm test")), None);
+        assert_eq!(detector.observe(2, &command("npm run build")), None);
+        assert_eq!(detector.observe(3, &command("npm run build")), None);
+        assert_eq!(
+            detector.observe(4, &command("npm run build")),
+            Some(WatchdogTrigger::RepeatedToolCalls {
+                tool_name: "bash".to_string(),
+                count: 3,
+            })
+        );
+    }
+
+    fn pending_approval(command_text: &str) -> NormalizedEntry {
+        let mut entry = command(command_text);
+        if let NormalizedEntryType::ToolUse { status, .. } = &mut entry.entry_type {
+            *status = ToolStatus::PendingApproval {
+                approval_id: "approval".to_string(),
+                requested_at: Utc::now(),
+                timeout_at: Utc::now(),
+            };
+        }
+        entry
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_time_limit() {
+        let msg_store = Arc::new(MsgStore::new());
+        let config = WatchdogConfig {
+            max_duration_secs: Some(60),
+            idle_timeout_secs: Some(30),
+            ..Default::default()
+        };
+
+        // Steady output keeps the idle timeout from firing
+        let store = msg_store.clone();
+        let output = tokio::spawn(async move {
+            loop {
+                store.push_stdout("working\n");
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            }
+        });
+
+        let started = Instant::now();
+        let trigger = watch(config, msg_store).await;
+        output.abort();
+
+        assert_eq!(
+            trigger,
+            WatchdogTrigger::TimeLimit {
+                limit: Duration::from_secs(60)
+            }
+        );
+        assert!(started.elapsed() >= Duration::from_secs(60));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_idle() {
+        let msg_store = Arc::new(MsgStore::new());
+        let config = WatchdogConfig {
+            idle_timeout_secs: Some(30),
+            ..Default::default()
+        };
+
+        let store = msg_store.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(20)).await;
+            store.push_stdout("last words\n");
+        });
+
+        let started = Instant::now();
+        let trigger = watch(config, msg_store).await;
+
+        assert_eq!(
+            trigger,
+            WatchdogTrigger::Idle {
+                limit: Duration::from_secs(30)
+            }
+        );
+        assert!(started.elapsed() >= Duration::from_secs(50));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_idle_paused_while_awaiting_approval() {
+        let msg_store = Arc::new(MsgStore::new());
+        let config = WatchdogConfig {
+            idle_timeout_secs: Some(30),
+            ..Default::default()
+        };
+        msg_store.push_patch(ConversationPatch::add_normalized_entry(
+            0,
+            pending_approval("rm -rf target"),
+        ));
+
+        // The user takes a while to approve; the agent is quiet meanwhile
+        let store = msg_store.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(120)).await;
+            store.push_patch(ConversationPatch::replace(0, command("rm -rf target")));
+        });
+
+        let started = Instant::now();
+        let trigger = watch(config, msg_store).await;
+
+        assert_eq!(
+            trigger,
+            WatchdogTrigger::Idle {
+                limit: Duration::from_secs(30)
+            }
+        );
+        assert!(started.elapsed() >= Duration::from_secs(150));
+    }
+}
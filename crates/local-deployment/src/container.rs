@@ -8,21 +8,26 @@ use std::{
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use chrono::Utc;
 use command_group::AsyncGroupChild;
 use db::{
     DBService,
     models::{
         coding_agent_turn::CodingAgentTurn,
         execution_process::{
-            ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
+            ExecutionContext, ExecutionProcess, ExecutionProcessFailureReason,
+            ExecutionProcessRunReason, ExecutionProcessStatus,
         },
         execution_process_repo_state::ExecutionProcessRepoState,
         project_repo::ProjectRepo,
         repo::Repo,
         scratch::{DraftFollowUpData, Scratch, ScratchType},
+        session::Session,
         task::{Task, TaskStatus},
+        task_plan::{TaskPlan, TaskPlanStatus},
         workspace::Workspace,
         workspace_repo::WorkspaceRepo,
+        workspace_storage::WorkspaceStorage,
     },
 };
 use deployment::{DeploymentError, RemoteClientNotConfigured};
@@ -34,30 +39,49 @@ use executors::{
     },
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
     env::ExecutionEnv,
-    executors::{BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender},
-    logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
+    executors::{
+        BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender,
+        StandardCodingAgentExecutor, SteerSender,
+    },
+    failure::AgentFailure,
+    logs::{
+        NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
+        utils::{
+            EntryIndexProvider,
+            patch::{add_normalized_entry, extract_normalized_entry_from_patch},
+        },
+    },
     profile::ExecutorProfileId,
+    sandbox::SandboxPolicy,
+    version,
+    watchdog::{self, WatchdogConfig, WatchdogTrigger},
 };
 use futures::{FutureExt, TryStreamExt, stream::select};
 use serde_json::json;
 use services::services::{
     analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
-    config::Config,
+    config::{Config, WorkspaceCleanupConfig},
     container::{ContainerError, ContainerRef, ContainerService},
     diff_stream::{self, DiffStreamHandle},
-    git::{Commit, GitCli, GitService},
+    git::{Commit, DiffTarget, GitCli, GitService},
     image::ImageService,
     notification::NotificationService,
     queued_message::QueuedMessageService,
+    secrets::SecretsService,
     share::SharePublisher,
+    stacked_workspace::StackedWorkspaceService,
+    task_plan,
     workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
+    workspace_storage,
 };
 use tokio::{sync::RwLock, task::JoinHandle};
 use tokio_util::io::ReaderStream;
 use utils::{
+    diff::compute_line_change_counts,
     log_msg::LogMsg,
     msg_store::MsgStore,
+    redact::{Redactor, redact_chunks},
     text::{git_branch_id, short_uuid, truncate_to_char_boundary},
 };
 use uuid::Uuid;
@@ -69,6 +93,7 @@ pub struct LocalContainerService {
     db: DBService,
     child_store: Arc<RwLock<HashMap<Uuid, Arc<RwLock<AsyncGroupChild>>>>>,
     interrupt_senders: Arc<RwLock<HashMap<Uuid, InterruptSender>>>,
+    steer_senders: Arc<RwLock<HashMap<Uuid, SteerSender>>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
     config: Arc<RwLock<Config>>,
     git: GitService,
@@ -76,6 +101,7 @@ pub struct LocalContainerService {
     analytics: Option<AnalyticsContext>,
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
+    secrets: SecretsService,
     publisher: Result<SharePublisher, RemoteClientNotConfigured>,
     notification_service: NotificationService,
 }
@@ -91,16 +117,19 @@ impl LocalContainerService {
         analytics: Option<AnalyticsContext>,
         approvals: Approvals,
         queued_message_service: QueuedMessageService,
+        secrets: SecretsService,
         publisher: Result<SharePublisher, RemoteClientNotConfigured>,
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
         let interrupt_senders = Arc::new(RwLock::new(HashMap::new()));
+        let steer_senders = Arc::new(RwLock::new(HashMap::new()));
         let notification_service = NotificationService::new(config.clone());
 
         let container = LocalContainerService {
             db,
             child_store,
             interrupt_senders,
+            steer_senders,
             msg_stores,
             config,
             git,
@@ -108,6 +137,7 @@ impl LocalContainerService {
             analytics,
             approvals,
             queued_message_service,
+            secrets,
             publisher,
             notification_service,
         };
@@ -142,6 +172,16 @@ impl LocalContainerService {
         map.remove(id)
     }
 
+    async fn add_steer_sender(&self, id: Uuid, sender: SteerSender) {
+        let mut map = self.steer_senders.write().await;
+        map.insert(id, sender);
+    }
+
+    async fn remove_steer_sender(&self, id: &Uuid) {
+        let mut map = self.steer_senders.write().await;
+        map.remove(id);
+    }
+
     pub async fn cleanup_workspace(db: &DBService, workspace: &Workspace) {
         let Some(container_ref) = &workspace.container_ref else {
             return;
@@ -176,37 +216,49 @@ impl LocalContainerService {
 
         // Clear container_ref so this workspace won't be picked up again
         let _ = Workspace::clear_container_ref(&db.pool, workspace.id).await;
+        let _ = WorkspaceStorage::clear_usage(&db.pool, workspace.id).await;
     }
 
-    pub async fn cleanup_expired_workspaces(db: &DBService) -> Result<(), DeploymentError> {
-        let expired_workspaces = Workspace::find_expired_for_cleanup(&db.pool).await?;
-        if expired_workspaces.is_empty() {
-            tracing::debug!("No expired workspaces found");
+    /// Measure workspaces whose disk usage may have changed and remove the
+    /// worktrees selected by the configured cleanup policies
+    pub async fn run_workspace_cleanup(
+        db: &DBService,
+        cleanup_config: &WorkspaceCleanupConfig,
+    ) -> Result<(), DeploymentError> {
+        let now = Utc::now();
+        let entries = workspace_storage::refresh_stale_disk_usage(&db.pool, now).await?;
+        let planned = workspace_storage::plan_cleanup(&entries, cleanup_config, now);
+        if planned.is_empty() {
+            tracing::debug!("No workspaces to clean up");
             return Ok(());
         }
-        tracing::info!(
-            "Found {} expired workspaces to clean up",
-            expired_workspaces.len()
-        );
-        for workspace in &expired_workspaces {
-            Self::cleanup_workspace(db, workspace).await;
+        tracing::info!("Found {} workspaces to clean up", planned.len());
+        for (workspace_id, reason) in planned {
+            let Some(workspace) = Workspace::find_by_id(&db.pool, workspace_id).await? else {
+                continue;
+            };
+            tracing::info!("Cleaning up workspace {} ({:?})", workspace_id, reason);
+            Self::cleanup_workspace(db, &workspace).await;
         }
         Ok(())
     }
 
     pub async fn spawn_workspace_cleanup(&self) {
         let db = self.db.clone();
-        let mut cleanup_interval = tokio::time::interval(tokio::time::Duration::from_secs(1800)); // 30 minutes
+        let config = self.config.clone();
         WorkspaceManager::cleanup_orphan_workspaces(&self.db.pool).await;
         tokio::spawn(async move {
             loop {
-                cleanup_interval.tick().await;
+                // Re-read each run so policy changes apply without a restart
+                let cleanup_config = config.read().await.workspace_cleanup.clone();
                 tracing::info!("Starting periodic workspace cleanup...");
-                Self::cleanup_expired_workspaces(&db)
+                Self::run_workspace_cleanup(&db, &cleanup_config)
                     .await
-                    .unwrap_or_else(|e| {
-                        tracing::error!("Failed to clean up expired workspaces: {}", e)
-                    });
+                    .unwrap_or_else(|e| tracing::error!("Failed to clean up workspaces: {}", e));
+                tokio::time::sleep(Duration::from_secs(
+                    u64::from(cleanup_config.interval_minutes.max(1)) * 60,
+                ))
+                .await;
             }
         });
     }
@@ -339,11 +391,13 @@ impl LocalContainerService {
     }
 
     /// Spawn a background task that polls the child process for completion and
-    /// cleans up the execution entry when it exits.
+    /// cleans up the execution entry when it exits. With a watchdog config, the
+    /// process is also stopped as failed once it exceeds one of the limits.
     pub fn spawn_exit_monitor(
         &self,
         exec_id: &Uuid,
         exit_signal: Option<ExecutorExitSignal>,
+        watchdog: Option<WatchdogConfig>,
     ) -> JoinHandle<()> {
         let exec_id = *exec_id;
         let child_store = self.child_store.clone();
@@ -361,7 +415,17 @@ impl LocalContainerService {
                 .map(|rx| rx.boxed()) // wait for result
                 .unwrap_or_else(|| std::future::pending().boxed()); // no signal, stall forever
 
+            let watchdog_msg_store = match watchdog {
+                Some(_) => msg_stores.read().await.get(&exec_id).cloned(),
+                None => None,
+            };
+            let mut watchdog_future = match (watchdog, watchdog_msg_store) {
+                (Some(config), Some(msg_store)) => watchdog::watch(config, msg_store).boxed(),
+                _ => std::future::pending().boxed(), // no limits, stall forever
+            };
+
             let status_result: std::io::Result<std::process::ExitStatus>;
+            let mut failure_reason = None;
 
             // Wait for process to exit, or exit signal from executor
             tokio::select! {
@@ -388,7 +452,19 @@ impl LocalContainerService {
                 exit_status_result = &mut process_exit_rx => {
                     status_result = exit_status_result.unwrap_or_else(|e| Err(std::io::Error::other(e)));
                 }
+                // Watchdog limit exceeded: explain why in the conversation, then stop the process
+                trigger = &mut watchdog_future => {
+                    tracing::warn!("Watchdog stopping execution process {}: {}", exec_id, trigger.message());
+                    container.stop_for_watchdog(exec_id, &trigger, &mut process_exit_rx).await;
+                    failure_reason = Some(watchdog_failure_reason(&trigger));
+                    status_result = Ok(failure_exit_status());
+                }
             }
+            // Release the watchdog's handle on the MsgStore before cleanup
+            drop(watchdog_future);
+
+            // The executor no longer reads messages, so stop accepting them
+            container.remove_steer_sender(&exec_id).await;
 
             let (exit_code, status) = match status_result {
                 Ok(exit_status) => {
@@ -403,6 +479,21 @@ impl LocalContainerService {
                 Err(_) => (None, ExecutionProcessStatus::Failed),
             };
 
+            // Recognise provider failures such as rate limits in the agent's output
+            if failure_reason.is_none()
+                && matches!(status, ExecutionProcessStatus::Failed)
+                && !ExecutionProcess::was_stopped(&db.pool, exec_id).await
+            {
+                failure_reason = container.classify_failure(exec_id).await;
+            }
+
+            if let Some(failure_reason) = failure_reason
+                && let Err(e) =
+                    ExecutionProcess::set_failure_reason(&db.pool, exec_id, failure_reason).await
+            {
+                tracing::error!("Failed to record execution process failure reason: {}", e);
+            }
+
             if !ExecutionProcess::was_stopped(&db.pool, exec_id).await
                 && let Err(e) =
                     ExecutionProcess::update_completion(&db.pool, exec_id, status, exit_code).await
@@ -465,7 +556,35 @@ impl LocalContainerService {
                     }
                 }
 
-                if container.should_finalize(&ctx) {
+                // A plan-first attempt's planning turn hands its plan over for approval
+                if success
+                    && matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::CodingAgent
+                    )
+                {
+                    container.submit_plan(&ctx).await;
+                }
+
+                // Transient failures are retried after a backoff instead of finalizing
+                let retry_scheduled = container.try_schedule_retry(&ctx).await;
+
+                if !retry_scheduled && container.should_finalize(&ctx) {
+                    // Keep workspaces stacked on this one up to date with its branch
+                    let stacked = StackedWorkspaceService::new(db.clone(), container.git.clone());
+                    match stacked.sync_children(&ctx.workspace).await {
+                        Ok(conflicts) => {
+                            for conflict in conflicts {
+                                conflict.notify(&container.notification_service).await;
+                            }
+                        }
+                        Err(e) => tracing::warn!(
+                            "Failed to sync stacked workspaces for {}: {}",
+                            ctx.workspace.id,
+                            e
+                        ),
+                    }
+
                     // Only execute queued messages if the execution succeeded
                     // If it failed or was killed, just clear the queue and finalize
                     let should_execute_queued = !matches!(
@@ -561,6 +680,267 @@ impl LocalContainerService {
         })
     }
 
+    /// Add an entry to a running process's conversation from outside its normalizer.
+    /// Providers for a store share one counter, so this can't reuse the
+    /// normalizer's indices.
+    async fn add_conversation_entry(&self, exec_id: Uuid, entry: NormalizedEntry) {
+        if let Some(msg_store) = self.msg_stores.read().await.get(&exec_id).cloned() {
+            let entry_index = EntryIndexProvider::start_from(&msg_store);
+            add_normalized_entry(&msg_store, &entry_index, entry);
+        }
+    }
+
+    /// Store the plan a plan-first attempt's planning turn ended with and ask
+    /// for its approval
+    async fn submit_plan(&self, ctx: &ExecutionContext) {
+        let pool = &self.db.pool;
+        let plan = match TaskPlan::find_by_workspace_id(pool, ctx.workspace.id).await {
+            Ok(Some(plan)) if plan.status == TaskPlanStatus::Planning => plan,
+            Ok(_) => return,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to load plan for workspace {}: {}",
+                    ctx.workspace.id,
+                    e
+                );
+                return;
+            }
+        };
+
+        let exec_id = ctx.execution_process.id;
+        let content = self
+            .msg_stores
+            .read()
+            .await
+            .get(&exec_id)
+            .and_then(|store| task_plan::extract_plan(store));
+        let Some(content) = content else {
+            tracing::warn!("Planning turn {} ended without a plan", exec_id);
+            return;
+        };
+
+        let approval_id = task_plan::request_approval(&self.approvals, &plan, &content, exec_id);
+        if let Err(e) = TaskPlan::submit(pool, plan.id, exec_id, &content, &approval_id).await {
+            tracing::error!(
+                "Failed to store plan for workspace {}: {}",
+                ctx.workspace.id,
+                e
+            );
+        }
+    }
+
+    /// Recognise why a coding agent run failed from its output
+    async fn classify_failure(&self, exec_id: Uuid) -> Option<ExecutionProcessFailureReason> {
+        let process = ExecutionProcess::find_by_id(&self.db.pool, exec_id)
+            .await
+            .ok()??;
+        let coding_agent = process.executor_action().ok()?.coding_agent()?;
+        let msg_store = self.msg_stores.read().await.get(&exec_id).cloned()?;
+        let failure = coding_agent.classify_failure(&msg_store)?;
+        tracing::info!(
+            "Execution process {} failed because {}",
+            exec_id,
+            failure.description()
+        );
+        Some(agent_failure_reason(failure))
+    }
+
+    /// Schedule a re-run of a coding agent turn that failed for a transient
+    /// reason, after the backoff from its profile's retry policy. Returns
+    /// whether a retry was scheduled.
+    async fn try_schedule_retry(&self, ctx: &ExecutionContext) -> bool {
+        let process = &ctx.execution_process;
+        if !matches!(process.run_reason, ExecutionProcessRunReason::CodingAgent)
+            || !matches!(process.status, ExecutionProcessStatus::Failed)
+            || !process
+                .failure_reason
+                .is_some_and(|reason| reason.is_retryable())
+        {
+            return false;
+        }
+        let Ok(action) = process.executor_action() else {
+            return false;
+        };
+        let policy = action.retry_policy();
+
+        // Consecutive retryable failures in this session, this one included
+        let attempt = match ExecutionProcess::find_by_session_id(
+            &self.db.pool,
+            ctx.session.id,
+            false,
+        )
+        .await
+        {
+            Ok(processes) => processes
+                .iter()
+                .rev()
+                .filter(|p| matches!(p.run_reason, ExecutionProcessRunReason::CodingAgent))
+                .take_while(|p| {
+                    matches!(p.status, ExecutionProcessStatus::Failed)
+                        && p.failure_reason.is_some_and(|reason| reason.is_retryable())
+                })
+                .count() as u32,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to count retries for session {}: {}",
+                    ctx.session.id,
+                    e
+                );
+                return false;
+            }
+        };
+        if attempt > policy.max_attempts() {
+            return false;
+        }
+
+        let delay = policy.backoff(attempt);
+        self.add_conversation_entry(
+            process.id,
+            NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::SystemMessage,
+                content: format!(
+                    "Retrying in {} seconds (attempt {} of {}).",
+                    delay.as_secs(),
+                    attempt,
+                    policy.max_attempts()
+                ),
+                metadata: None,
+            },
+        )
+        .await;
+        tracing::info!(
+            "Retrying execution process {} in {:?} (attempt {} of {})",
+            process.id,
+            delay,
+            attempt,
+            policy.max_attempts()
+        );
+
+        let container = self.clone();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            if let Err(e) = container.start_retry(&ctx).await {
+                tracing::error!(
+                    "Failed to retry execution process {}: {}",
+                    ctx.execution_process.id,
+                    e
+                );
+                container
+                    .finalize_task(container.publisher.as_ref().ok(), &ctx)
+                    .await;
+            }
+        });
+        true
+    }
+
+    /// Re-run a failed coding agent turn, resuming the session it started if
+    /// there is one. Skipped if the session has moved on during the backoff.
+    async fn start_retry(&self, ctx: &ExecutionContext) -> Result<(), ContainerError> {
+        let pool = &self.db.pool;
+        let failed = &ctx.execution_process;
+        let latest = ExecutionProcess::find_latest_by_session_and_run_reason(
+            pool,
+            ctx.session.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+        if latest.is_none_or(|latest| latest.id != failed.id)
+            || ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+                pool,
+                ctx.workspace.id,
+            )
+            .await?
+        {
+            tracing::info!(
+                "Skipping retry of execution process {}, the session has moved on",
+                failed.id
+            );
+            return Ok(());
+        }
+
+        let action = failed.executor_action().map_err(ContainerError::Other)?;
+        let agent_session_id = CodingAgentTurn::find_by_execution_process_id(pool, failed.id)
+            .await?
+            .and_then(|turn| turn.agent_session_id);
+        let typ = match (action.typ(), agent_session_id) {
+            (ExecutorActionType::CodingAgentInitialRequest(request), Some(session_id)) => {
+                ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                    prompt: request.prompt.clone(),
+                    session_id,
+                    executor_profile_id: request.executor_profile_id.clone(),
+                    working_dir: request.working_dir.clone(),
+                    instructions: None,
+                })
+            }
+            (ExecutorActionType::CodingAgentFollowUpRequest(request), Some(session_id)) => {
+                ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                    session_id,
+                    ..request.clone()
+                })
+            }
+            (typ, _) => typ.clone(),
+        };
+        let retry = ExecutorAction::new(typ, action.next_action.clone());
+
+        self.ensure_container_exists(&ctx.workspace).await?;
+        self.start_execution(
+            &ctx.workspace,
+            &ctx.session,
+            &retry,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Stop a process that exceeded a watchdog limit: record why, interrupt it
+    /// and give it a moment to exit before killing it
+    async fn stop_for_watchdog(
+        &self,
+        exec_id: Uuid,
+        trigger: &WatchdogTrigger,
+        process_exit_rx: &mut tokio::sync::oneshot::Receiver<
+            std::io::Result<std::process::ExitStatus>,
+        >,
+    ) {
+        self.add_conversation_entry(
+            exec_id,
+            NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::ErrorMessage {
+                    error_type: NormalizedEntryError::Other,
+                },
+                content: trigger.message(),
+                metadata: None,
+            },
+        )
+        .await;
+        self.remove_steer_sender(&exec_id).await;
+
+        if let Some(interrupt_sender) = self.take_interrupt_sender(&exec_id).await {
+            let _ = interrupt_sender.send(());
+            if tokio::time::timeout(Duration::from_secs(5), process_exit_rx)
+                .await
+                .is_ok()
+            {
+                return;
+            }
+        }
+
+        if let Some(child_lock) = self.child_store.read().await.get(&exec_id).cloned() {
+            let mut child = child_lock.write().await;
+            if let Err(err) = command::kill_process_group(&mut child).await {
+                tracing::error!(
+                    "Failed to kill process group after watchdog trigger: {} {}",
+                    exec_id,
+                    err
+                );
+            }
+        }
+    }
+
     pub fn spawn_os_exit_watcher(
         &self,
         exec_id: Uuid,
@@ -603,19 +983,41 @@ impl LocalContainerService {
         format!("{}-{}", short_uuid(workspace_id), task_title_id)
     }
 
-    async fn track_child_msgs_in_store(&self, id: Uuid, child: &mut AsyncGroupChild) {
-        let store = Arc::new(MsgStore::new());
+    /// Extend the project-secret redactor with the detectors enabled in config
+    async fn build_redactor(&self, mut redactor: Redactor) -> Redactor {
+        let config = self.config.read().await.log_redaction.clone();
+        if config.builtin_detectors {
+            redactor.add_builtin_detectors();
+        }
+        redactor.set_high_entropy_detection(config.high_entropy);
+        for pattern in &config.custom_patterns {
+            if let Err(e) = redactor.add_pattern("custom", pattern) {
+                tracing::warn!(
+                    "Ignoring invalid log redaction pattern '{}': {}",
+                    pattern,
+                    e
+                );
+            }
+        }
+        redactor
+    }
+
+    async fn track_child_msgs_in_store(
+        &self,
+        id: Uuid,
+        child: &mut AsyncGroupChild,
+        redactor: Arc<Redactor>,
+    ) {
+        let store = Arc::new(MsgStore::with_redactor(redactor.clone()));
 
         let out = child.inner().stdout.take().expect("no stdout");
         let err = child.inner().stderr.take().expect("no stderr");
 
-        // Map stdout bytes -> LogMsg::Stdout
-        let out = ReaderStream::new(out)
-            .map_ok(|chunk| LogMsg::Stdout(String::from_utf8_lossy(&chunk).into_owned()));
+        // Map stdout bytes -> LogMsg::Stdout, with secret values redacted
+        let out = redact_chunks(ReaderStream::new(out), redactor.clone()).map_ok(LogMsg::Stdout);
 
-        // Map stderr bytes -> LogMsg::Stderr
-        let err = ReaderStream::new(err)
-            .map_ok(|chunk| LogMsg::Stderr(String::from_utf8_lossy(&chunk).into_owned()));
+        // Map stderr bytes -> LogMsg::Stderr, with secret values redacted
+        let err = redact_chunks(ReaderStream::new(err), redactor).map_ok(LogMsg::Stderr);
 
         // If you have a JSON Patch source, map it to LogMsg::JsonPatch too, then select all three.
 
@@ -790,6 +1192,165 @@ impl LocalContainerService {
         Ok(())
     }
 
+    /// Report a sub-task's result to the session of the workspace it was created from.
+    /// The report is queued while the parent is running, otherwise it starts a follow-up.
+    /// Each status is reported once per sub-task workspace, however often it is reached.
+    async fn try_notify_parent_workspace(&self, child: &Workspace) -> Result<(), ContainerError> {
+        let pool = &self.db.pool;
+        let Some(task) = Task::find_by_id(pool, child.task_id).await? else {
+            return Ok(());
+        };
+        let Some(parent_id) = task.parent_workspace_id else {
+            return Ok(());
+        };
+        if !matches!(task.status, TaskStatus::InReview | TaskStatus::Done) {
+            return Ok(());
+        }
+        let Some(session) = Session::find_latest_by_workspace_id(pool, parent_id).await? else {
+            return Ok(());
+        };
+        let Some(latest_process) = ExecutionProcess::find_latest_by_session_and_run_reason(
+            pool,
+            session.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+
+        if !Workspace::claim_parent_report(pool, child.id, &task.status).await? {
+            return Ok(());
+        }
+
+        let result = self
+            .deliver_parent_report(&task, child, parent_id, &session, &latest_process)
+            .await;
+        if result.is_err()
+            && let Err(e) = Workspace::release_parent_report(pool, child.id, &task.status).await
+        {
+            tracing::warn!(
+                "Failed to release parent report claim for workspace {}: {}",
+                child.id,
+                e
+            );
+        }
+        result
+    }
+
+    /// Queue or start the follow-up carrying a sub-task's report on the parent session
+    async fn deliver_parent_report(
+        &self,
+        task: &Task,
+        child: &Workspace,
+        parent_id: Uuid,
+        session: &Session,
+        latest_process: &ExecutionProcess,
+    ) -> Result<(), ContainerError> {
+        let pool = &self.db.pool;
+        let report = self.subtask_report(task, child).await?;
+
+        if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, parent_id)
+            .await?
+        {
+            // Append to any message already queued so nothing is lost
+            let data = match self.queued_message_service.get_queued(session.id) {
+                Some(queued) => DraftFollowUpData {
+                    message: format!("{}\n\n{}", queued.data.message, report),
+                    variant: queued.data.variant,
+                },
+                None => DraftFollowUpData {
+                    message: report,
+                    variant: None,
+                },
+            };
+            tracing::info!(
+                "Queued sub-task report for task {} on parent session {}",
+                task.id,
+                session.id
+            );
+            self.queued_message_service.queue_message(session.id, data);
+            return Ok(());
+        }
+
+        let ctx = ExecutionProcess::load_context(pool, latest_process.id).await?;
+        self.ensure_container_exists(&ctx.workspace).await?;
+        let variant = ExecutionProcess::latest_executor_profile_for_session(pool, session.id)
+            .await
+            .ok()
+            .and_then(|profile| profile.variant);
+        tracing::info!(
+            "Starting parent session {} follow-up with sub-task report for task {}",
+            session.id,
+            task.id
+        );
+        self.start_queued_follow_up(
+            &ctx,
+            &DraftFollowUpData {
+                message: report,
+                variant,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Build the follow-up message describing a sub-task's status, summary and diff stats
+    async fn subtask_report(
+        &self,
+        task: &Task,
+        child: &Workspace,
+    ) -> Result<String, ContainerError> {
+        let pool = &self.db.pool;
+        let summary = CodingAgentTurn::find_latest_summary_for_workspace(pool, child.id).await?;
+
+        let (mut files, mut additions, mut deletions) = (0usize, 0usize, 0usize);
+        for workspace_repo in WorkspaceRepo::find_by_workspace_id(pool, child.id).await? {
+            let Some(repo) = Repo::find_by_id(pool, workspace_repo.repo_id).await? else {
+                continue;
+            };
+            let diffs = match self.git.get_diffs(
+                DiffTarget::Branch {
+                    repo_path: &repo.path,
+                    branch_name: &child.branch,
+                    base_branch: &workspace_repo.target_branch,
+                },
+                None,
+            ) {
+                Ok(diffs) => diffs,
+                Err(e) => {
+                    tracing::warn!("Failed to diff sub-task branch {}: {}", child.branch, e);
+                    continue;
+                }
+            };
+            for diff in diffs {
+                files += 1;
+                let (added, deleted) = match (diff.additions, diff.deletions) {
+                    (Some(added), Some(deleted)) => (added, deleted),
+                    _ => compute_line_change_counts(
+                        diff.old_content.as_deref().unwrap_or(""),
+                        diff.new_content.as_deref().unwrap_or(""),
+                    ),
+                };
+                additions += added;
+                deletions += deleted;
+            }
+        }
+
+        let status = match task.status {
+            TaskStatus::Done => "done",
+            _ => "ready for review",
+        };
+        let mut report = format!(
+            "Sub-task \"{}\" (task id {}) is {}.\nBranch: {}\nChanges: {} files changed, +{} -{}",
+            task.title, task.id, status, child.branch, files, additions, deletions
+        );
+        if let Some(summary) = summary {
+            report.push_str(&format!("\n\nSummary:\n{summary}"));
+        }
+        Ok(report)
+    }
+
     /// Start a follow-up execution from a queued message
     async fn start_queued_follow_up(
         &self,
@@ -833,12 +1394,14 @@ impl LocalContainerService {
                 session_id: agent_session_id,
                 executor_profile_id: executor_profile_id.clone(),
                 working_dir: working_dir.clone(),
+                instructions: None,
             })
         } else {
             ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
                 prompt: queued_data.message.clone(),
                 executor_profile_id: executor_profile_id.clone(),
                 working_dir,
+                instructions: None,
             })
         };
 
@@ -854,6 +1417,68 @@ impl LocalContainerService {
     }
 }
 
+/// Parts of a repo's git dir that its worktree writes to when committing: the
+/// worktree's own admin dir plus the shared objects, refs and reflogs. The
+/// config and hooks stay read-only, so a sandboxed process can't make git run
+/// commands outside the sandbox.
+async fn writable_git_paths(repo_path: &Path, worktree_path: &Path) -> Vec<PathBuf> {
+    let git_dir = repo_path.join(".git");
+    let mut paths = vec![
+        git_dir.join("objects"),
+        git_dir.join("refs"),
+        git_dir.join("logs"),
+    ];
+    // The worktree's `.git` file reads `gitdir: <repo>/.git/worktrees/<name>`
+    if let Ok(contents) = tokio::fs::read_to_string(worktree_path.join(".git")).await
+        && let Some(admin_dir) = contents.trim().strip_prefix("gitdir: ")
+    {
+        let admin_dir = worktree_path.join(admin_dir);
+        if admin_dir.starts_with(git_dir.join("worktrees")) {
+            paths.push(admin_dir);
+        }
+    }
+    paths
+}
+
+async fn record_executor_version(
+    pool: &sqlx::SqlitePool,
+    execution_process_id: Uuid,
+    version: Option<String>,
+) {
+    let Some(version) = version else {
+        return;
+    };
+    if let Err(e) =
+        ExecutionProcess::set_executor_version(pool, execution_process_id, &version).await
+    {
+        tracing::warn!(
+            "Failed to record executor version for process {}: {}",
+            execution_process_id,
+            e
+        );
+    }
+}
+
+fn watchdog_failure_reason(trigger: &WatchdogTrigger) -> ExecutionProcessFailureReason {
+    match trigger {
+        WatchdogTrigger::TimeLimit { .. } => ExecutionProcessFailureReason::TimeLimitExceeded,
+        WatchdogTrigger::Idle { .. } => ExecutionProcessFailureReason::Stalled,
+        WatchdogTrigger::RepeatedToolCalls { .. } => {
+            ExecutionProcessFailureReason::RepeatedToolCalls
+        }
+    }
+}
+
+fn agent_failure_reason(failure: AgentFailure) -> ExecutionProcessFailureReason {
+    match failure {
+        AgentFailure::RateLimited => ExecutionProcessFailureReason::RateLimited,
+        AgentFailure::Overloaded => ExecutionProcessFailureReason::Overloaded,
+        AgentFailure::AuthExpired => ExecutionProcessFailureReason::AuthExpired,
+        AgentFailure::Network => ExecutionProcessFailureReason::Network,
+        AgentFailure::ContextLengthExceeded => ExecutionProcessFailureReason::ContextLengthExceeded,
+    }
+}
+
 fn failure_exit_status() -> std::process::ExitStatus {
     #[cfg(unix)]
     {
@@ -893,6 +1518,16 @@ impl ContainerService for LocalContainerService {
         self.config.read().await.git_branch_prefix.clone()
     }
 
+    async fn notify_parent_workspace(&self, workspace: &Workspace) {
+        if let Err(e) = self.try_notify_parent_workspace(workspace).await {
+            tracing::warn!(
+                "Failed to notify parent workspace of sub-task workspace {}: {}",
+                workspace.id,
+                e
+            );
+        }
+    }
+
     fn workspace_to_current_dir(&self, workspace: &Workspace) -> PathBuf {
         PathBuf::from(workspace.container_ref.clone().unwrap_or_default())
     }
@@ -1087,6 +1722,30 @@ impl ContainerService for LocalContainerService {
         env.insert("VK_WORKSPACE_ID", workspace.id.to_string());
         env.insert("VK_WORKSPACE_BRANCH", &workspace.branch);
 
+        // Project secrets are injected as env vars and redacted from captured output
+        let secrets = self
+            .secrets
+            .resolve(&self.db.pool, project.id)
+            .await
+            .map_err(|e| ContainerError::Other(anyhow!("Failed to load project secrets: {e}")))?;
+        env.merge(&secrets.env);
+        let redactor = Arc::new(self.build_redactor(secrets.redactor).await);
+
+        // Project sandbox settings; executor profiles may override them at spawn time.
+        // The workspace and the parts of each repo's git dir its worktree commits
+        // to stay writable.
+        let sandbox_config = project
+            .sandbox_config
+            .as_ref()
+            .map(|config| config.0.clone())
+            .unwrap_or_default();
+        let mut workspace_roots = vec![current_dir.clone()];
+        for repo in WorkspaceRepo::find_repos_for_workspace(&self.db.pool, workspace.id).await? {
+            workspace_roots
+                .extend(writable_git_paths(&repo.path, &current_dir.join(&repo.name)).await);
+        }
+        let env = env.with_sandbox(SandboxPolicy::new(sandbox_config, workspace_roots));
+
         // Create the child and stream, add to execution tracker with timeout
         let mut spawned = tokio::time::timeout(
             Duration::from_secs(30),
@@ -1099,7 +1758,7 @@ impl ContainerService for LocalContainerService {
             ))
         })??;
 
-        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
+        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child, redactor)
             .await;
 
         self.add_child_to_store(execution_process.id, spawned.child)
@@ -1111,8 +1770,39 @@ impl ContainerService for LocalContainerService {
                 .await;
         }
 
+        // Store steer sender for messages sent while the executor is running
+        if let Some(steer_sender) = spawned.steer_sender {
+            self.add_steer_sender(execution_process.id, steer_sender)
+                .await;
+        }
+
         // Spawn unified exit monitor: watches OS exit and optional executor signal
-        let _hn = self.spawn_exit_monitor(&execution_process.id, spawned.exit_signal);
+        let _hn = self.spawn_exit_monitor(
+            &execution_process.id,
+            spawned.exit_signal,
+            executor_action.watchdog(),
+        );
+
+        // Record the agent CLI's version without holding up the launch; on a
+        // cache miss it's recorded once detection finishes
+        if let Some(command) = executor_action
+            .coding_agent()
+            .and_then(|agent| agent.version_command())
+        {
+            let pool = self.db.pool.clone();
+            let process_id = execution_process.id;
+            match version::cached_version(&command) {
+                Some(version) => record_executor_version(&pool, process_id, version).await,
+                None => {
+                    let detection = version::detect_version_in_background(command);
+                    tokio::spawn(async move {
+                        if let Ok(version) = detection.await {
+                            record_executor_version(&pool, process_id, version).await;
+                        }
+                    });
+                }
+            }
+        }
 
         Ok(())
     }
@@ -1136,6 +1826,7 @@ impl ContainerService for LocalContainerService {
 
         ExecutionProcess::update_completion(&self.db.pool, execution_process.id, status, exit_code)
             .await?;
+        self.remove_steer_sender(&execution_process.id).await;
 
         // Try graceful interrupt first, then force kill
         if let Some(interrupt_sender) = self.take_interrupt_sender(&execution_process.id).await {
@@ -1222,6 +1913,12 @@ impl ContainerService for LocalContainerService {
         Ok(())
     }
 
+    async fn steer_execution(&self, execution_process: &ExecutionProcess, message: String) -> bool {
+        let map = self.steer_senders.read().await;
+        map.get(&execution_process.id)
+            .is_some_and(|sender| sender.send(message).is_ok())
+    }
+
     async fn stream_diff(
         &self,
         workspace: &Workspace,
@@ -20,6 +20,7 @@ use services::services::{
     queued_message::QueuedMessageService,
     remote_client::{RemoteClient, RemoteClientError},
     repo::RepoService,
+    secrets::SecretsService,
     share::{ShareConfig, SharePublisher},
 };
 use tokio::sync::RwLock;
@@ -51,6 +52,7 @@ pub struct LocalDeployment {
     file_search_cache: Arc<FileSearchCache>,
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
+    secrets: SecretsService,
     share_publisher: Result<SharePublisher, RemoteClientNotConfigured>,
     share_config: Option<ShareConfig>,
     remote_client: Result<RemoteClient, RemoteClientNotConfigured>,
@@ -127,6 +129,7 @@ impl Deployment for LocalDeployment {
 
         let approvals = Approvals::new(msg_stores.clone());
         let queued_message_service = QueuedMessageService::new();
+        let secrets = SecretsService::new()?;
 
         let share_config = ShareConfig::from_env();
 
@@ -181,6 +184,7 @@ impl Deployment for LocalDeployment {
             analytics_ctx,
             approvals.clone(),
             queued_message_service.clone(),
+            secrets.clone(),
             share_publisher.clone(),
         )
         .await;
@@ -204,6 +208,7 @@ impl Deployment for LocalDeployment {
             file_search_cache,
             approvals,
             queued_message_service,
+            secrets,
             share_publisher,
             share_config: share_config.clone(),
             remote_client,
@@ -270,6 +275,10 @@ impl Deployment for LocalDeployment {
         &self.queued_message_service
     }
 
+    fn secrets(&self) -> &SecretsService {
+        &self.secrets
+    }
+
     fn share_publisher(&self) -> Result<SharePublisher, RemoteClientNotConfigured> {
         self.share_publisher.clone()
     }
@@ -0,0 +1,55 @@
+CREATE TABLE IF NOT EXISTS shared_task_comments (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    task_id         UUID NOT NULL REFERENCES shared_tasks(id) ON DELETE CASCADE,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    author_user_id  UUID REFERENCES users(id) ON DELETE SET NULL,
+    body            TEXT NOT NULL,
+    edited_at       TIMESTAMPTZ,
+    deleted_at      TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_shared_task_comments_task
+    ON shared_task_comments (task_id, created_at);
+
+CREATE INDEX IF NOT EXISTS idx_shared_task_comments_org
+    ON shared_task_comments (organization_id);
+
+CREATE TRIGGER trg_shared_task_comments_updated_at
+    BEFORE UPDATE ON shared_task_comments
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
+
+DO $$
+BEGIN
+    CREATE TYPE shared_task_activity_type AS ENUM (
+        'created',
+        'status_changed',
+        'assignee_changed',
+        'deleted'
+    );
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END
+$$;
+
+CREATE TABLE IF NOT EXISTS shared_task_activity (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    task_id         UUID NOT NULL REFERENCES shared_tasks(id) ON DELETE CASCADE,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    actor_user_id   UUID REFERENCES users(id) ON DELETE SET NULL,
+    event_type      shared_task_activity_type NOT NULL,
+    from_value      TEXT,
+    to_value        TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_shared_task_activity_task
+    ON shared_task_activity (task_id, created_at);
+
+CREATE INDEX IF NOT EXISTS idx_shared_task_activity_org
+    ON shared_task_activity (organization_id);
+
+SELECT electric_sync_table('public', 'shared_task_comments');
+SELECT electric_sync_table('public', 'shared_task_activity');
@@ -0,0 +1,50 @@
+-- Read-only organization role. Postgres only allows using a new enum value
+-- after the transaction that added it commits, so nothing below refers to it.
+ALTER TYPE member_role ADD VALUE IF NOT EXISTS 'viewer';
+
+-- Scoped members only see the projects listed in `project_members`. Existing
+-- members keep access to every project of their organization.
+ALTER TABLE organization_member_metadata
+    ADD COLUMN IF NOT EXISTS project_scoped BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS project_members (
+    project_id      UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    organization_id UUID NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, user_id),
+    FOREIGN KEY (organization_id, user_id)
+        REFERENCES organization_member_metadata (organization_id, user_id)
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_members_user
+    ON project_members (user_id, organization_id);
+
+-- Electric shapes can only filter on the row itself, so comments and activity
+-- carry their task's project for project-scoped members.
+ALTER TABLE shared_task_comments ADD COLUMN IF NOT EXISTS project_id UUID;
+UPDATE shared_task_comments c
+SET project_id = t.project_id
+FROM shared_tasks t
+WHERE c.task_id = t.id AND c.project_id IS NULL;
+ALTER TABLE shared_task_comments
+    ALTER COLUMN project_id SET NOT NULL,
+    ADD CONSTRAINT shared_task_comments_project_id_fkey
+        FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE;
+
+ALTER TABLE shared_task_activity ADD COLUMN IF NOT EXISTS project_id UUID;
+UPDATE shared_task_activity a
+SET project_id = t.project_id
+FROM shared_tasks t
+WHERE a.task_id = t.id AND a.project_id IS NULL;
+ALTER TABLE shared_task_activity
+    ALTER COLUMN project_id SET NOT NULL,
+    ADD CONSTRAINT shared_task_activity_project_id_fkey
+        FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_shared_task_comments_project
+    ON shared_task_comments (project_id);
+
+CREATE INDEX IF NOT EXISTS idx_shared_task_activity_project
+    ON shared_task_activity (project_id);
@@ -0,0 +1,33 @@
+-- Per-project sync between shared tasks and the issues of one GitHub repository
+CREATE TABLE IF NOT EXISTS github_issue_sync_configs (
+    project_id          UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    organization_id     UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- A repository syncs with at most one project
+    repository_id       UUID NOT NULL UNIQUE REFERENCES github_app_repositories(id) ON DELETE CASCADE,
+    -- Create shared tasks for issues opened on GitHub
+    import_new_issues   BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Open GitHub issues for shared tasks created in Vibe Kanban
+    export_new_tasks    BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Fallback creator for imported issues whose author isn't a member
+    enabled_by_user_id  UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER trg_github_issue_sync_configs_updated_at
+    BEFORE UPDATE ON github_issue_sync_configs
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
+
+-- Links a shared task to the GitHub issue it mirrors. The unique constraint on
+-- the issue prevents importing the same issue twice.
+CREATE TABLE IF NOT EXISTS shared_task_github_issues (
+    task_id             UUID PRIMARY KEY REFERENCES shared_tasks(id) ON DELETE CASCADE,
+    repository_id       UUID NOT NULL REFERENCES github_app_repositories(id) ON DELETE CASCADE,
+    github_issue_id     BIGINT NOT NULL,
+    issue_number        BIGINT NOT NULL,
+    html_url            TEXT NOT NULL,
+    last_synced_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (repository_id, github_issue_id)
+);
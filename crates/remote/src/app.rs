@@ -8,14 +8,13 @@ use crate::{
     AppState,
     auth::{
         GitHubOAuthProvider, GoogleOAuthProvider, JwtService, OAuthHandoffService,
-        OAuthTokenValidator, ProviderRegistry,
+        OAuthTokenValidator, OidcProvider, ProviderRegistry,
     },
-    config::RemoteServerConfig,
+    blob_store::{BlobStore, LocalBlobStore, S3BlobStore},
+    config::{BlobStoreConfig, RemoteServerConfig},
     db,
     github_app::GitHubAppService,
-    mail::LoopsMailer,
-    r2::R2Service,
-    routes,
+    mail, routes,
 };
 
 pub struct Server;
@@ -60,6 +59,16 @@ impl Server {
             )?);
         }
 
+        for oidc in auth_config.oidc() {
+            let provider = OidcProvider::discover(
+                oidc.clone(),
+                auth_config.jwt_secret().expose_secret().as_bytes(),
+            )
+            .await
+            .with_context(|| format!("failed to discover OIDC provider `{}`", oidc.id))?;
+            registry.register(provider);
+        }
+
         if registry.is_empty() {
             bail!("no OAuth providers configured");
         }
@@ -76,9 +85,7 @@ impl Server {
         let oauth_token_validator =
             Arc::new(OAuthTokenValidator::new(pool.clone(), registry.clone()));
 
-        let api_key = std::env::var("LOOPS_EMAIL_API_KEY")
-            .context("LOOPS_EMAIL_API_KEY environment variable is required")?;
-        let mailer = Arc::new(LoopsMailer::new(api_key));
+        let mailer = mail::build_mailer(&config.mailer).context("failed to create mailer")?;
 
         let server_public_base_url = config.server_public_base_url.clone().ok_or_else(|| {
             anyhow::anyhow!(
@@ -86,14 +93,28 @@ impl Server {
             )
         })?;
 
-        let r2 = config.r2.as_ref().map(R2Service::new);
-        if r2.is_some() {
-            tracing::info!("R2 storage service initialized");
-        } else {
-            tracing::warn!(
-                "R2 storage service not configured. Set R2_ACCESS_KEY_ID, R2_SECRET_ACCESS_KEY, R2_REVIEW_ENDPOINT, and R2_REVIEW_BUCKET to enable."
-            );
-        }
+        let (blob_store, local_blob_store) = match &config.blob_store {
+            Some(BlobStoreConfig::S3(s3_config)) => {
+                tracing::info!("S3 blob store initialized");
+                let store: Arc<dyn BlobStore> = Arc::new(S3BlobStore::new(s3_config));
+                (Some(store), None)
+            }
+            Some(BlobStoreConfig::Local(local_config)) => {
+                tracing::info!(root = %local_config.root.display(), "Local blob store initialized");
+                let store = Arc::new(LocalBlobStore::new(
+                    local_config,
+                    &server_public_base_url,
+                    auth_config.jwt_secret().expose_secret().as_bytes(),
+                ));
+                (Some(store.clone() as Arc<dyn BlobStore>), Some(store))
+            }
+            None => {
+                tracing::warn!(
+                    "Blob storage not configured. Set BLOB_STORE_BACKEND to `s3` (with S3_ACCESS_KEY_ID, S3_SECRET_ACCESS_KEY, S3_ENDPOINT and S3_BUCKET) or `local` to enable."
+                );
+                (None, None)
+            }
+        };
 
         let http_client = reqwest::Client::builder()
             .user_agent("VibeKanbanRemote/1.0")
@@ -133,7 +154,8 @@ impl Server {
             mailer,
             server_public_base_url,
             http_client,
-            r2,
+            blob_store,
+            local_blob_store,
             github_app,
         );
 
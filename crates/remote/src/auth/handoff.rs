@@ -262,7 +262,7 @@ impl OAuthHandoffService {
         );
 
         let grant = provider
-            .exchange_code(code, &redirect_uri)
+            .exchange_code(code, state_value, &redirect_uri)
             .await
             .map_err(HandoffError::Provider)?;
 
@@ -396,7 +396,7 @@ impl OAuthHandoffService {
     ) -> Result<ProviderUser, HandoffError> {
         let mut last_error: Option<AnyhowError> = None;
         for attempt in 1..=USER_FETCH_MAX_ATTEMPTS {
-            match provider.fetch_user(&grant.access_token).await {
+            match provider.fetch_user(grant).await {
                 Ok(user) => return Ok(user),
                 Err(err) => {
                     let retryable = attempt < USER_FETCH_MAX_ATTEMPTS && is_forbidden_error(&err);
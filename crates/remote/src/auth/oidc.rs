@@ -0,0 +1,607 @@
+//! Generic OpenID Connect provider for self-hosted identity providers
+//! (Keycloak, Okta, Authentik, ...).
+//!
+//! Endpoints are resolved through discovery, the authorization code flow uses
+//! PKCE, and the ID token is validated against the issuer's JWKS before any
+//! claims are trusted.
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Duration;
+use hmac::{Hmac, Mac};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, Validation, decode, decode_header,
+    jwk::{AlgorithmParameters, JwkSet},
+};
+use reqwest::Client;
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use url::Url;
+
+use super::provider::{
+    AuthorizationGrant, AuthorizationProvider, ProviderTokenDetails, ProviderUser,
+    TokenValidationError,
+};
+use crate::config::OidcProviderConfig;
+
+const USER_AGENT: &str = "VibeKanbanRemote/1.0";
+const TOKEN_EXPIRATION_LEEWAY_SECONDS: i64 = 20;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, Deserialize)]
+struct DiscoveryDocument {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+    userinfo_endpoint: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OidcTokenResponse {
+    Success {
+        access_token: String,
+        token_type: String,
+        scope: Option<String>,
+        expires_in: Option<i64>,
+        refresh_token: Option<String>,
+        id_token: Option<String>,
+    },
+    Error {
+        error: String,
+        error_description: Option<String>,
+    },
+}
+
+pub struct OidcProvider {
+    client: Client,
+    config: OidcProviderConfig,
+    discovery: DiscoveryDocument,
+    jwks: RwLock<JwkSet>,
+    /// Key used to derive per-handoff PKCE verifiers from the OAuth state
+    pkce_key: Vec<u8>,
+}
+
+impl OidcProvider {
+    /// Resolve the provider's endpoints and signing keys via discovery.
+    pub async fn discover(config: OidcProviderConfig, pkce_key: &[u8]) -> Result<Self> {
+        let client = Client::builder().user_agent(USER_AGENT).build()?;
+
+        let discovery_url = format!(
+            "{}/.well-known/openid-configuration",
+            config.issuer.trim_end_matches('/')
+        );
+        let discovery: DiscoveryDocument = client
+            .get(&discovery_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .with_context(|| format!("invalid discovery document at {discovery_url}"))?;
+
+        if discovery.issuer.trim_end_matches('/') != config.issuer.trim_end_matches('/') {
+            bail!(
+                "OIDC issuer mismatch for `{}`: configured {}, discovered {}",
+                config.id,
+                config.issuer,
+                discovery.issuer
+            );
+        }
+
+        let jwks = fetch_jwks(&client, &discovery.jwks_uri).await?;
+
+        Ok(Self {
+            client,
+            config,
+            discovery,
+            jwks: RwLock::new(jwks),
+            pkce_key: pkce_key.to_vec(),
+        })
+    }
+
+    fn pkce_verifier(&self, state: &str) -> String {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.pkce_key).expect("HMAC accepts keys of any size");
+        mac.update(b"oidc-pkce:");
+        mac.update(self.config.id.as_bytes());
+        mac.update(b":");
+        mac.update(state.as_bytes());
+        URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+    }
+
+    async fn decoding_key(&self, kid: Option<&str>) -> Result<DecodingKey> {
+        if let Some(key) = find_key(&*self.jwks.read().await, kid)? {
+            return Ok(key);
+        }
+
+        // Unknown key id: the IdP may have rotated its keys.
+        let refreshed = fetch_jwks(&self.client, &self.discovery.jwks_uri).await?;
+        let key = find_key(&refreshed, kid)?;
+        *self.jwks.write().await = refreshed;
+        key.context("no matching key in OIDC JWKS")
+    }
+
+    async fn validate_id_token(&self, id_token: &str) -> Result<Map<String, Value>> {
+        let header = decode_header(id_token).context("invalid ID token header")?;
+        if matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            bail!("symmetric ID token signatures are not supported");
+        }
+
+        let key = self.decoding_key(header.kid.as_deref()).await?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_audience(&[&self.config.client_id]);
+        validation.set_issuer(&[&self.discovery.issuer]);
+
+        let data = decode::<Map<String, Value>>(id_token, &key, &validation)
+            .context("ID token validation failed")?;
+        Ok(data.claims)
+    }
+
+    async fn fetch_userinfo(&self, access_token: &SecretString) -> Result<Map<String, Value>> {
+        let Some(endpoint) = &self.discovery.userinfo_endpoint else {
+            return Ok(Map::new());
+        };
+
+        let claims = self
+            .client
+            .get(endpoint)
+            .bearer_auth(access_token.expose_secret())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(claims)
+    }
+
+    /// Enforce the configured email domain and group restrictions.
+    fn check_access(&self, email: Option<&str>, claims: &Map<String, Value>) -> Result<()> {
+        if !self.config.allowed_email_domains.is_empty() {
+            // Providers that don't assert a verified email can't prove the domain
+            let verified = claim(claims, "email_verified").and_then(Value::as_bool) == Some(true);
+            let domain = email
+                .filter(|_| verified)
+                .and_then(|email| email.rsplit_once('@'))
+                .map(|(_, domain)| domain.to_ascii_lowercase());
+            let allowed = domain.as_deref().is_some_and(|domain| {
+                self.config
+                    .allowed_email_domains
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(domain))
+            });
+            if !allowed {
+                bail!(
+                    "email domain is not allowed for provider `{}`",
+                    self.config.id
+                );
+            }
+        }
+
+        if !self.config.allowed_groups.is_empty() {
+            let groups = string_list(claim(claims, &self.config.groups_claim));
+            let allowed = groups
+                .iter()
+                .any(|group| self.config.allowed_groups.contains(group));
+            if !allowed {
+                bail!(
+                    "user is not in an allowed group for provider `{}`",
+                    self.config.id
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn refresh(
+        &self,
+        refresh_token: &str,
+    ) -> Result<ProviderTokenDetails, TokenValidationError> {
+        let response = self
+            .client
+            .post(&self.discovery.token_endpoint)
+            .form(&[
+                ("client_id", self.config.client_id.as_str()),
+                ("client_secret", self.config.client_secret.expose_secret()),
+                ("refresh_token", refresh_token),
+                ("grant_type", "refresh_token"),
+            ])
+            .send()
+            .await
+            .map_err(|err| {
+                TokenValidationError::Temporary(format!("refresh request failed: {err}"))
+            })?;
+
+        match response.status() {
+            status if status.is_success() => {}
+            reqwest::StatusCode::BAD_REQUEST | reqwest::StatusCode::UNAUTHORIZED => {
+                return Err(TokenValidationError::InvalidOrRevoked);
+            }
+            status => {
+                return Err(TokenValidationError::Temporary(format!(
+                    "unexpected token refresh status: {status}"
+                )));
+            }
+        }
+
+        match response
+            .json::<OidcTokenResponse>()
+            .await
+            .map_err(|err| TokenValidationError::Temporary(err.to_string()))?
+        {
+            OidcTokenResponse::Success {
+                access_token,
+                expires_in,
+                refresh_token: new_refresh_token,
+                ..
+            } => Ok(ProviderTokenDetails {
+                provider: self.name().to_string(),
+                access_token,
+                refresh_token: Some(new_refresh_token.unwrap_or_else(|| refresh_token.to_string())),
+                expires_at: expires_in.map(|secs| chrono::Utc::now().timestamp() + secs),
+            }),
+            OidcTokenResponse::Error { .. } => Err(TokenValidationError::InvalidOrRevoked),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthorizationProvider for OidcProvider {
+    fn name(&self) -> &str {
+        &self.config.id
+    }
+
+    fn display_name(&self) -> &str {
+        &self.config.display_name
+    }
+
+    fn scopes(&self) -> Vec<&str> {
+        self.config.scopes.iter().map(String::as_str).collect()
+    }
+
+    fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url> {
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(self.pkce_verifier(state)));
+
+        let mut url = Url::parse(&self.discovery.authorization_endpoint)?;
+        {
+            let mut qp = url.query_pairs_mut();
+            qp.append_pair("client_id", &self.config.client_id);
+            qp.append_pair("redirect_uri", redirect_uri);
+            qp.append_pair("response_type", "code");
+            qp.append_pair("scope", &self.scopes().join(" "));
+            qp.append_pair("state", state);
+            qp.append_pair("code_challenge", &challenge);
+            qp.append_pair("code_challenge_method", "S256");
+        }
+        Ok(url)
+    }
+
+    async fn exchange_code(
+        &self,
+        code: &str,
+        state: &str,
+        redirect_uri: &str,
+    ) -> Result<AuthorizationGrant> {
+        let verifier = self.pkce_verifier(state);
+        let response = self
+            .client
+            .post(&self.discovery.token_endpoint)
+            .form(&[
+                ("client_id", self.config.client_id.as_str()),
+                ("client_secret", self.config.client_secret.expose_secret()),
+                ("code", code),
+                ("grant_type", "authorization_code"),
+                ("redirect_uri", redirect_uri),
+                ("code_verifier", verifier.as_str()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        match response.json::<OidcTokenResponse>().await? {
+            OidcTokenResponse::Success {
+                access_token,
+                token_type,
+                scope,
+                expires_in,
+                refresh_token,
+                id_token,
+            } => {
+                let id_token = id_token.context("OIDC token response is missing id_token")?;
+                // Reject forged or misdirected tokens before the grant is used.
+                self.validate_id_token(&id_token).await?;
+
+                Ok(AuthorizationGrant {
+                    access_token: SecretString::new(access_token.into()),
+                    token_type,
+                    scopes: scope
+                        .unwrap_or_default()
+                        .split_whitespace()
+                        .map(str::to_string)
+                        .collect(),
+                    refresh_token: refresh_token.map(|v| SecretString::new(v.into())),
+                    expires_in: expires_in.map(Duration::seconds),
+                    id_token: Some(SecretString::new(id_token.into())),
+                })
+            }
+            OidcTokenResponse::Error {
+                error,
+                error_description,
+            } => {
+                let detail = error_description.unwrap_or_else(|| error.clone());
+                bail!("{} token exchange failed: {detail}", self.config.id)
+            }
+        }
+    }
+
+    async fn fetch_user(&self, grant: &AuthorizationGrant) -> Result<ProviderUser> {
+        let id_token = grant
+            .id_token
+            .as_ref()
+            .context("OIDC grant is missing id_token")?;
+        let id_claims = self.validate_id_token(id_token.expose_secret()).await?;
+
+        // Userinfo often carries more profile claims; ID token claims win on conflict.
+        let mut claims = self.fetch_userinfo(&grant.access_token).await?;
+        if let Some(userinfo_sub) = claims.get("sub")
+            && userinfo_sub != id_claims.get("sub").unwrap_or(&Value::Null)
+        {
+            bail!("OIDC userinfo subject does not match the ID token");
+        }
+        claims.extend(id_claims);
+
+        let subject = claim(&claims, "sub")
+            .and_then(Value::as_str)
+            .context("OIDC claims are missing `sub`")?
+            .to_string();
+
+        let mapping = &self.config.claims;
+        let email = claim_string(&claims, &mapping.email);
+        self.check_access(email.as_deref(), &claims)?;
+
+        Ok(ProviderUser {
+            id: subject,
+            login: claim_string(&claims, &mapping.username),
+            email,
+            name: claim_string(&claims, &mapping.name),
+            avatar_url: claim_string(&claims, &mapping.avatar),
+        })
+    }
+
+    async fn validate_token(
+        &self,
+        token_details: &ProviderTokenDetails,
+        _max_retries: u32,
+    ) -> Result<Option<ProviderTokenDetails>, TokenValidationError> {
+        if let Some(expires_at) = token_details.expires_at
+            && chrono::Utc::now().timestamp() >= expires_at - TOKEN_EXPIRATION_LEEWAY_SECONDS
+        {
+            let Some(refresh_token) = &token_details.refresh_token else {
+                return Err(TokenValidationError::InvalidOrRevoked);
+            };
+            return self.refresh(refresh_token).await.map(Some);
+        }
+
+        let Some(endpoint) = &self.discovery.userinfo_endpoint else {
+            // Nothing to check against; rely on the token expiry.
+            return Ok(None);
+        };
+
+        let response = self
+            .client
+            .get(endpoint)
+            .bearer_auth(&token_details.access_token)
+            .send()
+            .await
+            .map_err(|err| {
+                TokenValidationError::Temporary(format!("userinfo request failed: {err}"))
+            })?;
+
+        match response.status() {
+            status if status.is_success() => Ok(None),
+            reqwest::StatusCode::UNAUTHORIZED => match &token_details.refresh_token {
+                Some(refresh_token) => self.refresh(refresh_token).await.map(Some),
+                None => Err(TokenValidationError::InvalidOrRevoked),
+            },
+            status => Err(TokenValidationError::Temporary(format!(
+                "unexpected userinfo status: {status}"
+            ))),
+        }
+    }
+}
+
+async fn fetch_jwks(client: &Client, jwks_uri: &str) -> Result<JwkSet> {
+    client
+        .get(jwks_uri)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .context("invalid OIDC JWKS")
+}
+
+fn find_key(jwks: &JwkSet, kid: Option<&str>) -> Result<Option<DecodingKey>> {
+    let jwk = match kid {
+        Some(kid) => jwks.find(kid),
+        // Without a key id, only an unambiguous single-key set is usable.
+        None if jwks.keys.len() == 1 => jwks.keys.first(),
+        None => None,
+    };
+
+    match jwk {
+        Some(jwk) if matches!(jwk.algorithm, AlgorithmParameters::OctetKey(_)) => {
+            bail!("symmetric keys in OIDC JWKS are not supported")
+        }
+        Some(jwk) => Ok(Some(DecodingKey::from_jwk(jwk)?)),
+        None => Ok(None),
+    }
+}
+
+/// Look up a claim by name, using dots to reach nested objects
+/// (e.g. `realm_access.roles`).
+fn claim<'a>(claims: &'a Map<String, Value>, path: &str) -> Option<&'a Value> {
+    if let Some(value) = claims.get(path) {
+        return Some(value);
+    }
+
+    let mut parts = path.split('.');
+    let mut current = claims.get(parts.next()?)?;
+    for part in parts {
+        current = current.as_object()?.get(part)?;
+    }
+    Some(current)
+}
+
+fn claim_string(claims: &Map<String, Value>, path: &str) -> Option<String> {
+    claim(claims, path)
+        .and_then(Value::as_str)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        Some(Value::String(item)) => item
+            .split([',', ' '])
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::config::OidcClaimMapping;
+
+    fn provider(allowed_email_domains: &[&str], allowed_groups: &[&str]) -> OidcProvider {
+        let to_strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        OidcProvider {
+            client: Client::new(),
+            config: OidcProviderConfig {
+                id: "corp".to_string(),
+                display_name: "Corp".to_string(),
+                issuer: "https://sso.example.com".to_string(),
+                client_id: "client".to_string(),
+                client_secret: SecretString::new("secret".into()),
+                scopes: vec!["openid".to_string()],
+                claims: OidcClaimMapping {
+                    email: "email".to_string(),
+                    username: "preferred_username".to_string(),
+                    name: "name".to_string(),
+                    avatar: "picture".to_string(),
+                },
+                groups_claim: "realm_access.roles".to_string(),
+                allowed_email_domains: to_strings(allowed_email_domains),
+                allowed_groups: to_strings(allowed_groups),
+            },
+            discovery: DiscoveryDocument {
+                issuer: "https://sso.example.com".to_string(),
+                authorization_endpoint: "https://sso.example.com/auth".to_string(),
+                token_endpoint: "https://sso.example.com/token".to_string(),
+                jwks_uri: "https://sso.example.com/jwks".to_string(),
+                userinfo_endpoint: None,
+            },
+            jwks: RwLock::new(JwkSet { keys: Vec::new() }),
+            pkce_key: b"pkce".to_vec(),
+        }
+    }
+
+    fn claims(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_check_access_allows_verified_email_in_domain() {
+        let provider = provider(&["example.com"], &[]);
+        let claims = claims(json!({ "email_verified": true }));
+
+        assert!(
+            provider
+                .check_access(Some("dev@Example.com"), &claims)
+                .is_ok()
+        );
+        assert!(
+            provider
+                .check_access(Some("dev@other.com"), &claims)
+                .is_err()
+        );
+        assert!(provider.check_access(None, &claims).is_err());
+    }
+
+    #[test]
+    fn test_check_access_requires_verified_email_for_domain_check() {
+        let provider = provider(&["example.com"], &[]);
+
+        let unverified = claims(json!({ "email_verified": false }));
+        assert!(
+            provider
+                .check_access(Some("dev@example.com"), &unverified)
+                .is_err()
+        );
+        let unasserted = claims(json!({}));
+        assert!(
+            provider
+                .check_access(Some("dev@example.com"), &unasserted)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_check_access_restricts_groups() {
+        let provider = provider(&[], &["engineering"]);
+
+        let member = claims(json!({ "realm_access": { "roles": ["staff", "engineering"] } }));
+        assert!(provider.check_access(None, &member).is_ok());
+        let outsider = claims(json!({ "realm_access": { "roles": ["staff"] } }));
+        assert!(provider.check_access(None, &outsider).is_err());
+        assert!(provider.check_access(None, &claims(json!({}))).is_err());
+    }
+
+    #[test]
+    fn test_check_access_without_restrictions_allows_anyone() {
+        let provider = provider(&[], &[]);
+        assert!(provider.check_access(None, &claims(json!({}))).is_ok());
+    }
+
+    #[test]
+    fn test_claim_supports_nested_paths() {
+        let claims = json!({
+            "email": "a@example.com",
+            "realm_access": { "roles": ["admin", "dev"] },
+            "dotted.name": "flat",
+        });
+        let claims = claims.as_object().unwrap();
+
+        assert_eq!(
+            claim_string(claims, "email").as_deref(),
+            Some("a@example.com")
+        );
+        assert_eq!(claim_string(claims, "dotted.name").as_deref(), Some("flat"));
+        assert_eq!(
+            string_list(claim(claims, "realm_access.roles")),
+            vec!["admin".to_string(), "dev".to_string()]
+        );
+        assert!(claim(claims, "realm_access.missing").is_none());
+    }
+}
@@ -59,11 +59,21 @@ pub struct ProviderTokenDetails {
 
 #[async_trait]
 pub trait AuthorizationProvider: Send + Sync {
-    fn name(&self) -> &'static str;
-    fn scopes(&self) -> &[&str];
+    fn name(&self) -> &str;
+    /// Human-readable label shown on login buttons
+    fn display_name(&self) -> &str {
+        self.name()
+    }
+    fn scopes(&self) -> Vec<&str>;
     fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url>;
-    async fn exchange_code(&self, code: &str, redirect_uri: &str) -> Result<AuthorizationGrant>;
-    async fn fetch_user(&self, access_token: &SecretString) -> Result<ProviderUser>;
+    /// `state` is the handoff state the authorization request was started with.
+    async fn exchange_code(
+        &self,
+        code: &str,
+        state: &str,
+        redirect_uri: &str,
+    ) -> Result<AuthorizationGrant>;
+    async fn fetch_user(&self, grant: &AuthorizationGrant) -> Result<ProviderUser>;
     async fn validate_token(
         &self,
         token_details: &ProviderTokenDetails,
@@ -97,6 +107,17 @@ impl ProviderRegistry {
     pub fn is_empty(&self) -> bool {
         self.providers.is_empty()
     }
+
+    /// Registered providers as `(name, display_name)` pairs, sorted by name.
+    pub fn list(&self) -> Vec<(String, String)> {
+        let mut providers: Vec<_> = self
+            .providers
+            .iter()
+            .map(|(key, provider)| (key.clone(), provider.display_name().to_string()))
+            .collect();
+        providers.sort();
+        providers
+    }
 }
 
 pub struct GitHubOAuthProvider {
@@ -159,12 +180,16 @@ struct GitHubEmail {
 
 #[async_trait]
 impl AuthorizationProvider for GitHubOAuthProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "github"
     }
 
-    fn scopes(&self) -> &[&str] {
-        &["read:user", "user:email"]
+    fn display_name(&self) -> &str {
+        "GitHub"
+    }
+
+    fn scopes(&self) -> Vec<&str> {
+        vec!["read:user", "user:email"]
     }
 
     fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url> {
@@ -180,7 +205,12 @@ impl AuthorizationProvider for GitHubOAuthProvider {
         Ok(url)
     }
 
-    async fn exchange_code(&self, code: &str, redirect_uri: &str) -> Result<AuthorizationGrant> {
+    async fn exchange_code(
+        &self,
+        code: &str,
+        _state: &str,
+        redirect_uri: &str,
+    ) -> Result<AuthorizationGrant> {
         let response = self
             .client
             .post("https://github.com/login/oauth/access_token")
@@ -218,7 +248,8 @@ impl AuthorizationProvider for GitHubOAuthProvider {
         }
     }
 
-    async fn fetch_user(&self, access_token: &SecretString) -> Result<ProviderUser> {
+    async fn fetch_user(&self, grant: &AuthorizationGrant) -> Result<ProviderUser> {
+        let access_token = &grant.access_token;
         let bearer = format!("Bearer {}", access_token.expose_secret());
 
         let user: GitHubUser = self
@@ -492,12 +523,16 @@ struct GoogleUser {
 
 #[async_trait]
 impl AuthorizationProvider for GoogleOAuthProvider {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         "google"
     }
 
-    fn scopes(&self) -> &[&str] {
-        &["openid", "email", "profile"]
+    fn display_name(&self) -> &str {
+        "Google"
+    }
+
+    fn scopes(&self) -> Vec<&str> {
+        vec!["openid", "email", "profile"]
     }
 
     fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url> {
@@ -515,7 +550,12 @@ impl AuthorizationProvider for GoogleOAuthProvider {
         Ok(url)
     }
 
-    async fn exchange_code(&self, code: &str, redirect_uri: &str) -> Result<AuthorizationGrant> {
+    async fn exchange_code(
+        &self,
+        code: &str,
+        _state: &str,
+        redirect_uri: &str,
+    ) -> Result<AuthorizationGrant> {
         let response = self
             .client
             .post("https://oauth2.googleapis.com/token")
@@ -567,7 +607,8 @@ impl AuthorizationProvider for GoogleOAuthProvider {
         }
     }
 
-    async fn fetch_user(&self, access_token: &SecretString) -> Result<ProviderUser> {
+    async fn fetch_user(&self, grant: &AuthorizationGrant) -> Result<ProviderUser> {
+        let access_token = &grant.access_token;
         let bearer = format!("Bearer {}", access_token.expose_secret());
 
         let profile: GoogleUser = self
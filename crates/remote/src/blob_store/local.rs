@@ -0,0 +1,298 @@
+use std::{
+    fmt::Display,
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use axum::body::Bytes;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+use super::{BlobStore, BlobStoreError, PresignedUpload, payload_object_key, review_folder_path};
+use crate::config::LocalBlobStoreConfig;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// HKDF label of the URL signing key, so signed URLs never share a key with
+/// the secret it is derived from
+const SIGNING_KEY_INFO: &[u8] = b"blob-url";
+
+/// HTTP method a signed URL is valid for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignedMethod {
+    Get,
+    Put,
+}
+
+impl SignedMethod {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SignedMethod::Get => "GET",
+            SignedMethod::Put => "PUT",
+        }
+    }
+}
+
+/// Blob store that keeps objects on the local filesystem.
+///
+/// Uploads and downloads go through the remote server's `/v1/blobs/{key}`
+/// endpoints, authorised by an HMAC signature over the method, key and expiry.
+pub struct LocalBlobStore {
+    root: PathBuf,
+    public_base_url: String,
+    signing_key: [u8; 32],
+    presign_expiry: Duration,
+    max_upload_bytes: u64,
+}
+
+impl LocalBlobStore {
+    /// `secret` is only used to derive the URL signing key.
+    pub fn new(config: &LocalBlobStoreConfig, public_base_url: &str, secret: &[u8]) -> Self {
+        let mut signing_key = [0u8; 32];
+        Hkdf::<Sha256>::new(None, secret)
+            .expand(SIGNING_KEY_INFO, &mut signing_key)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        Self {
+            root: config.root.clone(),
+            public_base_url: public_base_url.trim_end_matches('/').to_string(),
+            signing_key,
+            presign_expiry: Duration::from_secs(config.presign_expiry_secs),
+            max_upload_bytes: config.max_upload_bytes,
+        }
+    }
+
+    /// Check a signed URL and return the on-disk path of the object it refers to.
+    pub fn verify(
+        &self,
+        method: SignedMethod,
+        object_key: &str,
+        expires: i64,
+        signature: &str,
+    ) -> Result<PathBuf, BlobStoreError> {
+        if Utc::now().timestamp() > expires {
+            return Err(BlobStoreError::InvalidSignature);
+        }
+
+        let Ok(provided) = hex::decode(signature) else {
+            return Err(BlobStoreError::InvalidSignature);
+        };
+        let expected = self.signature(method, object_key, expires);
+        if !bool::from(expected[..].ct_eq(&provided)) {
+            return Err(BlobStoreError::InvalidSignature);
+        }
+
+        self.object_path(object_key)
+    }
+
+    /// Stream a request body into the object at `path`, replacing any previous content.
+    ///
+    /// The body is written to a unique temporary file next to the object, so
+    /// concurrent uploads never interleave, and moved into place once complete.
+    pub async fn write_object<S, E>(&self, path: &Path, mut body: S) -> Result<(), BlobStoreError>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Unpin,
+        E: Display,
+    {
+        let parent = path
+            .parent()
+            .ok_or_else(|| BlobStoreError::InvalidKey(path.display().to_string()))?;
+        tokio::fs::create_dir_all(parent).await?;
+
+        // Removed on drop unless persisted
+        let (file, tmp_path) = tempfile::NamedTempFile::new_in(parent)?.into_parts();
+        let mut file = tokio::fs::File::from_std(file);
+        let mut written = 0u64;
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.map_err(|e| BlobStoreError::Upload(e.to_string()))?;
+            written += chunk.len() as u64;
+            if written > self.max_upload_bytes {
+                return Err(BlobStoreError::TooLarge(self.max_upload_bytes));
+            }
+            file.write_all(&chunk).await?;
+        }
+        file.sync_all().await?;
+        drop(file);
+
+        tmp_path
+            .persist(path)
+            .map_err(|e| BlobStoreError::Io(e.error))?;
+        Ok(())
+    }
+
+    fn object_path(&self, object_key: &str) -> Result<PathBuf, BlobStoreError> {
+        let relative = Path::new(object_key);
+        let is_safe = !object_key.is_empty()
+            && relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if !is_safe {
+            return Err(BlobStoreError::InvalidKey(object_key.to_string()));
+        }
+        Ok(self.root.join(relative))
+    }
+
+    fn signature(&self, method: SignedMethod, object_key: &str, expires: i64) -> Vec<u8> {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.signing_key).expect("HMAC accepts keys of any size");
+        mac.update(format!("{}\n{object_key}\n{expires}", method.as_str()).as_bytes());
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    fn signed_url(&self, method: SignedMethod, object_key: &str) -> (String, DateTime<Utc>) {
+        let expires_at = Utc::now()
+            + chrono::Duration::from_std(self.presign_expiry).unwrap_or(chrono::Duration::hours(1));
+        let expires = expires_at.timestamp();
+        let signature = hex::encode(self.signature(method, object_key, expires));
+        let url = format!(
+            "{}/v1/blobs/{object_key}?expires={expires}&signature={signature}",
+            self.public_base_url
+        );
+        (url, expires_at)
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn create_presigned_upload(
+        &self,
+        review_id: Uuid,
+        _content_type: Option<&str>,
+    ) -> Result<PresignedUpload, BlobStoreError> {
+        let folder_path = review_folder_path(review_id);
+        let object_key = payload_object_key(review_id);
+        let (upload_url, expires_at) = self.signed_url(SignedMethod::Put, &object_key);
+
+        Ok(PresignedUpload {
+            upload_url,
+            object_key,
+            folder_path,
+            expires_at,
+        })
+    }
+
+    async fn upload_bytes(&self, review_id: Uuid, data: Vec<u8>) -> Result<String, BlobStoreError> {
+        let path = self.object_path(&payload_object_key(review_id))?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, data).await?;
+
+        Ok(review_folder_path(review_id))
+    }
+
+    async fn download_url(&self, object_key: &str) -> Result<String, BlobStoreError> {
+        self.object_path(object_key)?;
+        Ok(self.signed_url(SignedMethod::Get, object_key).0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> LocalBlobStore {
+        store_in(PathBuf::from("/tmp/blobs"))
+    }
+
+    fn store_in(root: PathBuf) -> LocalBlobStore {
+        let config = LocalBlobStoreConfig {
+            root,
+            presign_expiry_secs: 60,
+            max_upload_bytes: 8,
+        };
+        LocalBlobStore::new(&config, "http://localhost:8081/", b"test-key")
+    }
+
+    fn body(chunks: &[&'static str]) -> impl Stream<Item = Result<Bytes, String>> + Unpin {
+        futures::stream::iter(
+            chunks
+                .iter()
+                .map(|chunk| Ok(Bytes::from_static(chunk.as_bytes())))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_verify_accepts_own_signature() {
+        let store = store();
+        let key = "reviews/abc/payload.tar.gz";
+        let expires = Utc::now().timestamp() + 60;
+        let signature = hex::encode(store.signature(SignedMethod::Put, key, expires));
+
+        let path = store
+            .verify(SignedMethod::Put, key, expires, &signature)
+            .unwrap();
+        assert_eq!(path, PathBuf::from("/tmp/blobs/reviews/abc/payload.tar.gz"));
+
+        assert!(
+            store
+                .verify(SignedMethod::Get, key, expires, &signature)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_expired_signature() {
+        let store = store();
+        let key = "reviews/abc/payload.tar.gz";
+        let expires = Utc::now().timestamp() - 1;
+        let signature = hex::encode(store.signature(SignedMethod::Get, key, expires));
+
+        assert!(
+            store
+                .verify(SignedMethod::Get, key, expires, &signature)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_signing_key_is_derived_from_secret() {
+        let store = store();
+        assert_ne!(&store.signing_key[..], b"test-key");
+
+        let mut mac = HmacSha256::new_from_slice(b"test-key").unwrap();
+        mac.update(b"GET\nreviews/abc/payload.tar.gz\n0");
+        assert_ne!(
+            mac.finalize().into_bytes().to_vec(),
+            store.signature(SignedMethod::Get, "reviews/abc/payload.tar.gz", 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_object_replaces_content_and_enforces_limit() {
+        let root = tempfile::TempDir::new().unwrap();
+        let store = store_in(root.path().to_path_buf());
+        let path = store.object_path("reviews/abc/payload.tar.gz").unwrap();
+
+        store
+            .write_object(&path, body(&["1234", "5678"]))
+            .await
+            .unwrap();
+        store.write_object(&path, body(&["new"])).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+
+        let result = store.write_object(&path, body(&["12345", "6789"])).await;
+        assert!(matches!(result, Err(BlobStoreError::TooLarge(8))));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        // The partial upload is cleaned up
+        assert_eq!(
+            std::fs::read_dir(path.parent().unwrap()).unwrap().count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_object_path_rejects_traversal() {
+        let store = store();
+        assert!(store.object_path("../etc/passwd").is_err());
+        assert!(store.object_path("/etc/passwd").is_err());
+        assert!(store.object_path("reviews/./x").is_ok());
+    }
+}
@@ -0,0 +1,71 @@
+//! Storage backends for review artifacts.
+//!
+//! Review payloads are uploaded by clients through presigned URLs and read back
+//! by the review worker. [`S3BlobStore`] talks to any S3-compatible service
+//! (Cloudflare R2, AWS S3, MinIO); [`LocalBlobStore`] keeps objects on disk and
+//! serves signed URLs from the remote server itself.
+
+mod local;
+mod s3;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+pub use local::{LocalBlobStore, SignedMethod};
+pub use s3::S3BlobStore;
+use uuid::Uuid;
+
+/// Well-known filename for the payload tarball stored in each review folder.
+pub const PAYLOAD_FILENAME: &str = "payload.tar.gz";
+
+#[derive(Debug)]
+pub struct PresignedUpload {
+    pub upload_url: String,
+    pub object_key: String,
+    /// Folder path in the store (e.g., "reviews/{review_id}") - this is stored in the database.
+    pub folder_path: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BlobStoreError {
+    #[error("presign config error: {0}")]
+    PresignConfig(String),
+    #[error("presign error: {0}")]
+    Presign(String),
+    #[error("upload error: {0}")]
+    Upload(String),
+    #[error("invalid object key: {0}")]
+    InvalidKey(String),
+    #[error("signature invalid or expired")]
+    InvalidSignature,
+    #[error("upload exceeds the {0} byte limit")]
+    TooLarge(u64),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Create a URL the client can `PUT` the review payload to.
+    async fn create_presigned_upload(
+        &self,
+        review_id: Uuid,
+        content_type: Option<&str>,
+    ) -> Result<PresignedUpload, BlobStoreError>;
+
+    /// Upload bytes directly (for server-side uploads).
+    ///
+    /// Returns the folder path (e.g., "reviews/{review_id}") to store in the database.
+    async fn upload_bytes(&self, review_id: Uuid, data: Vec<u8>) -> Result<String, BlobStoreError>;
+
+    /// Create a time-limited URL the review worker can download an object from.
+    async fn download_url(&self, object_key: &str) -> Result<String, BlobStoreError>;
+}
+
+pub fn review_folder_path(review_id: Uuid) -> String {
+    format!("reviews/{review_id}")
+}
+
+pub fn payload_object_key(review_id: Uuid) -> String {
+    format!("{}/{PAYLOAD_FILENAME}", review_folder_path(review_id))
+}
@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use aws_credential_types::Credentials;
+use aws_sdk_s3::{
+    Client,
+    config::{Builder as S3ConfigBuilder, IdentityCache},
+    presigning::PresigningConfig,
+    primitives::ByteStream,
+};
+use chrono::Utc;
+use secrecy::ExposeSecret;
+use uuid::Uuid;
+
+use super::{BlobStore, BlobStoreError, PresignedUpload, payload_object_key, review_folder_path};
+use crate::config::S3Config;
+
+/// Blob store backed by an S3-compatible service (AWS S3, Cloudflare R2, MinIO).
+#[derive(Clone)]
+pub struct S3BlobStore {
+    client: Client,
+    bucket: String,
+    presign_expiry: Duration,
+}
+
+impl S3BlobStore {
+    pub fn new(config: &S3Config) -> Self {
+        let credentials = Credentials::new(
+            &config.access_key_id,
+            config.secret_access_key.expose_secret(),
+            None,
+            None,
+            "s3-static",
+        );
+
+        let mut builder =
+            S3ConfigBuilder::new()
+                .region(aws_sdk_s3::config::Region::new(config.region.clone()))
+                .credentials_provider(credentials)
+                .force_path_style(config.force_path_style)
+                .stalled_stream_protection(
+                    aws_sdk_s3::config::StalledStreamProtectionConfig::disabled(),
+                )
+                .identity_cache(IdentityCache::no_cache());
+
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.endpoint_url(endpoint);
+        }
+
+        let client = Client::from_conf(builder.build());
+
+        Self {
+            client,
+            bucket: config.bucket.clone(),
+            presign_expiry: Duration::from_secs(config.presign_expiry_secs),
+        }
+    }
+
+    fn presigning_config(&self) -> Result<PresigningConfig, BlobStoreError> {
+        PresigningConfig::builder()
+            .expires_in(self.presign_expiry)
+            .build()
+            .map_err(|e| BlobStoreError::PresignConfig(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn create_presigned_upload(
+        &self,
+        review_id: Uuid,
+        content_type: Option<&str>,
+    ) -> Result<PresignedUpload, BlobStoreError> {
+        let folder_path = review_folder_path(review_id);
+        let object_key = payload_object_key(review_id);
+
+        let mut request = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&object_key);
+
+        if let Some(ct) = content_type {
+            request = request.content_type(ct);
+        }
+
+        let presigned = request
+            .presigned(self.presigning_config()?)
+            .await
+            .map_err(|e| BlobStoreError::Presign(e.to_string()))?;
+
+        let expires_at = Utc::now()
+            + chrono::Duration::from_std(self.presign_expiry).unwrap_or(chrono::Duration::hours(1));
+
+        Ok(PresignedUpload {
+            upload_url: presigned.uri().to_string(),
+            object_key,
+            folder_path,
+            expires_at,
+        })
+    }
+
+    async fn upload_bytes(&self, review_id: Uuid, data: Vec<u8>) -> Result<String, BlobStoreError> {
+        let folder_path = review_folder_path(review_id);
+        let object_key = payload_object_key(review_id);
+
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&object_key)
+            .body(ByteStream::from(data))
+            .content_type("application/gzip")
+            .send()
+            .await
+            .map_err(|e| BlobStoreError::Upload(e.to_string()))?;
+
+        Ok(folder_path)
+    }
+
+    async fn download_url(&self, object_key: &str) -> Result<String, BlobStoreError> {
+        let presigned = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(object_key)
+            .presigned(self.presigning_config()?)
+            .await
+            .map_err(|e| BlobStoreError::Presign(e.to_string()))?;
+
+        Ok(presigned.uri().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use secrecy::SecretString;
+
+    use super::*;
+
+    fn store() -> S3BlobStore {
+        S3BlobStore::new(&S3Config {
+            access_key_id: "test-access-key".to_string(),
+            secret_access_key: SecretString::new("test-secret-key".into()),
+            endpoint: Some("http://localhost:9000".to_string()),
+            bucket: "reviews".to_string(),
+            region: "auto".to_string(),
+            force_path_style: true,
+            presign_expiry_secs: 60,
+        })
+    }
+
+    // Presigning is computed locally, so no S3 service is needed
+    #[tokio::test]
+    async fn test_presigned_upload_targets_payload_key() {
+        let review_id = Uuid::new_v4();
+        let upload = store()
+            .create_presigned_upload(review_id, Some("application/gzip"))
+            .await
+            .unwrap();
+
+        assert_eq!(upload.object_key, payload_object_key(review_id));
+        assert_eq!(upload.folder_path, review_folder_path(review_id));
+        assert!(upload.upload_url.starts_with(&format!(
+            "http://localhost:9000/reviews/{}",
+            upload.object_key
+        )));
+        assert!(upload.upload_url.contains("X-Amz-Expires=60"));
+        assert!(upload.upload_url.contains("X-Amz-Signature="));
+    }
+
+    #[tokio::test]
+    async fn test_download_url_is_presigned_for_object() {
+        let url = store()
+            .download_url("reviews/abc/payload.tar.gz")
+            .await
+            .unwrap();
+
+        assert!(url.starts_with("http://localhost:9000/reviews/reviews/abc/payload.tar.gz?"));
+        assert!(url.contains("X-Amz-Credential=test-access-key"));
+        assert!(url.contains("X-Amz-Signature="));
+    }
+}
@@ -1,4 +1,4 @@
-use std::env;
+use std::{env, path::PathBuf};
 
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
 use secrecy::SecretString;
@@ -13,54 +13,257 @@ pub struct RemoteServerConfig {
     pub electric_url: String,
     pub electric_secret: Option<SecretString>,
     pub electric_role_password: Option<SecretString>,
-    pub r2: Option<R2Config>,
-    pub review_worker_base_url: Option<String>,
+    pub blob_store: Option<BlobStoreConfig>,
+    pub review_worker: Option<ReviewWorkerConfig>,
     pub github_app: Option<GitHubAppConfig>,
+    pub mailer: MailerConfig,
 }
 
+/// Storage backend for review artifacts, selected by `BLOB_STORE_BACKEND`.
 #[derive(Debug, Clone)]
-pub struct R2Config {
+pub enum BlobStoreConfig {
+    S3(S3Config),
+    Local(LocalBlobStoreConfig),
+}
+
+impl BlobStoreConfig {
+    pub fn from_env() -> Result<Option<Self>, ConfigError> {
+        let backend = env::var("BLOB_STORE_BACKEND")
+            .ok()
+            .filter(|v| !v.is_empty());
+        match backend.as_deref() {
+            Some("s3") => Ok(Some(Self::S3(S3Config::from_env()?))),
+            Some("local") => Ok(Some(Self::Local(LocalBlobStoreConfig::from_env()))),
+            Some(_) => Err(ConfigError::InvalidVar("BLOB_STORE_BACKEND")),
+            None if s3_var("S3_ACCESS_KEY_ID", "R2_ACCESS_KEY_ID").is_some() => {
+                Ok(Some(Self::S3(S3Config::from_env()?)))
+            }
+            None => {
+                tracing::info!("BLOB_STORE_BACKEND not set, review storage disabled");
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct S3Config {
     pub access_key_id: String,
     pub secret_access_key: SecretString,
-    pub endpoint: String,
+    /// Custom endpoint for R2, MinIO and other S3-compatible services
+    pub endpoint: Option<String>,
     pub bucket: String,
+    pub region: String,
+    pub force_path_style: bool,
     pub presign_expiry_secs: u64,
 }
 
-impl R2Config {
-    pub fn from_env() -> Result<Option<Self>, ConfigError> {
-        let access_key_id = match env::var("R2_ACCESS_KEY_ID") {
-            Ok(v) => v,
-            Err(_) => {
-                tracing::info!("R2_ACCESS_KEY_ID not set, R2 storage disabled");
-                return Ok(None);
-            }
-        };
+impl S3Config {
+    /// Reads `S3_*` variables, falling back to the legacy `R2_*` names.
+    fn from_env() -> Result<Self, ConfigError> {
+        let access_key_id = s3_var("S3_ACCESS_KEY_ID", "R2_ACCESS_KEY_ID")
+            .ok_or(ConfigError::MissingVar("S3_ACCESS_KEY_ID"))?;
 
-        tracing::info!("R2_ACCESS_KEY_ID is set, checking other R2 env vars");
+        let secret_access_key = s3_var("S3_SECRET_ACCESS_KEY", "R2_SECRET_ACCESS_KEY")
+            .ok_or(ConfigError::MissingVar("S3_SECRET_ACCESS_KEY"))?;
 
-        let secret_access_key = env::var("R2_SECRET_ACCESS_KEY")
-            .map_err(|_| ConfigError::MissingVar("R2_SECRET_ACCESS_KEY"))?;
+        let endpoint = s3_var("S3_ENDPOINT", "R2_REVIEW_ENDPOINT");
 
-        let endpoint = env::var("R2_REVIEW_ENDPOINT")
-            .map_err(|_| ConfigError::MissingVar("R2_REVIEW_ENDPOINT"))?;
+        let bucket =
+            s3_var("S3_BUCKET", "R2_REVIEW_BUCKET").ok_or(ConfigError::MissingVar("S3_BUCKET"))?;
 
-        let bucket = env::var("R2_REVIEW_BUCKET")
-            .map_err(|_| ConfigError::MissingVar("R2_REVIEW_BUCKET"))?;
+        let region = env::var("S3_REGION").unwrap_or_else(|_| "auto".to_string());
 
-        let presign_expiry_secs = env::var("R2_PRESIGN_EXPIRY_SECS")
-            .ok()
+        let force_path_style = match env::var("S3_FORCE_PATH_STYLE") {
+            Ok(v) => v
+                .parse()
+                .map_err(|_| ConfigError::InvalidVar("S3_FORCE_PATH_STYLE"))?,
+            Err(_) => true,
+        };
+
+        let presign_expiry_secs = s3_var("S3_PRESIGN_EXPIRY_SECS", "R2_PRESIGN_EXPIRY_SECS")
             .and_then(|v| v.parse().ok())
             .unwrap_or(3600);
 
-        tracing::info!(endpoint = %endpoint, bucket = %bucket, "R2 config loaded successfully");
+        tracing::info!(endpoint = ?endpoint, bucket = %bucket, "S3 blob store config loaded successfully");
 
-        Ok(Some(Self {
+        Ok(Self {
             access_key_id,
             secret_access_key: SecretString::new(secret_access_key.into()),
             endpoint,
             bucket,
+            region,
+            force_path_style,
+            presign_expiry_secs,
+        })
+    }
+}
+
+fn s3_var(name: &str, legacy_name: &str) -> Option<String> {
+    [name, legacy_name]
+        .into_iter()
+        .find_map(|key| env::var(key).ok().filter(|v| !v.is_empty()))
+}
+
+/// Default limit for uploads to the local blob store (512 MiB)
+const DEFAULT_MAX_UPLOAD_BYTES: u64 = 512 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct LocalBlobStoreConfig {
+    pub root: PathBuf,
+    pub presign_expiry_secs: u64,
+    /// Uploads larger than this are rejected
+    pub max_upload_bytes: u64,
+}
+
+impl LocalBlobStoreConfig {
+    fn from_env() -> Self {
+        let root = env::var("BLOB_STORE_LOCAL_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("./data/blobs"));
+
+        let presign_expiry_secs = env::var("BLOB_STORE_PRESIGN_EXPIRY_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3600);
+
+        let max_upload_bytes = env::var("BLOB_STORE_MAX_UPLOAD_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAX_UPLOAD_BYTES);
+
+        tracing::info!(root = %root.display(), "local blob store config loaded successfully");
+
+        Self {
+            root,
             presign_expiry_secs,
+            max_upload_bytes,
+        }
+    }
+}
+
+/// Outgoing email backend, selected by `MAILER_BACKEND`.
+#[derive(Debug, Clone)]
+pub enum MailerConfig {
+    Loops {
+        api_key: SecretString,
+    },
+    Smtp(SmtpConfig),
+    /// Log rendered emails instead of sending them
+    Log,
+}
+
+impl MailerConfig {
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let backend = env::var("MAILER_BACKEND").ok().filter(|v| !v.is_empty());
+        let loops_api_key = env::var("LOOPS_EMAIL_API_KEY")
+            .ok()
+            .filter(|v| !v.is_empty());
+
+        match backend.as_deref() {
+            // Loops is the default. Emails are only dropped when `log` is chosen
+            // explicitly, never because the key went missing.
+            Some("loops") | None => {
+                let api_key =
+                    loops_api_key.ok_or(ConfigError::MissingVar("LOOPS_EMAIL_API_KEY"))?;
+                Ok(Self::Loops {
+                    api_key: SecretString::new(api_key.into()),
+                })
+            }
+            Some("smtp") => Ok(Self::Smtp(SmtpConfig::from_env()?)),
+            Some("log") => Ok(Self::Log),
+            Some(_) => Err(ConfigError::InvalidVar("MAILER_BACKEND")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpTls {
+    /// Upgrade a plain connection with STARTTLS (usually port 587)
+    StartTls,
+    /// TLS from the first byte (usually port 465)
+    Implicit,
+    /// Unencrypted, for local SMTP catchers
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub tls: SmtpTls,
+    pub username: Option<String>,
+    pub password: Option<SecretString>,
+    pub from: String,
+}
+
+impl SmtpConfig {
+    fn from_env() -> Result<Self, ConfigError> {
+        let host = env::var("SMTP_HOST").map_err(|_| ConfigError::MissingVar("SMTP_HOST"))?;
+
+        let tls = match env::var("SMTP_TLS").as_deref() {
+            Ok("starttls") | Err(_) => SmtpTls::StartTls,
+            Ok("tls") => SmtpTls::Implicit,
+            Ok("none") => SmtpTls::None,
+            Ok(_) => return Err(ConfigError::InvalidVar("SMTP_TLS")),
+        };
+
+        let port = match env::var("SMTP_PORT") {
+            Ok(v) => v
+                .parse()
+                .map_err(|_| ConfigError::InvalidVar("SMTP_PORT"))?,
+            Err(_) => match tls {
+                SmtpTls::StartTls => 587,
+                SmtpTls::Implicit => 465,
+                SmtpTls::None => 25,
+            },
+        };
+
+        let username = env::var("SMTP_USERNAME").ok().filter(|v| !v.is_empty());
+        let password = env::var("SMTP_PASSWORD")
+            .ok()
+            .map(|s| SecretString::new(s.into()));
+
+        let from = env::var("SMTP_FROM").map_err(|_| ConfigError::MissingVar("SMTP_FROM"))?;
+
+        tracing::info!(host = %host, port = port, tls = ?tls, "SMTP config loaded successfully");
+
+        Ok(Self {
+            host,
+            port,
+            tls,
+            username,
+            password,
+            from,
+        })
+    }
+}
+
+/// Review worker that runs code reviews, reached at `REVIEW_WORKER_BASE_URL`.
+/// Requests carry `REVIEW_WORKER_SECRET` as a bearer token.
+#[derive(Debug, Clone)]
+pub struct ReviewWorkerConfig {
+    pub base_url: String,
+    pub secret: SecretString,
+}
+
+impl ReviewWorkerConfig {
+    pub fn from_env() -> Result<Option<Self>, ConfigError> {
+        let Some(base_url) = env::var("REVIEW_WORKER_BASE_URL")
+            .ok()
+            .filter(|v| !v.is_empty())
+        else {
+            return Ok(None);
+        };
+
+        let secret = env::var("REVIEW_WORKER_SECRET")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .ok_or(ConfigError::MissingVar("REVIEW_WORKER_SECRET"))?;
+
+        Ok(Some(Self {
+            base_url,
+            secret: SecretString::new(secret.into()),
         }))
     }
 }
@@ -122,6 +325,8 @@ pub enum ConfigError {
     InvalidVar(&'static str),
     #[error("no OAuth providers configured")]
     NoOAuthProviders,
+    #[error("invalid OIDC provider `{id}`: {reason}")]
+    InvalidOidcProvider { id: String, reason: String },
 }
 
 impl RemoteServerConfig {
@@ -148,12 +353,14 @@ impl RemoteServerConfig {
             .ok()
             .map(|s| SecretString::new(s.into()));
 
-        let r2 = R2Config::from_env()?;
+        let blob_store = BlobStoreConfig::from_env()?;
 
-        let review_worker_base_url = env::var("REVIEW_WORKER_BASE_URL").ok();
+        let review_worker = ReviewWorkerConfig::from_env()?;
 
         let github_app = GitHubAppConfig::from_env()?;
 
+        let mailer = MailerConfig::from_env()?;
+
         Ok(Self {
             database_url,
             listen_addr,
@@ -162,9 +369,10 @@ impl RemoteServerConfig {
             electric_url,
             electric_secret,
             electric_role_password,
-            r2,
-            review_worker_base_url,
+            blob_store,
+            review_worker,
             github_app,
+            mailer,
         })
     }
 }
@@ -196,6 +404,7 @@ impl OAuthProviderConfig {
 pub struct AuthConfig {
     github: Option<OAuthProviderConfig>,
     google: Option<OAuthProviderConfig>,
+    oidc: Vec<OidcProviderConfig>,
     jwt_secret: SecretString,
     public_base_url: String,
 }
@@ -207,8 +416,11 @@ impl AuthConfig {
         validate_jwt_secret(&jwt_secret)?;
         let jwt_secret = SecretString::new(jwt_secret.into());
 
-        let github = match env::var("GITHUB_OAUTH_CLIENT_ID") {
-            Ok(client_id) => {
+        let github = match env::var("GITHUB_OAUTH_CLIENT_ID")
+            .ok()
+            .filter(|v| !v.is_empty())
+        {
+            Some(client_id) => {
                 let client_secret = env::var("GITHUB_OAUTH_CLIENT_SECRET")
                     .map_err(|_| ConfigError::MissingVar("GITHUB_OAUTH_CLIENT_SECRET"))?;
                 Some(OAuthProviderConfig::new(
@@ -216,11 +428,14 @@ impl AuthConfig {
                     SecretString::new(client_secret.into()),
                 ))
             }
-            Err(_) => None,
+            None => None,
         };
 
-        let google = match env::var("GOOGLE_OAUTH_CLIENT_ID") {
-            Ok(client_id) => {
+        let google = match env::var("GOOGLE_OAUTH_CLIENT_ID")
+            .ok()
+            .filter(|v| !v.is_empty())
+        {
+            Some(client_id) => {
                 let client_secret = env::var("GOOGLE_OAUTH_CLIENT_SECRET")
                     .map_err(|_| ConfigError::MissingVar("GOOGLE_OAUTH_CLIENT_SECRET"))?;
                 Some(OAuthProviderConfig::new(
@@ -228,10 +443,12 @@ impl AuthConfig {
                     SecretString::new(client_secret.into()),
                 ))
             }
-            Err(_) => None,
+            None => None,
         };
 
-        if github.is_none() && google.is_none() {
+        let oidc = OidcProviderConfig::all_from_env()?;
+
+        if github.is_none() && google.is_none() && oidc.is_empty() {
             return Err(ConfigError::NoOAuthProviders);
         }
 
@@ -241,6 +458,7 @@ impl AuthConfig {
         Ok(Self {
             github,
             google,
+            oidc,
             jwt_secret,
             public_base_url,
         })
@@ -254,6 +472,10 @@ impl AuthConfig {
         self.google.as_ref()
     }
 
+    pub fn oidc(&self) -> &[OidcProviderConfig] {
+        &self.oidc
+    }
+
     pub fn jwt_secret(&self) -> &SecretString {
         &self.jwt_secret
     }
@@ -263,6 +485,126 @@ impl AuthConfig {
     }
 }
 
+/// Claim names used to build the user profile, dotted for nested claims.
+#[derive(Debug, Clone)]
+pub struct OidcClaimMapping {
+    pub email: String,
+    pub username: String,
+    pub name: String,
+    pub avatar: String,
+}
+
+/// A generic OpenID Connect identity provider.
+///
+/// Providers are listed in `OIDC_PROVIDERS` (comma separated ids) and each id
+/// is configured through `OIDC_<ID>_*` variables.
+#[derive(Debug, Clone)]
+pub struct OidcProviderConfig {
+    pub id: String,
+    pub display_name: String,
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: SecretString,
+    pub scopes: Vec<String>,
+    pub claims: OidcClaimMapping,
+    pub groups_claim: String,
+    pub allowed_email_domains: Vec<String>,
+    pub allowed_groups: Vec<String>,
+}
+
+impl OidcProviderConfig {
+    const RESERVED_IDS: [&'static str; 2] = ["github", "google"];
+
+    fn all_from_env() -> Result<Vec<Self>, ConfigError> {
+        let Some(ids) = env::var("OIDC_PROVIDERS").ok().filter(|v| !v.is_empty()) else {
+            return Ok(Vec::new());
+        };
+
+        let mut providers: Vec<Self> = Vec::new();
+        for id in split_list(&ids) {
+            let id = id.to_ascii_lowercase();
+            if providers.iter().any(|p| p.id == id) {
+                continue;
+            }
+            providers.push(Self::from_env(id)?);
+        }
+        Ok(providers)
+    }
+
+    fn from_env(id: String) -> Result<Self, ConfigError> {
+        let invalid = |reason: String| ConfigError::InvalidOidcProvider {
+            id: id.clone(),
+            reason,
+        };
+
+        if !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(invalid(
+                "ids may only contain letters, digits, `-` and `_`".into(),
+            ));
+        }
+        if Self::RESERVED_IDS.contains(&id.as_str()) {
+            return Err(invalid("id is reserved for a built-in provider".into()));
+        }
+
+        let prefix = format!("OIDC_{}_", id.to_ascii_uppercase().replace('-', "_"));
+        let optional = |name: &str| {
+            env::var(format!("{prefix}{name}"))
+                .ok()
+                .filter(|v| !v.is_empty())
+        };
+        let required = |name: &str| {
+            optional(name).ok_or_else(|| invalid(format!("`{prefix}{name}` is not set")))
+        };
+
+        let issuer = required("ISSUER")?;
+        let client_id = required("CLIENT_ID")?;
+        let client_secret = SecretString::new(required("CLIENT_SECRET")?.into());
+
+        let mut scopes = optional("SCOPES")
+            .map(|v| split_list(&v))
+            .unwrap_or_else(|| vec!["openid".into(), "email".into(), "profile".into()]);
+        if !scopes.iter().any(|scope| scope == "openid") {
+            scopes.insert(0, "openid".into());
+        }
+
+        let claims = OidcClaimMapping {
+            email: optional("EMAIL_CLAIM").unwrap_or_else(|| "email".into()),
+            username: optional("USERNAME_CLAIM").unwrap_or_else(|| "preferred_username".into()),
+            name: optional("NAME_CLAIM").unwrap_or_else(|| "name".into()),
+            avatar: optional("AVATAR_CLAIM").unwrap_or_else(|| "picture".into()),
+        };
+
+        Ok(Self {
+            display_name: optional("DISPLAY_NAME").unwrap_or_else(|| id.clone()),
+            issuer,
+            client_id,
+            client_secret,
+            scopes,
+            claims,
+            groups_claim: optional("GROUPS_CLAIM").unwrap_or_else(|| "groups".into()),
+            allowed_email_domains: optional("ALLOWED_EMAIL_DOMAINS")
+                .map(|v| split_list(&v))
+                .unwrap_or_default(),
+            allowed_groups: optional("ALLOWED_GROUPS")
+                .map(|v| split_list(&v))
+                .unwrap_or_default(),
+            id,
+        })
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split([',', ' '])
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 fn validate_jwt_secret(secret: &str) -> Result<(), ConfigError> {
     let decoded = BASE64_STANDARD
         .decode(secret.as_bytes())
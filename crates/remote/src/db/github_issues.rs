@@ -0,0 +1,413 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum GitHubIssueDbError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("repository already syncs with another project")]
+    RepositoryInUse,
+    #[error("issue or task is already linked")]
+    AlreadyLinked,
+}
+
+/// Issue sync settings of a project
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct GitHubIssueSyncConfig {
+    pub project_id: Uuid,
+    pub organization_id: Uuid,
+    pub repository_id: Uuid,
+    pub import_new_issues: bool,
+    pub export_new_tasks: bool,
+    pub enabled_by_user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A sync config together with what's needed to call the GitHub API
+#[derive(Debug, Clone, FromRow)]
+pub struct IssueSyncTarget {
+    pub project_id: Uuid,
+    pub organization_id: Uuid,
+    pub repository_id: Uuid,
+    pub import_new_issues: bool,
+    pub export_new_tasks: bool,
+    pub enabled_by_user_id: Uuid,
+    pub github_installation_id: i64,
+    pub repo_full_name: String,
+}
+
+impl IssueSyncTarget {
+    /// Splits `repo_full_name` into owner and repository name.
+    pub fn owner_and_repo(&self) -> Option<(&str, &str)> {
+        self.repo_full_name.split_once('/')
+    }
+}
+
+/// Link between a shared task and a GitHub issue
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct SharedTaskGitHubIssue {
+    pub task_id: Uuid,
+    pub repository_id: Uuid,
+    pub github_issue_id: i64,
+    pub issue_number: i64,
+    pub html_url: String,
+    pub last_synced_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct NewIssueLink<'a> {
+    pub task_id: Uuid,
+    pub repository_id: Uuid,
+    pub github_issue_id: i64,
+    pub issue_number: i64,
+    pub html_url: &'a str,
+}
+
+pub struct GitHubIssueRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> GitHubIssueRepository<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    // ========== Sync configs ==========
+
+    pub async fn get_config(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Option<GitHubIssueSyncConfig>, GitHubIssueDbError> {
+        let config = sqlx::query_as!(
+            GitHubIssueSyncConfig,
+            r#"
+            SELECT
+                project_id,
+                organization_id,
+                repository_id,
+                import_new_issues,
+                export_new_tasks,
+                enabled_by_user_id,
+                created_at,
+                updated_at
+            FROM github_issue_sync_configs
+            WHERE project_id = $1
+            "#,
+            project_id
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(config)
+    }
+
+    pub async fn upsert_config(
+        &self,
+        project_id: Uuid,
+        organization_id: Uuid,
+        repository_id: Uuid,
+        import_new_issues: bool,
+        export_new_tasks: bool,
+        enabled_by_user_id: Uuid,
+    ) -> Result<GitHubIssueSyncConfig, GitHubIssueDbError> {
+        let config = sqlx::query_as!(
+            GitHubIssueSyncConfig,
+            r#"
+            INSERT INTO github_issue_sync_configs (
+                project_id,
+                organization_id,
+                repository_id,
+                import_new_issues,
+                export_new_tasks,
+                enabled_by_user_id
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (project_id) DO UPDATE
+            SET repository_id      = EXCLUDED.repository_id,
+                import_new_issues  = EXCLUDED.import_new_issues,
+                export_new_tasks   = EXCLUDED.export_new_tasks,
+                enabled_by_user_id = EXCLUDED.enabled_by_user_id
+            RETURNING
+                project_id,
+                organization_id,
+                repository_id,
+                import_new_issues,
+                export_new_tasks,
+                enabled_by_user_id,
+                created_at,
+                updated_at
+            "#,
+            project_id,
+            organization_id,
+            repository_id,
+            import_new_issues,
+            export_new_tasks,
+            enabled_by_user_id
+        )
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                GitHubIssueDbError::RepositoryInUse
+            }
+            _ => GitHubIssueDbError::Database(e),
+        })?;
+
+        Ok(config)
+    }
+
+    pub async fn delete_config(&self, project_id: Uuid) -> Result<bool, GitHubIssueDbError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM github_issue_sync_configs
+            WHERE project_id = $1
+            "#,
+            project_id
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Sync target of a project, if its installation is active.
+    pub async fn target_for_project(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Option<IssueSyncTarget>, GitHubIssueDbError> {
+        let target = sqlx::query_as!(
+            IssueSyncTarget,
+            r#"
+            SELECT
+                c.project_id,
+                c.organization_id,
+                c.repository_id,
+                c.import_new_issues,
+                c.export_new_tasks,
+                c.enabled_by_user_id,
+                i.github_installation_id,
+                r.repo_full_name
+            FROM github_issue_sync_configs c
+            INNER JOIN github_app_repositories r ON r.id = c.repository_id
+            INNER JOIN github_app_installations i ON i.id = r.installation_id
+            WHERE c.project_id = $1
+              AND i.suspended_at IS NULL
+            "#,
+            project_id
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(target)
+    }
+
+    /// Sync target for a repository, as identified in webhook payloads.
+    pub async fn target_for_github_repo(
+        &self,
+        github_installation_id: i64,
+        github_repo_id: i64,
+    ) -> Result<Option<IssueSyncTarget>, GitHubIssueDbError> {
+        let target = sqlx::query_as!(
+            IssueSyncTarget,
+            r#"
+            SELECT
+                c.project_id,
+                c.organization_id,
+                c.repository_id,
+                c.import_new_issues,
+                c.export_new_tasks,
+                c.enabled_by_user_id,
+                i.github_installation_id,
+                r.repo_full_name
+            FROM github_issue_sync_configs c
+            INNER JOIN github_app_repositories r ON r.id = c.repository_id
+            INNER JOIN github_app_installations i ON i.id = r.installation_id
+            WHERE i.github_installation_id = $1
+              AND r.github_repo_id = $2
+              AND i.suspended_at IS NULL
+            "#,
+            github_installation_id,
+            github_repo_id
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(target)
+    }
+
+    // ========== Task <-> issue links ==========
+
+    pub async fn find_link_by_task(
+        &self,
+        task_id: Uuid,
+    ) -> Result<Option<SharedTaskGitHubIssue>, GitHubIssueDbError> {
+        let link = sqlx::query_as!(
+            SharedTaskGitHubIssue,
+            r#"
+            SELECT
+                task_id,
+                repository_id,
+                github_issue_id,
+                issue_number,
+                html_url,
+                last_synced_at,
+                created_at
+            FROM shared_task_github_issues
+            WHERE task_id = $1
+            "#,
+            task_id
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    pub async fn find_link_by_issue(
+        &self,
+        repository_id: Uuid,
+        github_issue_id: i64,
+    ) -> Result<Option<SharedTaskGitHubIssue>, GitHubIssueDbError> {
+        let link = sqlx::query_as!(
+            SharedTaskGitHubIssue,
+            r#"
+            SELECT
+                task_id,
+                repository_id,
+                github_issue_id,
+                issue_number,
+                html_url,
+                last_synced_at,
+                created_at
+            FROM shared_task_github_issues
+            WHERE repository_id = $1 AND github_issue_id = $2
+            "#,
+            repository_id,
+            github_issue_id
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    pub async fn create_link(
+        &self,
+        link: NewIssueLink<'_>,
+    ) -> Result<SharedTaskGitHubIssue, GitHubIssueDbError> {
+        sqlx::query_as!(
+            SharedTaskGitHubIssue,
+            r#"
+            INSERT INTO shared_task_github_issues (
+                task_id,
+                repository_id,
+                github_issue_id,
+                issue_number,
+                html_url
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT DO NOTHING
+            RETURNING
+                task_id,
+                repository_id,
+                github_issue_id,
+                issue_number,
+                html_url,
+                last_synced_at,
+                created_at
+            "#,
+            link.task_id,
+            link.repository_id,
+            link.github_issue_id,
+            link.issue_number,
+            link.html_url
+        )
+        .fetch_optional(self.pool)
+        .await?
+        .ok_or(GitHubIssueDbError::AlreadyLinked)
+    }
+
+    pub async fn touch_link(&self, task_id: Uuid) -> Result<(), GitHubIssueDbError> {
+        sqlx::query!(
+            r#"
+            UPDATE shared_task_github_issues
+            SET last_synced_at = NOW()
+            WHERE task_id = $1
+            "#,
+            task_id
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes a task created for an issue that was linked concurrently.
+    pub async fn discard_unlinked_task(&self, task_id: Uuid) -> Result<(), GitHubIssueDbError> {
+        sqlx::query!(
+            r#"
+            DELETE FROM shared_tasks
+            WHERE id = $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM shared_task_github_issues WHERE task_id = $1
+              )
+            "#,
+            task_id
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // ========== GitHub account mapping ==========
+
+    /// User linked to a GitHub login through GitHub sign-in.
+    pub async fn user_for_github_login(
+        &self,
+        login: &str,
+    ) -> Result<Option<Uuid>, GitHubIssueDbError> {
+        let user_id = sqlx::query_scalar!(
+            r#"
+            SELECT user_id AS "user_id!: Uuid"
+            FROM oauth_accounts
+            WHERE provider = 'github'
+              AND LOWER(username) = LOWER($1)
+            ORDER BY updated_at DESC
+            LIMIT 1
+            "#,
+            login
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(user_id)
+    }
+
+    /// GitHub login of a user who signed in with GitHub.
+    pub async fn github_login_for_user(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<String>, GitHubIssueDbError> {
+        let login = sqlx::query_scalar!(
+            r#"
+            SELECT username AS "username!"
+            FROM oauth_accounts
+            WHERE provider = 'github'
+              AND user_id = $1
+              AND username IS NOT NULL
+            ORDER BY updated_at DESC
+            LIMIT 1
+            "#,
+            user_id
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(login)
+    }
+}
@@ -10,6 +10,8 @@ pub enum IdentityError {
     InvitationError(String),
     #[error("cannot delete organization: {0}")]
     CannotDeleteOrganization(String),
+    #[error("invalid project access: {0}")]
+    InvalidProjectAccess(String),
     #[error("organization conflict: {0}")]
     OrganizationConflict(String),
     #[error(transparent)]
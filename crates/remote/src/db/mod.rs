@@ -1,5 +1,6 @@
 pub mod auth;
 pub mod github_app;
+pub mod github_issues;
 pub mod identity_errors;
 pub mod invitations;
 pub mod oauth;
@@ -8,6 +9,8 @@ pub mod organization_members;
 pub mod organizations;
 pub mod projects;
 pub mod reviews;
+pub mod task_activity;
+pub mod task_comments;
 pub mod tasks;
 pub mod users;
 
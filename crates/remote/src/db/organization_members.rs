@@ -100,3 +100,378 @@ pub(super) async fn assert_admin(
         _ => Err(IdentityError::PermissionDenied),
     }
 }
+
+/// What an operation needs from the acting member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessLevel {
+    /// View projects, shared tasks, comments and activity
+    Read,
+    /// Create and change shared tasks and comments
+    Write,
+}
+
+/// A member's role together with their project restriction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemberAccess {
+    pub role: MemberRole,
+    /// Scoped members only see projects they were explicitly added to.
+    pub project_scoped: bool,
+}
+
+impl MemberAccess {
+    pub fn can_write(&self) -> bool {
+        matches!(self.role, MemberRole::Admin | MemberRole::Member)
+    }
+
+    /// Whether the member may act at `level` on a project; `granted` tells if
+    /// the member is listed in the project's `project_members`.
+    pub fn allows(&self, level: AccessLevel, granted: bool) -> bool {
+        if self.project_scoped && !granted {
+            return false;
+        }
+        match level {
+            AccessLevel::Read => true,
+            AccessLevel::Write => self.can_write(),
+        }
+    }
+
+    /// Organization-wide write access, e.g. for creating projects.
+    pub fn allows_org_write(&self) -> bool {
+        !self.project_scoped && self.can_write()
+    }
+}
+
+/// Outcome of resolving a user's access to a project.
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectAccess {
+    pub organization_id: Uuid,
+    pub member: Option<MemberAccess>,
+    pub granted: bool,
+}
+
+impl ProjectAccess {
+    /// Non-members and scoped members outside the project get `NotFound`, so
+    /// the project's existence isn't revealed; members lacking the role get
+    /// `PermissionDenied`.
+    pub fn check(&self, level: AccessLevel) -> Result<(), IdentityError> {
+        let Some(member) = self.member else {
+            return Err(IdentityError::NotFound);
+        };
+        if !member.allows(AccessLevel::Read, self.granted) {
+            return Err(IdentityError::NotFound);
+        }
+        if !member.allows(level, self.granted) {
+            return Err(IdentityError::PermissionDenied);
+        }
+        Ok(())
+    }
+}
+
+pub async fn member_access(
+    pool: &PgPool,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<MemberAccess>, IdentityError> {
+    let record = sqlx::query!(
+        r#"
+        SELECT
+            role           AS "role!: MemberRole",
+            project_scoped AS "project_scoped!"
+        FROM organization_member_metadata
+        WHERE organization_id = $1 AND user_id = $2
+        "#,
+        organization_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record.map(|r| MemberAccess {
+        role: r.role,
+        project_scoped: r.project_scoped,
+    }))
+}
+
+/// Resolves `user_id`'s access to a project, or `None` if the project doesn't
+/// exist.
+pub async fn project_access(
+    pool: &PgPool,
+    project_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<ProjectAccess>, IdentityError> {
+    let record = sqlx::query!(
+        r#"
+        SELECT
+            p.organization_id AS "organization_id!: Uuid",
+            m.role            AS "role?: MemberRole",
+            m.project_scoped  AS "project_scoped?",
+            EXISTS(
+                SELECT 1
+                FROM project_members pm
+                WHERE pm.project_id = p.id AND pm.user_id = $2
+            ) AS "granted!"
+        FROM projects p
+        LEFT JOIN organization_member_metadata m
+            ON m.organization_id = p.organization_id AND m.user_id = $2
+        WHERE p.id = $1
+        "#,
+        project_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record.map(|r| ProjectAccess {
+        organization_id: r.organization_id,
+        member: r.role.map(|role| MemberAccess {
+            role,
+            project_scoped: r.project_scoped.unwrap_or(false),
+        }),
+        granted: r.granted,
+    }))
+}
+
+pub async fn assert_project_access(
+    pool: &PgPool,
+    project_id: Uuid,
+    user_id: Uuid,
+    level: AccessLevel,
+) -> Result<Uuid, IdentityError> {
+    let access = project_access(pool, project_id, user_id)
+        .await?
+        .ok_or(IdentityError::NotFound)?;
+    access.check(level)?;
+    Ok(access.organization_id)
+}
+
+/// Everything a user can see: whole organizations for unscoped members, plus
+/// individual projects for scoped ones.
+#[derive(Debug, Clone, Default)]
+pub struct VisibleScopes {
+    pub organization_ids: Vec<Uuid>,
+    pub project_ids: Vec<Uuid>,
+}
+
+impl VisibleScopes {
+    pub fn is_empty(&self) -> bool {
+        self.organization_ids.is_empty() && self.project_ids.is_empty()
+    }
+}
+
+pub async fn visible_scopes(pool: &PgPool, user_id: Uuid) -> Result<VisibleScopes, IdentityError> {
+    let organization_ids = sqlx::query_scalar!(
+        r#"
+        SELECT organization_id
+        FROM organization_member_metadata
+        WHERE user_id = $1 AND NOT project_scoped
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let project_ids = sqlx::query_scalar!(
+        r#"
+        SELECT pm.project_id
+        FROM project_members pm
+        INNER JOIN organization_member_metadata m
+            ON m.organization_id = pm.organization_id AND m.user_id = pm.user_id
+        WHERE pm.user_id = $1 AND m.project_scoped
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(VisibleScopes {
+        organization_ids,
+        project_ids,
+    })
+}
+
+/// Projects a member is restricted to, or `None` when they see every project.
+pub async fn project_restriction(
+    pool: &PgPool,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<Vec<Uuid>>, IdentityError> {
+    let access = member_access(pool, organization_id, user_id)
+        .await?
+        .ok_or(IdentityError::NotFound)?;
+    if !access.project_scoped {
+        return Ok(None);
+    }
+
+    let project_ids = sqlx::query_scalar!(
+        r#"
+        SELECT project_id
+        FROM project_members
+        WHERE organization_id = $1 AND user_id = $2
+        ORDER BY created_at ASC
+        "#,
+        organization_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Some(project_ids))
+}
+
+/// Restricts a member to `project_ids`, or lifts the restriction with `None`.
+/// Projects must belong to the organization.
+pub async fn set_project_restriction(
+    pool: &PgPool,
+    organization_id: Uuid,
+    user_id: Uuid,
+    project_ids: Option<&[Uuid]>,
+) -> Result<(), IdentityError> {
+    let mut tx = pool.begin().await?;
+
+    let role = sqlx::query_scalar!(
+        r#"
+        SELECT role AS "role!: MemberRole"
+        FROM organization_member_metadata
+        WHERE organization_id = $1 AND user_id = $2
+        FOR UPDATE
+        "#,
+        organization_id,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(IdentityError::NotFound)?;
+
+    if role == MemberRole::Admin && project_ids.is_some() {
+        return Err(IdentityError::InvalidProjectAccess(
+            "Admins cannot be restricted to projects".to_string(),
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM project_members
+        WHERE organization_id = $1 AND user_id = $2
+        "#,
+        organization_id,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if let Some(project_ids) = project_ids {
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO project_members (project_id, user_id, organization_id)
+            SELECT p.id, $2, p.organization_id
+            FROM projects p
+            WHERE p.organization_id = $1 AND p.id = ANY($3)
+            ON CONFLICT DO NOTHING
+            "#,
+            organization_id,
+            user_id,
+            project_ids
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let mut unique = project_ids.to_vec();
+        unique.sort();
+        unique.dedup();
+        if inserted as usize != unique.len() {
+            return Err(IdentityError::InvalidProjectAccess(
+                "All projects must belong to the organization".to_string(),
+            ));
+        }
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE organization_member_metadata
+        SET project_scoped = $3
+        WHERE organization_id = $1 AND user_id = $2
+        "#,
+        organization_id,
+        user_id,
+        project_ids.is_some()
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access(role: MemberRole, project_scoped: bool, granted: bool) -> ProjectAccess {
+        ProjectAccess {
+            organization_id: Uuid::nil(),
+            member: Some(MemberAccess {
+                role,
+                project_scoped,
+            }),
+            granted,
+        }
+    }
+
+    #[test]
+    fn test_admin_and_member_can_read_and_write() {
+        for role in [MemberRole::Admin, MemberRole::Member] {
+            let access = access(role, false, false);
+            assert!(access.check(AccessLevel::Read).is_ok());
+            assert!(access.check(AccessLevel::Write).is_ok());
+            assert!(access.member.unwrap().allows_org_write());
+        }
+    }
+
+    #[test]
+    fn test_viewer_is_read_only() {
+        let access = access(MemberRole::Viewer, false, false);
+        assert!(access.check(AccessLevel::Read).is_ok());
+        assert!(matches!(
+            access.check(AccessLevel::Write),
+            Err(IdentityError::PermissionDenied)
+        ));
+        assert!(!access.member.unwrap().allows_org_write());
+    }
+
+    #[test]
+    fn test_scoped_member_only_sees_granted_projects() {
+        let outside = access(MemberRole::Member, true, false);
+        assert!(matches!(
+            outside.check(AccessLevel::Read),
+            Err(IdentityError::NotFound)
+        ));
+
+        let inside = access(MemberRole::Member, true, true);
+        assert!(inside.check(AccessLevel::Write).is_ok());
+        assert!(!inside.member.unwrap().allows_org_write());
+    }
+
+    #[test]
+    fn test_scoped_viewer_is_read_only_on_granted_projects() {
+        let access = access(MemberRole::Viewer, true, true);
+        assert!(access.check(AccessLevel::Read).is_ok());
+        assert!(matches!(
+            access.check(AccessLevel::Write),
+            Err(IdentityError::PermissionDenied)
+        ));
+    }
+
+    #[test]
+    fn test_non_member_has_no_access() {
+        let access = ProjectAccess {
+            organization_id: Uuid::nil(),
+            member: None,
+            granted: false,
+        };
+        assert!(matches!(
+            access.check(AccessLevel::Read),
+            Err(IdentityError::NotFound)
+        ));
+    }
+}
@@ -109,23 +109,37 @@ impl ProjectRepository {
         })
     }
 
-    pub async fn list_by_organization(
+    /// Projects of an organization that `user_id` can see; members restricted
+    /// to specific projects only get those.
+    pub async fn list_visible_to_user(
         pool: &PgPool,
         organization_id: Uuid,
+        user_id: Uuid,
     ) -> Result<Vec<Project>, ProjectError> {
         let rows = sqlx::query!(
             r#"
             SELECT
-                id               AS "id!: Uuid",
-                organization_id  AS "organization_id!: Uuid",
-                name             AS "name!",
-                metadata         AS "metadata!: Value",
-                created_at       AS "created_at!: DateTime<Utc>"
-            FROM projects
-            WHERE organization_id = $1
-            ORDER BY created_at DESC
+                p.id               AS "id!: Uuid",
+                p.organization_id  AS "organization_id!: Uuid",
+                p.name             AS "name!",
+                p.metadata         AS "metadata!: Value",
+                p.created_at       AS "created_at!: DateTime<Utc>"
+            FROM projects p
+            INNER JOIN organization_member_metadata m
+                ON m.organization_id = p.organization_id AND m.user_id = $2
+            WHERE p.organization_id = $1
+              AND (
+                  NOT m.project_scoped
+                  OR EXISTS (
+                      SELECT 1
+                      FROM project_members pm
+                      WHERE pm.project_id = p.id AND pm.user_id = $2
+                  )
+              )
+            ORDER BY p.created_at DESC
             "#,
-            organization_id
+            organization_id,
+            user_id
         )
         .fetch_all(pool)
         .await?;
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::Tx;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, TS)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "shared_task_activity_type", rename_all = "snake_case")]
+#[ts(export)]
+pub enum SharedTaskActivityType {
+    Created,
+    StatusChanged,
+    AssigneeChanged,
+    Deleted,
+}
+
+/// Audit entry for a change made to a shared task.
+///
+/// `from_value` / `to_value` hold the previous and new status (for
+/// `status_changed`) or assignee user id (for `assignee_changed`).
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, TS)]
+#[ts(export)]
+pub struct SharedTaskActivity {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub organization_id: Uuid,
+    pub project_id: Uuid,
+    pub actor_user_id: Option<Uuid>,
+    pub event_type: SharedTaskActivityType,
+    pub from_value: Option<String>,
+    pub to_value: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct NewSharedTaskActivity {
+    pub task_id: Uuid,
+    pub organization_id: Uuid,
+    pub actor_user_id: Option<Uuid>,
+    pub event_type: SharedTaskActivityType,
+    pub from_value: Option<String>,
+    pub to_value: Option<String>,
+}
+
+pub struct SharedTaskActivityRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> SharedTaskActivityRepository<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_by_task(
+        &self,
+        task_id: Uuid,
+    ) -> Result<Vec<SharedTaskActivity>, sqlx::Error> {
+        sqlx::query_as!(
+            SharedTaskActivity,
+            r#"
+            SELECT
+                id              AS "id!",
+                task_id         AS "task_id!",
+                organization_id AS "organization_id!",
+                project_id      AS "project_id!",
+                actor_user_id   AS "actor_user_id?: Uuid",
+                event_type      AS "event_type!: SharedTaskActivityType",
+                from_value      AS "from_value?",
+                to_value        AS "to_value?",
+                created_at      AS "created_at!"
+            FROM shared_task_activity
+            WHERE task_id = $1
+            ORDER BY created_at ASC
+            "#,
+            task_id
+        )
+        .fetch_all(self.pool)
+        .await
+    }
+
+    /// Records an activity entry as part of the caller's transaction so the
+    /// log stays consistent with the change it describes.
+    pub async fn record(tx: &mut Tx<'_>, entry: NewSharedTaskActivity) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO shared_task_activity (
+                task_id,
+                organization_id,
+                project_id,
+                actor_user_id,
+                event_type,
+                from_value,
+                to_value
+            )
+            SELECT $1, $2, t.project_id, $3, $4, $5, $6
+            FROM shared_tasks t
+            WHERE t.id = $1
+            "#,
+            entry.task_id,
+            entry.organization_id,
+            entry.actor_user_id,
+            entry.event_type as SharedTaskActivityType,
+            entry.from_value,
+            entry.to_value
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
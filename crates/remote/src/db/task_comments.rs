@@ -0,0 +1,253 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+pub const MAX_SHARED_TASK_COMMENT_BYTES: usize = 50 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, TS)]
+#[ts(export)]
+pub struct SharedTaskComment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub organization_id: Uuid,
+    pub project_id: Uuid,
+    pub author_user_id: Option<Uuid>,
+    /// Markdown body
+    pub body: String,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Error)]
+pub enum SharedTaskCommentError {
+    #[error("comment not found")]
+    NotFound,
+    #[error("only the author can modify this comment")]
+    Forbidden,
+    #[error("comment body cannot be empty")]
+    Empty,
+    #[error("comment body is too large")]
+    PayloadTooLarge,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct SharedTaskCommentRepository<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> SharedTaskCommentRepository<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_by_id(
+        &self,
+        comment_id: Uuid,
+    ) -> Result<Option<SharedTaskComment>, SharedTaskCommentError> {
+        let comment = sqlx::query_as!(
+            SharedTaskComment,
+            r#"
+            SELECT
+                id              AS "id!",
+                task_id         AS "task_id!",
+                organization_id AS "organization_id!",
+                project_id      AS "project_id!",
+                author_user_id  AS "author_user_id?: Uuid",
+                body            AS "body!",
+                edited_at       AS "edited_at?",
+                deleted_at      AS "deleted_at?",
+                created_at      AS "created_at!",
+                updated_at      AS "updated_at!"
+            FROM shared_task_comments
+            WHERE id = $1
+              AND deleted_at IS NULL
+            "#,
+            comment_id
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(comment)
+    }
+
+    pub async fn list_by_task(
+        &self,
+        task_id: Uuid,
+    ) -> Result<Vec<SharedTaskComment>, SharedTaskCommentError> {
+        let comments = sqlx::query_as!(
+            SharedTaskComment,
+            r#"
+            SELECT
+                id              AS "id!",
+                task_id         AS "task_id!",
+                organization_id AS "organization_id!",
+                project_id      AS "project_id!",
+                author_user_id  AS "author_user_id?: Uuid",
+                body            AS "body!",
+                edited_at       AS "edited_at?",
+                deleted_at      AS "deleted_at?",
+                created_at      AS "created_at!",
+                updated_at      AS "updated_at!"
+            FROM shared_task_comments
+            WHERE task_id = $1
+              AND deleted_at IS NULL
+            ORDER BY created_at ASC
+            "#,
+            task_id
+        )
+        .fetch_all(self.pool)
+        .await?;
+
+        Ok(comments)
+    }
+
+    pub async fn create(
+        &self,
+        task_id: Uuid,
+        author_user_id: Uuid,
+        body: &str,
+    ) -> Result<SharedTaskComment, SharedTaskCommentError> {
+        ensure_comment_body(body)?;
+
+        let comment = sqlx::query_as!(
+            SharedTaskComment,
+            r#"
+            INSERT INTO shared_task_comments (task_id, organization_id, project_id, author_user_id, body)
+            SELECT t.id, t.organization_id, t.project_id, $2, $3
+            FROM shared_tasks t
+            WHERE t.id = $1
+              AND t.deleted_at IS NULL
+            RETURNING id              AS "id!",
+                      task_id         AS "task_id!",
+                      organization_id AS "organization_id!",
+                      project_id      AS "project_id!",
+                      author_user_id  AS "author_user_id?: Uuid",
+                      body            AS "body!",
+                      edited_at       AS "edited_at?",
+                      deleted_at      AS "deleted_at?",
+                      created_at      AS "created_at!",
+                      updated_at      AS "updated_at!"
+            "#,
+            task_id,
+            author_user_id,
+            body
+        )
+        .fetch_optional(self.pool)
+        .await?
+        .ok_or(SharedTaskCommentError::NotFound)?;
+
+        Ok(comment)
+    }
+
+    pub async fn update(
+        &self,
+        comment_id: Uuid,
+        acting_user_id: Uuid,
+        body: &str,
+    ) -> Result<SharedTaskComment, SharedTaskCommentError> {
+        ensure_comment_body(body)?;
+        self.ensure_author(comment_id, acting_user_id).await?;
+
+        let comment = sqlx::query_as!(
+            SharedTaskComment,
+            r#"
+            UPDATE shared_task_comments AS c
+            SET body      = $3,
+                edited_at = NOW()
+            WHERE c.id = $1
+              AND c.author_user_id = $2
+              AND c.deleted_at IS NULL
+            RETURNING
+                c.id              AS "id!",
+                c.task_id         AS "task_id!",
+                c.organization_id AS "organization_id!",
+                c.project_id      AS "project_id!",
+                c.author_user_id  AS "author_user_id?: Uuid",
+                c.body            AS "body!",
+                c.edited_at       AS "edited_at?",
+                c.deleted_at      AS "deleted_at?",
+                c.created_at      AS "created_at!",
+                c.updated_at      AS "updated_at!"
+            "#,
+            comment_id,
+            acting_user_id,
+            body
+        )
+        .fetch_optional(self.pool)
+        .await?
+        .ok_or(SharedTaskCommentError::NotFound)?;
+
+        Ok(comment)
+    }
+
+    /// Soft-deletes a comment so synced clients observe the removal.
+    pub async fn delete(
+        &self,
+        comment_id: Uuid,
+        acting_user_id: Uuid,
+    ) -> Result<SharedTaskComment, SharedTaskCommentError> {
+        self.ensure_author(comment_id, acting_user_id).await?;
+
+        let comment = sqlx::query_as!(
+            SharedTaskComment,
+            r#"
+            UPDATE shared_task_comments AS c
+            SET deleted_at = NOW()
+            WHERE c.id = $1
+              AND c.author_user_id = $2
+              AND c.deleted_at IS NULL
+            RETURNING
+                c.id              AS "id!",
+                c.task_id         AS "task_id!",
+                c.organization_id AS "organization_id!",
+                c.project_id      AS "project_id!",
+                c.author_user_id  AS "author_user_id?: Uuid",
+                c.body            AS "body!",
+                c.edited_at       AS "edited_at?",
+                c.deleted_at      AS "deleted_at?",
+                c.created_at      AS "created_at!",
+                c.updated_at      AS "updated_at!"
+            "#,
+            comment_id,
+            acting_user_id
+        )
+        .fetch_optional(self.pool)
+        .await?
+        .ok_or(SharedTaskCommentError::NotFound)?;
+
+        Ok(comment)
+    }
+
+    async fn ensure_author(
+        &self,
+        comment_id: Uuid,
+        acting_user_id: Uuid,
+    ) -> Result<(), SharedTaskCommentError> {
+        let comment = self
+            .find_by_id(comment_id)
+            .await?
+            .ok_or(SharedTaskCommentError::NotFound)?;
+
+        if comment.author_user_id != Some(acting_user_id) {
+            return Err(SharedTaskCommentError::Forbidden);
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn ensure_comment_body(body: &str) -> Result<(), SharedTaskCommentError> {
+    if body.trim().is_empty() {
+        return Err(SharedTaskCommentError::Empty);
+    }
+    if body.len() > MAX_SHARED_TASK_COMMENT_BYTES {
+        return Err(SharedTaskCommentError::PayloadTooLarge);
+    }
+    Ok(())
+}
@@ -6,8 +6,10 @@ use ts_rs::TS;
 use uuid::Uuid;
 
 use super::{
+    Tx,
     identity_errors::IdentityError,
     projects::{ProjectError, ProjectRepository},
+    task_activity::{NewSharedTaskActivity, SharedTaskActivityRepository, SharedTaskActivityType},
     users::{UserData, fetch_user},
 };
 
@@ -25,6 +27,18 @@ pub enum TaskStatus {
     Cancelled,
 }
 
+impl TaskStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Todo => "todo",
+            TaskStatus::InProgress => "inprogress",
+            TaskStatus::InReview => "inreview",
+            TaskStatus::Done => "done",
+            TaskStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SharedTaskWithUser {
     pub task: SharedTask,
@@ -76,6 +90,20 @@ pub struct UpdateSharedTaskData {
 pub struct AssignTaskData {
     pub new_assignee_user_id: Option<Uuid>,
     pub previous_assignee_user_id: Option<Uuid>,
+    pub acting_user_id: Uuid,
+}
+
+/// Changes mirrored from an external tracker such as GitHub Issues. Unlike
+/// [`UpdateSharedTaskData`] these aren't restricted to the assignee.
+#[derive(Debug, Clone, Default)]
+pub struct ExternalTaskUpdate {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<TaskStatus>,
+    /// `Some(None)` clears the assignee
+    pub assignee_user_id: Option<Option<Uuid>>,
+    /// Member the change is attributed to, if known
+    pub acting_user_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -204,6 +232,19 @@ impl<'a> SharedTaskRepository<'a> {
         .fetch_one(&mut *tx)
         .await?;
 
+        SharedTaskActivityRepository::record(
+            &mut tx,
+            NewSharedTaskActivity {
+                task_id: task.id,
+                organization_id,
+                actor_user_id: Some(creator_user_id),
+                event_type: SharedTaskActivityType::Created,
+                from_value: None,
+                to_value: None,
+            },
+        )
+        .await?;
+
         let user = match assignee_user_id {
             Some(user_id) => fetch_user(&mut tx, user_id).await?,
             None => None,
@@ -220,6 +261,10 @@ impl<'a> SharedTaskRepository<'a> {
     ) -> Result<SharedTaskWithUser, SharedTaskError> {
         let mut tx = self.pool.begin().await.map_err(SharedTaskError::from)?;
 
+        let previous = lock_task_state(&mut tx, task_id)
+            .await?
+            .ok_or(SharedTaskError::NotFound)?;
+
         let task = sqlx::query_as!(
             SharedTask,
             r#"
@@ -258,6 +303,110 @@ impl<'a> SharedTaskRepository<'a> {
 
         ensure_text_size(&task.title, task.description.as_deref())?;
 
+        if task.status != previous.status {
+            SharedTaskActivityRepository::record(
+                &mut tx,
+                NewSharedTaskActivity {
+                    task_id,
+                    organization_id: task.organization_id,
+                    actor_user_id: Some(data.acting_user_id),
+                    event_type: SharedTaskActivityType::StatusChanged,
+                    from_value: Some(previous.status.as_str().to_string()),
+                    to_value: Some(task.status.as_str().to_string()),
+                },
+            )
+            .await?;
+        }
+
+        let user = match task.assignee_user_id {
+            Some(user_id) => fetch_user(&mut tx, user_id).await?,
+            None => None,
+        };
+
+        tx.commit().await.map_err(SharedTaskError::from)?;
+        Ok(SharedTaskWithUser::new(task, user))
+    }
+
+    pub async fn apply_external_update(
+        &self,
+        task_id: Uuid,
+        data: ExternalTaskUpdate,
+    ) -> Result<SharedTaskWithUser, SharedTaskError> {
+        let mut tx = self.pool.begin().await.map_err(SharedTaskError::from)?;
+
+        let previous = lock_task_state(&mut tx, task_id)
+            .await?
+            .ok_or(SharedTaskError::NotFound)?;
+
+        let task = sqlx::query_as!(
+            SharedTask,
+            r#"
+        UPDATE shared_tasks AS t
+        SET title            = COALESCE($2, t.title),
+            description      = COALESCE($3, t.description),
+            status           = COALESCE($4, t.status),
+            assignee_user_id = CASE WHEN $5 THEN $6 ELSE t.assignee_user_id END,
+            updated_at       = NOW()
+        WHERE t.id = $1
+          AND t.deleted_at IS NULL
+        RETURNING
+            t.id                AS "id!",
+            t.organization_id   AS "organization_id!: Uuid",
+            t.project_id        AS "project_id!",
+            t.creator_user_id   AS "creator_user_id?: Uuid",
+            t.assignee_user_id  AS "assignee_user_id?: Uuid",
+            t.deleted_by_user_id AS "deleted_by_user_id?: Uuid",
+            t.title             AS "title!",
+            t.description       AS "description?",
+            t.status            AS "status!: TaskStatus",
+            t.deleted_at        AS "deleted_at?",
+            t.shared_at         AS "shared_at?",
+            t.created_at        AS "created_at!",
+            t.updated_at        AS "updated_at!"
+        "#,
+            task_id,
+            data.title,
+            data.description,
+            data.status as Option<TaskStatus>,
+            data.assignee_user_id.is_some(),
+            data.assignee_user_id.flatten()
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| SharedTaskError::NotFound)?;
+
+        ensure_text_size(&task.title, task.description.as_deref())?;
+
+        if task.status != previous.status {
+            SharedTaskActivityRepository::record(
+                &mut tx,
+                NewSharedTaskActivity {
+                    task_id,
+                    organization_id: task.organization_id,
+                    actor_user_id: data.acting_user_id,
+                    event_type: SharedTaskActivityType::StatusChanged,
+                    from_value: Some(previous.status.as_str().to_string()),
+                    to_value: Some(task.status.as_str().to_string()),
+                },
+            )
+            .await?;
+        }
+
+        if task.assignee_user_id != previous.assignee_user_id {
+            SharedTaskActivityRepository::record(
+                &mut tx,
+                NewSharedTaskActivity {
+                    task_id,
+                    organization_id: task.organization_id,
+                    actor_user_id: data.acting_user_id,
+                    event_type: SharedTaskActivityType::AssigneeChanged,
+                    from_value: previous.assignee_user_id.map(|id| id.to_string()),
+                    to_value: task.assignee_user_id.map(|id| id.to_string()),
+                },
+            )
+            .await?;
+        }
+
         let user = match task.assignee_user_id {
             Some(user_id) => fetch_user(&mut tx, user_id).await?,
             None => None,
@@ -274,6 +423,10 @@ impl<'a> SharedTaskRepository<'a> {
     ) -> Result<SharedTaskWithUser, SharedTaskError> {
         let mut tx = self.pool.begin().await.map_err(SharedTaskError::from)?;
 
+        let previous = lock_task_state(&mut tx, task_id)
+            .await?
+            .ok_or(SharedTaskError::NotFound)?;
+
         let task = sqlx::query_as!(
             SharedTask,
             r#"
@@ -305,6 +458,21 @@ impl<'a> SharedTaskRepository<'a> {
         .await?
         .ok_or_else(|| SharedTaskError::Conflict("previous assignee mismatch".to_string()))?;
 
+        if task.assignee_user_id != previous.assignee_user_id {
+            SharedTaskActivityRepository::record(
+                &mut tx,
+                NewSharedTaskActivity {
+                    task_id,
+                    organization_id: task.organization_id,
+                    actor_user_id: Some(data.acting_user_id),
+                    event_type: SharedTaskActivityType::AssigneeChanged,
+                    from_value: previous.assignee_user_id.map(|id| id.to_string()),
+                    to_value: task.assignee_user_id.map(|id| id.to_string()),
+                },
+            )
+            .await?;
+        }
+
         let user = match data.new_assignee_user_id {
             Some(user_id) => fetch_user(&mut tx, user_id).await?,
             None => None,
@@ -352,6 +520,19 @@ impl<'a> SharedTaskRepository<'a> {
         .await?
         .ok_or_else(|| SharedTaskError::Conflict("user not authorized".to_string()))?;
 
+        SharedTaskActivityRepository::record(
+            &mut tx,
+            NewSharedTaskActivity {
+                task_id,
+                organization_id: task.organization_id,
+                actor_user_id: Some(data.acting_user_id),
+                event_type: SharedTaskActivityType::Deleted,
+                from_value: None,
+                to_value: None,
+            },
+        )
+        .await?;
+
         tx.commit().await.map_err(SharedTaskError::from)?;
         Ok(SharedTaskWithUser::new(task, None))
     }
@@ -369,6 +550,14 @@ impl<'a> SharedTaskRepository<'a> {
             WHERE t.id = ANY($1)
               AND t.deleted_at IS NULL
               AND om.user_id = $2
+              AND (
+                  NOT om.project_scoped
+                  OR EXISTS (
+                      SELECT 1
+                      FROM project_members pm
+                      WHERE pm.project_id = t.project_id AND pm.user_id = $2
+                  )
+              )
             "#,
             task_ids,
             user_id
@@ -380,6 +569,36 @@ impl<'a> SharedTaskRepository<'a> {
     }
 }
 
+struct TaskState {
+    status: TaskStatus,
+    assignee_user_id: Option<Uuid>,
+}
+
+/// Reads and row-locks the fields tracked by the activity log so the recorded
+/// `from_value` matches what the update actually replaced.
+async fn lock_task_state(
+    tx: &mut Tx<'_>,
+    task_id: Uuid,
+) -> Result<Option<TaskState>, SharedTaskError> {
+    let state = sqlx::query_as!(
+        TaskState,
+        r#"
+        SELECT
+            status           AS "status!: TaskStatus",
+            assignee_user_id AS "assignee_user_id?: Uuid"
+        FROM shared_tasks
+        WHERE id = $1
+          AND deleted_at IS NULL
+        FOR UPDATE
+        "#,
+        task_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(state)
+}
+
 pub(crate) fn ensure_text_size(
     title: &str,
     description: Option<&str>,
@@ -394,13 +613,10 @@ pub(crate) fn ensure_text_size(
 }
 
 impl SharedTaskRepository<'_> {
-    pub async fn organization_id(
-        pool: &PgPool,
-        task_id: Uuid,
-    ) -> Result<Option<Uuid>, sqlx::Error> {
+    pub async fn project_id(pool: &PgPool, task_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
         sqlx::query_scalar!(
             r#"
-            SELECT organization_id
+            SELECT project_id
             FROM shared_tasks
             WHERE id = $1
             "#,
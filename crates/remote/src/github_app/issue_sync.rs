@@ -0,0 +1,615 @@
+//! Two-way sync between shared tasks and GitHub issues.
+//!
+//! Changes from GitHub arrive through `issues` webhooks and are applied with
+//! [`SharedTaskRepository::apply_external_update`]. Changes made in Vibe Kanban
+//! are pushed back through the installation token. Webhooks sent on behalf of
+//! the app itself are ignored so a push doesn't bounce back as an update.
+
+use sqlx::PgPool;
+use thiserror::Error;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use super::service::{GitHubAppError, GitHubAppService, GitHubIssue, IssueUpdate};
+use crate::db::{
+    github_issues::{
+        GitHubIssueDbError, GitHubIssueRepository, IssueSyncTarget, NewIssueLink,
+        SharedTaskGitHubIssue,
+    },
+    identity_errors::IdentityError,
+    organization_members::{AccessLevel, project_access},
+    tasks::{
+        CreateSharedTaskData, ExternalTaskUpdate, SharedTask, SharedTaskError,
+        SharedTaskRepository, SharedTaskWithUser, TaskStatus,
+    },
+};
+
+#[derive(Debug, Error)]
+pub enum IssueSyncError {
+    #[error("GitHub error: {0}")]
+    GitHub(#[from] GitHubAppError),
+    #[error(transparent)]
+    Database(#[from] GitHubIssueDbError),
+    #[error(transparent)]
+    Task(#[from] SharedTaskError),
+    #[error(transparent)]
+    Identity(#[from] IdentityError),
+    #[error("issue sync is not enabled for this project")]
+    NotConfigured,
+    #[error("invalid repository name: {0}")]
+    InvalidRepository(String),
+    #[error("pull requests can't be imported as tasks")]
+    PullRequest,
+}
+
+/// Task status for an issue, given the task's current status.
+///
+/// Reopening an issue moves a finished task back to `todo`; other open
+/// statuses are kept since GitHub can't express them.
+pub fn task_status_for_issue(
+    state: &str,
+    state_reason: Option<&str>,
+    current: TaskStatus,
+) -> TaskStatus {
+    match state {
+        "closed" if state_reason == Some("not_planned") => TaskStatus::Cancelled,
+        "closed" => TaskStatus::Done,
+        _ => match current {
+            TaskStatus::Done | TaskStatus::Cancelled => TaskStatus::Todo,
+            status => status,
+        },
+    }
+}
+
+/// Issue state and state reason for a task status.
+pub fn issue_state_for_task(status: TaskStatus) -> (&'static str, Option<&'static str>) {
+    match status {
+        TaskStatus::Done => ("closed", Some("completed")),
+        TaskStatus::Cancelled => ("closed", Some("not_planned")),
+        TaskStatus::Todo | TaskStatus::InProgress | TaskStatus::InReview => ("open", None),
+    }
+}
+
+/// What an `issues` webhook does for an issue that isn't linked to a task yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnlinkedIssueAction {
+    Import,
+    Ignore,
+}
+
+fn issue_event_action(action: &str, import_new_issues: bool) -> UnlinkedIssueAction {
+    if action == "opened" && import_new_issues {
+        UnlinkedIssueAction::Import
+    } else {
+        UnlinkedIssueAction::Ignore
+    }
+}
+
+/// Changes that bring `task` in line with `issue`, or `None` if they already
+/// match. `assignee` is the result of mapping the issue's assignees to members.
+fn task_update_for_issue(
+    task: &SharedTask,
+    issue: &GitHubIssue,
+    assignee: Option<Option<Uuid>>,
+    actor: Option<Uuid>,
+) -> Option<ExternalTaskUpdate> {
+    let body = issue.body.clone().unwrap_or_default();
+    let status = task_status_for_issue(&issue.state, issue.state_reason.as_deref(), task.status);
+
+    let update = ExternalTaskUpdate {
+        title: (issue.title != task.title).then(|| issue.title.clone()),
+        description: (body != task.description.as_deref().unwrap_or_default()).then_some(body),
+        status: (status != task.status).then_some(status),
+        assignee_user_id: assignee.filter(|assignee| *assignee != task.assignee_user_id),
+        acting_user_id: actor,
+    };
+    let unchanged = update.title.is_none()
+        && update.description.is_none()
+        && update.status.is_none()
+        && update.assignee_user_id.is_none();
+    (!unchanged).then_some(update)
+}
+
+pub struct IssueSyncService<'a> {
+    pool: &'a PgPool,
+    github_app: &'a GitHubAppService,
+}
+
+impl<'a> IssueSyncService<'a> {
+    pub fn new(pool: &'a PgPool, github_app: &'a GitHubAppService) -> Self {
+        Self { pool, github_app }
+    }
+
+    fn repo(&self) -> GitHubIssueRepository<'a> {
+        GitHubIssueRepository::new(self.pool)
+    }
+
+    /// Login the app acts as when pushing changes to GitHub.
+    fn bot_login(&self) -> String {
+        format!("{}[bot]", self.github_app.app_slug())
+    }
+
+    // ========== GitHub -> Vibe Kanban ==========
+
+    /// Applies an `issues` webhook event.
+    pub async fn handle_issue_event(
+        &self,
+        github_installation_id: i64,
+        github_repo_id: i64,
+        action: &str,
+        sender_login: &str,
+        issue: &GitHubIssue,
+    ) -> Result<(), IssueSyncError> {
+        if issue.pull_request.is_some() {
+            return Ok(());
+        }
+
+        if sender_login.eq_ignore_ascii_case(&self.bot_login()) {
+            debug!(
+                issue_number = issue.number,
+                "Ignoring issue event sent by the app"
+            );
+            return Ok(());
+        }
+
+        let Some(target) = self
+            .repo()
+            .target_for_github_repo(github_installation_id, github_repo_id)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        let link = self
+            .repo()
+            .find_link_by_issue(target.repository_id, issue.id)
+            .await?;
+        match (link, issue_event_action(action, target.import_new_issues)) {
+            (Some(link), _) => {
+                let actor = self.member_for_login(&target, sender_login).await?;
+                self.apply_issue(&target, &link, issue, actor).await?;
+            }
+            (None, UnlinkedIssueAction::Import) => {
+                self.import_issue(&target, issue).await?;
+            }
+            (None, UnlinkedIssueAction::Ignore) => {}
+        }
+
+        Ok(())
+    }
+
+    /// Imports an issue of the project's repository by number.
+    pub async fn import_issue_by_number(
+        &self,
+        project_id: Uuid,
+        issue_number: i64,
+    ) -> Result<SharedTaskWithUser, IssueSyncError> {
+        let target = self
+            .repo()
+            .target_for_project(project_id)
+            .await?
+            .ok_or(IssueSyncError::NotConfigured)?;
+        let (owner, repo) = owner_and_repo(&target)?;
+
+        let issue = self
+            .github_app
+            .get_issue(target.github_installation_id, owner, repo, issue_number)
+            .await?;
+        if issue.pull_request.is_some() {
+            return Err(IssueSyncError::PullRequest);
+        }
+
+        if self
+            .repo()
+            .find_link_by_issue(target.repository_id, issue.id)
+            .await?
+            .is_some()
+        {
+            return Err(GitHubIssueDbError::AlreadyLinked.into());
+        }
+
+        self.import_issue(&target, &issue).await
+    }
+
+    /// Creates a shared task mirroring `issue` and links the two.
+    async fn import_issue(
+        &self,
+        target: &IssueSyncTarget,
+        issue: &GitHubIssue,
+    ) -> Result<SharedTaskWithUser, IssueSyncError> {
+        let creator_user_id = self
+            .member_for_login(target, &issue.user.login)
+            .await?
+            .unwrap_or(target.enabled_by_user_id);
+        let assignee_user_id = self.assignee_for_issue(target, issue).await?.flatten();
+
+        let tasks = SharedTaskRepository::new(self.pool);
+        let mut task = tasks
+            .create(CreateSharedTaskData {
+                project_id: target.project_id,
+                title: issue.title.clone(),
+                description: issue.body.clone(),
+                creator_user_id,
+                assignee_user_id,
+            })
+            .await?;
+
+        let link = NewIssueLink {
+            task_id: task.task.id,
+            repository_id: target.repository_id,
+            github_issue_id: issue.id,
+            issue_number: issue.number,
+            html_url: &issue.html_url,
+        };
+        match self.repo().create_link(link).await {
+            Ok(_) => {}
+            // Another delivery of the same event won the race
+            Err(GitHubIssueDbError::AlreadyLinked) => {
+                self.repo().discard_unlinked_task(task.task.id).await?;
+                return Err(GitHubIssueDbError::AlreadyLinked.into());
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        let status = task_status_for_issue(
+            &issue.state,
+            issue.state_reason.as_deref(),
+            task.task.status,
+        );
+        if status != task.task.status {
+            task = tasks
+                .apply_external_update(
+                    task.task.id,
+                    ExternalTaskUpdate {
+                        status: Some(status),
+                        acting_user_id: Some(creator_user_id),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+        }
+
+        info!(
+            project_id = %target.project_id,
+            task_id = %task.task.id,
+            issue_number = issue.number,
+            "Imported GitHub issue"
+        );
+        Ok(task)
+    }
+
+    /// Mirrors the issue's title, body, state and assignee onto its task.
+    async fn apply_issue(
+        &self,
+        target: &IssueSyncTarget,
+        link: &SharedTaskGitHubIssue,
+        issue: &GitHubIssue,
+        actor: Option<Uuid>,
+    ) -> Result<(), IssueSyncError> {
+        let tasks = SharedTaskRepository::new(self.pool);
+        let Some(task) = tasks.find_by_id(link.task_id).await? else {
+            return Ok(());
+        };
+
+        let assignee = self.assignee_for_issue(target, issue).await?;
+        let Some(update) = task_update_for_issue(&task, issue, assignee, actor) else {
+            return Ok(());
+        };
+
+        tasks.apply_external_update(task.id, update).await?;
+        self.repo().touch_link(task.id).await?;
+        Ok(())
+    }
+
+    // ========== Vibe Kanban -> GitHub ==========
+
+    /// Opens an issue for a newly created task if the project exports new
+    /// tasks.
+    pub async fn export_new_task(&self, task: &SharedTask) -> Result<(), IssueSyncError> {
+        let Some(target) = self.repo().target_for_project(task.project_id).await? else {
+            return Ok(());
+        };
+        if !target.export_new_tasks {
+            return Ok(());
+        }
+
+        self.create_issue_for_task(&target, task).await?;
+        Ok(())
+    }
+
+    /// Opens an issue for an existing task on request.
+    pub async fn export_task(
+        &self,
+        task: &SharedTask,
+    ) -> Result<SharedTaskGitHubIssue, IssueSyncError> {
+        let target = self
+            .repo()
+            .target_for_project(task.project_id)
+            .await?
+            .ok_or(IssueSyncError::NotConfigured)?;
+        if self.repo().find_link_by_task(task.id).await?.is_some() {
+            return Err(GitHubIssueDbError::AlreadyLinked.into());
+        }
+
+        self.create_issue_for_task(&target, task).await
+    }
+
+    async fn create_issue_for_task(
+        &self,
+        target: &IssueSyncTarget,
+        task: &SharedTask,
+    ) -> Result<SharedTaskGitHubIssue, IssueSyncError> {
+        let (owner, repo) = owner_and_repo(target)?;
+        let assignees = match task.assignee_user_id {
+            Some(user_id) => self
+                .repo()
+                .github_login_for_user(user_id)
+                .await?
+                .into_iter()
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let mut issue = self
+            .github_app
+            .create_issue(
+                target.github_installation_id,
+                owner,
+                repo,
+                &task.title,
+                task.description.as_deref(),
+                &assignees,
+            )
+            .await?;
+
+        let (state, state_reason) = issue_state_for_task(task.status);
+        if state != issue.state {
+            issue = self
+                .github_app
+                .update_issue(
+                    target.github_installation_id,
+                    owner,
+                    repo,
+                    issue.number,
+                    &IssueUpdate {
+                        state: Some(state.to_string()),
+                        state_reason: state_reason.map(str::to_string),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+        }
+
+        let link = self
+            .repo()
+            .create_link(NewIssueLink {
+                task_id: task.id,
+                repository_id: target.repository_id,
+                github_issue_id: issue.id,
+                issue_number: issue.number,
+                html_url: &issue.html_url,
+            })
+            .await?;
+
+        info!(
+            project_id = %target.project_id,
+            task_id = %task.id,
+            issue_number = issue.number,
+            "Exported shared task to GitHub"
+        );
+        Ok(link)
+    }
+
+    /// Pushes a task's title, description, status and assignee to its linked
+    /// issue. Tasks without an issue are left alone.
+    pub async fn push_task(&self, task: &SharedTask) -> Result<(), IssueSyncError> {
+        let Some(link) = self.repo().find_link_by_task(task.id).await? else {
+            return Ok(());
+        };
+        let Some(target) = self.repo().target_for_project(task.project_id).await? else {
+            return Ok(());
+        };
+        if target.repository_id != link.repository_id {
+            // The project was pointed at another repository since linking
+            return Ok(());
+        }
+        let (owner, repo) = owner_and_repo(&target)?;
+
+        // Leave GitHub's assignees alone when the assignee has no GitHub login
+        let assignees = match task.assignee_user_id {
+            Some(user_id) => self
+                .repo()
+                .github_login_for_user(user_id)
+                .await?
+                .map(|login| vec![login]),
+            None => Some(Vec::new()),
+        };
+
+        let (state, state_reason) = issue_state_for_task(task.status);
+        let update = IssueUpdate {
+            title: Some(task.title.clone()),
+            body: Some(task.description.clone().unwrap_or_default()),
+            state: Some(state.to_string()),
+            state_reason: state_reason.map(str::to_string),
+            assignees,
+        };
+
+        self.github_app
+            .update_issue(
+                target.github_installation_id,
+                owner,
+                repo,
+                link.issue_number,
+                &update,
+            )
+            .await?;
+        self.repo().touch_link(task.id).await?;
+        Ok(())
+    }
+
+    // ========== Account mapping ==========
+
+    /// Member who signed in with the GitHub account `login` and may change
+    /// tasks in the project.
+    async fn member_for_login(
+        &self,
+        target: &IssueSyncTarget,
+        login: &str,
+    ) -> Result<Option<Uuid>, IssueSyncError> {
+        let Some(user_id) = self.repo().user_for_github_login(login).await? else {
+            return Ok(None);
+        };
+
+        let can_write = project_access(self.pool, target.project_id, user_id)
+            .await?
+            .is_some_and(|access| access.check(AccessLevel::Write).is_ok());
+        Ok(can_write.then_some(user_id))
+    }
+
+    /// Task assignee for an issue: `Some(None)` when the issue is unassigned,
+    /// `None` when none of its assignees map to a member.
+    async fn assignee_for_issue(
+        &self,
+        target: &IssueSyncTarget,
+        issue: &GitHubIssue,
+    ) -> Result<Option<Option<Uuid>>, IssueSyncError> {
+        if issue.assignees.is_empty() {
+            return Ok(Some(None));
+        }
+
+        for assignee in &issue.assignees {
+            if let Some(user_id) = self.member_for_login(target, &assignee.login).await? {
+                return Ok(Some(Some(user_id)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn owner_and_repo(target: &IssueSyncTarget) -> Result<(&str, &str), IssueSyncError> {
+    target
+        .owner_and_repo()
+        .ok_or_else(|| IssueSyncError::InvalidRepository(target.repo_full_name.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use super::*;
+
+    fn task(status: TaskStatus, assignee_user_id: Option<Uuid>) -> SharedTask {
+        SharedTask {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            creator_user_id: None,
+            assignee_user_id,
+            deleted_by_user_id: None,
+            title: "Fix login".to_string(),
+            description: Some("It breaks".to_string()),
+            status,
+            deleted_at: None,
+            shared_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn issue(title: &str, body: &str, state: &str, state_reason: Option<&str>) -> GitHubIssue {
+        serde_json::from_value(json!({
+            "id": 1,
+            "number": 7,
+            "title": title,
+            "body": body,
+            "state": state,
+            "state_reason": state_reason,
+            "html_url": "https://github.com/acme/app/issues/7",
+            "user": { "login": "octocat" },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_unlinked_issues_are_imported_only_when_opened_with_import_enabled() {
+        assert_eq!(
+            issue_event_action("opened", true),
+            UnlinkedIssueAction::Import
+        );
+        assert_eq!(
+            issue_event_action("opened", false),
+            UnlinkedIssueAction::Ignore
+        );
+        for action in ["edited", "closed", "reopened", "assigned"] {
+            assert_eq!(
+                issue_event_action(action, true),
+                UnlinkedIssueAction::Ignore
+            );
+        }
+    }
+
+    #[test]
+    fn test_task_update_for_unchanged_issue_is_none() {
+        let task = task(TaskStatus::InProgress, None);
+        let issue = issue("Fix login", "It breaks", "open", None);
+
+        assert!(task_update_for_issue(&task, &issue, Some(None), None).is_none());
+        // Assignees that don't map to members leave the task's assignee alone
+        assert!(task_update_for_issue(&task, &issue, None, None).is_none());
+    }
+
+    #[test]
+    fn test_task_update_for_edited_and_closed_issue() {
+        let task = task(TaskStatus::InProgress, None);
+        let actor = Uuid::new_v4();
+        let issue = issue(
+            "Fix login on Safari",
+            "It breaks",
+            "closed",
+            Some("not_planned"),
+        );
+
+        let update = task_update_for_issue(&task, &issue, Some(None), Some(actor)).unwrap();
+        assert_eq!(update.title.as_deref(), Some("Fix login on Safari"));
+        assert!(update.description.is_none());
+        assert_eq!(update.status, Some(TaskStatus::Cancelled));
+        assert!(update.assignee_user_id.is_none());
+        assert_eq!(update.acting_user_id, Some(actor));
+    }
+
+    #[test]
+    fn test_task_update_for_reassigned_issue() {
+        let previous = Uuid::new_v4();
+        let task = task(TaskStatus::Todo, Some(previous));
+        let issue = issue("Fix login", "It breaks", "open", None);
+
+        let member = Uuid::new_v4();
+        let update = task_update_for_issue(&task, &issue, Some(Some(member)), None).unwrap();
+        assert_eq!(update.assignee_user_id, Some(Some(member)));
+
+        let update = task_update_for_issue(&task, &issue, Some(None), None).unwrap();
+        assert_eq!(update.assignee_user_id, Some(None));
+    }
+
+    #[test]
+    fn test_issue_state_round_trips_through_task_status() {
+        for status in [
+            TaskStatus::Done,
+            TaskStatus::Cancelled,
+            TaskStatus::InReview,
+        ] {
+            let (state, reason) = issue_state_for_task(status);
+            assert_eq!(task_status_for_issue(state, reason, status), status);
+        }
+
+        assert_eq!(
+            task_status_for_issue("open", Some("reopened"), TaskStatus::Done),
+            TaskStatus::Todo
+        );
+        assert_eq!(
+            task_status_for_issue("closed", None, TaskStatus::InProgress),
+            TaskStatus::Done
+        );
+    }
+}
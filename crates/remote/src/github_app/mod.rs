@@ -1,9 +1,14 @@
+mod issue_sync;
 mod jwt;
 mod pr_review;
 mod service;
 mod webhook;
 
+pub use issue_sync::{IssueSyncError, IssueSyncService};
 pub use jwt::GitHubAppJwt;
 pub use pr_review::{PrReviewError, PrReviewParams, PrReviewService};
-pub use service::{GitHubAppService, InstallationInfo, PrDetails, PrRef, Repository};
+pub use service::{
+    GitHubAppService, GitHubIssue, GitHubUser, InstallationInfo, IssueUpdate, PrDetails, PrRef,
+    Repository,
+};
 pub use webhook::verify_webhook_signature;
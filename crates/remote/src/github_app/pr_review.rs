@@ -1,9 +1,10 @@
 //! PR Review service for webhook-triggered code reviews.
 
-use std::{fs::File, path::Path};
+use std::{fs::File, path::Path, sync::Arc};
 
 use flate2::{Compression, write::GzEncoder};
 use reqwest::Client;
+use secrecy::ExposeSecret;
 use sqlx::PgPool;
 use tar::Builder;
 use thiserror::Error;
@@ -12,8 +13,9 @@ use uuid::Uuid;
 
 use super::service::{GitHubAppError, GitHubAppService};
 use crate::{
+    blob_store::{BlobStore, BlobStoreError, payload_object_key},
+    config::ReviewWorkerConfig,
     db::reviews::{CreateWebhookReviewParams, ReviewError, ReviewRepository},
-    r2::{R2Error, R2Service},
 };
 
 /// Parameters extracted from webhook payload for PR review
@@ -33,8 +35,8 @@ pub struct PrReviewParams {
 pub enum PrReviewError {
     #[error("GitHub error: {0}")]
     GitHub(#[from] GitHubAppError),
-    #[error("R2 error: {0}")]
-    R2(#[from] R2Error),
+    #[error("Storage error: {0}")]
+    Storage(#[from] BlobStoreError),
     #[error("Database error: {0}")]
     Database(#[from] ReviewError),
     #[error("Archive error: {0}")]
@@ -46,25 +48,25 @@ pub enum PrReviewError {
 /// Service for processing webhook-triggered PR reviews
 pub struct PrReviewService {
     github_app: GitHubAppService,
-    r2: R2Service,
+    blob_store: Arc<dyn BlobStore>,
     http_client: Client,
-    worker_base_url: String,
+    worker: ReviewWorkerConfig,
     server_base_url: String,
 }
 
 impl PrReviewService {
     pub fn new(
         github_app: GitHubAppService,
-        r2: R2Service,
+        blob_store: Arc<dyn BlobStore>,
         http_client: Client,
-        worker_base_url: String,
+        worker: ReviewWorkerConfig,
         server_base_url: String,
     ) -> Self {
         Self {
             github_app,
-            r2,
+            blob_store,
             http_client,
-            worker_base_url,
+            worker,
             server_base_url,
         }
     }
@@ -74,7 +76,7 @@ impl PrReviewService {
     /// This will:
     /// 1. Clone the repository at the PR head commit
     /// 2. Create a tarball of the repository
-    /// 3. Upload the tarball to R2
+    /// 3. Upload the tarball to the blob store
     /// 4. Create a review record in the database
     /// 5. Start the review worker
     ///
@@ -119,9 +121,9 @@ impl PrReviewService {
         let tarball_size_mb = tarball.len() as f64 / 1_048_576.0;
         debug!(review_id = %review_id, size_mb = tarball_size_mb, "Tarball created");
 
-        // 4. Upload to R2
-        let r2_path = self.r2.upload_bytes(review_id, tarball).await?;
-        debug!(review_id = %review_id, r2_path = %r2_path, "Uploaded to R2");
+        // 4. Upload to the blob store
+        let folder_path = self.blob_store.upload_bytes(review_id, tarball).await?;
+        debug!(review_id = %review_id, folder_path = %folder_path, "Uploaded payload");
 
         // 5. Create review record in database
         let gh_pr_url = format!(
@@ -133,7 +135,7 @@ impl PrReviewService {
         repo.create_webhook_review(CreateWebhookReviewParams {
             id: review_id,
             gh_pr_url: &gh_pr_url,
-            r2_path: &r2_path,
+            r2_path: &folder_path,
             pr_title: &params.pr_title,
             github_installation_id: params.installation_id,
             pr_owner: &params.owner,
@@ -145,11 +147,10 @@ impl PrReviewService {
         debug!(review_id = %review_id, "Review record created");
 
         // 6. Start the review worker
-        let codebase_url = format!(
-            "{}/reviews/{}/payload.tar.gz",
-            self.r2_public_url(),
-            review_id
-        );
+        let codebase_url = self
+            .blob_store
+            .download_url(&payload_object_key(review_id))
+            .await?;
         let callback_url = format!("{}/review/{}", self.server_base_url, review_id);
 
         let start_request = serde_json::json!({
@@ -165,7 +166,8 @@ impl PrReviewService {
 
         let response = self
             .http_client
-            .post(format!("{}/review/start", self.worker_base_url))
+            .post(format!("{}/review/start", self.worker.base_url))
+            .bearer_auth(self.worker.secret.expose_secret())
             .json(&start_request)
             .send()
             .await
@@ -185,16 +187,6 @@ impl PrReviewService {
 
         Ok(review_id)
     }
-
-    /// Get the public URL for R2 (used to construct codebase URLs for the worker).
-    /// This assumes the R2 bucket has public read access configured.
-    fn r2_public_url(&self) -> &str {
-        // The worker needs to be able to fetch the tarball from R2.
-        // This is typically configured via a public bucket URL or CDN.
-        // For now, we'll use the worker base URL as a proxy assumption.
-        // In production, this should be configured separately.
-        &self.worker_base_url
-    }
 }
 
 /// Create a tar.gz archive from a directory
@@ -80,6 +80,46 @@ pub struct PrRef {
     pub ref_name: String,
 }
 
+/// A GitHub issue, as returned by the REST API and `issues` webhooks
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubIssue {
+    pub id: i64,
+    pub number: i64,
+    pub title: String,
+    pub body: Option<String>,
+    /// `open` or `closed`
+    pub state: String,
+    /// `completed`, `not_planned` or `reopened`
+    pub state_reason: Option<String>,
+    pub html_url: String,
+    pub user: GitHubUser,
+    #[serde(default)]
+    pub assignees: Vec<GitHubUser>,
+    /// Present when the issue is a pull request
+    pub pull_request: Option<serde_json::Value>,
+}
+
+/// A GitHub user reference
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubUser {
+    pub login: String,
+}
+
+/// Fields to set on an issue. `None` leaves the field unchanged.
+#[derive(Debug, Default, Serialize)]
+pub struct IssueUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignees: Option<Vec<String>>,
+}
+
 /// Service for interacting with the GitHub App API
 #[derive(Clone)]
 pub struct GitHubAppService {
@@ -450,4 +490,118 @@ impl GitHubAppService {
         let pr: PrDetails = response.json().await?;
         Ok(pr)
     }
+
+    pub async fn get_issue(
+        &self,
+        installation_id: i64,
+        owner: &str,
+        repo: &str,
+        issue_number: i64,
+    ) -> Result<GitHubIssue, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}",
+            GITHUB_API_BASE, owner, repo, issue_number
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", USER_AGENT)
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = response.text().await.unwrap_or_default();
+            return Err(GitHubAppError::Api { status, message });
+        }
+
+        let issue: GitHubIssue = response.json().await?;
+        Ok(issue)
+    }
+
+    pub async fn create_issue(
+        &self,
+        installation_id: i64,
+        owner: &str,
+        repo: &str,
+        title: &str,
+        body: Option<&str>,
+        assignees: &[String],
+    ) -> Result<GitHubIssue, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+
+        let url = format!("{}/repos/{}/{}/issues", GITHUB_API_BASE, owner, repo);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", USER_AGENT)
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({
+                "title": title,
+                "body": body,
+                "assignees": assignees,
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = response.text().await.unwrap_or_default();
+            warn!(owner, repo, status, message, "Failed to create issue");
+            return Err(GitHubAppError::Api { status, message });
+        }
+
+        let issue: GitHubIssue = response.json().await?;
+        info!(owner, repo, issue_number = issue.number, "Created issue");
+        Ok(issue)
+    }
+
+    pub async fn update_issue(
+        &self,
+        installation_id: i64,
+        owner: &str,
+        repo: &str,
+        issue_number: i64,
+        update: &IssueUpdate,
+    ) -> Result<GitHubIssue, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}",
+            GITHUB_API_BASE, owner, repo, issue_number
+        );
+
+        let response = self
+            .client
+            .patch(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", USER_AGENT)
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(update)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = response.text().await.unwrap_or_default();
+            warn!(
+                owner,
+                repo, issue_number, status, message, "Failed to update issue"
+            );
+            return Err(GitHubAppError::Api { status, message });
+        }
+
+        let issue: GitHubIssue = response.json().await?;
+        Ok(issue)
+    }
 }
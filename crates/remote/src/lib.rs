@@ -1,10 +1,10 @@
 mod app;
 mod auth;
+pub mod blob_store;
 pub mod config;
 pub mod db;
 pub mod github_app;
 pub mod mail;
-pub mod r2;
 pub mod routes;
 mod state;
 pub mod validated_where;
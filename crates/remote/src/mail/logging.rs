@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+
+use super::{Mailer, templates, templates::RenderedEmail};
+use crate::db::{organization_members::MemberRole, tasks::TaskStatus};
+
+/// Mailer that only logs the rendered emails. Intended for development.
+pub struct LogMailer;
+
+impl LogMailer {
+    fn log(&self, email: &str, rendered: RenderedEmail) {
+        tracing::info!(
+            "Email to {email}\n\
+             Subject: {}\n\n\
+             {}",
+            rendered.subject,
+            rendered.text
+        );
+    }
+}
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send_org_invitation(
+        &self,
+        org_name: &str,
+        email: &str,
+        accept_url: &str,
+        role: MemberRole,
+        invited_by: Option<&str>,
+    ) {
+        self.log(
+            email,
+            templates::org_invitation(org_name, accept_url, role, invited_by),
+        );
+    }
+
+    async fn send_review_ready(&self, email: &str, review_url: &str, pr_name: &str) {
+        self.log(email, templates::review_ready(review_url, pr_name));
+    }
+
+    async fn send_review_failed(&self, email: &str, pr_name: &str, review_id: &str) {
+        self.log(email, templates::review_failed(pr_name, review_id));
+    }
+
+    async fn send_task_assigned(&self, email: &str, task_title: &str, assigned_by: Option<&str>) {
+        self.log(email, templates::task_assigned(task_title, assigned_by));
+    }
+
+    async fn send_task_status_changed(
+        &self,
+        email: &str,
+        task_title: &str,
+        from: TaskStatus,
+        to: TaskStatus,
+        changed_by: Option<&str>,
+    ) {
+        self.log(
+            email,
+            templates::task_status_changed(task_title, from, to, changed_by),
+        );
+    }
+}
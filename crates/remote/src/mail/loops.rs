@@ -3,28 +3,13 @@ use std::time::Duration;
 use async_trait::async_trait;
 use serde_json::json;
 
-use crate::db::organization_members::MemberRole;
+use super::Mailer;
+use crate::db::{organization_members::MemberRole, tasks::TaskStatus};
 
 const LOOPS_INVITE_TEMPLATE_ID: &str = "cmhvy2wgs3s13z70i1pxakij9";
 const LOOPS_REVIEW_READY_TEMPLATE_ID: &str = "cmj47k5ge16990iylued9by17";
 const LOOPS_REVIEW_FAILED_TEMPLATE_ID: &str = "cmj49ougk1c8s0iznavijdqpo";
 
-#[async_trait]
-pub trait Mailer: Send + Sync {
-    async fn send_org_invitation(
-        &self,
-        org_name: &str,
-        email: &str,
-        accept_url: &str,
-        role: MemberRole,
-        invited_by: Option<&str>,
-    );
-
-    async fn send_review_ready(&self, email: &str, review_url: &str, pr_name: &str);
-
-    async fn send_review_failed(&self, email: &str, pr_name: &str, review_id: &str);
-}
-
 pub struct LoopsMailer {
     client: reqwest::Client,
     api_key: String,
@@ -54,6 +39,7 @@ impl Mailer for LoopsMailer {
         let role_str = match role {
             MemberRole::Admin => "admin",
             MemberRole::Member => "member",
+            MemberRole::Viewer => "viewer",
         };
         let inviter = invited_by.unwrap_or("someone");
 
@@ -181,4 +167,19 @@ impl Mailer for LoopsMailer {
             }
         }
     }
+
+    async fn send_task_assigned(&self, email: &str, _task_title: &str, _assigned_by: Option<&str>) {
+        tracing::debug!("No Loops template for task assignment, skipping email to {email}");
+    }
+
+    async fn send_task_status_changed(
+        &self,
+        email: &str,
+        _task_title: &str,
+        _from: TaskStatus,
+        _to: TaskStatus,
+        _changed_by: Option<&str>,
+    ) {
+        tracing::debug!("No Loops template for task status changes, skipping email to {email}");
+    }
 }
@@ -0,0 +1,61 @@
+//! Outgoing email.
+//!
+//! [`LoopsMailer`] sends through the hosted Loops service, [`SmtpMailer`] talks
+//! to any SMTP relay using the local templates, and [`LogMailer`] only logs the
+//! rendered messages (useful in development).
+
+mod logging;
+mod loops;
+mod smtp;
+pub mod templates;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+pub use logging::LogMailer;
+pub use loops::LoopsMailer;
+use secrecy::ExposeSecret;
+pub use smtp::SmtpMailer;
+
+use crate::{
+    config::MailerConfig,
+    db::{organization_members::MemberRole, tasks::TaskStatus},
+};
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send_org_invitation(
+        &self,
+        org_name: &str,
+        email: &str,
+        accept_url: &str,
+        role: MemberRole,
+        invited_by: Option<&str>,
+    );
+
+    async fn send_review_ready(&self, email: &str, review_url: &str, pr_name: &str);
+
+    async fn send_review_failed(&self, email: &str, pr_name: &str, review_id: &str);
+
+    async fn send_task_assigned(&self, email: &str, task_title: &str, assigned_by: Option<&str>);
+
+    async fn send_task_status_changed(
+        &self,
+        email: &str,
+        task_title: &str,
+        from: TaskStatus,
+        to: TaskStatus,
+        changed_by: Option<&str>,
+    );
+}
+
+pub fn build_mailer(config: &MailerConfig) -> anyhow::Result<Arc<dyn Mailer>> {
+    let mailer: Arc<dyn Mailer> = match config {
+        MailerConfig::Loops { api_key } => {
+            Arc::new(LoopsMailer::new(api_key.expose_secret().to_string()))
+        }
+        MailerConfig::Smtp(smtp) => Arc::new(SmtpMailer::new(smtp)?),
+        MailerConfig::Log => Arc::new(LogMailer),
+    };
+    Ok(mailer)
+}
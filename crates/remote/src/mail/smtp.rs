@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, MultiPart},
+    transport::smtp::authentication::Credentials,
+};
+use secrecy::ExposeSecret;
+
+use super::{Mailer, templates, templates::RenderedEmail};
+use crate::{
+    config::{SmtpConfig, SmtpTls},
+    db::{organization_members::MemberRole, tasks::TaskStatus},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SmtpMailerError {
+    #[error("invalid SMTP_FROM address: {0}")]
+    InvalidFrom(#[from] lettre::address::AddressError),
+    #[error(transparent)]
+    Transport(#[from] lettre::transport::smtp::Error),
+}
+
+/// Mailer that delivers the local templates through an SMTP relay.
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(config: &SmtpConfig) -> Result<Self, SmtpMailerError> {
+        let builder = match config.tls {
+            SmtpTls::Implicit => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)?,
+            SmtpTls::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?
+            }
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+        };
+
+        let mut builder = builder
+            .port(config.port)
+            .timeout(Some(Duration::from_secs(10)));
+
+        if let Some(username) = &config.username {
+            let password = config
+                .password
+                .as_ref()
+                .map(|p| p.expose_secret().to_string())
+                .unwrap_or_default();
+            builder = builder.credentials(Credentials::new(username.clone(), password));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from: config.from.parse()?,
+        })
+    }
+
+    async fn send(&self, email: &str, rendered: RenderedEmail) {
+        let to: Mailbox = match email.parse() {
+            Ok(to) => to,
+            Err(err) => {
+                tracing::warn!(error = %err, "Invalid recipient address {email}");
+                return;
+            }
+        };
+
+        let message = match Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(rendered.subject)
+            .multipart(MultiPart::alternative_plain_html(
+                rendered.text,
+                rendered.html,
+            )) {
+            Ok(message) => message,
+            Err(err) => {
+                tracing::error!(error = ?err, "Failed to build email to {email}");
+                return;
+            }
+        };
+
+        match self.transport.send(message).await {
+            Ok(_) => tracing::debug!("Email sent via SMTP to {email}"),
+            Err(err) => tracing::error!(error = ?err, "SMTP send failed"),
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send_org_invitation(
+        &self,
+        org_name: &str,
+        email: &str,
+        accept_url: &str,
+        role: MemberRole,
+        invited_by: Option<&str>,
+    ) {
+        self.send(
+            email,
+            templates::org_invitation(org_name, accept_url, role, invited_by),
+        )
+        .await;
+    }
+
+    async fn send_review_ready(&self, email: &str, review_url: &str, pr_name: &str) {
+        self.send(email, templates::review_ready(review_url, pr_name))
+            .await;
+    }
+
+    async fn send_review_failed(&self, email: &str, pr_name: &str, review_id: &str) {
+        self.send(email, templates::review_failed(pr_name, review_id))
+            .await;
+    }
+
+    async fn send_task_assigned(&self, email: &str, task_title: &str, assigned_by: Option<&str>) {
+        self.send(email, templates::task_assigned(task_title, assigned_by))
+            .await;
+    }
+
+    async fn send_task_status_changed(
+        &self,
+        email: &str,
+        task_title: &str,
+        from: TaskStatus,
+        to: TaskStatus,
+        changed_by: Option<&str>,
+    ) {
+        self.send(
+            email,
+            templates::task_status_changed(task_title, from, to, changed_by),
+        )
+        .await;
+    }
+}
@@ -0,0 +1,196 @@
+//! Local email templates used by the SMTP and log mailers.
+//!
+//! Templates live in `templates/email` and use `{{name}}` placeholders. Values
+//! are HTML-escaped when rendering the HTML part.
+
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+use crate::db::{organization_members::MemberRole, tasks::TaskStatus};
+
+const LAYOUT_HTML: &str = include_str!("../../templates/email/layout.html");
+const ORG_INVITATION_HTML: &str = include_str!("../../templates/email/org_invitation.html");
+const ORG_INVITATION_TXT: &str = include_str!("../../templates/email/org_invitation.txt");
+const REVIEW_READY_HTML: &str = include_str!("../../templates/email/review_ready.html");
+const REVIEW_READY_TXT: &str = include_str!("../../templates/email/review_ready.txt");
+const REVIEW_FAILED_HTML: &str = include_str!("../../templates/email/review_failed.html");
+const REVIEW_FAILED_TXT: &str = include_str!("../../templates/email/review_failed.txt");
+const TASK_ASSIGNED_HTML: &str = include_str!("../../templates/email/task_assigned.html");
+const TASK_ASSIGNED_TXT: &str = include_str!("../../templates/email/task_assigned.txt");
+const TASK_STATUS_CHANGED_HTML: &str =
+    include_str!("../../templates/email/task_status_changed.html");
+const TASK_STATUS_CHANGED_TXT: &str = include_str!("../../templates/email/task_status_changed.txt");
+
+static PLACEHOLDER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{\{(\w+)\}\}").unwrap());
+
+#[derive(Debug, Clone)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+pub fn org_invitation(
+    org_name: &str,
+    accept_url: &str,
+    role: MemberRole,
+    invited_by: Option<&str>,
+) -> RenderedEmail {
+    let role = match role {
+        MemberRole::Admin => "an admin",
+        MemberRole::Member => "a member",
+        MemberRole::Viewer => "a viewer",
+    };
+    let vars = [
+        ("org_name", org_name),
+        ("accept_url", accept_url),
+        ("role", role),
+        ("invited_by", invited_by.unwrap_or("Someone")),
+    ];
+    render(
+        format!("You've been invited to join {org_name}"),
+        ORG_INVITATION_TXT,
+        ORG_INVITATION_HTML,
+        &vars,
+    )
+}
+
+pub fn review_ready(review_url: &str, pr_name: &str) -> RenderedEmail {
+    let vars = [("review_url", review_url), ("pr_name", pr_name)];
+    render(
+        format!("Your review of \"{pr_name}\" is ready"),
+        REVIEW_READY_TXT,
+        REVIEW_READY_HTML,
+        &vars,
+    )
+}
+
+pub fn review_failed(pr_name: &str, review_id: &str) -> RenderedEmail {
+    let vars = [("pr_name", pr_name), ("review_id", review_id)];
+    render(
+        format!("Your review of \"{pr_name}\" failed"),
+        REVIEW_FAILED_TXT,
+        REVIEW_FAILED_HTML,
+        &vars,
+    )
+}
+
+pub fn task_assigned(task_title: &str, assigned_by: Option<&str>) -> RenderedEmail {
+    let vars = [
+        ("task_title", task_title),
+        ("assigned_by", assigned_by.unwrap_or("Someone")),
+    ];
+    render(
+        format!("Task assigned to you: {task_title}"),
+        TASK_ASSIGNED_TXT,
+        TASK_ASSIGNED_HTML,
+        &vars,
+    )
+}
+
+pub fn task_status_changed(
+    task_title: &str,
+    from: TaskStatus,
+    to: TaskStatus,
+    changed_by: Option<&str>,
+) -> RenderedEmail {
+    let vars = [
+        ("task_title", task_title),
+        ("from_status", status_label(from)),
+        ("to_status", status_label(to)),
+        ("changed_by", changed_by.unwrap_or("Someone")),
+    ];
+    render(
+        format!("{task_title} moved to {}", status_label(to)),
+        TASK_STATUS_CHANGED_TXT,
+        TASK_STATUS_CHANGED_HTML,
+        &vars,
+    )
+}
+
+fn status_label(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Todo => "To Do",
+        TaskStatus::InProgress => "In Progress",
+        TaskStatus::InReview => "In Review",
+        TaskStatus::Done => "Done",
+        TaskStatus::Cancelled => "Cancelled",
+    }
+}
+
+fn render(subject: String, text: &str, html: &str, vars: &[(&str, &str)]) -> RenderedEmail {
+    let content = substitute(html, vars, escape_html);
+    RenderedEmail {
+        text: substitute(text, vars, str::to_string),
+        html: LAYOUT_HTML.replace("{{content}}", &content),
+        subject,
+    }
+}
+
+/// Fills every placeholder in one pass, so values that contain `{{name}}` are
+/// left as they are. Unknown placeholders are kept.
+fn substitute(template: &str, vars: &[(&str, &str)], encode: impl Fn(&str) -> String) -> String {
+    PLACEHOLDER
+        .replace_all(template, |caps: &Captures| {
+            match vars.iter().find(|(name, _)| *name == &caps[1]) {
+                Some((_, value)) => encode(value),
+                None => caps[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_values_are_escaped() {
+        let email = task_assigned("<script>alert(1)</script>", Some("Ann & Bob"));
+
+        assert!(email.html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(email.html.contains("Ann &amp; Bob"));
+        assert!(email.text.contains("<script>alert(1)</script>"));
+        assert!(!email.html.contains("{{"));
+    }
+
+    #[test]
+    fn test_all_placeholders_are_filled() {
+        let emails = [
+            org_invitation("Acme", "https://x/accept", MemberRole::Admin, None),
+            review_ready("https://x/review", "Fix bug"),
+            review_failed("Fix bug", "123"),
+            task_status_changed("Task", TaskStatus::Todo, TaskStatus::Done, Some("Ann")),
+        ];
+
+        for email in emails {
+            assert!(!email.text.contains("{{"), "{}", email.text);
+            assert!(!email.html.contains("{{"), "{}", email.html);
+        }
+    }
+
+    #[test]
+    fn test_values_are_not_substituted_again() {
+        let email = task_assigned("{{assigned_by}}", Some("Ann"));
+
+        assert!(email.subject.contains("{{assigned_by}}"));
+        assert!(email.text.contains("{{assigned_by}}"));
+        assert!(email.html.contains("{{assigned_by}}"));
+    }
+}
@@ -0,0 +1,109 @@
+//! Signed upload and download endpoints for the local filesystem blob store.
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use serde::Deserialize;
+use tokio_util::io::ReaderStream;
+
+use crate::{
+    AppState,
+    blob_store::{BlobStoreError, SignedMethod},
+};
+
+/// Uploads stream the raw body, which `DefaultBodyLimit` doesn't cover; the
+/// store enforces its configured maximum size while writing instead.
+pub fn public_router() -> Router<AppState> {
+    Router::new().route("/blobs/{*object_key}", get(download_blob).put(upload_blob))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SignedUrlQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
+pub async fn upload_blob(
+    State(state): State<AppState>,
+    Path(object_key): Path<String>,
+    Query(query): Query<SignedUrlQuery>,
+    body: Body,
+) -> Result<StatusCode, BlobError> {
+    let store = state.local_blob_store().ok_or(BlobError::NotConfigured)?;
+    let path = store.verify(
+        SignedMethod::Put,
+        &object_key,
+        query.expires,
+        &query.signature,
+    )?;
+
+    store.write_object(&path, body.into_data_stream()).await?;
+
+    Ok(StatusCode::OK)
+}
+
+pub async fn download_blob(
+    State(state): State<AppState>,
+    Path(object_key): Path<String>,
+    Query(query): Query<SignedUrlQuery>,
+) -> Result<Response, BlobError> {
+    let store = state.local_blob_store().ok_or(BlobError::NotConfigured)?;
+    let path = store.verify(
+        SignedMethod::Get,
+        &object_key,
+        query.expires,
+        &query.signature,
+    )?;
+
+    let file = match tokio::fs::File::open(&path).await {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Err(BlobError::NotFound),
+        Err(e) => return Err(BlobError::Store(e.into())),
+    };
+    let length = file.metadata().await.map_err(BlobStoreError::from)?.len();
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (header::CONTENT_LENGTH, length.to_string()),
+        ],
+        Body::from_stream(ReaderStream::new(file)),
+    )
+        .into_response())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BlobError {
+    #[error("local blob store not configured")]
+    NotConfigured,
+    #[error("blob not found")]
+    NotFound,
+    #[error(transparent)]
+    Store(#[from] BlobStoreError),
+}
+
+impl IntoResponse for BlobError {
+    fn into_response(self) -> Response {
+        match self {
+            BlobError::NotConfigured | BlobError::NotFound => StatusCode::NOT_FOUND.into_response(),
+            BlobError::Store(BlobStoreError::InvalidSignature) => {
+                StatusCode::FORBIDDEN.into_response()
+            }
+            BlobError::Store(BlobStoreError::InvalidKey(_)) => {
+                StatusCode::BAD_REQUEST.into_response()
+            }
+            BlobError::Store(BlobStoreError::TooLarge(_)) => {
+                StatusCode::PAYLOAD_TOO_LARGE.into_response()
+            }
+            BlobError::Store(e) => {
+                tracing::error!(error = %e, "local blob store request failed");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
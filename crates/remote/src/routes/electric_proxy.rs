@@ -14,12 +14,24 @@ use tracing::error;
 use uuid::Uuid;
 
 use crate::{
-    AppState, auth::RequestContext, db::organizations::OrganizationRepository, validated_where,
+    AppState,
+    auth::RequestContext,
+    db::organization_members::{self, VisibleScopes},
+    validated_where,
     validated_where::ValidatedWhere,
 };
 
 pub fn router() -> Router<AppState> {
-    Router::new().route("/shape/shared_tasks", get(proxy_shared_tasks))
+    Router::new()
+        .route("/shape/shared_tasks", get(proxy_shared_tasks))
+        .route(
+            "/shape/shared_task_comments",
+            get(proxy_shared_task_comments),
+        )
+        .route(
+            "/shape/shared_task_activity",
+            get(proxy_shared_task_activity),
+        )
 }
 
 /// Electric protocol query parameters that are safe to forward.
@@ -48,31 +60,103 @@ pub async fn proxy_shared_tasks(
     axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
     Query(params): Query<HashMap<String, String>>,
 ) -> Result<Response, ProxyError> {
-    // Get user's organization memberships
-    let org_repo = OrganizationRepository::new(state.pool());
-    let orgs = org_repo
-        .list_user_organizations(ctx.user.id)
-        .await
-        .map_err(|e| ProxyError::Authorization(format!("failed to fetch organizations: {e}")))?;
-
-    if orgs.is_empty() {
+    let Some(scopes) = user_visible_scopes(&state, &ctx).await? else {
         // User has no org memberships - return empty result
         return Ok(empty_shape_response());
+    };
+
+    // Build org/project filter using compile-time validated WHERE clause
+    let query = validated_where!(
+        "shared_tasks",
+        r#""organization_id" = ANY($1) OR "project_id" = ANY($2)"#,
+        &scopes.organization_ids,
+        &scopes.project_ids
+    );
+    let query_params = &scope_params(&scopes);
+    tracing::debug!("Proxying Electric Shape request for shared_tasks table{query:?}");
+    proxy_table(&state, &query, &params, query_params).await
+}
+
+/// Proxy Shape requests for the `shared_task_comments` table.
+///
+/// Route: GET /v1/shape/shared_task_comments?offset=-1
+pub async fn proxy_shared_task_comments(
+    State(state): State<AppState>,
+    axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, ProxyError> {
+    let Some(scopes) = user_visible_scopes(&state, &ctx).await? else {
+        return Ok(empty_shape_response());
+    };
+
+    let query = validated_where!(
+        "shared_task_comments",
+        r#""organization_id" = ANY($1) OR "project_id" = ANY($2)"#,
+        &scopes.organization_ids,
+        &scopes.project_ids
+    );
+    let query_params = &scope_params(&scopes);
+    tracing::debug!("Proxying Electric Shape request for shared_task_comments table{query:?}");
+    proxy_table(&state, &query, &params, query_params).await
+}
+
+/// Proxy Shape requests for the `shared_task_activity` table.
+///
+/// Route: GET /v1/shape/shared_task_activity?offset=-1
+pub async fn proxy_shared_task_activity(
+    State(state): State<AppState>,
+    axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, ProxyError> {
+    let Some(scopes) = user_visible_scopes(&state, &ctx).await? else {
+        return Ok(empty_shape_response());
+    };
+
+    let query = validated_where!(
+        "shared_task_activity",
+        r#""organization_id" = ANY($1) OR "project_id" = ANY($2)"#,
+        &scopes.organization_ids,
+        &scopes.project_ids
+    );
+    let query_params = &scope_params(&scopes);
+    tracing::debug!("Proxying Electric Shape request for shared_task_activity table{query:?}");
+    proxy_table(&state, &query, &params, query_params).await
+}
+
+/// Returns the organizations and projects the user can see, or `None` when
+/// there are none.
+async fn user_visible_scopes(
+    state: &AppState,
+    ctx: &RequestContext,
+) -> Result<Option<VisibleScopes>, ProxyError> {
+    let scopes = organization_members::visible_scopes(state.pool(), ctx.user.id)
+        .await
+        .map_err(|e| ProxyError::Authorization(format!("failed to fetch memberships: {e}")))?;
+
+    if scopes.is_empty() {
+        return Ok(None);
     }
 
-    // Build org_id filter using compile-time validated WHERE clause
-    let org_uuids: Vec<Uuid> = orgs.iter().map(|o| o.id).collect();
-    let query = validated_where!("shared_tasks", r#""organization_id" = ANY($1)"#, &org_uuids);
-    let query_params = &[format!(
+    Ok(Some(scopes))
+}
+
+/// Electric params for the `$1` (organizations) and `$2` (projects) filters.
+fn scope_params(scopes: &VisibleScopes) -> [String; 2] {
+    [
+        uuid_array_param(&scopes.organization_ids),
+        uuid_array_param(&scopes.project_ids),
+    ]
+}
+
+/// Formats UUIDs as a Postgres array literal for an Electric `params[n]` value.
+fn uuid_array_param(ids: &[Uuid]) -> String {
+    format!(
         "{{{}}}",
-        org_uuids
-            .iter()
+        ids.iter()
             .map(|u| u.to_string())
             .collect::<Vec<_>>()
             .join(",")
-    )];
-    tracing::debug!("Proxying Electric Shape request for shared_tasks table{query:?}");
-    proxy_table(&state, &query, &params, query_params).await
+    )
 }
 
 /// Proxy a Shape request to Electric for a specific table.
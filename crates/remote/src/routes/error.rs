@@ -5,7 +5,10 @@ use axum::{
 };
 use serde_json::json;
 
-use crate::db::{identity_errors::IdentityError, projects::ProjectError, tasks::SharedTaskError};
+use crate::db::{
+    identity_errors::IdentityError, projects::ProjectError, task_comments::SharedTaskCommentError,
+    tasks::SharedTaskError,
+};
 
 #[derive(Debug)]
 pub struct ErrorResponse {
@@ -77,6 +80,36 @@ pub(crate) fn task_error_response(error: SharedTaskError, context: &str) -> Resp
     response.into_response()
 }
 
+pub(crate) fn comment_error_response(error: SharedTaskCommentError, context: &str) -> Response {
+    let response = match error {
+        SharedTaskCommentError::NotFound => (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "comment not found" })),
+        ),
+        SharedTaskCommentError::Forbidden => (
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "only the author can modify this comment" })),
+        ),
+        SharedTaskCommentError::Empty => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "comment body cannot be empty" })),
+        ),
+        SharedTaskCommentError::PayloadTooLarge => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "comment body cannot exceed 50 KiB" })),
+        ),
+        SharedTaskCommentError::Database(err) => {
+            tracing::error!(?err, "{context}", context = context);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "internal server error" })),
+            )
+        }
+    };
+
+    response.into_response()
+}
+
 pub(crate) fn identity_error_response(error: IdentityError, message: &str) -> Response {
     match error {
         IdentityError::NotFound => (StatusCode::BAD_REQUEST, Json(json!({ "error": message }))),
@@ -84,7 +117,7 @@ pub(crate) fn identity_error_response(error: IdentityError, message: &str) -> Re
             StatusCode::FORBIDDEN,
             Json(json!({ "error": "permission denied" })),
         ),
-        IdentityError::InvitationError(msg) => {
+        IdentityError::InvitationError(msg) | IdentityError::InvalidProjectAccess(msg) => {
             (StatusCode::BAD_REQUEST, Json(json!({ "error": msg })))
         }
         IdentityError::CannotDeleteOrganization(msg) => {
@@ -12,7 +12,10 @@ use serde::{Deserialize, Serialize};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use super::error::ErrorResponse;
+use super::{
+    error::ErrorResponse,
+    organization_members::{ensure_member_access, ensure_org_write_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
@@ -20,7 +23,9 @@ use crate::{
         github_app::GitHubAppRepository2, identity_errors::IdentityError,
         organizations::OrganizationRepository, reviews::ReviewRepository,
     },
-    github_app::{PrReviewParams, PrReviewService, verify_webhook_signature},
+    github_app::{
+        GitHubIssue, IssueSyncService, PrReviewParams, PrReviewService, verify_webhook_signature,
+    },
 };
 
 // ========== Public Routes ==========
@@ -341,23 +346,19 @@ pub async fn update_repo_review_enabled(
 }
 
 /// GET /v1/organizations/:org_id/github-app/repositories
-/// Fetches repositories from GitHub API, syncs to DB, and returns the list
+/// Fetches repositories from GitHub API, syncs to DB, and returns the list.
+/// Members without org write access get the cached list.
 pub async fn fetch_repositories(
     State(state): State<AppState>,
     axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
     Path(org_id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    // Check user is member of organization
-    let org_repo = OrganizationRepository::new(state.pool());
-    org_repo
-        .assert_membership(org_id, ctx.user.id)
+    // Any member may read the cached list; refreshing it from GitHub writes
+    // to the installation, so it needs org write access
+    ensure_member_access(state.pool(), org_id, ctx.user.id).await?;
+    let can_sync = ensure_org_write_access(state.pool(), org_id, ctx.user.id)
         .await
-        .map_err(|e| match e {
-            IdentityError::PermissionDenied | IdentityError::NotFound => {
-                ErrorResponse::new(StatusCode::FORBIDDEN, "Access denied")
-            }
-            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
-        })?;
+        .is_ok();
 
     let gh_repo = GitHubAppRepository2::new(state.pool());
 
@@ -372,20 +373,22 @@ pub async fn fetch_repositories(
         ErrorResponse::new(StatusCode::NOT_IMPLEMENTED, "GitHub App not configured")
     })?;
 
-    match github_app
-        .list_installation_repos(installation.github_installation_id)
-        .await
-    {
-        Ok(repos) => {
-            let repo_data: Vec<(i64, String)> =
-                repos.into_iter().map(|r| (r.id, r.full_name)).collect();
-            if let Err(e) = gh_repo.sync_repositories(installation.id, &repo_data).await {
-                warn!(?e, "Failed to sync repositories from GitHub API");
+    if can_sync {
+        match github_app
+            .list_installation_repos(installation.github_installation_id)
+            .await
+        {
+            Ok(repos) => {
+                let repo_data: Vec<(i64, String)> =
+                    repos.into_iter().map(|r| (r.id, r.full_name)).collect();
+                if let Err(e) = gh_repo.sync_repositories(installation.id, &repo_data).await {
+                    warn!(?e, "Failed to sync repositories from GitHub API");
+                }
+            }
+            Err(e) => {
+                warn!(?e, "Failed to fetch repositories from GitHub API");
+                // Continue with cached data
             }
-        }
-        Err(e) => {
-            warn!(?e, "Failed to fetch repositories from GitHub API");
-            // Continue with cached data
         }
     }
 
@@ -655,6 +658,7 @@ pub async fn handle_webhook(
         "installation_repositories" => handle_installation_repos_event(&state, &payload).await,
         "pull_request" => handle_pull_request_event(&state, github_app, &payload).await,
         "issue_comment" => handle_issue_comment_event(&state, github_app, &payload).await,
+        "issues" => handle_issues_event(&state, github_app, &payload).await,
         _ => {
             info!(event_type, "Ignoring unhandled webhook event");
             StatusCode::OK.into_response()
@@ -859,11 +863,11 @@ async fn try_trigger_pr_review(
         }
     }
 
-    // Check if R2 and review worker are configured
-    let r2 = state.r2().ok_or("R2 not configured")?;
-    let worker_base_url = state
+    // Check if blob storage and review worker are configured
+    let blob_store = state.blob_store().ok_or("Blob storage not configured")?;
+    let worker = state
         .config
-        .review_worker_base_url
+        .review_worker
         .as_ref()
         .ok_or("Review worker not configured")?;
 
@@ -891,9 +895,8 @@ async fn try_trigger_pr_review(
 
     // Spawn async task to process PR review
     let github_app_clone = github_app.clone();
-    let r2_clone = r2.clone();
     let http_client = state.http_client.clone();
-    let worker_url = worker_base_url.clone();
+    let worker = worker.clone();
     let server_url = state.server_public_base_url.clone();
     let pool = state.pool.clone();
     let installation_id = ctx.installation_id;
@@ -904,9 +907,9 @@ async fn try_trigger_pr_review(
     tokio::spawn(async move {
         let service = PrReviewService::new(
             github_app_clone,
-            r2_clone,
+            blob_store,
             http_client,
-            worker_url,
+            worker,
             server_url,
         );
 
@@ -1043,6 +1046,56 @@ async fn handle_issue_comment_event(
     StatusCode::OK.into_response()
 }
 
+async fn handle_issues_event(
+    state: &AppState,
+    github_app: &crate::github_app::GitHubAppService,
+    payload: &serde_json::Value,
+) -> Response {
+    let action = payload["action"].as_str().unwrap_or("");
+
+    if !matches!(
+        action,
+        "opened" | "edited" | "closed" | "reopened" | "assigned" | "unassigned"
+    ) {
+        return StatusCode::OK.into_response();
+    }
+
+    let issue: GitHubIssue = match serde_json::from_value(payload["issue"].clone()) {
+        Ok(issue) => issue,
+        Err(e) => {
+            warn!(?e, "Failed to parse issue from webhook payload");
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+    };
+
+    let installation_id = payload["installation"]["id"].as_i64().unwrap_or(0);
+    let github_repo_id = payload["repository"]["id"].as_i64().unwrap_or(0);
+    let sender_login = payload["sender"]["login"].as_str().unwrap_or("");
+
+    let sync = IssueSyncService::new(state.pool(), github_app);
+    if let Err(e) = sync
+        .handle_issue_event(
+            installation_id,
+            github_repo_id,
+            action,
+            sender_login,
+            &issue,
+        )
+        .await
+    {
+        error!(
+            ?e,
+            installation_id,
+            github_repo_id,
+            issue_number = issue.number,
+            action,
+            "Failed to sync GitHub issue"
+        );
+    }
+
+    StatusCode::OK.into_response()
+}
+
 // ========== Debug Endpoint ==========
 
 /// Parse a GitHub PR URL into (owner, repo, pr_number)
@@ -1084,19 +1137,18 @@ pub async fn trigger_pr_review(
     let github_app = state.github_app().ok_or_else(|| {
         ErrorResponse::new(StatusCode::SERVICE_UNAVAILABLE, "GitHub App not configured")
     })?;
-    let r2 = state
-        .r2()
-        .ok_or_else(|| ErrorResponse::new(StatusCode::SERVICE_UNAVAILABLE, "R2 not configured"))?;
-    let worker_base_url = state
-        .config
-        .review_worker_base_url
-        .as_ref()
-        .ok_or_else(|| {
-            ErrorResponse::new(
-                StatusCode::SERVICE_UNAVAILABLE,
-                "Review worker not configured",
-            )
-        })?;
+    let blob_store = state.blob_store().ok_or_else(|| {
+        ErrorResponse::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Blob storage not configured",
+        )
+    })?;
+    let worker = state.config.review_worker.as_ref().ok_or_else(|| {
+        ErrorResponse::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Review worker not configured",
+        )
+    })?;
 
     // 3. Look up installation by owner
     let gh_repo = GitHubAppRepository2::new(state.pool());
@@ -1125,9 +1177,9 @@ pub async fn trigger_pr_review(
     // 5. Create service and process review
     let service = PrReviewService::new(
         github_app.clone(),
-        r2.clone(),
+        blob_store,
         state.http_client.clone(),
-        worker_base_url.clone(),
+        worker.clone(),
         state.server_public_base_url.clone(),
     );
 
@@ -0,0 +1,339 @@
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use serde::{Deserialize, Serialize};
+use tracing::{Span, error, instrument};
+use uuid::Uuid;
+
+use super::{
+    error::{ErrorResponse, identity_error_response, task_error_response},
+    organization_members::{ensure_project_access, ensure_task_access},
+    tasks::SharedTaskResponse,
+};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{
+        github_app::GitHubAppRepository2,
+        github_issues::{
+            GitHubIssueDbError, GitHubIssueRepository, GitHubIssueSyncConfig, SharedTaskGitHubIssue,
+        },
+        organization_members::AccessLevel,
+        organizations::OrganizationRepository,
+        tasks::SharedTaskRepository,
+    },
+    github_app::{GitHubAppService, IssueSyncError, IssueSyncService},
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/projects/{project_id}/github-issue-sync",
+            get(get_sync_config)
+                .put(update_sync_config)
+                .delete(delete_sync_config),
+        )
+        .route(
+            "/projects/{project_id}/github-issue-sync/import",
+            post(import_issue),
+        )
+        .route(
+            "/tasks/{task_id}/github-issue",
+            get(get_task_issue).post(create_task_issue),
+        )
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssueSyncConfigResponse {
+    pub config: Option<GitHubIssueSyncConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateIssueSyncConfigRequest {
+    /// Id of a repository of the organization's GitHub App installation
+    pub repository_id: Uuid,
+    #[serde(default = "default_true")]
+    pub import_new_issues: bool,
+    #[serde(default)]
+    pub export_new_tasks: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportIssueRequest {
+    pub issue_number: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskIssueResponse {
+    pub issue: Option<SharedTaskGitHubIssue>,
+}
+
+fn github_app(state: &AppState) -> Result<&GitHubAppService, ErrorResponse> {
+    state
+        .github_app()
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_IMPLEMENTED, "GitHub App not configured"))
+}
+
+/// Resolves the project's organization and requires the user to administer it.
+async fn ensure_project_admin(
+    state: &AppState,
+    user_id: Uuid,
+    project_id: Uuid,
+) -> Result<Uuid, Response> {
+    let organization_id =
+        ensure_project_access(state.pool(), user_id, project_id, AccessLevel::Read)
+            .await
+            .map_err(IntoResponse::into_response)?;
+    Span::current().record("org_id", format_args!("{organization_id}"));
+
+    OrganizationRepository::new(state.pool())
+        .assert_admin(organization_id, user_id)
+        .await
+        .map_err(|err| identity_error_response(err, "failed to authorize issue sync"))?;
+
+    Ok(organization_id)
+}
+
+fn issue_db_error(error: GitHubIssueDbError, context: &str) -> ErrorResponse {
+    match error {
+        GitHubIssueDbError::RepositoryInUse => ErrorResponse::new(
+            StatusCode::CONFLICT,
+            "repository already syncs with another project",
+        ),
+        GitHubIssueDbError::AlreadyLinked => {
+            ErrorResponse::new(StatusCode::CONFLICT, "already linked to a GitHub issue")
+        }
+        GitHubIssueDbError::Database(err) => {
+            error!(?err, "{context}", context = context);
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        }
+    }
+}
+
+fn issue_sync_error(error: IssueSyncError, context: &str) -> Response {
+    match error {
+        IssueSyncError::Database(err) => issue_db_error(err, context).into_response(),
+        IssueSyncError::Task(err) => task_error_response(err, context),
+        IssueSyncError::Identity(err) => identity_error_response(err, context),
+        IssueSyncError::NotConfigured => ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "issue sync is not enabled for this project",
+        )
+        .into_response(),
+        IssueSyncError::PullRequest => ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "pull requests can't be imported as tasks",
+        )
+        .into_response(),
+        IssueSyncError::GitHub(err) => {
+            error!(?err, "{context}", context = context);
+            ErrorResponse::new(StatusCode::BAD_GATEWAY, "GitHub request failed").into_response()
+        }
+        IssueSyncError::InvalidRepository(name) => {
+            error!(repo_full_name = %name, "{context}", context = context);
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+                .into_response()
+        }
+    }
+}
+
+#[instrument(
+    name = "github_issues.get_sync_config",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id, project_id = %project_id, org_id = tracing::field::Empty)
+)]
+async fn get_sync_config(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<IssueSyncConfigResponse>, Response> {
+    let organization_id =
+        ensure_project_access(state.pool(), ctx.user.id, project_id, AccessLevel::Read)
+            .await
+            .map_err(IntoResponse::into_response)?;
+    Span::current().record("org_id", format_args!("{organization_id}"));
+
+    let config = GitHubIssueRepository::new(state.pool())
+        .get_config(project_id)
+        .await
+        .map_err(|e| issue_db_error(e, "failed to load issue sync config").into_response())?;
+
+    Ok(Json(IssueSyncConfigResponse { config }))
+}
+
+#[instrument(
+    name = "github_issues.update_sync_config",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id, project_id = %project_id, org_id = tracing::field::Empty)
+)]
+async fn update_sync_config(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<UpdateIssueSyncConfigRequest>,
+) -> Result<Json<IssueSyncConfigResponse>, Response> {
+    github_app(&state).map_err(IntoResponse::into_response)?;
+    let organization_id = ensure_project_admin(&state, ctx.user.id, project_id).await?;
+
+    // The repository must belong to the organization's installation
+    let gh_repo = GitHubAppRepository2::new(state.pool());
+    let installation = gh_repo
+        .get_by_organization(organization_id)
+        .await
+        .map_err(|e| {
+            error!(?e, "Failed to get GitHub App installation");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        })?
+        .ok_or_else(|| {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "GitHub App not installed").into_response()
+        })?;
+    let repositories = gh_repo
+        .get_repositories(installation.id)
+        .await
+        .map_err(|e| {
+            error!(?e, "Failed to get repositories");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        })?;
+    if !repositories
+        .iter()
+        .any(|repo| repo.id == payload.repository_id)
+    {
+        return Err(
+            ErrorResponse::new(StatusCode::NOT_FOUND, "repository not found").into_response(),
+        );
+    }
+
+    let config = GitHubIssueRepository::new(state.pool())
+        .upsert_config(
+            project_id,
+            organization_id,
+            payload.repository_id,
+            payload.import_new_issues,
+            payload.export_new_tasks,
+            ctx.user.id,
+        )
+        .await
+        .map_err(|e| issue_db_error(e, "failed to save issue sync config").into_response())?;
+
+    Ok(Json(IssueSyncConfigResponse {
+        config: Some(config),
+    }))
+}
+
+#[instrument(
+    name = "github_issues.delete_sync_config",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id, project_id = %project_id, org_id = tracing::field::Empty)
+)]
+async fn delete_sync_config(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<StatusCode, Response> {
+    ensure_project_admin(&state, ctx.user.id, project_id).await?;
+
+    let deleted = GitHubIssueRepository::new(state.pool())
+        .delete_config(project_id)
+        .await
+        .map_err(|e| issue_db_error(e, "failed to delete issue sync config").into_response())?;
+
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ErrorResponse::new(StatusCode::NOT_FOUND, "issue sync is not enabled").into_response())
+    }
+}
+
+#[instrument(
+    name = "github_issues.import_issue",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id, project_id = %project_id, org_id = tracing::field::Empty)
+)]
+async fn import_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<ImportIssueRequest>,
+) -> Result<Response, Response> {
+    let github_app = github_app(&state).map_err(IntoResponse::into_response)?;
+    let organization_id =
+        ensure_project_access(state.pool(), ctx.user.id, project_id, AccessLevel::Write)
+            .await
+            .map_err(IntoResponse::into_response)?;
+    Span::current().record("org_id", format_args!("{organization_id}"));
+
+    let task = IssueSyncService::new(state.pool(), github_app)
+        .import_issue_by_number(project_id, payload.issue_number)
+        .await
+        .map_err(|e| issue_sync_error(e, "failed to import GitHub issue"))?;
+
+    Ok((StatusCode::CREATED, Json(SharedTaskResponse::from(task))).into_response())
+}
+
+#[instrument(
+    name = "github_issues.get_task_issue",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id, task_id = %task_id, org_id = tracing::field::Empty)
+)]
+async fn get_task_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<TaskIssueResponse>, Response> {
+    let organization_id = ensure_task_access(state.pool(), ctx.user.id, task_id, AccessLevel::Read)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    Span::current().record("org_id", format_args!("{organization_id}"));
+
+    let issue = GitHubIssueRepository::new(state.pool())
+        .find_link_by_task(task_id)
+        .await
+        .map_err(|e| issue_db_error(e, "failed to load linked issue").into_response())?;
+
+    Ok(Json(TaskIssueResponse { issue }))
+}
+
+#[instrument(
+    name = "github_issues.create_task_issue",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id, task_id = %task_id, org_id = tracing::field::Empty)
+)]
+async fn create_task_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(task_id): Path<Uuid>,
+) -> Result<Response, Response> {
+    let github_app = github_app(&state).map_err(IntoResponse::into_response)?;
+    let organization_id =
+        ensure_task_access(state.pool(), ctx.user.id, task_id, AccessLevel::Write)
+            .await
+            .map_err(IntoResponse::into_response)?;
+    Span::current().record("org_id", format_args!("{organization_id}"));
+
+    let task = SharedTaskRepository::new(state.pool())
+        .find_by_id(task_id)
+        .await
+        .map_err(|e| task_error_response(e, "failed to load shared task"))?
+        .ok_or_else(|| {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "shared task not found").into_response()
+        })?;
+
+    let issue = IssueSyncService::new(state.pool(), github_app)
+        .export_task(&task)
+        .await
+        .map_err(|e| issue_sync_error(e, "failed to create GitHub issue"))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(TaskIssueResponse { issue: Some(issue) }),
+    )
+        .into_response())
+}
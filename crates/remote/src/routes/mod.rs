@@ -14,15 +14,18 @@ use tracing::{Level, field};
 
 use crate::{AppState, auth::require_session};
 
+mod blobs;
 mod electric_proxy;
 mod error;
 mod github_app;
+mod github_issues;
 mod identity;
 mod oauth;
 pub(crate) mod organization_members;
 mod organizations;
 mod projects;
 mod review;
+pub mod task_comments;
 pub mod tasks;
 mod tokens;
 
@@ -53,17 +56,20 @@ pub fn router(state: AppState) -> Router {
         .merge(organization_members::public_router())
         .merge(tokens::public_router())
         .merge(review::public_router())
+        .merge(blobs::public_router())
         .merge(github_app::public_router());
 
     let v1_protected = Router::<AppState>::new()
         .merge(identity::router())
         .merge(projects::router())
         .merge(tasks::router())
+        .merge(task_comments::router())
         .merge(organizations::router())
         .merge(organization_members::protected_router())
         .merge(oauth::protected_router())
         .merge(electric_proxy::router())
         .merge(github_app::protected_router())
+        .merge(github_issues::router())
         .layer(middleware::from_fn_with_state(
             state.clone(),
             require_session,
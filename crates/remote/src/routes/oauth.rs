@@ -7,7 +7,7 @@ use axum::{
     response::{IntoResponse, Redirect, Response},
     routing::{get, post},
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tracing::warn;
 use url::Url;
 use utils::api::oauth::{
@@ -24,6 +24,7 @@ use crate::{
 
 pub fn public_router() -> Router<AppState> {
     Router::new()
+        .route("/oauth/providers", get(list_providers))
         .route("/oauth/web/init", post(web_init))
         .route("/oauth/web/redeem", post(web_redeem))
         .route("/oauth/{provider}/start", get(authorize_start))
@@ -36,6 +37,27 @@ pub fn protected_router() -> Router<AppState> {
         .route("/oauth/logout", post(logout))
 }
 
+#[derive(Debug, Serialize)]
+pub struct OAuthProviderInfo {
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListOAuthProvidersResponse {
+    pub providers: Vec<OAuthProviderInfo>,
+}
+
+pub async fn list_providers(State(state): State<AppState>) -> Json<ListOAuthProvidersResponse> {
+    let providers = state
+        .providers()
+        .list()
+        .into_iter()
+        .map(|(name, display_name)| OAuthProviderInfo { name, display_name })
+        .collect();
+    Json(ListOAuthProvidersResponse { providers })
+}
+
 pub async fn web_init(
     State(state): State<AppState>,
     Json(payload): Json<HandoffInitRequest>,
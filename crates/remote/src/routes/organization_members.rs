@@ -10,21 +10,21 @@ use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use tracing::warn;
 use utils::api::organizations::{
-    ListMembersResponse, OrganizationMemberWithProfile, RevokeInvitationRequest,
-    UpdateMemberRoleRequest, UpdateMemberRoleResponse,
+    ListMembersResponse, MemberProjectAccess, OrganizationMemberWithProfile,
+    RevokeInvitationRequest, UpdateMemberProjectAccessRequest, UpdateMemberRoleRequest,
+    UpdateMemberRoleResponse,
 };
 use uuid::Uuid;
 
-use super::error::{ErrorResponse, membership_error};
+use super::error::{ErrorResponse, identity_error_response, membership_error};
 use crate::{
     AppState,
     auth::RequestContext,
     db::{
         identity_errors::IdentityError,
         invitations::{Invitation, InvitationRepository},
-        organization_members::{self, MemberRole},
+        organization_members::{self, AccessLevel, MemberRole},
         organizations::OrganizationRepository,
-        projects::ProjectRepository,
         tasks::SharedTaskRepository,
     },
 };
@@ -54,6 +54,10 @@ pub fn protected_router() -> Router<AppState> {
             "/organizations/{org_id}/members/{user_id}/role",
             patch(update_member_role),
         )
+        .route(
+            "/organizations/{org_id}/members/{user_id}/projects",
+            get(get_member_project_access).patch(update_member_project_access),
+        )
 }
 
 #[derive(Debug, Deserialize)]
@@ -394,7 +398,7 @@ pub async fn update_member_role(
     Json(payload): Json<UpdateMemberRoleRequest>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let user = ctx.user;
-    if user.id == user_id && payload.role == MemberRole::Member {
+    if user.id == user_id && payload.role != MemberRole::Admin {
         return Err(ErrorResponse::new(
             StatusCode::BAD_REQUEST,
             "Cannot demote yourself",
@@ -443,7 +447,7 @@ pub async fn update_member_role(
         }));
     }
 
-    if target.role == MemberRole::Admin && payload.role == MemberRole::Member {
+    if target.role == MemberRole::Admin && payload.role != MemberRole::Admin {
         let admin_ids = sqlx::query_scalar!(
             r#"
             SELECT user_id
@@ -468,7 +472,9 @@ pub async fn update_member_role(
     sqlx::query!(
         r#"
         UPDATE organization_member_metadata
-        SET role = $3
+        SET role = $3,
+            -- Admins always see every project
+            project_scoped = project_scoped AND $3 <> 'admin'::member_role
         WHERE organization_id = $1 AND user_id = $2
         "#,
         org_id,
@@ -489,6 +495,87 @@ pub async fn update_member_role(
     }))
 }
 
+pub async fn get_member_project_access(
+    State(state): State<AppState>,
+    axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if ctx.user.id != user_id {
+        ensure_admin_access(&state.pool, org_id, ctx.user.id).await?;
+    }
+
+    let project_ids = organization_members::project_restriction(&state.pool, org_id, user_id)
+        .await
+        .map_err(|e| match e {
+            IdentityError::NotFound => {
+                ErrorResponse::new(StatusCode::NOT_FOUND, "Member not found")
+            }
+            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
+        })?;
+
+    Ok(Json(MemberProjectAccess {
+        user_id,
+        project_ids,
+    }))
+}
+
+pub async fn update_member_project_access(
+    State(state): State<AppState>,
+    axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateMemberProjectAccessRequest>,
+) -> Result<axum::response::Response, ErrorResponse> {
+    ensure_admin_access(&state.pool, org_id, ctx.user.id).await?;
+
+    match organization_members::set_project_restriction(
+        &state.pool,
+        org_id,
+        user_id,
+        payload.project_ids.as_deref(),
+    )
+    .await
+    {
+        Ok(()) => {}
+        Err(IdentityError::NotFound) => {
+            return Err(ErrorResponse::new(
+                StatusCode::NOT_FOUND,
+                "Member not found",
+            ));
+        }
+        Err(error) => return Ok(identity_error_response(error, "invalid project access")),
+    }
+
+    let project_ids = organization_members::project_restriction(&state.pool, org_id, user_id)
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+
+    Ok(Json(MemberProjectAccess {
+        user_id,
+        project_ids,
+    })
+    .into_response())
+}
+
+pub(crate) async fn ensure_org_write_access(
+    pool: &PgPool,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ErrorResponse> {
+    let access = organization_members::member_access(pool, organization_id, user_id)
+        .await
+        .map_err(|err| membership_error(err, "Not a member of organization"))?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::FORBIDDEN, "Not a member of organization"))?;
+
+    if !access.allows_org_write() {
+        return Err(ErrorResponse::new(
+            StatusCode::FORBIDDEN,
+            "write access required",
+        ));
+    }
+
+    Ok(())
+}
+
 pub(crate) async fn ensure_member_access(
     pool: &PgPool,
     organization_id: Uuid,
@@ -514,11 +601,12 @@ pub(crate) async fn ensure_project_access(
     pool: &PgPool,
     user_id: Uuid,
     project_id: Uuid,
+    level: AccessLevel,
 ) -> Result<Uuid, ErrorResponse> {
-    let organization_id = ProjectRepository::organization_id(pool, project_id)
+    let access = organization_members::project_access(pool, project_id, user_id)
         .await
         .map_err(|error| {
-            tracing::error!(?error, %project_id, "failed to load project");
+            tracing::error!(?error, %project_id, "failed to load project access");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?
         .ok_or_else(|| {
@@ -530,37 +618,29 @@ pub(crate) async fn ensure_project_access(
             ErrorResponse::new(StatusCode::NOT_FOUND, "project not found")
         })?;
 
-    organization_members::assert_membership(pool, organization_id, user_id)
-        .await
-        .map_err(|err| {
-            if let IdentityError::Database(error) = &err {
-                tracing::error!(
-                    ?error,
-                    %organization_id,
-                    %project_id,
-                    "failed to authorize project membership"
-                );
-            } else {
-                warn!(
-                    ?err,
-                    %organization_id,
-                    %project_id,
-                    %user_id,
-                    "project access denied"
-                );
-            }
-            membership_error(err, "project not accessible")
-        })?;
+    access.check(level).map_err(|err| {
+        warn!(
+            ?err,
+            organization_id = %access.organization_id,
+            %project_id,
+            %user_id,
+            ?level,
+            "project access denied"
+        );
+        let message = access_denied_message(&err, "project not accessible");
+        membership_error(err, message)
+    })?;
 
-    Ok(organization_id)
+    Ok(access.organization_id)
 }
 
 pub(crate) async fn ensure_task_access(
     pool: &PgPool,
     user_id: Uuid,
     task_id: Uuid,
+    level: AccessLevel,
 ) -> Result<Uuid, ErrorResponse> {
-    let organization_id = SharedTaskRepository::organization_id(pool, task_id)
+    let project_id = SharedTaskRepository::project_id(pool, task_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, %task_id, "failed to load shared task");
@@ -575,27 +655,34 @@ pub(crate) async fn ensure_task_access(
             ErrorResponse::new(StatusCode::NOT_FOUND, "shared task not found")
         })?;
 
-    organization_members::assert_membership(pool, organization_id, user_id)
+    let access = organization_members::project_access(pool, project_id, user_id)
         .await
-        .map_err(|err| {
-            if let IdentityError::Database(error) = &err {
-                tracing::error!(
-                    ?error,
-                    %organization_id,
-                    %task_id,
-                    "failed to authorize shared task access"
-                );
-            } else {
-                warn!(
-                    ?err,
-                    %organization_id,
-                    %task_id,
-                    %user_id,
-                    "shared task access denied"
-                );
-            }
-            membership_error(err, "task not accessible")
-        })?;
+        .map_err(|error| {
+            tracing::error!(?error, %task_id, "failed to authorize shared task access");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "shared task not found"))?;
+
+    access.check(level).map_err(|err| {
+        warn!(
+            ?err,
+            organization_id = %access.organization_id,
+            %task_id,
+            %user_id,
+            ?level,
+            "shared task access denied"
+        );
+        let message = access_denied_message(&err, "task not accessible");
+        membership_error(err, message)
+    })?;
+
+    Ok(access.organization_id)
+}
 
-    Ok(organization_id)
+/// Members lacking the role get a clearer message than outsiders.
+fn access_denied_message(err: &IdentityError, default: &'static str) -> &'static str {
+    match err {
+        IdentityError::PermissionDenied => "write access required",
+        _ => default,
+    }
 }
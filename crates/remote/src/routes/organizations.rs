@@ -125,6 +125,7 @@ pub async fn get_organization(
     let user_role = match role {
         MemberRole::Admin => "ADMIN",
         MemberRole::Member => "MEMBER",
+        MemberRole::Viewer => "VIEWER",
     }
     .to_string();
 
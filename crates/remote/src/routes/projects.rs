@@ -10,11 +10,17 @@ use tracing::instrument;
 use utils::api::projects::{ListProjectsResponse, RemoteProject};
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_member_access};
+use super::{
+    error::ErrorResponse,
+    organization_members::{ensure_member_access, ensure_org_write_access, ensure_project_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::projects::{CreateProjectData, Project, ProjectError, ProjectRepository},
+    db::{
+        organization_members::AccessLevel,
+        projects::{CreateProjectData, Project, ProjectError, ProjectRepository},
+    },
 };
 
 #[derive(Debug, Deserialize)]
@@ -49,7 +55,13 @@ async fn list_projects(
     let target_org = params.organization_id;
     ensure_member_access(state.pool(), target_org, ctx.user.id).await?;
 
-    let projects = match ProjectRepository::list_by_organization(state.pool(), target_org).await {
+    let projects = match ProjectRepository::list_visible_to_user(
+        state.pool(),
+        target_org,
+        ctx.user.id,
+    )
+    .await
+    {
         Ok(rows) => rows.into_iter().map(to_remote_project).collect(),
         Err(error) => {
             tracing::error!(?error, org_id = %target_org, "failed to list remote projects");
@@ -73,6 +85,8 @@ async fn get_project(
     Extension(ctx): Extension<RequestContext>,
     Path(project_id): Path<Uuid>,
 ) -> Result<Json<RemoteProject>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, project_id, AccessLevel::Read).await?;
+
     let record = ProjectRepository::fetch_by_id(state.pool(), project_id)
         .await
         .map_err(|error| {
@@ -81,8 +95,6 @@ async fn get_project(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
 
-    ensure_member_access(state.pool(), record.organization_id, ctx.user.id).await?;
-
     Ok(Json(to_remote_project(record)))
 }
 
@@ -102,7 +114,7 @@ async fn create_project(
         metadata,
     } = payload;
 
-    ensure_member_access(state.pool(), organization_id, ctx.user.id).await?;
+    ensure_org_write_access(state.pool(), organization_id, ctx.user.id).await?;
 
     let mut tx = state.pool().begin().await.map_err(|error| {
         tracing::error!(?error, "failed to start transaction for project creation");
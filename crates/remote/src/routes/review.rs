@@ -9,13 +9,14 @@ use axum::{
     routing::{get, post},
 };
 use chrono::{DateTime, Duration, Utc};
+use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
     AppState,
+    blob_store::{BlobStoreError, payload_object_key},
     db::reviews::{CreateReviewParams, ReviewRepository},
-    r2::R2Error,
 };
 
 pub fn public_router() -> Router<AppState> {
@@ -58,10 +59,10 @@ pub struct ReviewMetadataResponse {
 
 #[derive(Debug, thiserror::Error)]
 pub enum ReviewError {
-    #[error("R2 storage not configured")]
+    #[error("blob storage not configured")]
     NotConfigured,
     #[error("failed to generate upload URL: {0}")]
-    R2Error(#[from] R2Error),
+    Storage(#[from] BlobStoreError),
     #[error("rate limit exceeded")]
     RateLimited,
     #[error("unable to determine client IP")]
@@ -74,6 +75,10 @@ pub enum ReviewError {
     WorkerError(#[from] reqwest::Error),
     #[error("invalid review ID")]
     InvalidReviewId,
+    #[error("codebase URL must reference the review's upload")]
+    InvalidCodebaseUrl,
+    #[error("unsupported review URL")]
+    InvalidReviewUrl,
 }
 
 impl IntoResponse for ReviewError {
@@ -83,8 +88,8 @@ impl IntoResponse for ReviewError {
                 StatusCode::SERVICE_UNAVAILABLE,
                 "Review upload service not available",
             ),
-            ReviewError::R2Error(e) => {
-                tracing::error!(error = %e, "R2 presign failed");
+            ReviewError::Storage(e) => {
+                tracing::error!(error = %e, "Blob store presign failed");
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "Failed to generate upload URL",
@@ -116,6 +121,11 @@ impl IntoResponse for ReviewError {
                 )
             }
             ReviewError::InvalidReviewId => (StatusCode::BAD_REQUEST, "Invalid review ID"),
+            ReviewError::InvalidCodebaseUrl => (
+                StatusCode::BAD_REQUEST,
+                "codebaseUrl must reference the review's upload",
+            ),
+            ReviewError::InvalidReviewUrl => (StatusCode::BAD_REQUEST, "Unsupported review URL"),
         };
 
         let body = serde_json::json!({
@@ -126,13 +136,25 @@ impl IntoResponse for ReviewError {
     }
 }
 
-/// Ensures the GitHub URL has the https:// protocol prefix
-fn normalize_github_url(url: &str) -> String {
+/// Ensures the review URL has a protocol prefix, defaulting to https://.
+/// Besides web URLs, only `file://{repo}#{base}..{head}` identifiers for
+/// reviews of local commit ranges are accepted.
+fn normalize_github_url(url: &str) -> Result<String, ReviewError> {
     let url = url.trim();
-    if url.starts_with("https://") || url.starts_with("http://") {
-        url.to_string()
-    } else {
-        format!("https://{}", url)
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return Ok(format!("https://{}", url));
+    };
+    match scheme.to_ascii_lowercase().as_str() {
+        "http" | "https" => Ok(url.to_string()),
+        // Local ranges name the repository, never a path on the client's machine
+        "file" => {
+            let repo = rest.split('#').next().unwrap_or_default();
+            if repo.is_empty() || repo.contains(['/', '\\']) {
+                return Err(ReviewError::InvalidReviewUrl);
+            }
+            Ok(url.to_string())
+        }
+        _ => Err(ReviewError::InvalidReviewUrl),
     }
 }
 
@@ -197,7 +219,7 @@ pub async fn init_review_upload(
     headers: HeaderMap,
     Json(payload): Json<InitReviewRequest>,
 ) -> Result<Json<InitReviewResponse>, ReviewError> {
-    // 1. Generate the review ID upfront (used in both R2 path and DB record)
+    // 1. Generate the review ID upfront (used in both storage path and DB record)
     let review_id = Uuid::new_v4();
 
     // 2. Extract IP (required for rate limiting)
@@ -207,15 +229,17 @@ pub async fn init_review_upload(
     let repo = ReviewRepository::new(state.pool());
     check_rate_limit(&repo, ip).await?;
 
-    // 4. Get R2 service
-    let r2 = state.r2().ok_or(ReviewError::NotConfigured)?;
+    // 4. Get blob store
+    let blob_store = state.blob_store().ok_or(ReviewError::NotConfigured)?;
 
     // 5. Generate presigned URL with review ID in path
     let content_type = payload.content_type.as_deref();
-    let upload = r2.create_presigned_upload(review_id, content_type).await?;
+    let upload = blob_store
+        .create_presigned_upload(review_id, content_type)
+        .await?;
 
     // 6. Normalize the GitHub PR URL to ensure it has https:// prefix
-    let normalized_url = normalize_github_url(&payload.gh_pr_url);
+    let normalized_url = normalize_github_url(&payload.gh_pr_url)?;
 
     // 7. Insert DB record with the same review ID, storing folder path
     let review = repo
@@ -241,15 +265,20 @@ pub async fn init_review_upload(
 
 /// Proxy a request to the review worker and return the response.
 async fn proxy_to_worker(state: &AppState, path: &str) -> Result<Response, ReviewError> {
-    let base_url = state
+    let worker = state
         .config
-        .review_worker_base_url
+        .review_worker
         .as_ref()
         .ok_or(ReviewError::WorkerNotConfigured)?;
 
-    let url = format!("{}{}", base_url.trim_end_matches('/'), path);
+    let url = format!("{}{}", worker.base_url.trim_end_matches('/'), path);
 
-    let response = state.http_client.get(&url).send().await?;
+    let response = state
+        .http_client
+        .get(&url)
+        .bearer_auth(worker.secret.expose_secret())
+        .send()
+        .await?;
 
     let status = response.status();
     let headers = response.headers().clone();
@@ -271,15 +300,21 @@ async fn proxy_post_to_worker(
     path: &str,
     body: serde_json::Value,
 ) -> Result<Response, ReviewError> {
-    let base_url = state
+    let worker = state
         .config
-        .review_worker_base_url
+        .review_worker
         .as_ref()
         .ok_or(ReviewError::WorkerNotConfigured)?;
 
-    let url = format!("{}{}", base_url.trim_end_matches('/'), path);
+    let url = format!("{}{}", worker.base_url.trim_end_matches('/'), path);
 
-    let response = state.http_client.post(&url).json(&body).send().await?;
+    let response = state
+        .http_client
+        .post(&url)
+        .bearer_auth(worker.secret.expose_secret())
+        .json(&body)
+        .send()
+        .await?;
 
     let status = response.status();
     let headers = response.headers().clone();
@@ -295,10 +330,38 @@ async fn proxy_post_to_worker(
 }
 
 /// POST /review/start - Start review processing on worker
+///
+/// The payload must be the review's own CLI upload, referenced as
+/// `r2://{object_key}`; that's replaced with a download URL so workers don't
+/// need blob store credentials.
+/// Workers always report back to this server's `/review/{id}` callbacks.
 pub async fn start_review(
     State(state): State<AppState>,
-    Json(body): Json<serde_json::Value>,
+    Json(mut body): Json<serde_json::Value>,
 ) -> Result<Response, ReviewError> {
+    let review_id: Uuid = body["id"]
+        .as_str()
+        .and_then(|id| id.parse().ok())
+        .ok_or(ReviewError::InvalidReviewId)?;
+
+    // Verify review exists in our database
+    let repo = ReviewRepository::new(state.pool());
+    let _review = repo.get_by_id(review_id).await?;
+
+    // Only the payload uploaded for this review may be reviewed
+    let object_key = payload_object_key(review_id);
+    if body["codebaseUrl"]
+        .as_str()
+        .and_then(|url| url.strip_prefix("r2://"))
+        != Some(object_key.as_str())
+    {
+        return Err(ReviewError::InvalidCodebaseUrl);
+    }
+    let blob_store = state.blob_store().ok_or(ReviewError::NotConfigured)?;
+    body["codebaseUrl"] = blob_store.download_url(&object_key).await?.into();
+
+    body["callbackUrl"] = format!("{}/review/{}", state.server_public_base_url, review_id).into();
+
     proxy_post_to_worker(&state, "/review/start", body).await
 }
 
@@ -487,3 +550,47 @@ pub async fn review_failed(
 
     Ok(StatusCode::OK)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_adds_https_to_bare_urls() {
+        assert_eq!(
+            normalize_github_url(" github.com/owner/repo/pull/1 ").unwrap(),
+            "https://github.com/owner/repo/pull/1"
+        );
+        assert_eq!(
+            normalize_github_url("https://gitlab.com/group/repo/-/merge_requests/3").unwrap(),
+            "https://gitlab.com/group/repo/-/merge_requests/3"
+        );
+    }
+
+    #[test]
+    fn normalize_accepts_local_range_identifiers() {
+        assert_eq!(
+            normalize_github_url("file://repo#abc123..def456").unwrap(),
+            "file://repo#abc123..def456"
+        );
+    }
+
+    #[test]
+    fn normalize_rejects_other_schemes_and_local_paths() {
+        for url in [
+            "javascript://alert(1)",
+            "ftp://example.com/pr",
+            "file:///home/user/repo#abc..def",
+            "file://../repo#abc..def",
+            "file://#abc..def",
+        ] {
+            assert!(
+                matches!(
+                    normalize_github_url(url),
+                    Err(ReviewError::InvalidReviewUrl)
+                ),
+                "{url} should be rejected"
+            );
+        }
+    }
+}
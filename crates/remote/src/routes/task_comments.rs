@@ -0,0 +1,249 @@
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, patch},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{Span, instrument};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{error::comment_error_response, organization_members::ensure_task_access};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{
+        organization_members::AccessLevel,
+        task_activity::{SharedTaskActivity, SharedTaskActivityRepository},
+        task_comments::{SharedTaskComment, SharedTaskCommentError, SharedTaskCommentRepository},
+    },
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/tasks/{task_id}/comments",
+            get(list_task_comments).post(create_task_comment),
+        )
+        .route(
+            "/tasks/{task_id}/comments/{comment_id}",
+            patch(update_task_comment).delete(delete_task_comment),
+        )
+        .route("/tasks/{task_id}/activity", get(list_task_activity))
+}
+
+#[instrument(
+    name = "task_comments.list",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id, task_id = %task_id, org_id = tracing::field::Empty)
+)]
+pub async fn list_task_comments(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(task_id): Path<Uuid>,
+) -> Response {
+    let pool = state.pool();
+    match ensure_task_access(pool, ctx.user.id, task_id, AccessLevel::Read).await {
+        Ok(org_id) => {
+            Span::current().record("org_id", format_args!("{org_id}"));
+        }
+        Err(error) => return error.into_response(),
+    };
+
+    match SharedTaskCommentRepository::new(pool)
+        .list_by_task(task_id)
+        .await
+    {
+        Ok(comments) => (
+            StatusCode::OK,
+            Json(ListSharedTaskCommentsResponse { comments }),
+        )
+            .into_response(),
+        Err(error) => comment_error_response(error, "failed to list task comments"),
+    }
+}
+
+#[instrument(
+    name = "task_comments.create",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id, task_id = %task_id, org_id = tracing::field::Empty)
+)]
+pub async fn create_task_comment(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<CreateSharedTaskCommentRequest>,
+) -> Response {
+    let pool = state.pool();
+    match ensure_task_access(pool, ctx.user.id, task_id, AccessLevel::Write).await {
+        Ok(org_id) => {
+            Span::current().record("org_id", format_args!("{org_id}"));
+        }
+        Err(error) => return error.into_response(),
+    };
+
+    match SharedTaskCommentRepository::new(pool)
+        .create(task_id, ctx.user.id, &payload.body)
+        .await
+    {
+        Ok(comment) => (StatusCode::CREATED, Json(comment)).into_response(),
+        Err(error) => comment_error_response(error, "failed to create task comment"),
+    }
+}
+
+#[instrument(
+    name = "task_comments.update",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id, task_id = %task_id, comment_id = %comment_id, org_id = tracing::field::Empty)
+)]
+pub async fn update_task_comment(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateSharedTaskCommentRequest>,
+) -> Response {
+    let pool = state.pool();
+    if let Err(response) = ensure_comment_on_task(&state, &ctx, task_id, comment_id).await {
+        return response;
+    }
+
+    match SharedTaskCommentRepository::new(pool)
+        .update(comment_id, ctx.user.id, &payload.body)
+        .await
+    {
+        Ok(comment) => (StatusCode::OK, Json(comment)).into_response(),
+        Err(error) => comment_error_response(error, "failed to update task comment"),
+    }
+}
+
+#[instrument(
+    name = "task_comments.delete",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id, task_id = %task_id, comment_id = %comment_id, org_id = tracing::field::Empty)
+)]
+pub async fn delete_task_comment(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    let pool = state.pool();
+    if let Err(response) = ensure_comment_on_task(&state, &ctx, task_id, comment_id).await {
+        return response;
+    }
+
+    match SharedTaskCommentRepository::new(pool)
+        .delete(comment_id, ctx.user.id)
+        .await
+    {
+        Ok(comment) => (StatusCode::OK, Json(comment)).into_response(),
+        Err(error) => comment_error_response(error, "failed to delete task comment"),
+    }
+}
+
+#[instrument(
+    name = "task_comments.list_activity",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id, task_id = %task_id, org_id = tracing::field::Empty)
+)]
+pub async fn list_task_activity(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(task_id): Path<Uuid>,
+) -> Response {
+    let pool = state.pool();
+    match ensure_task_access(pool, ctx.user.id, task_id, AccessLevel::Read).await {
+        Ok(org_id) => {
+            Span::current().record("org_id", format_args!("{org_id}"));
+        }
+        Err(error) => return error.into_response(),
+    };
+
+    match SharedTaskActivityRepository::new(pool)
+        .list_by_task(task_id)
+        .await
+    {
+        Ok(activity) => (
+            StatusCode::OK,
+            Json(ListSharedTaskActivityResponse { activity }),
+        )
+            .into_response(),
+        Err(error) => {
+            tracing::error!(?error, "failed to list task activity");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "internal server error" })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Checks task access and that the comment belongs to the task in the path.
+async fn ensure_comment_on_task(
+    state: &AppState,
+    ctx: &RequestContext,
+    task_id: Uuid,
+    comment_id: Uuid,
+) -> Result<(), Response> {
+    let pool = state.pool();
+    let org_id = ensure_task_access(pool, ctx.user.id, task_id, AccessLevel::Write)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    Span::current().record("org_id", format_args!("{org_id}"));
+
+    match SharedTaskCommentRepository::new(pool)
+        .find_by_id(comment_id)
+        .await
+    {
+        Ok(Some(comment)) if comment.task_id == task_id => Ok(()),
+        Ok(_) => Err(comment_error_response(
+            SharedTaskCommentError::NotFound,
+            "comment not found",
+        )),
+        Err(error) => Err(comment_error_response(error, "failed to load task comment")),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CreateSharedTaskCommentRequest {
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UpdateSharedTaskCommentRequest {
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ListSharedTaskCommentsResponse {
+    pub comments: Vec<SharedTaskComment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ListSharedTaskActivityResponse {
+    pub activity: Vec<SharedTaskActivity>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_author_edits_are_forbidden() {
+        let response = comment_error_response(SharedTaskCommentError::Forbidden, "test");
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn deleted_comments_are_not_found() {
+        let response = comment_error_response(SharedTaskCommentError::NotFound, "test");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
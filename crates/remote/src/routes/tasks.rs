@@ -7,6 +7,7 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use sqlx::PgPool;
 use tracing::{Span, instrument};
 use ts_rs::TS;
 use uuid::Uuid;
@@ -19,14 +20,16 @@ use crate::{
     AppState,
     auth::RequestContext,
     db::{
-        organization_members,
+        identity_errors::IdentityError,
+        organization_members::{self, AccessLevel},
         tasks::{
             AssignTaskData, CreateSharedTaskData, DeleteTaskData, SharedTask, SharedTaskError,
             SharedTaskRepository, SharedTaskWithUser, TaskStatus, UpdateSharedTaskData,
             ensure_text_size,
         },
-        users::{UserData, UserRepository},
+        users::{User, UserData, UserRepository},
     },
+    github_app::IssueSyncService,
 };
 
 pub fn router() -> Router<AppState> {
@@ -57,13 +60,14 @@ pub async fn get_task_assignees_by_project(
 ) -> Response {
     let pool = state.pool();
 
-    let _org_id = match ensure_project_access(pool, ctx.user.id, query.project_id).await {
-        Ok(org) => {
-            Span::current().record("org_id", format_args!("{org}"));
-            org
-        }
-        Err(error) => return error.into_response(),
-    };
+    let _org_id =
+        match ensure_project_access(pool, ctx.user.id, query.project_id, AccessLevel::Read).await {
+            Ok(org) => {
+                Span::current().record("org_id", format_args!("{org}"));
+                org
+            }
+            Err(error) => return error.into_response(),
+        };
 
     let user_repo = UserRepository::new(pool);
     let assignees = match user_repo.fetch_assignees_by_project(query.project_id).await {
@@ -93,7 +97,6 @@ pub async fn create_shared_task(
 ) -> Response {
     let pool = state.pool();
     let repo = SharedTaskRepository::new(pool);
-    let user_repo = UserRepository::new(pool);
     let CreateSharedTaskRequest {
         project_id,
         title,
@@ -105,23 +108,17 @@ pub async fn create_shared_task(
         return task_error_response(error, "shared task payload too large");
     }
 
-    let organization_id = match ensure_project_access(pool, ctx.user.id, project_id).await {
+    match ensure_project_access(pool, ctx.user.id, project_id, AccessLevel::Write).await {
         Ok(org_id) => {
             Span::current().record("org_id", format_args!("{org_id}"));
-            org_id
         }
         Err(error) => return error.into_response(),
     };
 
-    if let Some(assignee) = assignee_user_id.as_ref() {
-        if let Err(err) = user_repo.fetch_user(*assignee).await {
-            return identity_error_response(err, "assignee not found or inactive");
-        }
-        if let Err(err) =
-            organization_members::assert_membership(pool, organization_id, *assignee).await
-        {
-            return identity_error_response(err, "assignee not part of organization");
-        }
+    if let Some(assignee) = assignee_user_id
+        && let Err(response) = check_assignee(pool, project_id, assignee).await
+    {
+        return response;
     }
 
     let data = CreateSharedTaskData {
@@ -133,7 +130,10 @@ pub async fn create_shared_task(
     };
 
     match repo.create(data).await {
-        Ok(task) => (StatusCode::CREATED, Json(SharedTaskResponse::from(task))).into_response(),
+        Ok(task) => {
+            sync_github_issue(&state, &task.task, true);
+            (StatusCode::CREATED, Json(SharedTaskResponse::from(task))).into_response()
+        }
         Err(error) => task_error_response(error, "failed to create shared task"),
     }
 }
@@ -150,13 +150,14 @@ pub async fn update_shared_task(
     Json(payload): Json<UpdateSharedTaskRequest>,
 ) -> Response {
     let pool = state.pool();
-    let _organization_id = match ensure_task_access(pool, ctx.user.id, task_id).await {
-        Ok(org_id) => {
-            Span::current().record("org_id", format_args!("{org_id}"));
-            org_id
-        }
-        Err(error) => return error.into_response(),
-    };
+    let _organization_id =
+        match ensure_task_access(pool, ctx.user.id, task_id, AccessLevel::Write).await {
+            Ok(org_id) => {
+                Span::current().record("org_id", format_args!("{org_id}"));
+                org_id
+            }
+            Err(error) => return error.into_response(),
+        };
 
     let repo = SharedTaskRepository::new(pool);
     let existing = match repo.find_by_id(task_id).await {
@@ -197,7 +198,13 @@ pub async fn update_shared_task(
     };
 
     match repo.update(task_id, data).await {
-        Ok(task) => (StatusCode::OK, Json(SharedTaskResponse::from(task))).into_response(),
+        Ok(task) => {
+            if task.task.status != existing.status {
+                notify_status_changed(&state, &ctx.user, &task.task, existing.status);
+            }
+            sync_github_issue(&state, &task.task, false);
+            (StatusCode::OK, Json(SharedTaskResponse::from(task))).into_response()
+        }
         Err(error) => task_error_response(error, "failed to update shared task"),
     }
 }
@@ -214,16 +221,16 @@ pub async fn assign_task(
     Json(payload): Json<AssignSharedTaskRequest>,
 ) -> Response {
     let pool = state.pool();
-    let organization_id = match ensure_task_access(pool, ctx.user.id, task_id).await {
-        Ok(org_id) => {
-            Span::current().record("org_id", format_args!("{org_id}"));
-            org_id
-        }
-        Err(error) => return error.into_response(),
-    };
+    let _organization_id =
+        match ensure_task_access(pool, ctx.user.id, task_id, AccessLevel::Write).await {
+            Ok(org_id) => {
+                Span::current().record("org_id", format_args!("{org_id}"));
+                org_id
+            }
+            Err(error) => return error.into_response(),
+        };
 
     let repo = SharedTaskRepository::new(pool);
-    let user_repo = UserRepository::new(pool);
 
     let existing = match repo.find_by_id(task_id).await {
         Ok(Some(task)) => task,
@@ -242,24 +249,24 @@ pub async fn assign_task(
         );
     }
 
-    if let Some(assignee) = payload.new_assignee_user_id.as_ref() {
-        if let Err(err) = user_repo.fetch_user(*assignee).await {
-            return identity_error_response(err, "assignee not found or inactive");
-        }
-        if let Err(err) =
-            organization_members::assert_membership(pool, organization_id, *assignee).await
-        {
-            return identity_error_response(err, "assignee not part of organization");
-        }
+    if let Some(assignee) = payload.new_assignee_user_id
+        && let Err(response) = check_assignee(pool, existing.project_id, assignee).await
+    {
+        return response;
     }
 
     let data = AssignTaskData {
         new_assignee_user_id: payload.new_assignee_user_id,
         previous_assignee_user_id: Some(ctx.user.id),
+        acting_user_id: ctx.user.id,
     };
 
     match repo.assign_task(task_id, data).await {
-        Ok(task) => (StatusCode::OK, Json(SharedTaskResponse::from(task))).into_response(),
+        Ok(task) => {
+            notify_task_assigned(&state, &ctx.user, &task.task);
+            sync_github_issue(&state, &task.task, false);
+            (StatusCode::OK, Json(SharedTaskResponse::from(task))).into_response()
+        }
         Err(error) => task_error_response(error, "failed to transfer task assignment"),
     }
 }
@@ -275,13 +282,14 @@ pub async fn delete_shared_task(
     Path(task_id): Path<Uuid>,
 ) -> Response {
     let pool = state.pool();
-    let _organization_id = match ensure_task_access(pool, ctx.user.id, task_id).await {
-        Ok(org_id) => {
-            Span::current().record("org_id", format_args!("{org_id}"));
-            org_id
-        }
-        Err(error) => return error.into_response(),
-    };
+    let _organization_id =
+        match ensure_task_access(pool, ctx.user.id, task_id, AccessLevel::Write).await {
+            Ok(org_id) => {
+                Span::current().record("org_id", format_args!("{org_id}"));
+                org_id
+            }
+            Err(error) => return error.into_response(),
+        };
 
     let repo = SharedTaskRepository::new(pool);
 
@@ -331,6 +339,117 @@ pub async fn check_tasks_existence(
     }
 }
 
+/// Assignees must exist and have write access to the task's project.
+async fn check_assignee(pool: &PgPool, project_id: Uuid, assignee: Uuid) -> Result<(), Response> {
+    if let Err(err) = UserRepository::new(pool).fetch_user(assignee).await {
+        return Err(identity_error_response(
+            err,
+            "assignee not found or inactive",
+        ));
+    }
+
+    match organization_members::assert_project_access(
+        pool,
+        project_id,
+        assignee,
+        AccessLevel::Write,
+    )
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(IdentityError::NotFound | IdentityError::PermissionDenied) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "assignee cannot work on this project" })),
+        )
+            .into_response()),
+        Err(err) => Err(identity_error_response(err, "failed to check assignee")),
+    }
+}
+
+/// Email the new assignee, unless they assigned the task to themselves.
+fn notify_task_assigned(state: &AppState, actor: &User, task: &SharedTask) {
+    let Some(assignee_id) = task.assignee_user_id else {
+        return;
+    };
+    if assignee_id == actor.id {
+        return;
+    }
+
+    let pool = state.pool.clone();
+    let mailer = state.mailer.clone();
+    let title = task.title.clone();
+    let actor_name = display_name(actor);
+    tokio::spawn(async move {
+        match UserRepository::new(&pool).fetch_user(assignee_id).await {
+            Ok(assignee) => {
+                mailer
+                    .send_task_assigned(&assignee.email, &title, Some(&actor_name))
+                    .await
+            }
+            Err(error) => tracing::warn!(?error, "failed to load assignee for notification"),
+        }
+    });
+}
+
+/// Email the task creator when someone else changes the task status.
+fn notify_status_changed(state: &AppState, actor: &User, task: &SharedTask, from: TaskStatus) {
+    let Some(creator_id) = task.creator_user_id else {
+        return;
+    };
+    if creator_id == actor.id {
+        return;
+    }
+
+    let pool = state.pool.clone();
+    let mailer = state.mailer.clone();
+    let title = task.title.clone();
+    let to = task.status;
+    let actor_name = display_name(actor);
+    tokio::spawn(async move {
+        match UserRepository::new(&pool).fetch_user(creator_id).await {
+            Ok(creator) => {
+                mailer
+                    .send_task_status_changed(&creator.email, &title, from, to, Some(&actor_name))
+                    .await
+            }
+            Err(error) => tracing::warn!(?error, "failed to load task creator for notification"),
+        }
+    });
+}
+
+/// Mirror the task onto its GitHub issue when the project syncs with one.
+/// New tasks get an issue if the project exports them.
+fn sync_github_issue(state: &AppState, task: &SharedTask, created: bool) {
+    if state.github_app().is_none() {
+        return;
+    }
+
+    let state = state.clone();
+    let task = task.clone();
+    tokio::spawn(async move {
+        let Some(github_app) = state.github_app() else {
+            return;
+        };
+        let sync = IssueSyncService::new(state.pool(), github_app);
+        let result = if created {
+            sync.export_new_task(&task).await
+        } else {
+            sync.push_task(&task).await
+        };
+        if let Err(error) = result {
+            tracing::warn!(?error, task_id = %task.id, "failed to sync task to GitHub issue");
+        }
+    });
+}
+
+fn display_name(user: &User) -> String {
+    match (&user.first_name, &user.last_name) {
+        (Some(first), Some(last)) => format!("{first} {last}"),
+        (Some(first), None) => first.clone(),
+        _ => user.username.clone().unwrap_or_else(|| user.email.clone()),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckTasksRequest {
     pub task_ids: Vec<Uuid>,
@@ -4,10 +4,10 @@ use sqlx::PgPool;
 
 use crate::{
     auth::{JwtService, OAuthHandoffService, OAuthTokenValidator, ProviderRegistry},
+    blob_store::{BlobStore, LocalBlobStore},
     config::RemoteServerConfig,
     github_app::GitHubAppService,
     mail::Mailer,
-    r2::R2Service,
 };
 
 #[derive(Clone)]
@@ -20,7 +20,8 @@ pub struct AppState {
     pub http_client: reqwest::Client,
     handoff: Arc<OAuthHandoffService>,
     oauth_token_validator: Arc<OAuthTokenValidator>,
-    r2: Option<R2Service>,
+    blob_store: Option<Arc<dyn BlobStore>>,
+    local_blob_store: Option<Arc<LocalBlobStore>>,
     github_app: Option<Arc<GitHubAppService>>,
 }
 
@@ -35,7 +36,8 @@ impl AppState {
         mailer: Arc<dyn Mailer>,
         server_public_base_url: String,
         http_client: reqwest::Client,
-        r2: Option<R2Service>,
+        blob_store: Option<Arc<dyn BlobStore>>,
+        local_blob_store: Option<Arc<LocalBlobStore>>,
         github_app: Option<Arc<GitHubAppService>>,
     ) -> Self {
         Self {
@@ -47,7 +49,8 @@ impl AppState {
             http_client,
             handoff,
             oauth_token_validator,
-            r2,
+            blob_store,
+            local_blob_store,
             github_app,
         }
     }
@@ -76,8 +79,13 @@ impl AppState {
         Arc::clone(&self.oauth_token_validator)
     }
 
-    pub fn r2(&self) -> Option<&R2Service> {
-        self.r2.as_ref()
+    pub fn blob_store(&self) -> Option<Arc<dyn BlobStore>> {
+        self.blob_store.clone()
+    }
+
+    /// The filesystem store, when it is the active backend; used by the signed-URL endpoints.
+    pub fn local_blob_store(&self) -> Option<&LocalBlobStore> {
+        self.local_blob_store.as_deref()
     }
 
     pub fn github_app(&self) -> Option<&GitHubAppService> {
@@ -0,0 +1,233 @@
+//! Integration tests for shared task comments and the task activity log:
+//! - only the author can edit or delete a comment
+//! - soft-deleted comments are hidden from listings
+//! - status and assignee changes are recorded as activity
+//!
+//! These need a Postgres database and are skipped unless `DATABASE_URL` is set.
+
+use remote::db::{
+    organizations::OrganizationRepository,
+    projects::{CreateProjectData, ProjectRepository},
+    task_activity::{SharedTaskActivityRepository, SharedTaskActivityType},
+    task_comments::{SharedTaskCommentError, SharedTaskCommentRepository},
+    tasks::{
+        AssignTaskData, CreateSharedTaskData, SharedTask, SharedTaskRepository, TaskStatus,
+        UpdateSharedTaskData,
+    },
+    users::{UpsertUser, UserRepository},
+};
+use sqlx::{PgPool, postgres::PgPoolOptions};
+use uuid::Uuid;
+
+async fn setup_db() -> Option<PgPool> {
+    let Ok(database_url) = std::env::var("DATABASE_URL") else {
+        eprintln!("DATABASE_URL is not set, skipping");
+        return None;
+    };
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&database_url)
+        .await
+        .unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+    Some(pool)
+}
+
+async fn create_user(pool: &PgPool) -> Uuid {
+    let id = Uuid::new_v4();
+    let email = format!("{id}@example.com");
+    UserRepository::new(pool)
+        .upsert_user(UpsertUser {
+            id,
+            email: &email,
+            first_name: None,
+            last_name: None,
+            username: None,
+        })
+        .await
+        .unwrap();
+    id
+}
+
+/// Creates a task in a fresh organization, assigned to its creator
+async fn create_task(pool: &PgPool, creator_user_id: Uuid) -> SharedTask {
+    let slug = format!("org-{}", Uuid::new_v4());
+    let org = OrganizationRepository::new(pool)
+        .create_organization("Test org", &slug, creator_user_id)
+        .await
+        .unwrap();
+
+    let mut tx = pool.begin().await.unwrap();
+    let project = ProjectRepository::insert(
+        &mut tx,
+        CreateProjectData {
+            organization_id: org.id,
+            name: "Test project".to_string(),
+            metadata: serde_json::json!({}),
+        },
+    )
+    .await
+    .unwrap();
+    tx.commit().await.unwrap();
+
+    SharedTaskRepository::new(pool)
+        .create(CreateSharedTaskData {
+            project_id: project.id,
+            title: "Test task".to_string(),
+            description: None,
+            creator_user_id,
+            assignee_user_id: Some(creator_user_id),
+        })
+        .await
+        .unwrap()
+        .task
+}
+
+#[tokio::test]
+async fn only_author_can_edit_or_delete_comment() {
+    let Some(pool) = setup_db().await else {
+        return;
+    };
+    let author = create_user(&pool).await;
+    let other = create_user(&pool).await;
+    let task = create_task(&pool, author).await;
+    let comments = SharedTaskCommentRepository::new(&pool);
+
+    let comment = comments.create(task.id, author, "First").await.unwrap();
+
+    let result = comments.update(comment.id, other, "Hijacked").await;
+    assert!(matches!(result, Err(SharedTaskCommentError::Forbidden)));
+    let result = comments.delete(comment.id, other).await;
+    assert!(matches!(result, Err(SharedTaskCommentError::Forbidden)));
+
+    let unchanged = comments.find_by_id(comment.id).await.unwrap().unwrap();
+    assert_eq!(unchanged.body, "First");
+    assert!(unchanged.edited_at.is_none());
+
+    let edited = comments.update(comment.id, author, "Edited").await.unwrap();
+    assert_eq!(edited.body, "Edited");
+    assert!(edited.edited_at.is_some());
+
+    let deleted = comments.delete(comment.id, author).await.unwrap();
+    assert!(deleted.deleted_at.is_some());
+}
+
+#[tokio::test]
+async fn deleted_comments_are_hidden() {
+    let Some(pool) = setup_db().await else {
+        return;
+    };
+    let author = create_user(&pool).await;
+    let task = create_task(&pool, author).await;
+    let comments = SharedTaskCommentRepository::new(&pool);
+
+    let kept = comments.create(task.id, author, "Kept").await.unwrap();
+    let removed = comments.create(task.id, author, "Removed").await.unwrap();
+    comments.delete(removed.id, author).await.unwrap();
+
+    let listed = comments.list_by_task(task.id).await.unwrap();
+    assert_eq!(
+        listed.iter().map(|c| c.id).collect::<Vec<_>>(),
+        vec![kept.id]
+    );
+    assert!(comments.find_by_id(removed.id).await.unwrap().is_none());
+
+    // A deleted comment can't be edited or deleted again
+    let result = comments.update(removed.id, author, "Back").await;
+    assert!(matches!(result, Err(SharedTaskCommentError::NotFound)));
+    let result = comments.delete(removed.id, author).await;
+    assert!(matches!(result, Err(SharedTaskCommentError::NotFound)));
+}
+
+#[tokio::test]
+async fn status_and_assignee_changes_are_recorded() {
+    let Some(pool) = setup_db().await else {
+        return;
+    };
+    let creator = create_user(&pool).await;
+    let assignee = create_user(&pool).await;
+    let task = create_task(&pool, creator).await;
+    let tasks = SharedTaskRepository::new(&pool);
+
+    tasks
+        .update(
+            task.id,
+            UpdateSharedTaskData {
+                title: None,
+                description: None,
+                status: Some(TaskStatus::InProgress),
+                acting_user_id: creator,
+            },
+        )
+        .await
+        .unwrap();
+    tasks
+        .assign_task(
+            task.id,
+            AssignTaskData {
+                new_assignee_user_id: Some(assignee),
+                previous_assignee_user_id: Some(creator),
+                acting_user_id: creator,
+            },
+        )
+        .await
+        .unwrap();
+
+    let activity = SharedTaskActivityRepository::new(&pool)
+        .list_by_task(task.id)
+        .await
+        .unwrap();
+
+    let status = activity
+        .iter()
+        .find(|a| a.event_type == SharedTaskActivityType::StatusChanged)
+        .expect("status change recorded");
+    assert_eq!(status.actor_user_id, Some(creator));
+    assert_eq!(
+        status.from_value.as_deref(),
+        Some(TaskStatus::Todo.as_str())
+    );
+    assert_eq!(
+        status.to_value.as_deref(),
+        Some(TaskStatus::InProgress.as_str())
+    );
+
+    let assigned = activity
+        .iter()
+        .find(|a| a.event_type == SharedTaskActivityType::AssigneeChanged)
+        .expect("assignee change recorded");
+    assert_eq!(assigned.actor_user_id, Some(creator));
+    assert_eq!(
+        assigned.from_value.as_deref(),
+        Some(creator.to_string().as_str())
+    );
+    assert_eq!(
+        assigned.to_value.as_deref(),
+        Some(assignee.to_string().as_str())
+    );
+
+    // Updates that leave the status alone don't add a status entry
+    tasks
+        .update(
+            task.id,
+            UpdateSharedTaskData {
+                title: Some("Renamed".to_string()),
+                description: None,
+                status: None,
+                acting_user_id: assignee,
+            },
+        )
+        .await
+        .unwrap();
+    let activity = SharedTaskActivityRepository::new(&pool)
+        .list_by_task(task.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        activity
+            .iter()
+            .filter(|a| a.event_type == SharedTaskActivityType::StatusChanged)
+            .count(),
+        1
+    );
+}
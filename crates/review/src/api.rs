@@ -28,7 +28,7 @@ struct InitRequest {
 }
 
 /// Request body for POST /review/start
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StartRequest {
     pub id: String,
@@ -38,10 +38,14 @@ pub struct StartRequest {
     pub repo: String,
     pub codebase_url: String,
     pub base_commit: String,
+    /// Where the worker reports completion, as `{callback_url}/success` or
+    /// `{callback_url}/failed`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<String>,
 }
 
 /// Response from GET /review/{id}/status
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StatusResponse {
     pub status: ReviewStatus,
@@ -50,7 +54,7 @@ pub struct StatusResponse {
 }
 
 /// Possible review statuses
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ReviewStatus {
     Queued,
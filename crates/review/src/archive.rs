@@ -1,11 +1,18 @@
-use std::{fs::File, path::Path};
+use std::{
+    fs::File,
+    path::{Component, Path},
+};
 
-use flate2::{Compression, write::GzEncoder};
-use tar::Builder;
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use tar::{Archive, Builder};
 use tracing::debug;
 
 use crate::error::ReviewError;
 
+/// Written in place of the uploaded repository config, which is dropped
+const MINIMAL_GIT_CONFIG: &str =
+    "[core]\n\trepositoryformatversion = 0\n\tfilemode = true\n\tbare = false\n";
+
 /// Create a tar.gz archive from a directory
 pub fn create_tarball(source_dir: &Path) -> Result<Vec<u8>, ReviewError> {
     debug!("Creating tarball from {}", source_dir.display());
@@ -31,6 +38,71 @@ pub fn create_tarball(source_dir: &Path) -> Result<Vec<u8>, ReviewError> {
     Ok(buffer)
 }
 
+/// Extract a tar.gz archive created by [`create_tarball`] into `dest_dir`.
+///
+/// The repository config and hooks are skipped: options such as
+/// `diff.external` or `core.fsmonitor` would let the uploader run commands
+/// whenever git is used in the extracted repository.
+pub fn extract_tarball(payload: &[u8], dest_dir: &Path) -> Result<(), ReviewError> {
+    debug!(
+        "Extracting {} bytes to {}",
+        payload.len(),
+        dest_dir.display()
+    );
+
+    std::fs::create_dir_all(dest_dir).map_err(|e| ReviewError::ExtractFailed(e.to_string()))?;
+
+    let decoder = GzDecoder::new(payload);
+    let mut archive = Archive::new(decoder);
+    let entries = archive
+        .entries()
+        .map_err(|e| ReviewError::ExtractFailed(e.to_string()))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| ReviewError::ExtractFailed(e.to_string()))?;
+        // `create_tarball` never adds links, and they could point outside `dest_dir`
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_symlink() || entry_type.is_hard_link() {
+            let path = entry.path().map(|p| p.display().to_string());
+            return Err(ReviewError::ExtractFailed(format!(
+                "archive contains a link: {}",
+                path.unwrap_or_default()
+            )));
+        }
+        let path = entry
+            .path()
+            .map_err(|e| ReviewError::ExtractFailed(e.to_string()))?
+            .into_owned();
+        if is_untrusted_git_path(&path) {
+            debug!("Skipping {}", path.display());
+            continue;
+        }
+        // `unpack_in` skips entries that would escape `dest_dir`
+        entry
+            .unpack_in(dest_dir)
+            .map_err(|e| ReviewError::ExtractFailed(e.to_string()))?;
+    }
+
+    let git_dir = dest_dir.join(".git");
+    if git_dir.is_dir() {
+        std::fs::write(git_dir.join("config"), MINIMAL_GIT_CONFIG)
+            .map_err(|e| ReviewError::ExtractFailed(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Whether `relative_path` is the repository config or one of its hooks
+fn is_untrusted_git_path(relative_path: &Path) -> bool {
+    let mut components = relative_path
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .map(|component| component.as_os_str());
+    components.next().is_some_and(|first| first == ".git")
+        && components
+            .next()
+            .is_some_and(|second| second == "config" || second == "hooks")
+}
+
 fn add_directory_to_archive<W: std::io::Write>(
     archive: &mut Builder<W>,
     base_dir: &Path,
@@ -51,7 +123,10 @@ fn add_directory_to_archive<W: std::io::Write>(
             .metadata()
             .map_err(|e| ReviewError::ArchiveFailed(e.to_string()))?;
 
-        if metadata.is_dir() {
+        if is_untrusted_git_path(relative_path) {
+            // Dropped on extraction anyway
+            continue;
+        } else if metadata.is_dir() {
             // Recursively add directory contents
             add_directory_to_archive(archive, base_dir, &path)?;
         } else if metadata.is_file() {
@@ -103,4 +178,72 @@ mod tests {
         assert!(entries.contains(&"file1.txt".to_string()));
         assert!(entries.contains(&"subdir/file2.txt".to_string()));
     }
+
+    #[test]
+    fn test_extract_tarball_round_trip() {
+        let source = TempDir::new().unwrap();
+        std::fs::create_dir(source.path().join("subdir")).unwrap();
+        std::fs::write(source.path().join("subdir/file.txt"), "content").unwrap();
+
+        let tarball = create_tarball(source.path()).expect("Should create tarball");
+
+        let dest = TempDir::new().unwrap();
+        extract_tarball(&tarball, dest.path()).expect("Should extract tarball");
+
+        let content = std::fs::read_to_string(dest.path().join("subdir/file.txt")).unwrap();
+        assert_eq!(content, "content");
+    }
+
+    #[test]
+    fn test_extract_tarball_drops_git_config_and_hooks() {
+        let mut buffer = Vec::new();
+        {
+            let mut archive = Builder::new(GzEncoder::new(&mut buffer, Compression::default()));
+            for (path, contents) in [
+                ("./.git/config", "[diff]\n\texternal = /tmp/evil\n"),
+                (".git/hooks/post-checkout", "#!/bin/sh\n"),
+                (".git/HEAD", "ref: refs/heads/main\n"),
+                ("src/config", "kept"),
+            ] {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(contents.len() as u64);
+                header.set_mode(0o644);
+                archive
+                    .append_data(&mut header, path, contents.as_bytes())
+                    .unwrap();
+            }
+            archive.into_inner().unwrap().finish().unwrap();
+        }
+
+        let dest = TempDir::new().unwrap();
+        extract_tarball(&buffer, dest.path()).expect("Should extract tarball");
+
+        let config = std::fs::read_to_string(dest.path().join(".git/config")).unwrap();
+        assert_eq!(config, MINIMAL_GIT_CONFIG);
+        assert!(!dest.path().join(".git/hooks").exists());
+        assert!(dest.path().join(".git/HEAD").exists());
+        assert_eq!(
+            std::fs::read_to_string(dest.path().join("src/config")).unwrap(),
+            "kept"
+        );
+    }
+
+    #[test]
+    fn test_extract_tarball_rejects_symlinks() {
+        let mut buffer = Vec::new();
+        {
+            let mut archive = Builder::new(GzEncoder::new(&mut buffer, Compression::default()));
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(tar::EntryType::Symlink);
+            header.set_size(0);
+            archive
+                .append_link(&mut header, "passwd", "/etc/passwd")
+                .unwrap();
+            archive.into_inner().unwrap().finish().unwrap();
+        }
+
+        let dest = TempDir::new().unwrap();
+        assert!(extract_tarball(&buffer, dest.path()).is_err());
+        assert!(dest.path().join("passwd").symlink_metadata().is_err());
+    }
 }
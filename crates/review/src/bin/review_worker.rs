@@ -0,0 +1,91 @@
+use std::{net::SocketAddr, path::PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use review::worker::{
+    LocalReviewer, ReviewAgent, ReviewStore, parse_agent_profile,
+    server::{WorkerState, router},
+};
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+/// Runs reviews with a local coding agent and serves the results to the remote
+/// server, which reaches it through `REVIEW_WORKER_BASE_URL`.
+#[derive(Parser, Debug)]
+#[command(name = "review-worker")]
+#[command(version)]
+struct Args {
+    /// Address to listen on
+    #[arg(long, env = "REVIEW_WORKER_ADDR", default_value = "127.0.0.1:8787")]
+    addr: SocketAddr,
+
+    /// Directory review results are stored in
+    #[arg(long, env = "REVIEW_WORKER_DATA_DIR")]
+    data_dir: Option<PathBuf>,
+
+    /// Coding agent profile as AGENT[:VARIANT], e.g. CLAUDE_CODE or CODEX:HIGH
+    #[arg(long, env = "REVIEW_WORKER_AGENT", default_value = "CLAUDE_CODE")]
+    agent: String,
+
+    /// Hosts the agent may connect to, in place of the default model providers
+    #[arg(long, env = "REVIEW_WORKER_ALLOWED_HOSTS", value_delimiter = ',')]
+    allowed_hosts: Vec<String>,
+
+    /// Maximum number of reviews running at once
+    #[arg(long, env = "REVIEW_WORKER_MAX_CONCURRENT", default_value_t = 1)]
+    max_concurrent: usize,
+
+    /// Shared secret the remote server sends as a bearer token
+    #[arg(long, env = "REVIEW_WORKER_SECRET", hide_env_values = true)]
+    secret: String,
+
+    /// Accept file:// codebase URLs. For local development only: lets callers
+    /// read any file the worker can access.
+    #[arg(long, env = "REVIEW_WORKER_ALLOW_FILE_URLS")]
+    allow_file_urls: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Install rustls crypto provider before any TLS operations
+    rustls::crypto::aws_lc_rs::default_provider()
+        .install_default()
+        .expect("Failed to install rustls crypto provider");
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .init();
+
+    let args = Args::parse();
+    anyhow::ensure!(
+        !args.secret.is_empty(),
+        "REVIEW_WORKER_SECRET must not be empty"
+    );
+
+    let profile = parse_agent_profile(&args.agent)?;
+    let store = ReviewStore::new(args.data_dir.unwrap_or_else(ReviewStore::default_root));
+    info!(
+        "Storing reviews in {} using {}",
+        store.root().display(),
+        profile
+    );
+
+    let mut agent = ReviewAgent::new(profile);
+    if !args.allowed_hosts.is_empty() {
+        agent = agent.allowed_hosts(args.allowed_hosts);
+    }
+    let reviewer = LocalReviewer::new(store, agent);
+    let state = WorkerState::new(reviewer, args.max_concurrent, &args.secret)
+        .allow_file_urls(args.allow_file_urls);
+    let app = router(state);
+
+    let listener = tokio::net::TcpListener::bind(args.addr)
+        .await
+        .with_context(|| format!("Failed to bind {}", args.addr))?;
+    info!("Review worker listening on {}", args.addr);
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
@@ -8,7 +8,10 @@ use std::{
 use serde::Deserialize;
 use tracing::debug;
 
-use crate::error::ReviewError;
+use crate::{
+    error::ReviewError,
+    sessions::{self, SessionMessage},
+};
 
 /// Represents a Claude Code project directory
 #[derive(Debug, Clone)]
@@ -278,7 +281,7 @@ pub fn find_projects_by_branch(
 }
 
 /// Check if two branch names match using fuzzy matching
-fn branches_match(target: &str, session_branch: &str) -> bool {
+pub fn branches_match(target: &str, session_branch: &str) -> bool {
     let target_normalized = normalize_branch(target);
     let session_normalized = normalize_branch(session_branch);
 
@@ -308,12 +311,6 @@ fn extract_branch_slug(branch: &str) -> String {
     branch.rsplit('/').next().unwrap_or(branch).to_string()
 }
 
-/// A record with timestamp for sorting
-struct TimestampedMessage {
-    timestamp: String,
-    message: serde_json::Value,
-}
-
 /// Concatenate multiple JSONL files into a single JSON array of messages.
 ///
 /// Filters to include only:
@@ -322,7 +319,14 @@ struct TimestampedMessage {
 ///
 /// For assistant messages, only text content blocks are kept (tool_use, etc. are filtered out).
 pub fn concatenate_sessions_to_json(session_paths: &[PathBuf]) -> Result<String, ReviewError> {
-    let mut all_messages: Vec<TimestampedMessage> = Vec::new();
+    sessions::messages_to_json(load_session_messages(session_paths)?)
+}
+
+/// Load the filtered messages of multiple JSONL files, unsorted
+pub fn load_session_messages(
+    session_paths: &[PathBuf],
+) -> Result<Vec<SessionMessage>, ReviewError> {
+    let mut all_messages: Vec<SessionMessage> = Vec::new();
 
     for path in session_paths {
         let file = File::open(path)
@@ -351,18 +355,12 @@ pub fn concatenate_sessions_to_json(session_paths: &[PathBuf]) -> Result<String,
 
             // Extract and filter the message
             if let Some(message) = extract_filtered_message(&record) {
-                all_messages.push(TimestampedMessage { timestamp, message });
+                all_messages.push(SessionMessage { timestamp, message });
             }
         }
     }
 
-    // Sort by timestamp
-    all_messages.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
-
-    // Extract just the messages
-    let messages: Vec<serde_json::Value> = all_messages.into_iter().map(|m| m.message).collect();
-
-    serde_json::to_string(&messages).map_err(|e| ReviewError::JsonlParseFailed(e.to_string()))
+    Ok(all_messages)
 }
 
 /// Extract and filter a message from a JSONL record.
@@ -11,6 +11,22 @@ pub enum ReviewError {
     #[error("Invalid GitHub PR URL format. Expected: https://github.com/owner/repo/pull/123")]
     InvalidPrUrl,
 
+    #[error(
+        "GitLab CLI (glab) is not installed. Install it from https://gitlab.com/gitlab-org/cli"
+    )]
+    GlabNotInstalled,
+
+    #[error("GitLab CLI is not authenticated. Run 'glab auth login' first.")]
+    GlabNotAuthenticated,
+
+    #[error(
+        "Invalid GitLab MR URL format. Expected: https://gitlab.com/group/repo/-/merge_requests/123"
+    )]
+    InvalidMrUrl,
+
+    #[error("Invalid review target: {0}")]
+    InvalidTarget(String),
+
     #[error("Failed to get PR information: {0}")]
     PrInfoFailed(String),
 
@@ -35,9 +51,24 @@ pub enum ReviewError {
     #[error("Review timed out after 10 minutes")]
     Timeout,
 
-    #[error("Failed to discover Claude Code sessions: {0}")]
+    #[error("Failed to discover agent sessions: {0}")]
     SessionDiscoveryFailed(String),
 
     #[error("Failed to parse JSONL file: {0}")]
     JsonlParseFailed(String),
+
+    #[error("Failed to extract archive: {0}")]
+    ExtractFailed(String),
+
+    #[error("Failed to compute diff: {0}")]
+    DiffFailed(String),
+
+    #[error("Unknown coding agent: {0}")]
+    UnknownAgent(String),
+
+    #[error("Coding agent failed: {0}")]
+    AgentFailed(String),
+
+    #[error("Failed to store review results: {0}")]
+    StorageFailed(String),
 }
@@ -0,0 +1,245 @@
+use std::{path::Path, process::Command};
+
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::{error::ReviewError, github::PrInfo};
+
+/// A parsed GitLab merge request URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MrUrl {
+    pub host: String,
+    /// Full project path, including subgroups (e.g. `group/subgroup/repo`)
+    pub project: String,
+    pub iid: i64,
+}
+
+/// Response from `glab mr view --output json`
+#[derive(Debug, Deserialize)]
+struct GlabMrView {
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    source_branch: String,
+    sha: String,
+    diff_refs: Option<GlabDiffRefs>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlabDiffRefs {
+    base_sha: String,
+    head_sha: String,
+}
+
+/// Parse a GitLab MR URL to extract host, project path, and MR IID
+///
+/// Expected format: https://gitlab.com/group/repo/-/merge_requests/123
+pub fn parse_mr_url(url: &str) -> Result<MrUrl, ReviewError> {
+    let url = url.trim().trim_end_matches('/');
+    let without_scheme = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+
+    let (project_url, mr_part) = without_scheme
+        .split_once("/-/merge_requests/")
+        .ok_or(ReviewError::InvalidMrUrl)?;
+    let (host, project) = project_url
+        .split_once('/')
+        .ok_or(ReviewError::InvalidMrUrl)?;
+
+    // Ignore trailing tabs like /diffs or /commits
+    let iid: i64 = mr_part
+        .split('/')
+        .next()
+        .and_then(|iid| iid.parse().ok())
+        .ok_or(ReviewError::InvalidMrUrl)?;
+
+    if host.is_empty() || !project.contains('/') || iid <= 0 {
+        return Err(ReviewError::InvalidMrUrl);
+    }
+
+    Ok(MrUrl {
+        host: host.to_string(),
+        project: project.to_string(),
+        iid,
+    })
+}
+
+/// Check if the GitLab CLI is installed
+fn ensure_glab_available() -> Result<(), ReviewError> {
+    let output = Command::new("which")
+        .arg("glab")
+        .output()
+        .map_err(|_| ReviewError::GlabNotInstalled)?;
+
+    if !output.status.success() {
+        return Err(ReviewError::GlabNotInstalled);
+    }
+
+    Ok(())
+}
+
+/// `glab` picks the instance from `GITLAB_HOST`, which also covers self-hosted GitLab
+fn glab(host: &str) -> Command {
+    let mut command = Command::new("glab");
+    command.env("GITLAB_HOST", host);
+    command
+}
+
+/// Get MR information using `glab mr view`
+pub fn get_mr_info(mr: &MrUrl) -> Result<PrInfo, ReviewError> {
+    ensure_glab_available()?;
+
+    debug!("Fetching MR info for {}!{}", mr.project, mr.iid);
+
+    let output = glab(&mr.host)
+        .args([
+            "mr",
+            "view",
+            &mr.iid.to_string(),
+            "--repo",
+            &mr.project,
+            "--output",
+            "json",
+        ])
+        .output()
+        .map_err(|e| ReviewError::PrInfoFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let lower = stderr.to_ascii_lowercase();
+
+        if lower.contains("glab auth login") || lower.contains("401") {
+            return Err(ReviewError::GlabNotAuthenticated);
+        }
+
+        return Err(ReviewError::PrInfoFailed(stderr.to_string()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mr_view: GlabMrView =
+        serde_json::from_str(&stdout).map_err(|e| ReviewError::PrInfoFailed(e.to_string()))?;
+
+    let diff_refs = mr_view.diff_refs.ok_or_else(|| {
+        ReviewError::PrInfoFailed("Merge request has no diff refs yet".to_string())
+    })?;
+
+    let (owner, repo) = mr
+        .project
+        .rsplit_once('/')
+        .ok_or(ReviewError::InvalidMrUrl)?;
+
+    Ok(PrInfo {
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+        title: mr_view.title,
+        description: mr_view.description.unwrap_or_default(),
+        base_commit: diff_refs.base_sha,
+        head_commit: if diff_refs.head_sha.is_empty() {
+            mr_view.sha
+        } else {
+            diff_refs.head_sha
+        },
+        head_ref_name: mr_view.source_branch,
+    })
+}
+
+/// Clone a repository using `glab repo clone`
+pub fn clone_repo(mr: &MrUrl, target_dir: &Path) -> Result<(), ReviewError> {
+    ensure_glab_available()?;
+
+    debug!("Cloning {} to {}", mr.project, target_dir.display());
+
+    let output = glab(&mr.host)
+        .args([
+            "repo",
+            "clone",
+            &mr.project,
+            target_dir
+                .to_str()
+                .ok_or_else(|| ReviewError::CloneFailed("Invalid target path".to_string()))?,
+        ])
+        .output()
+        .map_err(|e| ReviewError::CloneFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ReviewError::CloneFailed(stderr.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Checkout the MR head commit
+///
+/// Fetches the MR's `refs/merge-requests/<iid>/head` ref, which GitLab keeps
+/// even for forks and deleted source branches.
+pub fn checkout_mr(mr: &MrUrl, commit_sha: &str, repo_dir: &Path) -> Result<(), ReviewError> {
+    debug!("Fetching MR !{} in {}", mr.iid, repo_dir.display());
+
+    let output = Command::new("git")
+        .args([
+            "fetch",
+            "origin",
+            &format!("refs/merge-requests/{}/head", mr.iid),
+        ])
+        .current_dir(repo_dir)
+        .output()
+        .map_err(|e| ReviewError::CheckoutFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ReviewError::CheckoutFailed(format!(
+            "Failed to fetch merge request: {stderr}"
+        )));
+    }
+
+    debug!("Checking out commit {commit_sha}");
+
+    let output = Command::new("git")
+        .args(["checkout", commit_sha])
+        .current_dir(repo_dir)
+        .output()
+        .map_err(|e| ReviewError::CheckoutFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ReviewError::CheckoutFailed(format!(
+            "Failed to checkout commit: {stderr}"
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mr_url_valid() {
+        let mr = parse_mr_url("https://gitlab.com/group/subgroup/repo/-/merge_requests/42")
+            .expect("Should parse valid URL");
+        assert_eq!(mr.host, "gitlab.com");
+        assert_eq!(mr.project, "group/subgroup/repo");
+        assert_eq!(mr.iid, 42);
+    }
+
+    #[test]
+    fn test_parse_mr_url_self_hosted_with_tab() {
+        let mr = parse_mr_url("https://git.example.com/team/app/-/merge_requests/7/diffs/")
+            .expect("Should parse");
+        assert_eq!(mr.host, "git.example.com");
+        assert_eq!(mr.project, "team/app");
+        assert_eq!(mr.iid, 7);
+    }
+
+    #[test]
+    fn test_parse_mr_url_invalid_format() {
+        assert!(parse_mr_url("https://gitlab.com/group/repo").is_err());
+        assert!(parse_mr_url("https://gitlab.com/group/repo/-/issues/3").is_err());
+        assert!(parse_mr_url("https://gitlab.com/repo/-/merge_requests/3").is_err());
+        assert!(parse_mr_url("https://gitlab.com/group/repo/-/merge_requests/abc").is_err());
+    }
+}
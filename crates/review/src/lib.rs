@@ -0,0 +1,12 @@
+pub mod api;
+pub mod archive;
+pub mod claude_session;
+pub mod config;
+pub mod error;
+pub mod github;
+pub mod gitlab;
+pub mod local;
+pub mod session_selector;
+pub mod sessions;
+pub mod target;
+pub mod worker;
@@ -0,0 +1,190 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use tracing::debug;
+
+use crate::{error::ReviewError, github::PrInfo};
+
+/// Run git in `repo_dir` and return trimmed stdout
+fn git(repo_dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_dir)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Resolve the top-level directory of the repository containing `path`
+pub fn repo_root(path: &Path) -> Result<PathBuf, ReviewError> {
+    if !path.is_dir() {
+        return Err(ReviewError::InvalidTarget(format!(
+            "{} is not a directory",
+            path.display()
+        )));
+    }
+
+    git(path, &["rev-parse", "--show-toplevel"])
+        .map(PathBuf::from)
+        .map_err(|e| ReviewError::InvalidTarget(format!("{}: {e}", path.display())))
+}
+
+fn resolve_commit(repo_dir: &Path, reference: &str) -> Result<String, ReviewError> {
+    git(
+        repo_dir,
+        &["rev-parse", "--verify", &format!("{reference}^{{commit}}")],
+    )
+    .map_err(|e| ReviewError::PrInfoFailed(format!("Unknown ref '{reference}': {e}")))
+}
+
+/// Branch name for `reference`, falling back to the ref as given for tags and SHAs
+fn ref_name(repo_dir: &Path, reference: &str) -> String {
+    match git(repo_dir, &["rev-parse", "--abbrev-ref", reference]) {
+        Ok(name) if !name.is_empty() && name != "HEAD" => name,
+        _ => reference.to_string(),
+    }
+}
+
+/// Split a remote URL into `(owner, repo)`, for both
+/// `https://host/owner/repo.git` and `git@host:owner/repo.git`
+fn owner_and_repo_from_remote(url: &str) -> Option<(String, String)> {
+    let url = url.trim().trim_end_matches('/').trim_end_matches(".git");
+    let path = match url.split_once("://") {
+        // Drop the host (and any credentials) after the scheme
+        Some((_, rest)) => rest.split_once('/')?.1,
+        // scp-like syntax
+        None => url.split_once(':')?.1,
+    };
+
+    let (owner, repo) = path.rsplit_once('/')?;
+    if owner.is_empty() || repo.is_empty() {
+        return None;
+    }
+    Some((owner.to_string(), repo.to_string()))
+}
+
+/// Build review metadata for `base..head` in a local repository
+///
+/// The title is the commit subject when the range holds a single commit and
+/// the head branch otherwise; the description lists the commits in order.
+pub fn get_local_info(repo_dir: &Path, base: &str, head: &str) -> Result<PrInfo, ReviewError> {
+    debug!("Reading {base}..{head} in {}", repo_dir.display());
+
+    let base_commit = resolve_commit(repo_dir, base)?;
+    let head_commit = resolve_commit(repo_dir, head)?;
+    let head_ref_name = ref_name(repo_dir, head);
+
+    let subjects = git(
+        repo_dir,
+        &[
+            "log",
+            "--reverse",
+            "--format=%s",
+            &format!("{base_commit}..{head_commit}"),
+        ],
+    )
+    .map_err(ReviewError::PrInfoFailed)?;
+    let subjects: Vec<&str> = subjects.lines().filter(|s| !s.is_empty()).collect();
+
+    if subjects.is_empty() {
+        return Err(ReviewError::PrInfoFailed(format!(
+            "No commits between {base} and {head}"
+        )));
+    }
+
+    let title = match subjects.as_slice() {
+        [subject] => subject.to_string(),
+        _ => head_ref_name.clone(),
+    };
+    let description = subjects
+        .iter()
+        .map(|subject| format!("- {subject}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let (owner, repo) = git(repo_dir, &["remote", "get-url", "origin"])
+        .ok()
+        .and_then(|url| owner_and_repo_from_remote(&url))
+        .unwrap_or_else(|| {
+            let name = repo_dir
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("repo")
+                .to_string();
+            ("local".to_string(), name)
+        });
+
+    Ok(PrInfo {
+        owner,
+        repo,
+        title,
+        description,
+        base_commit,
+        head_commit,
+        head_ref_name,
+    })
+}
+
+/// Clone a local repository, so uncommitted changes stay out of the review
+pub fn clone_repo(source_dir: &Path, target_dir: &Path) -> Result<(), ReviewError> {
+    debug!(
+        "Cloning {} to {}",
+        source_dir.display(),
+        target_dir.display()
+    );
+
+    let output = Command::new("git")
+        .args(["clone", "--quiet", "--no-checkout"])
+        .arg(source_dir)
+        .arg(target_dir)
+        .output()
+        .map_err(|e| ReviewError::CloneFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ReviewError::CloneFailed(stderr.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Checkout `commit_sha` in a clone made by [`clone_repo`]
+///
+/// Unlike [`crate::github::checkout_commit`] nothing is fetched: the commit
+/// must be reachable from a branch or tag of the source repository.
+pub fn checkout_commit(commit_sha: &str, repo_dir: &Path) -> Result<(), ReviewError> {
+    debug!("Checking out commit {commit_sha}");
+
+    git(repo_dir, &["checkout", "--quiet", "--detach", commit_sha]).map_err(|e| {
+        ReviewError::CheckoutFailed(format!(
+            "Failed to checkout commit (is it on a branch?): {e}"
+        ))
+    })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_and_repo_from_remote() {
+        assert_eq!(
+            owner_and_repo_from_remote("https://github.com/owner/repo.git"),
+            Some(("owner".to_string(), "repo".to_string()))
+        );
+        assert_eq!(
+            owner_and_repo_from_remote("git@gitlab.com:group/sub/repo.git"),
+            Some(("group/sub".to_string(), "repo".to_string()))
+        );
+        assert_eq!(owner_and_repo_from_remote("/srv/git/repo"), None);
+    }
+}
@@ -1,22 +1,21 @@
-mod api;
-mod archive;
-mod claude_session;
-mod config;
-mod error;
-mod github;
-mod session_selector;
-
-use std::time::Duration;
+use std::{path::Path, time::Duration};
 
 use anyhow::Result;
-use api::{ReviewApiClient, ReviewStatus, StartRequest};
 use clap::Parser;
-use error::ReviewError;
-use github::{checkout_commit, clone_repo, get_pr_info, parse_pr_url};
 use indicatif::{ProgressBar, ProgressStyle};
+use review::{
+    api::{ReviewApiClient, ReviewStatus, StartRequest},
+    archive, config,
+    error::ReviewError,
+    github::PrInfo,
+    session_selector, sessions,
+    target::ReviewTarget,
+    worker::{LocalReviewer, ReviewAgent, ReviewJob, ReviewStore, parse_agent_profile},
+};
 use tempfile::TempDir;
 use tracing::debug;
 use tracing_subscriber::EnvFilter;
+use uuid::Uuid;
 
 const DEFAULT_API_URL: &str = "https://api.vibekanban.com";
 const POLL_INTERVAL: Duration = Duration::from_secs(10);
@@ -35,12 +34,29 @@ const BANNER: &str = r#"
 #[derive(Parser, Debug)]
 #[command(name = "review")]
 #[command(
-    about = "Vibe-Kanban Review helps you review GitHub pull requests by turning them into a clear, story-driven summary instead of a wall of diffs. You provide a pull request URL, optionally link a Claude Code project for additional context, and it builds a narrative that highlights key events and important decisions, helping you prioritise what actually needs attention. It's particularly useful when reviewing large amounts of AI-generated code. Note that code is uploaded to and processed on Vibe-Kanban servers using AI."
+    about = "Vibe-Kanban Review helps you review GitHub pull requests, GitLab merge requests and local branches by turning them into a clear, story-driven summary instead of a wall of diffs. You provide a pull request URL, a merge request URL or a local repository with --base/--head (or a vibe-kanban --workspace), optionally link the coding agent sessions that produced it (Claude Code, Codex, Gemini, Qwen or vibe-kanban) for additional context, and it builds a narrative that highlights key events and important decisions, helping you prioritise what actually needs attention. It's particularly useful when reviewing large amounts of AI-generated code. Note that code is uploaded to and processed on Vibe-Kanban servers using AI, unless you pass --local to run the review with a coding agent on your machine."
 )]
 #[command(version)]
 struct Args {
-    /// GitHub PR URL (e.g., https://github.com/owner/repo/pull/123)
-    pr_url: String,
+    /// GitHub PR URL (e.g., https://github.com/owner/repo/pull/123), GitLab MR URL, or path to a local git repository
+    #[arg(required_unless_present = "workspace")]
+    target: Option<String>,
+
+    /// Base ref when reviewing a local repository (e.g. main)
+    #[arg(long)]
+    base: Option<String>,
+
+    /// Head ref when reviewing a local repository
+    #[arg(long, default_value = "HEAD")]
+    head: String,
+
+    /// Review a vibe-kanban workspace's branch against its target branch
+    #[arg(long, conflicts_with = "target")]
+    workspace: Option<Uuid>,
+
+    /// Repository to review when the workspace has several
+    #[arg(long, requires = "workspace")]
+    repo: Option<String>,
 
     /// Enable verbose output
     #[arg(short, long, default_value_t = false)]
@@ -49,6 +65,14 @@ struct Args {
     /// API base URL
     #[arg(long, env = "REVIEW_API_URL", default_value = DEFAULT_API_URL)]
     api_url: String,
+
+    /// Run the review on this machine with a local coding agent instead of uploading it
+    #[arg(long, default_value_t = false)]
+    local: bool,
+
+    /// Coding agent profile for --local, as AGENT[:VARIANT] (e.g. CLAUDE_CODE or CODEX:HIGH)
+    #[arg(long, env = "REVIEW_AGENT", default_value = "CLAUDE_CODE")]
+    agent: String,
 }
 
 fn show_disclaimer() {
@@ -117,7 +141,9 @@ async fn main() -> Result<()> {
 
     println!("{}", BANNER);
 
-    show_disclaimer();
+    if !args.local {
+        show_disclaimer();
+    }
 
     debug!("Args: {:?}", args);
 
@@ -131,61 +157,85 @@ async fn main() -> Result<()> {
 }
 
 async fn run(args: Args) -> Result<(), ReviewError> {
-    // 1. Load config and prompt for email
-    let mut config = config::Config::load();
-    let email = prompt_email(&mut config);
-
-    // 2. Parse PR URL
-    let spinner = create_spinner("Parsing PR URL...");
-    let (owner, repo, pr_number) = parse_pr_url(&args.pr_url)?;
-    spinner.finish_with_message(format!("PR: {owner}/{repo}#{pr_number}"));
+    // 1. Load config and prompt for email (local reviews don't send one)
+    let email = if args.local {
+        None
+    } else {
+        let mut config = config::Config::load();
+        Some(prompt_email(&mut config))
+    };
 
-    // 3. Get PR info
-    let spinner = create_spinner("Fetching PR information...");
-    let pr_info = get_pr_info(&owner, &repo, pr_number)?;
-    spinner.finish_with_message(format!("PR: {}", pr_info.title));
-
-    // 4. Select Claude Code session (optional)
-    let session_files = match session_selector::select_session(&pr_info.head_ref_name) {
-        Ok(session_selector::SessionSelection::Selected(files)) => {
-            println!("  Selected {} session file(s)", files.len());
-            Some(files)
-        }
-        Ok(session_selector::SessionSelection::Skipped) => {
-            println!("  Skipping project attachment");
-            None
+    // 2. Resolve what to review
+    let spinner = create_spinner("Resolving review target...");
+    let target = match (&args.workspace, &args.target) {
+        (Some(workspace_id), _) => {
+            ReviewTarget::from_workspace(*workspace_id, args.repo.as_deref()).await?
         }
-        Err(e) => {
-            debug!("Session selection error: {}", e);
-            println!("  No sessions found");
-            None
+        (None, Some(target)) => ReviewTarget::parse(target, args.base.as_deref(), &args.head)?,
+        (None, None) => {
+            return Err(ReviewError::InvalidTarget(
+                "Pass a PR/MR URL, a repository path or --workspace".to_string(),
+            ));
         }
     };
+    spinner.finish_with_message(format!("Reviewing: {target}"));
+
+    // 3. Get PR info
+    let spinner = create_spinner("Fetching change information...");
+    let pr_info = target.fetch_info()?;
+    spinner.finish_with_message(format!("Title: {}", pr_info.title));
+
+    // 4. Select coding agent sessions (optional)
+    let session_sources = sessions::all_sources();
+    let session_group =
+        match session_selector::select_session(&session_sources, &pr_info.head_ref_name).await {
+            Ok(session_selector::SessionSelection::Selected(group)) => {
+                println!(
+                    "  Selected {} session(s) from {}",
+                    group.session_count, group.source
+                );
+                Some(group)
+            }
+            Ok(session_selector::SessionSelection::Skipped) => {
+                println!("  Skipping project attachment");
+                None
+            }
+            Err(e) => {
+                debug!("Session selection error: {}", e);
+                println!("  No sessions found");
+                None
+            }
+        };
 
     // 5. Clone repository to temp directory
     let temp_dir = TempDir::new().map_err(|e| ReviewError::CloneFailed(e.to_string()))?;
-    let repo_dir = temp_dir.path().join(&repo);
+    let repo_dir = temp_dir.path().join(target.repo_name());
 
     let spinner = create_spinner("Cloning repository...");
-    clone_repo(&owner, &repo, &repo_dir)?;
+    target.clone_repo(&repo_dir)?;
     spinner.finish_with_message("Repository cloned");
 
-    // 6. Checkout PR head commit
-    let spinner = create_spinner("Checking out PR...");
-    checkout_commit(&pr_info.head_commit, &repo_dir)?;
-    spinner.finish_with_message("PR checked out");
-
-    // 7. Create tarball (with optional session data)
-    let spinner = create_spinner("Creating archive...");
+    // 6. Checkout head commit
+    let spinner = create_spinner("Checking out changes...");
+    target.checkout(&pr_info, &repo_dir)?;
+    spinner.finish_with_message("Changes checked out");
 
     // If sessions were selected, write .agent-messages.json to repo root
-    if let Some(ref files) = session_files {
-        let json_content = claude_session::concatenate_sessions_to_json(files)?;
+    if let Some(ref group) = session_group {
+        let messages = sessions::load_group_messages(&session_sources, group).await?;
+        let json_content = sessions::messages_to_json(messages)?;
         let agent_messages_path = repo_dir.join(".agent-messages.json");
         std::fs::write(&agent_messages_path, json_content)
             .map_err(|e| ReviewError::ArchiveFailed(e.to_string()))?;
     }
 
+    let Some(email) = email else {
+        return run_local(&args, pr_info, &repo_dir).await;
+    };
+
+    // 7. Create tarball (with optional session data)
+    let spinner = create_spinner("Creating archive...");
+
     let payload = archive::create_tarball(&repo_dir)?;
     let size_mb = payload.len() as f64 / 1_048_576.0;
     spinner.finish_with_message(format!("Archive created ({size_mb:.2} MB)"));
@@ -193,7 +243,8 @@ async fn run(args: Args) -> Result<(), ReviewError> {
     // 8. Initialize review
     let client = ReviewApiClient::new(args.api_url.clone());
     let spinner = create_spinner("Initializing review...");
-    let init_response = client.init(&args.pr_url, &email, &pr_info.title).await?;
+    let review_url = target.review_url(&pr_info);
+    let init_response = client.init(&review_url, &email, &pr_info.title).await?;
     spinner.finish_with_message(format!("Review ID: {}", init_response.review_id));
 
     // 9. Upload archive
@@ -213,6 +264,7 @@ async fn run(args: Args) -> Result<(), ReviewError> {
             repo: pr_info.repo,
             codebase_url,
             base_commit: pr_info.base_commit,
+            callback_url: None,
         })
         .await?;
     spinner.finish_with_message(format!("Review started, we'll send you an email at {} when the review is ready. This can take a few minutes, you may now close the terminal", email));
@@ -258,3 +310,48 @@ async fn run(args: Args) -> Result<(), ReviewError> {
 
     Ok(())
 }
+
+/// Review the checked-out PR with a local coding agent, without uploading anything.
+async fn run_local(args: &Args, pr_info: PrInfo, repo_dir: &Path) -> Result<(), ReviewError> {
+    let profile = parse_agent_profile(&args.agent)?;
+    let store = ReviewStore::new(ReviewStore::default_root());
+    // The sandbox needs bubblewrap, which only exists on Linux
+    let agent = ReviewAgent::new(profile.clone()).sandboxed(cfg!(target_os = "linux"));
+    let reviewer = LocalReviewer::new(store, agent);
+
+    let job = ReviewJob {
+        id: Uuid::new_v4(),
+        title: pr_info.title,
+        description: pr_info.description,
+        base_commit: pr_info.base_commit,
+    };
+
+    let spinner = create_spinner(&format!("Reviewing with {profile}..."));
+    let result = match reviewer.run_in_dir(&job, repo_dir).await {
+        Ok(result) => {
+            spinner.finish_with_message("Review completed!");
+            result
+        }
+        Err(e) => {
+            spinner.finish_with_message("Review failed");
+            return Err(e);
+        }
+    };
+
+    println!("\n{}\n", result.summary);
+    for (index, comment) in result.comments.iter().enumerate() {
+        println!("{}. {}", index + 1, comment.comment);
+        for fragment in &comment.fragments {
+            println!(
+                "   {}:{}-{}  {}",
+                fragment.file, fragment.start_line, fragment.end_line, fragment.message
+            );
+        }
+        println!();
+    }
+
+    println!("Results saved to:");
+    println!("  {}", reviewer.store().review_dir(job.id).display());
+
+    Ok(())
+}
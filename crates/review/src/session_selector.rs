@@ -1,75 +1,71 @@
-use std::{path::PathBuf, time::SystemTime};
+use std::time::SystemTime;
 
 use dialoguer::{Select, theme::ColorfulTheme};
 use tracing::debug;
 
 use crate::{
-    claude_session::{
-        ClaudeProject, discover_projects, discover_sessions, find_projects_by_branch,
-    },
     error::ReviewError,
+    sessions::{SessionGroup, SessionSource, discover_all, find_groups_by_branch},
 };
 
 /// Result of session selection process
 pub enum SessionSelection {
-    /// User selected session files to include (all sessions from a project)
-    Selected(Vec<PathBuf>),
+    /// User selected a session group to include (all of its sessions)
+    Selected(SessionGroup),
     /// User chose to skip session attachment
     Skipped,
 }
 
-/// Prompt user to select a Claude Code project
+/// Prompt user to select coding agent sessions from any of `sources`
 ///
 /// Flow:
 /// 1. Try auto-match by branch name
 /// 2. If match found, confirm with user
-/// 3. If no match or user declines, show scrollable project list
+/// 3. If no match or user declines, show scrollable list of session groups
 /// 4. Allow user to skip entirely
 ///
-/// When a project is selected, ALL sessions from that project are included.
-pub fn select_session(pr_branch: &str) -> Result<SessionSelection, ReviewError> {
-    debug!(
-        "Looking for Claude Code projects matching branch: {}",
-        pr_branch
-    );
+/// When a group is selected, ALL sessions from that group are included.
+pub async fn select_session(
+    sources: &[Box<dyn SessionSource>],
+    pr_branch: &str,
+) -> Result<SessionSelection, ReviewError> {
+    debug!("Looking for agent sessions matching branch: {}", pr_branch);
 
-    let projects = discover_projects()?;
+    let groups = discover_all(sources).await;
 
-    if projects.is_empty() {
-        debug!("No Claude Code projects found");
+    if groups.is_empty() {
+        debug!("No agent sessions found");
         return Ok(SessionSelection::Skipped);
     }
 
     // Try auto-match by branch
-    let matches = find_projects_by_branch(&projects, pr_branch)?;
-
-    if !matches.is_empty() {
-        // Found a matching project, ask for confirmation
-        let (project, sessions) = &matches[0];
+    let matches = find_groups_by_branch(&groups, pr_branch);
 
+    if let Some(group) = matches.first() {
+        // Found a matching group, ask for confirmation
         println!();
         println!();
         println!(
-            "Found matching Claude Code project for branch '{}'",
-            pr_branch
+            "Found matching {} sessions for branch '{}'",
+            group.source, pr_branch
         );
-        println!("  Project: {}", project.name);
-        if let Some(ref prompt) = project.first_prompt {
+        println!("  Project: {}", group.name);
+        if let Some(ref prompt) = group.first_prompt {
             println!("  \"{}\"", prompt);
         }
         println!(
             "  {} session{} · Last modified: {}",
-            project.session_count,
-            if project.session_count == 1 { "" } else { "s" },
-            format_time_ago(project.modified_at)
+            group.session_count,
+            if group.session_count == 1 { "" } else { "s" },
+            format_time_ago(group.modified_at)
         );
         println!();
 
         let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Use this project to improve review quality?")
+            .with_prompt("Use these sessions to improve review quality?")
             .items(&[
-                "Yes, use this project",
-                "No, choose a different project",
+                "Yes, use these sessions",
+                "No, choose different sessions",
                 "Skip (generate review from just code changes)",
             ])
             .default(0)
@@ -78,9 +74,8 @@ pub fn select_session(pr_branch: &str) -> Result<SessionSelection, ReviewError>
 
         match selection {
             0 => {
-                // Yes, use all sessions from this project
-                let paths: Vec<PathBuf> = sessions.iter().map(|s| s.path.clone()).collect();
-                return Ok(SessionSelection::Selected(paths));
+                // Yes, use all sessions from this group
+                return Ok(SessionSelection::Selected((*group).clone()));
             }
             2 => {
                 // Skip
@@ -92,22 +87,22 @@ pub fn select_session(pr_branch: &str) -> Result<SessionSelection, ReviewError>
         }
     }
 
-    // Manual selection: select a project
-    select_project(&projects)
+    // Manual selection: select a group
+    select_group(groups)
 }
 
-/// Manual project selection - returns all sessions from selected project
-fn select_project(projects: &[ClaudeProject]) -> Result<SessionSelection, ReviewError> {
-    // Build project list with rich metadata
+/// Manual selection - returns the selected group with all its sessions
+fn select_group(mut groups: Vec<SessionGroup>) -> Result<SessionSelection, ReviewError> {
+    // Build list with rich metadata
     let mut items: Vec<String> = Vec::new();
-    items.push("Skip (no project)\n".to_string());
-    items.extend(projects.iter().map(format_project_item));
-    items.push("Skip (no project)\n".to_string());
+    items.push("Skip (no sessions)\n".to_string());
+    items.extend(groups.iter().map(format_group_item));
+    items.push("Skip (no sessions)\n".to_string());
 
     println!();
     println!();
     let selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Select a Claude Code project to improve review quality")
+        .with_prompt("Select coding agent sessions to improve review quality")
         .items(&items)
         .default(0)
         .max_length(5)
@@ -119,36 +114,34 @@ fn select_project(projects: &[ClaudeProject]) -> Result<SessionSelection, Review
         return Ok(SessionSelection::Skipped);
     }
 
-    let project = &projects[selection];
-    let sessions = discover_sessions(project)?;
-
-    // Return all session paths from this project
-    let paths: Vec<PathBuf> = sessions.iter().map(|s| s.path.clone()).collect();
-    Ok(SessionSelection::Selected(paths))
+    Ok(SessionSelection::Selected(
+        groups.swap_remove(selection - 1),
+    ))
 }
 
-/// Format a project item for display in the selection list
-fn format_project_item(project: &ClaudeProject) -> String {
-    let prompt_line = project
+/// Format a session group for display in the selection list
+fn format_group_item(group: &SessionGroup) -> String {
+    let prompt_line = group
         .first_prompt
         .as_ref()
         .map(|p| format!("\n  \"{}\"", p))
         .unwrap_or_default();
 
-    let branch = project
+    let branch = group
         .git_branch
         .as_ref()
         .map(|b| format!("branch: {}", b))
         .unwrap_or_else(|| "no branch".to_string());
 
     format!(
-        "{}{}\n  {} · {} session{} · {}\n",
-        project.name,
+        "{} ({}){}\n  {} · {} session{} · {}\n",
+        group.name,
+        group.source,
         prompt_line,
         branch,
-        project.session_count,
-        if project.session_count == 1 { "" } else { "s" },
-        format_time_ago(project.modified_at)
+        group.session_count,
+        if group.session_count == 1 { "" } else { "s" },
+        format_time_ago(group.modified_at)
     )
 }
 
@@ -0,0 +1,172 @@
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use async_trait::async_trait;
+use serde_json::Value;
+use tracing::debug;
+
+use super::{
+    SessionGroup, SessionLocation, SessionMessage, SessionSource, SessionSourceKind,
+    system_time_to_timestamp, truncate_prompt,
+};
+use crate::error::ReviewError;
+
+/// Sessions recorded by vibe-kanban's ACP harness under
+/// `~/.vibe-kanban/<namespace>/<session_id>.jsonl`, one group per session.
+///
+/// The files hold `{"user": ...}` and `{"assistant": ...}` lines without
+/// timestamps or branch, so messages keep file order and groups are only
+/// offered for manual selection.
+pub struct AcpSource {
+    kind: SessionSourceKind,
+    namespace: &'static str,
+}
+
+impl AcpSource {
+    pub fn gemini() -> Self {
+        Self {
+            kind: SessionSourceKind::Gemini,
+            namespace: "gemini_sessions",
+        }
+    }
+
+    pub fn qwen() -> Self {
+        Self {
+            kind: SessionSourceKind::Qwen,
+            namespace: "qwen_sessions",
+        }
+    }
+
+    /// Mirrors the executors' `SessionManager`, which writes under `dev/` in debug builds
+    fn sessions_dir(&self) -> Option<PathBuf> {
+        let mut vk_dir = dirs::home_dir()?.join(".vibe-kanban");
+        if cfg!(debug_assertions) {
+            vk_dir = vk_dir.join("dev");
+        }
+        Some(vk_dir.join(self.namespace))
+    }
+}
+
+#[async_trait]
+impl SessionSource for AcpSource {
+    fn kind(&self) -> SessionSourceKind {
+        self.kind
+    }
+
+    async fn discover(&self) -> Result<Vec<SessionGroup>, ReviewError> {
+        let sessions_dir = self.sessions_dir().ok_or_else(|| {
+            ReviewError::SessionDiscoveryFailed("Could not find home directory".into())
+        })?;
+
+        if !sessions_dir.exists() {
+            debug!(
+                "{} sessions directory does not exist: {:?}",
+                self.kind, sessions_dir
+            );
+            return Ok(Vec::new());
+        }
+
+        let entries = fs::read_dir(&sessions_dir)
+            .map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+
+        let mut groups = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+            let path = entry.path();
+
+            if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+                continue;
+            }
+
+            let first_prompt = first_user_prompt(&path);
+            // Sessions that never got a prompt have nothing to offer the review
+            if first_prompt.is_none() {
+                continue;
+            }
+
+            let session_id = path
+                .file_stem()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+            let modified_at = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+
+            groups.push(SessionGroup {
+                source: self.kind,
+                name: format!("session {}", session_id.chars().take(8).collect::<String>()),
+                git_branch: None,
+                first_prompt,
+                session_count: 1,
+                modified_at,
+                location: SessionLocation::Files(vec![path]),
+            });
+        }
+
+        groups.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+        Ok(groups)
+    }
+
+    async fn load_messages(
+        &self,
+        group: &SessionGroup,
+    ) -> Result<Vec<SessionMessage>, ReviewError> {
+        let SessionLocation::Files(paths) = &group.location else {
+            return Ok(Vec::new());
+        };
+
+        // Without per-line timestamps, stamp every message with the file's
+        // modification time; the stable sort keeps them in file order
+        let timestamp = system_time_to_timestamp(group.modified_at);
+
+        let mut messages = Vec::new();
+        for path in paths {
+            let file = File::open(path)
+                .map_err(|e| ReviewError::JsonlParseFailed(format!("{}: {}", path.display(), e)))?;
+
+            for line in BufReader::new(file).lines() {
+                let line = line.map_err(|e| {
+                    ReviewError::JsonlParseFailed(format!("{}: {}", path.display(), e))
+                })?;
+                let Ok(record) = serde_json::from_str::<Value>(&line) else {
+                    continue;
+                };
+                if let Some((role, text)) = extract_text(&record) {
+                    messages.push(SessionMessage::text(role, text, timestamp.clone()));
+                }
+            }
+        }
+
+        Ok(messages)
+    }
+}
+
+/// `{"user": "..."}` and `{"assistant": "..."}` lines; tool calls, plans and
+/// thinking are skipped
+fn extract_text(record: &Value) -> Option<(&'static str, &str)> {
+    if let Some(text) = record.get("user").and_then(|v| v.as_str()) {
+        return Some(("user", text));
+    }
+    if let Some(text) = record.get("assistant").and_then(|v| v.as_str()) {
+        return Some(("assistant", text));
+    }
+    None
+}
+
+fn first_user_prompt(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    BufReader::new(file)
+        .lines()
+        .take(50)
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+        .find_map(|record| match extract_text(&record) {
+            Some(("user", text)) => Some(truncate_prompt(text, 60)),
+            _ => None,
+        })
+}
@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+
+use super::{SessionGroup, SessionLocation, SessionMessage, SessionSource, SessionSourceKind};
+use crate::{claude_session, error::ReviewError};
+
+/// Claude Code projects under `~/.claude/projects`
+pub struct ClaudeSource;
+
+#[async_trait]
+impl SessionSource for ClaudeSource {
+    fn kind(&self) -> SessionSourceKind {
+        SessionSourceKind::ClaudeCode
+    }
+
+    async fn discover(&self) -> Result<Vec<SessionGroup>, ReviewError> {
+        let projects = claude_session::discover_projects()?;
+
+        let mut groups = Vec::with_capacity(projects.len());
+        for project in projects {
+            let sessions = claude_session::discover_sessions(&project)?;
+            groups.push(SessionGroup {
+                source: SessionSourceKind::ClaudeCode,
+                name: project.name,
+                git_branch: project.git_branch,
+                first_prompt: project.first_prompt,
+                session_count: project.session_count,
+                modified_at: project.modified_at,
+                location: SessionLocation::Files(
+                    sessions.into_iter().map(|session| session.path).collect(),
+                ),
+            });
+        }
+
+        Ok(groups)
+    }
+
+    async fn load_messages(
+        &self,
+        group: &SessionGroup,
+    ) -> Result<Vec<SessionMessage>, ReviewError> {
+        match &group.location {
+            SessionLocation::Files(paths) => claude_session::load_session_messages(paths),
+            SessionLocation::Workspace(_) => Ok(Vec::new()),
+        }
+    }
+}
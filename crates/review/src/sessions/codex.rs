@@ -0,0 +1,316 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use async_trait::async_trait;
+use serde_json::Value;
+use tracing::debug;
+
+use super::{
+    SessionGroup, SessionLocation, SessionMessage, SessionSource, SessionSourceKind,
+    truncate_prompt,
+};
+use crate::error::ReviewError;
+
+/// Context Codex injects as user messages, which isn't part of the conversation
+const INJECTED_PREFIXES: &[&str] = &[
+    "<environment_context>",
+    "<user_instructions>",
+    "# AGENTS.md instructions",
+];
+
+/// Codex rollout files under `~/.codex/sessions/YYYY/MM/DD/rollout-*.jsonl`,
+/// grouped by the directory Codex ran in
+pub struct CodexSource;
+
+/// Metadata from the head of a rollout file
+#[derive(Debug, Default)]
+struct RolloutMetadata {
+    cwd: Option<String>,
+    git_branch: Option<String>,
+    first_prompt: Option<String>,
+}
+
+fn get_codex_sessions_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".codex").join("sessions"))
+}
+
+#[async_trait]
+impl SessionSource for CodexSource {
+    fn kind(&self) -> SessionSourceKind {
+        SessionSourceKind::Codex
+    }
+
+    async fn discover(&self) -> Result<Vec<SessionGroup>, ReviewError> {
+        let sessions_dir = get_codex_sessions_dir().ok_or_else(|| {
+            ReviewError::SessionDiscoveryFailed("Could not find home directory".into())
+        })?;
+
+        if !sessions_dir.exists() {
+            debug!(
+                "Codex sessions directory does not exist: {:?}",
+                sessions_dir
+            );
+            return Ok(Vec::new());
+        }
+
+        let mut rollouts = Vec::new();
+        collect_rollout_files(&sessions_dir, &mut rollouts)?;
+
+        // Newest first, so the first rollout seen for a directory sets its metadata
+        rollouts.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let mut groups: Vec<SessionGroup> = Vec::new();
+        let mut index_by_cwd: HashMap<String, usize> = HashMap::new();
+
+        for (path, modified_at) in rollouts {
+            let metadata = extract_rollout_metadata(&path);
+            let cwd = metadata.cwd.unwrap_or_default();
+
+            if let Some(&index) = index_by_cwd.get(&cwd) {
+                let group = &mut groups[index];
+                group.session_count += 1;
+                if let SessionLocation::Files(paths) = &mut group.location {
+                    paths.push(path);
+                }
+                continue;
+            }
+
+            let name = Path::new(&cwd)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown")
+                .to_string();
+
+            index_by_cwd.insert(cwd, groups.len());
+            groups.push(SessionGroup {
+                source: SessionSourceKind::Codex,
+                name,
+                git_branch: metadata.git_branch,
+                first_prompt: metadata.first_prompt,
+                session_count: 1,
+                modified_at,
+                location: SessionLocation::Files(vec![path]),
+            });
+        }
+
+        Ok(groups)
+    }
+
+    async fn load_messages(
+        &self,
+        group: &SessionGroup,
+    ) -> Result<Vec<SessionMessage>, ReviewError> {
+        let SessionLocation::Files(paths) = &group.location else {
+            return Ok(Vec::new());
+        };
+
+        let mut messages = Vec::new();
+        for path in paths {
+            let file = File::open(path)
+                .map_err(|e| ReviewError::JsonlParseFailed(format!("{}: {}", path.display(), e)))?;
+
+            for (line_num, line) in BufReader::new(file).lines().enumerate() {
+                let line = line.map_err(|e| {
+                    ReviewError::JsonlParseFailed(format!(
+                        "{}:{}: {}",
+                        path.display(),
+                        line_num + 1,
+                        e
+                    ))
+                })?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                // Codex may append partial lines while running; skip what doesn't parse
+                let Ok(record) = serde_json::from_str::<Value>(&line) else {
+                    debug!(
+                        "Skipping unparseable line {}:{}",
+                        path.display(),
+                        line_num + 1
+                    );
+                    continue;
+                };
+
+                if let Some(message) = extract_message(&record) {
+                    messages.push(message);
+                }
+            }
+        }
+
+        Ok(messages)
+    }
+}
+
+fn collect_rollout_files(
+    dir: &Path,
+    rollouts: &mut Vec<(PathBuf, SystemTime)>,
+) -> Result<(), ReviewError> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            collect_rollout_files(&path, rollouts)?;
+            continue;
+        }
+
+        let is_rollout = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|name| name.starts_with("rollout-") && name.ends_with(".jsonl"));
+        if !is_rollout {
+            continue;
+        }
+
+        let modified_at = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        rollouts.push((path, modified_at));
+    }
+
+    Ok(())
+}
+
+/// The record body: `payload` in current rollouts, the record itself in older ones
+fn record_payload(record: &Value) -> &Value {
+    record.get("payload").unwrap_or(record)
+}
+
+fn extract_rollout_metadata(path: &Path) -> RolloutMetadata {
+    let Ok(file) = File::open(path) else {
+        return RolloutMetadata::default();
+    };
+
+    let mut metadata = RolloutMetadata::default();
+
+    // Check first 50 lines for metadata
+    for line in BufReader::new(file).lines().take(50) {
+        let Ok(line) = line else { continue };
+        let Ok(record) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let payload = record_payload(&record);
+
+        if metadata.cwd.is_none()
+            && let Some(cwd) = payload.get("cwd").and_then(|v| v.as_str())
+        {
+            metadata.cwd = Some(cwd.to_string());
+            metadata.git_branch = payload
+                .get("git")
+                .and_then(|git| git.get("branch"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+        }
+
+        if metadata.first_prompt.is_none()
+            && let Some(message) = extract_message(&record)
+            && message.message.get("role").and_then(|r| r.as_str()) == Some("user")
+            && let Some(text) = message.message["content"][0]
+                .get("text")
+                .and_then(|t| t.as_str())
+        {
+            metadata.first_prompt = Some(truncate_prompt(text, 60));
+        }
+
+        if metadata.cwd.is_some() && metadata.first_prompt.is_some() {
+            break;
+        }
+    }
+
+    metadata
+}
+
+/// Extract a user or assistant message with its text content, skipping
+/// reasoning, tool calls and injected context
+fn extract_message(record: &Value) -> Option<SessionMessage> {
+    let payload = record_payload(record);
+    if payload.get("type").and_then(|t| t.as_str()) != Some("message") {
+        return None;
+    }
+
+    let role = payload.get("role")?.as_str()?;
+    if role != "user" && role != "assistant" {
+        return None;
+    }
+
+    let text_blocks: Vec<Value> = payload
+        .get("content")?
+        .as_array()?
+        .iter()
+        .filter(|block| {
+            matches!(
+                block.get("type").and_then(|t| t.as_str()),
+                Some("input_text" | "output_text" | "text")
+            )
+        })
+        .filter_map(|block| block.get("text").and_then(|t| t.as_str()))
+        .filter(|text| {
+            let text = text.trim_start();
+            !INJECTED_PREFIXES
+                .iter()
+                .any(|prefix| text.starts_with(prefix))
+        })
+        .map(|text| serde_json::json!({ "type": "text", "text": text }))
+        .collect();
+
+    if text_blocks.is_empty() {
+        return None;
+    }
+
+    let timestamp = record
+        .get("timestamp")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    Some(SessionMessage {
+        timestamp,
+        message: serde_json::json!({ "role": role, "content": text_blocks }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_message() {
+        let user: Value = serde_json::from_str(
+            r#"{"timestamp":"2025-10-01T10:00:00.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"Add a parser"}]}}"#,
+        )
+        .unwrap();
+        let message = extract_message(&user).expect("Should extract user message");
+        assert_eq!(message.timestamp, "2025-10-01T10:00:00.000Z");
+        assert_eq!(message.message["role"], "user");
+        assert_eq!(message.message["content"][0]["text"], "Add a parser");
+
+        let assistant: Value = serde_json::from_str(
+            r#"{"timestamp":"2025-10-01T10:01:00.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"Done"}]}}"#,
+        )
+        .unwrap();
+        let message = extract_message(&assistant).expect("Should extract assistant message");
+        assert_eq!(message.message["content"][0]["text"], "Done");
+
+        let context: Value = serde_json::from_str(
+            r#"{"timestamp":"2025-10-01T10:00:00.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"<environment_context>\n<cwd>/tmp</cwd>\n</environment_context>"}]}}"#,
+        )
+        .unwrap();
+        assert!(extract_message(&context).is_none());
+
+        let reasoning: Value = serde_json::from_str(
+            r#"{"timestamp":"2025-10-01T10:00:30.000Z","type":"response_item","payload":{"type":"reasoning","summary":[]}}"#,
+        )
+        .unwrap();
+        assert!(extract_message(&reasoning).is_none());
+    }
+}
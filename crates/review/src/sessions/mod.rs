@@ -0,0 +1,179 @@
+//! Coding agent session sources.
+//!
+//! Each source discovers [`SessionGroup`]s (a project, worktree or workspace
+//! holding one or more sessions) and loads them as user/assistant text
+//! messages, in the same shape [`crate::claude_session`] produces, so the
+//! review gets a single `.agent-messages.json` whichever agent wrote the code.
+
+mod acp;
+mod claude;
+mod codex;
+mod vibe_kanban;
+
+use std::{fmt, path::PathBuf, time::SystemTime};
+
+pub use acp::AcpSource;
+use async_trait::async_trait;
+pub use claude::ClaudeSource;
+pub use codex::CodexSource;
+use serde_json::json;
+use uuid::Uuid;
+pub use vibe_kanban::VibeKanbanSource;
+
+use crate::{claude_session::branches_match, error::ReviewError};
+
+/// Agent that produced a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionSourceKind {
+    ClaudeCode,
+    Codex,
+    Gemini,
+    Qwen,
+    VibeKanban,
+}
+
+impl fmt::Display for SessionSourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionSourceKind::ClaudeCode => write!(f, "Claude Code"),
+            SessionSourceKind::Codex => write!(f, "Codex"),
+            SessionSourceKind::Gemini => write!(f, "Gemini"),
+            SessionSourceKind::Qwen => write!(f, "Qwen"),
+            SessionSourceKind::VibeKanban => write!(f, "Vibe Kanban"),
+        }
+    }
+}
+
+/// Where the sessions of a group live
+#[derive(Debug, Clone)]
+pub enum SessionLocation {
+    /// Session files on disk
+    Files(Vec<PathBuf>),
+    /// A vibe-kanban workspace
+    Workspace(Uuid),
+}
+
+/// A set of sessions that belong together, e.g. one project or workspace
+#[derive(Debug, Clone)]
+pub struct SessionGroup {
+    pub source: SessionSourceKind,
+    pub name: String,
+    pub git_branch: Option<String>,
+    pub first_prompt: Option<String>,
+    pub session_count: usize,
+    pub modified_at: SystemTime,
+    pub location: SessionLocation,
+}
+
+/// A filtered message with the timestamp used for ordering
+#[derive(Debug, Clone)]
+pub struct SessionMessage {
+    pub timestamp: String,
+    pub message: serde_json::Value,
+}
+
+impl SessionMessage {
+    /// A message with a single text content block
+    pub fn text(role: &str, text: &str, timestamp: String) -> Self {
+        Self {
+            timestamp,
+            message: json!({
+                "role": role,
+                "content": [{ "type": "text", "text": text }],
+            }),
+        }
+    }
+}
+
+#[async_trait]
+pub trait SessionSource: Send + Sync {
+    fn kind(&self) -> SessionSourceKind;
+
+    /// Discover session groups, most recently modified first
+    async fn discover(&self) -> Result<Vec<SessionGroup>, ReviewError>;
+
+    /// Load the user and assistant text messages of a group
+    async fn load_messages(&self, group: &SessionGroup)
+    -> Result<Vec<SessionMessage>, ReviewError>;
+}
+
+/// All built-in session sources
+pub fn all_sources() -> Vec<Box<dyn SessionSource>> {
+    vec![
+        Box::new(ClaudeSource),
+        Box::new(CodexSource),
+        Box::new(AcpSource::gemini()),
+        Box::new(AcpSource::qwen()),
+        Box::new(VibeKanbanSource::new()),
+    ]
+}
+
+/// Discover groups from every source, most recently modified first.
+/// Sources that fail are skipped so one broken agent install doesn't hide the rest.
+pub async fn discover_all(sources: &[Box<dyn SessionSource>]) -> Vec<SessionGroup> {
+    let mut groups = Vec::new();
+    for source in sources {
+        match source.discover().await {
+            Ok(found) => groups.extend(found),
+            Err(e) => tracing::debug!("Skipping {} sessions: {}", source.kind(), e),
+        }
+    }
+    groups.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    groups
+}
+
+/// Groups whose branch matches `target_branch`, keeping the input order
+pub fn find_groups_by_branch<'a>(
+    groups: &'a [SessionGroup],
+    target_branch: &str,
+) -> Vec<&'a SessionGroup> {
+    groups
+        .iter()
+        .filter(|group| {
+            group
+                .git_branch
+                .as_deref()
+                .is_some_and(|branch| branches_match(target_branch, branch))
+        })
+        .collect()
+}
+
+/// Load the messages of `group` from the source that discovered it
+pub async fn load_group_messages(
+    sources: &[Box<dyn SessionSource>],
+    group: &SessionGroup,
+) -> Result<Vec<SessionMessage>, ReviewError> {
+    let source = sources
+        .iter()
+        .find(|source| source.kind() == group.source)
+        .ok_or_else(|| {
+            ReviewError::SessionDiscoveryFailed(format!("No session source for {}", group.source))
+        })?;
+    source.load_messages(group).await
+}
+
+/// Sort messages by timestamp and serialize them as a JSON array
+pub fn messages_to_json(mut messages: Vec<SessionMessage>) -> Result<String, ReviewError> {
+    // Stable sort keeps the file order of messages that share a timestamp
+    messages.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+    let messages: Vec<serde_json::Value> = messages.into_iter().map(|m| m.message).collect();
+
+    serde_json::to_string(&messages).map_err(|e| ReviewError::JsonlParseFailed(e.to_string()))
+}
+
+/// Truncate a prompt for display in the selection list
+pub(crate) fn truncate_prompt(s: &str, max_len: usize) -> String {
+    let s = s.replace('\n', " ");
+    if s.chars().count() <= max_len {
+        s
+    } else {
+        let truncated: String = s.chars().take(max_len - 3).collect();
+        format!("{truncated}...")
+    }
+}
+
+/// Format a `SystemTime` as an RFC 3339 timestamp for ordering messages
+pub(crate) fn system_time_to_timestamp(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
@@ -0,0 +1,242 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::SystemTime,
+};
+
+use async_trait::async_trait;
+use db::models::{
+    execution_process::ExecutionProcess, execution_process_logs::ExecutionProcessLogs,
+    session::Session, task::Task, workspace::Workspace,
+};
+use executors::{
+    actions::ExecutorActionType,
+    executors::StandardCodingAgentExecutor,
+    logs::{NormalizedEntryType, utils::patch::collect_normalized_entries},
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
+use futures::{StreamExt, future};
+use sqlx::{
+    SqlitePool,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+};
+use tracing::debug;
+use utils::{assets::asset_dir, log_msg::LogMsg, msg_store::MsgStore};
+
+use super::{
+    SessionGroup, SessionLocation, SessionMessage, SessionSource, SessionSourceKind,
+    truncate_prompt,
+};
+use crate::error::ReviewError;
+
+/// Coding agent conversations from vibe-kanban's own database, one group per
+/// workspace. Workspace branches are matched against the PR branch like any
+/// other source.
+pub struct VibeKanbanSource {
+    db_path: PathBuf,
+}
+
+impl VibeKanbanSource {
+    pub fn new() -> Self {
+        Self {
+            db_path: asset_dir().join("db.sqlite"),
+        }
+    }
+
+    /// Opens the database read-only so the review never migrates or locks it
+    pub(crate) async fn connect(&self) -> Result<Option<SqlitePool>, ReviewError> {
+        if !self.db_path.exists() {
+            debug!("vibe-kanban database does not exist: {:?}", self.db_path);
+            return Ok(None);
+        }
+
+        let database_url = format!("sqlite://{}", self.db_path.to_string_lossy());
+        let options = SqliteConnectOptions::from_str(&database_url)
+            .map_err(db_error)?
+            .read_only(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .map_err(db_error)?;
+        Ok(Some(pool))
+    }
+}
+
+impl Default for VibeKanbanSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn db_error(e: impl std::fmt::Display) -> ReviewError {
+    ReviewError::SessionDiscoveryFailed(e.to_string())
+}
+
+#[async_trait]
+impl SessionSource for VibeKanbanSource {
+    fn kind(&self) -> SessionSourceKind {
+        SessionSourceKind::VibeKanban
+    }
+
+    async fn discover(&self) -> Result<Vec<SessionGroup>, ReviewError> {
+        let Some(pool) = self.connect().await? else {
+            return Ok(Vec::new());
+        };
+
+        let workspaces = Workspace::fetch_all(&pool, None).await.map_err(db_error)?;
+
+        let mut groups = Vec::new();
+        for workspace in workspaces {
+            let sessions = Session::find_by_workspace_id(&pool, workspace.id)
+                .await
+                .map_err(db_error)?;
+            if sessions.is_empty() {
+                continue;
+            }
+
+            let task = Task::find_by_id(&pool, workspace.task_id)
+                .await
+                .map_err(db_error)?;
+            let (name, first_prompt) = match task {
+                Some(task) => (
+                    task.title,
+                    task.description
+                        .filter(|d| !d.trim().is_empty())
+                        .map(|d| truncate_prompt(&d, 60)),
+                ),
+                None => (workspace.branch.clone(), None),
+            };
+
+            groups.push(SessionGroup {
+                source: SessionSourceKind::VibeKanban,
+                name,
+                git_branch: Some(workspace.branch),
+                first_prompt,
+                session_count: sessions.len(),
+                modified_at: SystemTime::from(workspace.updated_at),
+                location: SessionLocation::Workspace(workspace.id),
+            });
+        }
+
+        pool.close().await;
+        groups.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+        Ok(groups)
+    }
+
+    async fn load_messages(
+        &self,
+        group: &SessionGroup,
+    ) -> Result<Vec<SessionMessage>, ReviewError> {
+        let SessionLocation::Workspace(workspace_id) = group.location else {
+            return Ok(Vec::new());
+        };
+        let Some(pool) = self.connect().await? else {
+            return Ok(Vec::new());
+        };
+
+        let workspace = Workspace::find_by_id(&pool, workspace_id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| db_error(format!("Workspace {workspace_id} not found")))?;
+        let sessions = Session::find_by_workspace_id(&pool, workspace_id)
+            .await
+            .map_err(db_error)?;
+
+        let mut messages = Vec::new();
+        for session in sessions {
+            let processes = ExecutionProcess::find_by_session_id(&pool, session.id, false)
+                .await
+                .map_err(db_error)?;
+
+            for process in processes {
+                let Ok(action) = process.executor_action() else {
+                    continue;
+                };
+                let (prompt, profile, is_follow_up) = match action.typ() {
+                    ExecutorActionType::CodingAgentInitialRequest(request) => {
+                        (&request.prompt, &request.executor_profile_id, false)
+                    }
+                    ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                        (&request.prompt, &request.executor_profile_id, true)
+                    }
+                    ExecutorActionType::ScriptRequest(_) => continue,
+                };
+
+                // Every message of a turn shares its start time, so the
+                // stable sort keeps the prompt ahead of the replies
+                let timestamp = process
+                    .started_at
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+                messages.push(SessionMessage::text("user", prompt, timestamp.clone()));
+
+                let records = ExecutionProcessLogs::find_by_execution_id(&pool, process.id)
+                    .await
+                    .map_err(db_error)?;
+                let worktree = workspace
+                    .container_ref
+                    .as_deref()
+                    .map(PathBuf::from)
+                    .unwrap_or_default();
+                for reply in assistant_replies(&records, profile, is_follow_up, &worktree).await {
+                    messages.push(SessionMessage::text("assistant", &reply, timestamp.clone()));
+                }
+            }
+        }
+
+        pool.close().await;
+        Ok(messages)
+    }
+}
+
+/// Replays stored raw logs through the executor's normalizer, the same way
+/// the server rebuilds conversations for finished processes, and returns the
+/// final text of each assistant message
+async fn assistant_replies(
+    records: &[ExecutionProcessLogs],
+    profile: &ExecutorProfileId,
+    is_follow_up: bool,
+    worktree: &Path,
+) -> Vec<String> {
+    let raw_messages = match ExecutionProcessLogs::parse_logs(records) {
+        Ok(messages) => messages,
+        Err(e) => {
+            debug!("Failed to parse stored logs: {}", e);
+            return Vec::new();
+        }
+    };
+    if raw_messages.is_empty() {
+        return Vec::new();
+    }
+
+    let store = Arc::new(MsgStore::new());
+    for msg in raw_messages {
+        if matches!(
+            msg,
+            LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_)
+        ) {
+            store.push(msg);
+        }
+    }
+    store.push_finished();
+
+    let executor = ExecutorConfigs::get_cached().get_coding_agent_or_default(profile);
+    if is_follow_up {
+        executor.normalize_follow_up_logs(store.clone(), worktree);
+    } else {
+        executor.normalize_logs(store.clone(), worktree);
+    }
+
+    // Only the normalizer's patches follow the stored logs' `Finished`
+    let normalized = store
+        .history_plus_stream()
+        .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(_)))));
+    collect_normalized_entries(normalized)
+        .await
+        .into_iter()
+        .filter(|entry| matches!(entry.entry_type, NormalizedEntryType::AssistantMessage))
+        .map(|entry| entry.content)
+        .filter(|content| !content.trim().is_empty())
+        .collect()
+}
@@ -0,0 +1,271 @@
+//! What to review: a GitHub PR, a GitLab MR, or a commit range in a local
+//! repository (including a vibe-kanban workspace branch).
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use db::models::{workspace::Workspace, workspace_repo::WorkspaceRepo};
+use uuid::Uuid;
+
+use crate::{
+    error::ReviewError,
+    github::{self, PrInfo},
+    gitlab::{self, MrUrl},
+    local,
+    sessions::VibeKanbanSource,
+};
+
+#[derive(Debug, Clone)]
+pub enum ReviewTarget {
+    GitHubPr {
+        owner: String,
+        repo: String,
+        number: i64,
+    },
+    GitLabMr(MrUrl),
+    /// `base..head` in a local repository
+    Local {
+        repo_dir: PathBuf,
+        base: String,
+        head: String,
+    },
+}
+
+impl fmt::Display for ReviewTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReviewTarget::GitHubPr {
+                owner,
+                repo,
+                number,
+            } => write!(f, "{owner}/{repo}#{number}"),
+            ReviewTarget::GitLabMr(mr) => write!(f, "{}!{}", mr.project, mr.iid),
+            ReviewTarget::Local {
+                repo_dir,
+                base,
+                head,
+            } => write!(f, "{} {base}..{head}", repo_dir.display()),
+        }
+    }
+}
+
+impl ReviewTarget {
+    /// Parse a PR/MR URL, or a local repository path reviewed from `base` to `head`
+    pub fn parse(input: &str, base: Option<&str>, head: &str) -> Result<Self, ReviewError> {
+        let input = input.trim();
+
+        if input.contains("/-/merge_requests/") {
+            return Ok(ReviewTarget::GitLabMr(gitlab::parse_mr_url(input)?));
+        }
+
+        if input.starts_with("https://")
+            || input.starts_with("http://")
+            || input.starts_with("github.com/")
+        {
+            let (owner, repo, number) = github::parse_pr_url(input)?;
+            return Ok(ReviewTarget::GitHubPr {
+                owner,
+                repo,
+                number,
+            });
+        }
+
+        let base = base.ok_or_else(|| {
+            ReviewError::InvalidTarget(
+                "--base is required when reviewing a local repository".to_string(),
+            )
+        })?;
+
+        Ok(ReviewTarget::Local {
+            repo_dir: local::repo_root(&PathBuf::from(input))?,
+            base: base.to_string(),
+            head: head.to_string(),
+        })
+    }
+
+    /// Review a vibe-kanban workspace's branch against its target branch.
+    ///
+    /// Workspaces spanning several repositories need `repo_name` to pick one.
+    pub async fn from_workspace(
+        workspace_id: Uuid,
+        repo_name: Option<&str>,
+    ) -> Result<Self, ReviewError> {
+        let pool = VibeKanbanSource::new().connect().await?.ok_or_else(|| {
+            ReviewError::InvalidTarget("vibe-kanban database not found".to_string())
+        })?;
+
+        let workspace = Workspace::find_by_id(&pool, workspace_id)
+            .await
+            .map_err(|e| ReviewError::InvalidTarget(e.to_string()))?
+            .ok_or_else(|| {
+                ReviewError::InvalidTarget(format!("Workspace {workspace_id} not found"))
+            })?;
+        let mut repos =
+            WorkspaceRepo::find_repos_with_target_branch_for_workspace(&pool, workspace_id)
+                .await
+                .map_err(|e| ReviewError::InvalidTarget(e.to_string()))?;
+        pool.close().await;
+
+        let index = match (repo_name, repos.len()) {
+            (_, 0) => {
+                return Err(ReviewError::InvalidTarget(format!(
+                    "Workspace {workspace_id} has no repositories"
+                )));
+            }
+            (None, 1) => 0,
+            (None, _) => {
+                let names: Vec<&str> = repos.iter().map(|r| r.repo.name.as_str()).collect();
+                return Err(ReviewError::InvalidTarget(format!(
+                    "Workspace has several repositories, pick one with --repo: {}",
+                    names.join(", ")
+                )));
+            }
+            (Some(name), _) => repos
+                .iter()
+                .position(|r| r.repo.name == name)
+                .ok_or_else(|| {
+                    ReviewError::InvalidTarget(format!(
+                        "Workspace has no repository named '{name}'"
+                    ))
+                })?,
+        };
+        let repo = repos.swap_remove(index);
+
+        // Branches are shared with the workspace worktree, so the source repo
+        // sees the agent's commits
+        Ok(ReviewTarget::Local {
+            repo_dir: repo.repo.path,
+            base: repo.target_branch,
+            head: workspace.branch,
+        })
+    }
+
+    /// Fetch title, description and the commits under review
+    pub fn fetch_info(&self) -> Result<PrInfo, ReviewError> {
+        match self {
+            ReviewTarget::GitHubPr {
+                owner,
+                repo,
+                number,
+            } => github::get_pr_info(owner, repo, *number),
+            ReviewTarget::GitLabMr(mr) => gitlab::get_mr_info(mr),
+            ReviewTarget::Local {
+                repo_dir,
+                base,
+                head,
+            } => local::get_local_info(repo_dir, base, head),
+        }
+    }
+
+    /// Clone the repository into `target_dir`
+    pub fn clone_repo(&self, target_dir: &Path) -> Result<(), ReviewError> {
+        match self {
+            ReviewTarget::GitHubPr { owner, repo, .. } => {
+                github::clone_repo(owner, repo, target_dir)
+            }
+            ReviewTarget::GitLabMr(mr) => gitlab::clone_repo(mr, target_dir),
+            ReviewTarget::Local { repo_dir, .. } => local::clone_repo(repo_dir, target_dir),
+        }
+    }
+
+    /// Checkout the head commit in a clone made by [`Self::clone_repo`]
+    pub fn checkout(&self, info: &PrInfo, repo_dir: &Path) -> Result<(), ReviewError> {
+        match self {
+            ReviewTarget::GitHubPr { .. } => github::checkout_commit(&info.head_commit, repo_dir),
+            ReviewTarget::GitLabMr(mr) => gitlab::checkout_mr(mr, &info.head_commit, repo_dir),
+            ReviewTarget::Local { .. } => local::checkout_commit(&info.head_commit, repo_dir),
+        }
+    }
+
+    /// Directory name for the clone
+    pub fn repo_name(&self) -> String {
+        match self {
+            ReviewTarget::GitHubPr { repo, .. } => repo.clone(),
+            ReviewTarget::GitLabMr(mr) => mr
+                .project
+                .rsplit('/')
+                .next()
+                .unwrap_or(&mr.project)
+                .to_string(),
+            ReviewTarget::Local { repo_dir, .. } => repo_dir
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("repo")
+                .to_string(),
+        }
+    }
+
+    /// URL recorded with a hosted review. Local ranges have no web page, so
+    /// they're recorded as `file://{repo}#{base}..{head}`, naming the
+    /// repository rather than its path on this machine.
+    pub fn review_url(&self, info: &PrInfo) -> String {
+        match self {
+            ReviewTarget::GitHubPr {
+                owner,
+                repo,
+                number,
+            } => format!("https://github.com/{owner}/{repo}/pull/{number}"),
+            ReviewTarget::GitLabMr(mr) => format!(
+                "https://{}/{}/-/merge_requests/{}",
+                mr.host, mr.project, mr.iid
+            ),
+            ReviewTarget::Local { .. } => format!(
+                "file://{}#{}..{}",
+                self.repo_name(),
+                info.base_commit,
+                info.head_commit
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_target_urls() {
+        let target = ReviewTarget::parse("https://github.com/owner/repo/pull/12", None, "HEAD")
+            .expect("Should parse GitHub PR");
+        assert!(matches!(target, ReviewTarget::GitHubPr { number: 12, .. }));
+
+        let target = ReviewTarget::parse(
+            "https://gitlab.com/group/repo/-/merge_requests/3",
+            None,
+            "HEAD",
+        )
+        .expect("Should parse GitLab MR");
+        assert!(matches!(
+            target,
+            ReviewTarget::GitLabMr(MrUrl { iid: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_local_target_requires_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = ReviewTarget::parse(dir.path().to_str().unwrap(), None, "HEAD");
+        assert!(matches!(result, Err(ReviewError::InvalidTarget(_))));
+    }
+
+    #[test]
+    fn test_local_review_url_names_repo_not_path() {
+        let target = ReviewTarget::Local {
+            repo_dir: PathBuf::from("/home/user/projects/my-repo"),
+            base: "main".to_string(),
+            head: "HEAD".to_string(),
+        };
+        let info = PrInfo {
+            owner: String::new(),
+            repo: "my-repo".to_string(),
+            title: String::new(),
+            description: String::new(),
+            base_commit: "abc123".to_string(),
+            head_commit: "def456".to_string(),
+            head_ref_name: "HEAD".to_string(),
+        };
+        assert_eq!(target.review_url(&info), "file://my-repo#abc123..def456");
+    }
+}
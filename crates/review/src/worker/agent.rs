@@ -0,0 +1,304 @@
+use std::{path::Path, str::FromStr, time::Duration};
+
+use executors::{
+    env::ExecutionEnv,
+    executors::{BaseCodingAgent, SpawnedChild, StandardCodingAgentExecutor},
+    profile::{ExecutorConfigs, ExecutorProfileId},
+    sandbox::{SandboxConfig, SandboxPolicy},
+};
+use tokio::io::AsyncRead;
+use tracing::{debug, warn};
+
+use super::{
+    ReviewJob,
+    egress::{DEFAULT_ALLOWED_HOSTS, EgressProxy},
+    result::AgentReview,
+};
+use crate::error::ReviewError;
+
+/// File the agent writes its review to, relative to the repository root
+const OUTPUT_FILE: &str = ".vibe-review.json";
+
+const AGENT_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+fn review_prompt(job: &ReviewJob) -> String {
+    format!(
+        r#"You are reviewing a pull request. Turn it into a clear, story-driven review instead of a wall of diffs.
+
+Title: {title}
+
+Description:
+{description}
+
+The repository is checked out at the head of the pull request. See the changes with `git diff {base_commit}...HEAD`. If `.agent-messages.json` exists, it holds the coding agent conversation that produced the changes; use it to explain why decisions were made.
+
+Do not modify any files except the output file described below.
+
+Write the review as JSON to `{output_file}` in the repository root:
+
+{{
+  "summary": "What the pull request does and what deserves the reviewer's attention, in a few sentences",
+  "comments": [
+    {{
+      "comment": "One step of the story, in the order a reviewer should read the change",
+      "fragments": [
+        {{
+          "file": "path/relative/to/repo.rs",
+          "start_line": 10,
+          "end_line": 24,
+          "message": "What this code does and anything risky about it"
+        }}
+      ]
+    }}
+  ]
+}}
+
+Line numbers refer to the file at HEAD. Highlight key events and important decisions, and call out anything that needs close attention."#,
+        title = job.title,
+        description = job.description,
+        base_commit = job.base_commit,
+        output_file = OUTPUT_FILE,
+    )
+}
+
+/// Parses `AGENT[:VARIANT]`, e.g. `CLAUDE_CODE` or `codex:high`.
+pub fn parse_agent_profile(value: &str) -> Result<ExecutorProfileId, ReviewError> {
+    let (agent, variant) = match value.split_once(':') {
+        Some((agent, variant)) => (agent, Some(variant)),
+        None => (value, None),
+    };
+
+    let normalized = agent.trim().replace('-', "_").to_ascii_uppercase();
+    let executor = BaseCodingAgent::from_str(&normalized)
+        .map_err(|_| ReviewError::UnknownAgent(value.to_string()))?;
+
+    Ok(match variant {
+        Some(variant) => {
+            ExecutorProfileId::with_variant(executor, variant.trim().to_ascii_uppercase())
+        }
+        None => ExecutorProfileId::new(executor),
+    })
+}
+
+/// Runs a coding agent from the executor profiles over a checked-out
+/// repository and collects the review it writes.
+///
+/// The agent runs in the executor sandbox: it can only write inside the
+/// repository (plus the profile's writable paths) and only reach the hosts in
+/// `allowed_hosts`, through an [`EgressProxy`].
+#[derive(Debug, Clone)]
+pub struct ReviewAgent {
+    profile: ExecutorProfileId,
+    sandboxed: bool,
+    allowed_hosts: Vec<String>,
+}
+
+impl ReviewAgent {
+    pub fn new(profile: ExecutorProfileId) -> Self {
+        Self {
+            profile,
+            sandboxed: true,
+            allowed_hosts: DEFAULT_ALLOWED_HOSTS
+                .iter()
+                .map(|host| host.to_string())
+                .collect(),
+        }
+    }
+
+    /// Run the agent without the sandbox, for platforms that lack one
+    pub fn sandboxed(mut self, sandboxed: bool) -> Self {
+        self.sandboxed = sandboxed;
+        self
+    }
+
+    /// Hosts the agent may connect to, in place of the default providers
+    pub fn allowed_hosts(mut self, allowed_hosts: Vec<String>) -> Self {
+        self.allowed_hosts = allowed_hosts;
+        self
+    }
+
+    pub fn profile(&self) -> &ExecutorProfileId {
+        &self.profile
+    }
+
+    pub async fn run(
+        &self,
+        repo_dir: &Path,
+        job: &ReviewJob,
+        log_path: &Path,
+    ) -> Result<AgentReview, ReviewError> {
+        let agent = ExecutorConfigs::get_cached()
+            .get_coding_agent(&self.profile)
+            .ok_or_else(|| ReviewError::UnknownAgent(self.profile.to_string()))?;
+
+        let output_path = repo_dir.join(OUTPUT_FILE);
+        let _ = tokio::fs::remove_file(&output_path).await;
+
+        let prompt = review_prompt(job);
+
+        let proxy = EgressProxy::start(self.allowed_hosts.clone())
+            .await
+            .map_err(|e| ReviewError::AgentFailed(format!("failed to start egress proxy: {e}")))?;
+        let env = self.execution_env(repo_dir, &proxy.url());
+
+        debug!("Starting {} in {}", self.profile, repo_dir.display());
+
+        let SpawnedChild {
+            mut child,
+            exit_signal,
+            interrupt_sender: _interrupt_sender,
+            steer_sender: _steer_sender,
+        } = agent
+            .spawn(repo_dir, &prompt, &env)
+            .await
+            .map_err(|e| ReviewError::AgentFailed(e.to_string()))?;
+
+        // Nobody consumes the agent's output, so drain it to keep the pipes flowing
+        if let Some(stdout) = child.inner().stdout.take() {
+            tokio::spawn(append_to_log(stdout, log_path.to_path_buf()));
+        }
+        if let Some(stderr) = child.inner().stderr.take() {
+            tokio::spawn(append_to_log(stderr, log_path.to_path_buf()));
+        }
+
+        let exit_signal = async move {
+            match exit_signal {
+                Some(signal) => {
+                    let _ = signal.await;
+                }
+                None => std::future::pending().await,
+            }
+        };
+
+        let finished = tokio::time::timeout(AGENT_TIMEOUT, async {
+            tokio::select! {
+                status = child.wait() => status.ok().map(|status| status.success()),
+                _ = exit_signal => None,
+            }
+        })
+        .await;
+
+        // Agents that signal completion may leave the process running
+        let _ = child.kill().await;
+
+        let exited_ok = match finished {
+            Ok(exited_ok) => exited_ok,
+            Err(_) => {
+                return Err(ReviewError::AgentFailed(format!(
+                    "timed out after {} minutes",
+                    AGENT_TIMEOUT.as_secs() / 60
+                )));
+            }
+        };
+
+        let contents = match tokio::fs::read(&output_path).await {
+            Ok(contents) => contents,
+            Err(e) => {
+                if exited_ok == Some(false) {
+                    return Err(ReviewError::AgentFailed(format!(
+                        "agent exited with an error, see {}",
+                        log_path.display()
+                    )));
+                }
+                return Err(ReviewError::AgentFailed(format!(
+                    "agent did not write {OUTPUT_FILE}: {e}"
+                )));
+            }
+        };
+        let _ = tokio::fs::remove_file(&output_path).await;
+
+        serde_json::from_slice(&contents)
+            .map_err(|e| ReviewError::AgentFailed(format!("invalid {OUTPUT_FILE}: {e}")))
+    }
+
+    fn execution_env(&self, repo_dir: &Path, proxy_url: &str) -> ExecutionEnv {
+        let mut env = ExecutionEnv::new();
+        for var in [
+            "HTTPS_PROXY",
+            "https_proxy",
+            "HTTP_PROXY",
+            "http_proxy",
+            "ALL_PROXY",
+            "all_proxy",
+        ] {
+            env.insert(var, proxy_url);
+        }
+        env.insert("NO_PROXY", "");
+        env.insert("no_proxy", "");
+
+        let sandbox = SandboxConfig {
+            enabled: Some(self.sandboxed),
+            allow_network: Some(true),
+            writable_paths: None,
+        };
+        env.with_sandbox(SandboxPolicy::new(sandbox, vec![repo_dir.to_path_buf()]))
+    }
+}
+
+async fn append_to_log(mut reader: impl AsyncRead + Unpin, log_path: std::path::PathBuf) {
+    let file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .await;
+
+    match file {
+        Ok(mut file) => {
+            if let Err(e) = tokio::io::copy(&mut reader, &mut file).await {
+                debug!("Agent log stream ended: {}", e);
+            }
+        }
+        Err(e) => {
+            warn!("Failed to open {}: {}", log_path.display(), e);
+            let _ = tokio::io::copy(&mut reader, &mut tokio::io::sink()).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_agent_profile() {
+        let profile = parse_agent_profile("claude-code").expect("Should parse agent");
+        assert_eq!(profile, ExecutorProfileId::new(BaseCodingAgent::ClaudeCode));
+
+        let profile = parse_agent_profile("CODEX:high").expect("Should parse variant");
+        assert_eq!(
+            profile,
+            ExecutorProfileId::with_variant(BaseCodingAgent::Codex, "HIGH".to_string())
+        );
+
+        assert!(parse_agent_profile("unknown-agent").is_err());
+    }
+
+    #[test]
+    fn test_review_prompt_substitutes_once() {
+        let job = ReviewJob {
+            id: uuid::Uuid::new_v4(),
+            title: "Mention {base_commit} in the docs".to_string(),
+            description: "Fixes {output_file}".to_string(),
+            base_commit: "0123456789abcdef0123456789abcdef01234567".to_string(),
+        };
+
+        let prompt = review_prompt(&job);
+        assert!(prompt.contains("Title: Mention {base_commit} in the docs"));
+        assert!(prompt.contains("Fixes {output_file}"));
+        assert!(prompt.contains("git diff 0123456789abcdef0123456789abcdef01234567...HEAD"));
+        assert!(prompt.contains(&format!("JSON to `{OUTPUT_FILE}`")));
+    }
+
+    #[test]
+    fn test_execution_env_is_sandboxed_to_repo() {
+        let agent = ReviewAgent::new(ExecutorProfileId::new(BaseCodingAgent::ClaudeCode));
+        let env = agent.execution_env(Path::new("/tmp/review"), "http://127.0.0.1:9");
+
+        assert!(env.sandbox.is_enabled());
+        assert_eq!(env.sandbox.workspace_roots, vec![Path::new("/tmp/review")]);
+        assert_eq!(
+            env.vars.get("HTTPS_PROXY").map(String::as_str),
+            Some("http://127.0.0.1:9")
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! HTTPS proxy that only tunnels to an allow-list of hosts.
+//!
+//! The review agent runs over code from the uploader, so its network access is
+//! limited to the model provider: the agent is pointed at this proxy through
+//! the usual `HTTPS_PROXY` variables and `CONNECT` requests to any other host
+//! are refused.
+
+use std::{io, net::SocketAddr, sync::Arc};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use tracing::{debug, warn};
+
+/// Hosts of the model providers the supported agents talk to
+pub const DEFAULT_ALLOWED_HOSTS: &[&str] = &[
+    "api.anthropic.com",
+    "api.openai.com",
+    "chatgpt.com",
+    "generativelanguage.googleapis.com",
+];
+
+/// Largest request head accepted from the agent
+const MAX_HEAD_LEN: usize = 8 * 1024;
+
+/// Proxy listening on a loopback port; stops when dropped
+pub struct EgressProxy {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl EgressProxy {
+    pub async fn start(allowed_hosts: Vec<String>) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let allowed_hosts: Arc<[String]> = allowed_hosts.into();
+
+        let task = tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    continue;
+                };
+                let allowed_hosts = allowed_hosts.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = tunnel(stream, &allowed_hosts).await {
+                        debug!("Egress proxy connection ended: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(Self { addr, task })
+    }
+
+    /// Value for the `HTTPS_PROXY` family of variables
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+}
+
+impl Drop for EgressProxy {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn tunnel(mut client: TcpStream, allowed_hosts: &[String]) -> io::Result<()> {
+    let head = read_head(&mut client).await?;
+    let Some((host, port)) = connect_target(&head) else {
+        client
+            .write_all(b"HTTP/1.1 405 Method Not Allowed\r\nContent-Length: 0\r\n\r\n")
+            .await?;
+        return Ok(());
+    };
+    if port != 443 || !is_allowed(host, allowed_hosts) {
+        warn!("Blocked agent connection to {}:{}", host, port);
+        client
+            .write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n")
+            .await?;
+        return Ok(());
+    }
+
+    let mut upstream = TcpStream::connect((host, port)).await?;
+    client
+        .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+        .await?;
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+/// Reads the request line and headers. Clients wait for the `CONNECT`
+/// response before sending anything else, so nothing past the head is lost.
+async fn read_head(client: &mut TcpStream) -> io::Result<String> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        if head.len() > MAX_HEAD_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request head too long",
+            ));
+        }
+        let read = client.read(&mut buf).await?;
+        if read == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        head.extend_from_slice(&buf[..read]);
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+/// Host and port of a `CONNECT host:port HTTP/1.1` request
+fn connect_target(head: &str) -> Option<(&str, u16)> {
+    let mut parts = head.lines().next()?.split_whitespace();
+    if parts.next()? != "CONNECT" {
+        return None;
+    }
+    let (host, port) = parts.next()?.rsplit_once(':')?;
+    Some((host, port.parse().ok()?))
+}
+
+fn is_allowed(host: &str, allowed_hosts: &[String]) -> bool {
+    let host = host.to_ascii_lowercase();
+    allowed_hosts.iter().any(|allowed| host == *allowed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connect_target() {
+        assert_eq!(
+            connect_target("CONNECT api.anthropic.com:443 HTTP/1.1\r\nHost: x\r\n\r\n"),
+            Some(("api.anthropic.com", 443))
+        );
+        assert_eq!(
+            connect_target("GET http://example.com/ HTTP/1.1\r\n\r\n"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refuses_hosts_outside_allow_list() {
+        let proxy = EgressProxy::start(vec!["api.anthropic.com".to_string()])
+            .await
+            .unwrap();
+
+        let mut client = TcpStream::connect(proxy.addr).await.unwrap();
+        client
+            .write_all(b"CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 403"));
+    }
+}
@@ -0,0 +1,268 @@
+//! Self-hostable review worker.
+//!
+//! Runs reviews with a local coding agent instead of the hosted service and
+//! stores the results in the format the remote `/review` routes serve. The
+//! `review-worker` binary exposes it over HTTP for `REVIEW_WORKER_BASE_URL`;
+//! `review --local` calls [`LocalReviewer`] directly.
+
+mod agent;
+mod egress;
+pub mod result;
+pub mod server;
+mod store;
+
+use std::{collections::BTreeMap, path::Path};
+
+pub use agent::{ReviewAgent, parse_agent_profile};
+use result::{ReviewResult, file_hash, is_repo_relative};
+pub use store::ReviewStore;
+use tokio::process::Command;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::{api::ReviewStatus, archive, error::ReviewError};
+
+/// What to review
+#[derive(Debug, Clone)]
+pub struct ReviewJob {
+    pub id: Uuid,
+    pub title: String,
+    pub description: String,
+    /// Commit the changes are compared against
+    pub base_commit: String,
+}
+
+pub struct LocalReviewer {
+    store: ReviewStore,
+    agent: ReviewAgent,
+}
+
+impl LocalReviewer {
+    pub fn new(store: ReviewStore, agent: ReviewAgent) -> Self {
+        Self { store, agent }
+    }
+
+    pub fn store(&self) -> &ReviewStore {
+        &self.store
+    }
+
+    /// Unpacks a payload produced by [`archive::create_tarball`] and reviews it.
+    pub async fn run_payload(
+        &self,
+        job: &ReviewJob,
+        payload: Vec<u8>,
+    ) -> Result<ReviewResult, ReviewError> {
+        self.set_status(job.id, ReviewStatus::Extracting, "Extracting codebase")
+            .await?;
+
+        let repo_dir = self.store.repo_dir(job.id);
+        let extract_dir = repo_dir.clone();
+        let extracted = match tokio::task::spawn_blocking(move || {
+            archive::extract_tarball(&payload, &extract_dir)
+        })
+        .await
+        {
+            Ok(result) => result,
+            Err(e) => Err(ReviewError::ExtractFailed(e.to_string())),
+        };
+
+        let result = match extracted {
+            Ok(()) => self.run_in_dir(job, &repo_dir).await,
+            Err(e) => self.fail(job.id, e).await,
+        };
+        self.store.remove_repo(job.id).await;
+        result
+    }
+
+    /// Reviews a repository that is already checked out at the head commit.
+    pub async fn run_in_dir(
+        &self,
+        job: &ReviewJob,
+        repo_dir: &Path,
+    ) -> Result<ReviewResult, ReviewError> {
+        match self.review(job, repo_dir).await {
+            Ok(result) => {
+                self.store
+                    .set_status(job.id, ReviewStatus::Completed, None, None)
+                    .await?;
+                info!("Review {} completed", job.id);
+                Ok(result)
+            }
+            Err(e) => self.fail(job.id, e).await,
+        }
+    }
+
+    async fn review(&self, job: &ReviewJob, repo_dir: &Path) -> Result<ReviewResult, ReviewError> {
+        let diff = git_diff(repo_dir, &job.base_commit).await?;
+        self.store.write_diff(job.id, &diff).await?;
+
+        self.set_status(
+            job.id,
+            ReviewStatus::Running,
+            &format!("Running {}", self.agent.profile()),
+        )
+        .await?;
+        let review = self
+            .agent
+            .run(repo_dir, job, &self.store.log_path(job.id))
+            .await?;
+
+        let mut file_hash_map = BTreeMap::new();
+        for path in review.referenced_files() {
+            if !is_repo_relative(path) {
+                warn!("Skipping file outside the repository: {path}");
+                continue;
+            }
+            let Some(contents) = read_repo_file(repo_dir, path).await else {
+                debug!("Referenced file not found: {path}");
+                continue;
+            };
+            let hash = file_hash(path);
+            self.store.write_file(job.id, &hash, &contents).await?;
+            file_hash_map.insert(hash, path.to_string());
+        }
+
+        let result = ReviewResult {
+            summary: review.summary,
+            comments: review.comments,
+            file_hash_map,
+        };
+        self.store.write_result(job.id, &result).await?;
+        Ok(result)
+    }
+
+    async fn set_status(
+        &self,
+        review_id: Uuid,
+        status: ReviewStatus,
+        progress: &str,
+    ) -> Result<(), ReviewError> {
+        self.store
+            .set_status(review_id, status, Some(progress.to_string()), None)
+            .await
+    }
+
+    async fn fail<T>(&self, review_id: Uuid, error: ReviewError) -> Result<T, ReviewError> {
+        warn!("Review {} failed: {}", review_id, error);
+        if let Err(e) = self
+            .store
+            .set_status(
+                review_id,
+                ReviewStatus::Failed,
+                None,
+                Some(error.to_string()),
+            )
+            .await
+        {
+            warn!("Failed to record review failure: {}", e);
+        }
+        Err(error)
+    }
+}
+
+/// Reads a file the agent referenced, refusing symlinks that resolve outside
+/// `repo_dir`
+async fn read_repo_file(repo_dir: &Path, path: &str) -> Option<Vec<u8>> {
+    let repo_dir = tokio::fs::canonicalize(repo_dir).await.ok()?;
+    let file = tokio::fs::canonicalize(repo_dir.join(path)).await.ok()?;
+    if !file.starts_with(&repo_dir) {
+        warn!("Skipping file that resolves outside the repository: {path}");
+        return None;
+    }
+    tokio::fs::read(file).await.ok()
+}
+
+/// Whether `value` is a full SHA-1 commit id, the only form of base commit the
+/// worker accepts
+pub fn is_commit_sha(value: &str) -> bool {
+    value.len() == 40
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// Diff of the head commit against the merge base with `base_commit`.
+///
+/// The repository comes from the uploader, so external diff drivers, the
+/// fsmonitor hook and any system or global config are switched off.
+async fn git_diff(repo_dir: &Path, base_commit: &str) -> Result<String, ReviewError> {
+    if !is_commit_sha(base_commit) {
+        return Err(ReviewError::DiffFailed(format!(
+            "invalid base commit: {base_commit}"
+        )));
+    }
+
+    let output = Command::new("git")
+        .args([
+            "-c",
+            "core.fsmonitor=",
+            "-c",
+            "core.hooksPath=/dev/null",
+            "diff",
+            "--no-ext-diff",
+            "--no-textconv",
+            "--no-color",
+            "--end-of-options",
+            &format!("{base_commit}...HEAD"),
+        ])
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .env("GIT_CONFIG_GLOBAL", "/dev/null")
+        .current_dir(repo_dir)
+        .output()
+        .await
+        .map_err(|e| ReviewError::DiffFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ReviewError::DiffFailed(stderr.to_string()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_is_commit_sha() {
+        assert!(is_commit_sha("0123456789abcdef0123456789abcdef01234567"));
+        assert!(!is_commit_sha("0123456789ABCDEF0123456789ABCDEF01234567"));
+        assert!(!is_commit_sha("0123456"));
+        assert!(!is_commit_sha("--output=/tmp/diff"));
+        assert!(!is_commit_sha("--output=/tmp/diff0123456789012345678901"));
+    }
+
+    #[tokio::test]
+    async fn test_read_repo_file() {
+        let repo = TempDir::new().unwrap();
+        std::fs::create_dir(repo.path().join("src")).unwrap();
+        std::fs::write(repo.path().join("src/lib.rs"), "fn main() {}").unwrap();
+
+        assert_eq!(
+            read_repo_file(repo.path(), "src/lib.rs").await.as_deref(),
+            Some(b"fn main() {}".as_slice())
+        );
+        assert_eq!(read_repo_file(repo.path(), "missing.rs").await, None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_read_repo_file_refuses_symlinks_out_of_repo() {
+        let outside = TempDir::new().unwrap();
+        std::fs::write(outside.path().join("secret"), "secret").unwrap();
+        let repo = TempDir::new().unwrap();
+        std::fs::write(repo.path().join("file.txt"), "content").unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret"), repo.path().join("leak"))
+            .unwrap();
+        std::os::unix::fs::symlink("file.txt", repo.path().join("alias")).unwrap();
+
+        assert_eq!(read_repo_file(repo.path(), "leak").await, None);
+        assert_eq!(
+            read_repo_file(repo.path(), "alias").await.as_deref(),
+            Some(b"content".as_slice())
+        );
+    }
+}
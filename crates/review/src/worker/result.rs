@@ -0,0 +1,114 @@
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A range of lines in a file the review points at
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeFragment {
+    pub file: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub message: String,
+}
+
+/// One step of the review story
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewComment {
+    pub comment: String,
+    #[serde(default)]
+    pub fragments: Vec<CodeFragment>,
+}
+
+/// What the agent writes to its output file
+#[derive(Debug, Clone, Deserialize)]
+pub struct AgentReview {
+    pub summary: String,
+    #[serde(default)]
+    pub comments: Vec<ReviewComment>,
+}
+
+/// Review result served at `GET /review/{id}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewResult {
+    pub summary: String,
+    pub comments: Vec<ReviewComment>,
+    /// File hash -> path, for files served at `GET /review/{id}/file/{hash}`
+    #[serde(rename = "fileHashMap")]
+    pub file_hash_map: BTreeMap<String, String>,
+}
+
+impl AgentReview {
+    /// Paths referenced by the review's fragments, without duplicates
+    pub fn referenced_files(&self) -> Vec<&str> {
+        let mut files: Vec<&str> = self
+            .comments
+            .iter()
+            .flat_map(|comment| comment.fragments.iter())
+            .map(|fragment| fragment.file.as_str())
+            .collect();
+        files.sort_unstable();
+        files.dedup();
+        files
+    }
+}
+
+/// Stable identifier for a file path in the review
+pub fn file_hash(path: &str) -> String {
+    let digest = Sha256::digest(path.as_bytes());
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Whether `path` stays inside the repository it's relative to
+pub fn is_repo_relative(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_review_referenced_files() {
+        let review: AgentReview = serde_json::from_str(
+            r#"{
+                "summary": "Adds a parser",
+                "comments": [
+                    {
+                        "comment": "Parser entry point",
+                        "fragments": [
+                            {"file": "src/parser.rs", "start_line": 1, "end_line": 10, "message": "new"},
+                            {"file": "src/lib.rs", "start_line": 3, "end_line": 3, "message": "export"}
+                        ]
+                    },
+                    {
+                        "comment": "Error handling",
+                        "fragments": [
+                            {"file": "src/parser.rs", "start_line": 20, "end_line": 30, "message": "errors"}
+                        ]
+                    }
+                ]
+            }"#,
+        )
+        .expect("Should parse agent output");
+
+        assert_eq!(
+            review.referenced_files(),
+            vec!["src/lib.rs", "src/parser.rs"]
+        );
+    }
+
+    #[test]
+    fn test_is_repo_relative() {
+        assert!(is_repo_relative("src/main.rs"));
+        assert!(!is_repo_relative("../secret"));
+        assert!(!is_repo_relative("/etc/passwd"));
+        assert!(!is_repo_relative(""));
+    }
+}
@@ -0,0 +1,278 @@
+//! HTTP API matching what the remote server proxies to `REVIEW_WORKER_BASE_URL`.
+//!
+//! Every request must carry the shared `REVIEW_WORKER_SECRET` as a bearer token.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    body::Body,
+    extract::{Path, Request, State},
+    http::{StatusCode, header},
+    middleware::{Next, from_fn_with_state},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use reqwest::Client;
+use sha2::{Digest, Sha256};
+use tokio::sync::Semaphore;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use super::{LocalReviewer, ReviewJob, is_commit_sha};
+use crate::{
+    api::{ReviewStatus, StartRequest},
+    error::ReviewError,
+};
+
+#[derive(Clone)]
+pub struct WorkerState {
+    reviewer: Arc<LocalReviewer>,
+    http_client: Client,
+    permits: Arc<Semaphore>,
+    /// SHA-256 of the shared secret, so comparisons don't depend on its length
+    secret_digest: [u8; 32],
+    allow_file_urls: bool,
+}
+
+impl WorkerState {
+    /// `max_concurrent` limits how many agents run at once; further reviews
+    /// stay queued. Requests must authenticate with `secret`.
+    pub fn new(reviewer: LocalReviewer, max_concurrent: usize, secret: &str) -> Self {
+        Self {
+            reviewer: Arc::new(reviewer),
+            http_client: Client::new(),
+            permits: Arc::new(Semaphore::new(max_concurrent.max(1))),
+            secret_digest: Sha256::digest(secret.as_bytes()).into(),
+            allow_file_urls: false,
+        }
+    }
+
+    /// Accept `file://` codebase URLs, which read payloads from the worker's
+    /// own filesystem. Only meant for local development.
+    pub fn allow_file_urls(mut self, allow: bool) -> Self {
+        self.allow_file_urls = allow;
+        self
+    }
+}
+
+pub fn router(state: WorkerState) -> Router {
+    Router::new()
+        .route("/review/start", post(start_review))
+        .route("/review/{id}/status", get(get_status))
+        .route("/review/{id}", get(get_review))
+        .route("/review/{id}/file/{file_hash}", get(get_file))
+        .route("/review/{id}/diff", get(get_diff))
+        .layer(from_fn_with_state(state.clone(), require_secret))
+        .with_state(state)
+}
+
+async fn require_secret(
+    State(state): State<WorkerState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| {
+            <[u8; 32]>::from(Sha256::digest(token.as_bytes())) == state.secret_digest
+        });
+
+    if !authorized {
+        return error_response(StatusCode::UNAUTHORIZED, "Unauthorized");
+    }
+    next.run(request).await
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+fn parse_review_id(id: &str) -> Result<Uuid, Response> {
+    id.parse()
+        .map_err(|_| error_response(StatusCode::BAD_REQUEST, "Invalid review ID"))
+}
+
+fn found(contents: Option<Vec<u8>>, content_type: &'static str) -> Response {
+    match contents {
+        Some(contents) => Response::builder()
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(contents))
+            .unwrap(),
+        None => error_response(StatusCode::NOT_FOUND, "Not found"),
+    }
+}
+
+async fn start_review(
+    State(state): State<WorkerState>,
+    Json(request): Json<StartRequest>,
+) -> Response {
+    let review_id = match parse_review_id(&request.id) {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+    if !is_commit_sha(&request.base_commit) {
+        return error_response(StatusCode::BAD_REQUEST, "Invalid base commit");
+    }
+
+    if let Err(e) = state
+        .reviewer
+        .store()
+        .set_status(review_id, ReviewStatus::Queued, None, None)
+        .await
+    {
+        error!("Failed to queue review {}: {}", review_id, e);
+        return error_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to queue review");
+    }
+
+    info!(
+        "Queued review {} for {}/{}",
+        review_id, request.org, request.repo
+    );
+    tokio::spawn(process_review(state, review_id, request));
+
+    (
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({ "id": review_id })),
+    )
+        .into_response()
+}
+
+async fn process_review(state: WorkerState, review_id: Uuid, request: StartRequest) {
+    let Ok(_permit) = state.permits.clone().acquire_owned().await else {
+        return;
+    };
+
+    let job = ReviewJob {
+        id: review_id,
+        title: request.title,
+        description: request.description,
+        base_commit: request.base_commit,
+    };
+
+    let result = match download_payload(
+        &state.http_client,
+        &request.codebase_url,
+        state.allow_file_urls,
+    )
+    .await
+    {
+        Ok(payload) => state.reviewer.run_payload(&job, payload).await.map(|_| ()),
+        Err(e) => {
+            let _ = state
+                .reviewer
+                .store()
+                .set_status(review_id, ReviewStatus::Failed, None, Some(e.to_string()))
+                .await;
+            Err(e)
+        }
+    };
+
+    let Some(callback_url) = request.callback_url else {
+        return;
+    };
+    let outcome = if result.is_ok() { "success" } else { "failed" };
+    let url = format!("{}/{outcome}", callback_url.trim_end_matches('/'));
+    match state.http_client.post(&url).send().await {
+        Ok(response) if !response.status().is_success() => {
+            warn!("Callback {} returned {}", url, response.status());
+        }
+        Ok(_) => {}
+        Err(e) => warn!("Callback {} failed: {}", url, e),
+    }
+}
+
+/// Fetches the review payload from an `http(s)://` URL, or a `file://` path
+/// when `allow_file_urls` is set.
+async fn download_payload(
+    client: &Client,
+    codebase_url: &str,
+    allow_file_urls: bool,
+) -> Result<Vec<u8>, ReviewError> {
+    if let Some(path) = codebase_url
+        .strip_prefix("file://")
+        .filter(|_| allow_file_urls)
+    {
+        return tokio::fs::read(path)
+            .await
+            .map_err(|e| ReviewError::ExtractFailed(format!("{path}: {e}")));
+    }
+
+    if !codebase_url.starts_with("https://") && !codebase_url.starts_with("http://") {
+        return Err(ReviewError::ExtractFailed(format!(
+            "unsupported codebase URL: {codebase_url}"
+        )));
+    }
+
+    let response = client
+        .get(codebase_url)
+        .send()
+        .await
+        .map_err(|e| ReviewError::ExtractFailed(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(ReviewError::ExtractFailed(format!(
+            "download returned {}",
+            response.status()
+        )));
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| ReviewError::ExtractFailed(e.to_string()))?;
+    Ok(bytes.to_vec())
+}
+
+async fn get_status(State(state): State<WorkerState>, Path(id): Path<String>) -> Response {
+    let review_id = match parse_review_id(&id) {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    match state.reviewer.store().status(review_id).await {
+        Some(status) => Json(status).into_response(),
+        None => error_response(StatusCode::NOT_FOUND, "Review not found"),
+    }
+}
+
+async fn get_review(State(state): State<WorkerState>, Path(id): Path<String>) -> Response {
+    let review_id = match parse_review_id(&id) {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    found(
+        state.reviewer.store().result(review_id).await,
+        "application/json",
+    )
+}
+
+async fn get_file(
+    State(state): State<WorkerState>,
+    Path((id, file_hash)): Path<(String, String)>,
+) -> Response {
+    let review_id = match parse_review_id(&id) {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    found(
+        state.reviewer.store().file(review_id, &file_hash).await,
+        "text/plain; charset=utf-8",
+    )
+}
+
+async fn get_diff(State(state): State<WorkerState>, Path(id): Path<String>) -> Response {
+    let review_id = match parse_review_id(&id) {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    found(
+        state.reviewer.store().diff(review_id).await,
+        "text/plain; charset=utf-8",
+    )
+}
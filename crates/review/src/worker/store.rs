@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use super::result::ReviewResult;
+use crate::{
+    api::{ReviewStatus, StatusResponse},
+    error::ReviewError,
+};
+
+const STATUS_FILE: &str = "status.json";
+const RESULT_FILE: &str = "review.json";
+const DIFF_FILE: &str = "diff.patch";
+const LOG_FILE: &str = "agent.log";
+const REPO_DIR: &str = "repo";
+const FILES_DIR: &str = "files";
+
+/// On-disk layout of review results, one directory per review:
+///
+/// ```text
+/// <root>/<review_id>/status.json
+/// <root>/<review_id>/review.json
+/// <root>/<review_id>/diff.patch
+/// <root>/<review_id>/files/<hash>
+/// <root>/<review_id>/agent.log
+/// <root>/<review_id>/repo/        (removed once the review finishes)
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReviewStore {
+    root: PathBuf,
+}
+
+fn storage_error(e: impl std::fmt::Display) -> ReviewError {
+    ReviewError::StorageFailed(e.to_string())
+}
+
+impl ReviewStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Default location: `<data dir>/vibe-kanban/reviews`
+    pub fn default_root() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("vibe-kanban")
+            .join("reviews")
+    }
+
+    pub fn review_dir(&self, review_id: Uuid) -> PathBuf {
+        self.root.join(review_id.to_string())
+    }
+
+    pub fn repo_dir(&self, review_id: Uuid) -> PathBuf {
+        self.review_dir(review_id).join(REPO_DIR)
+    }
+
+    pub fn log_path(&self, review_id: Uuid) -> PathBuf {
+        self.review_dir(review_id).join(LOG_FILE)
+    }
+
+    async fn write(&self, review_id: Uuid, name: &str, contents: &[u8]) -> Result<(), ReviewError> {
+        let path = self.review_dir(review_id).join(name);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(storage_error)?;
+        }
+
+        // Write then rename so readers never see a partial file
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, contents)
+            .await
+            .map_err(storage_error)?;
+        tokio::fs::rename(&tmp, &path).await.map_err(storage_error)
+    }
+
+    async fn read(&self, review_id: Uuid, name: &str) -> Option<Vec<u8>> {
+        tokio::fs::read(self.review_dir(review_id).join(name))
+            .await
+            .ok()
+    }
+
+    pub async fn set_status(
+        &self,
+        review_id: Uuid,
+        status: ReviewStatus,
+        progress: Option<String>,
+        error: Option<String>,
+    ) -> Result<(), ReviewError> {
+        let status = StatusResponse {
+            status,
+            progress,
+            error,
+        };
+        let contents = serde_json::to_vec(&status).map_err(storage_error)?;
+        self.write(review_id, STATUS_FILE, &contents).await
+    }
+
+    pub async fn status(&self, review_id: Uuid) -> Option<StatusResponse> {
+        let contents = self.read(review_id, STATUS_FILE).await?;
+        serde_json::from_slice(&contents).ok()
+    }
+
+    pub async fn write_result(
+        &self,
+        review_id: Uuid,
+        result: &ReviewResult,
+    ) -> Result<(), ReviewError> {
+        let contents = serde_json::to_vec(result).map_err(storage_error)?;
+        self.write(review_id, RESULT_FILE, &contents).await
+    }
+
+    /// Serialized [`ReviewResult`], as served by `GET /review/{id}`
+    pub async fn result(&self, review_id: Uuid) -> Option<Vec<u8>> {
+        self.read(review_id, RESULT_FILE).await
+    }
+
+    pub async fn write_diff(&self, review_id: Uuid, diff: &str) -> Result<(), ReviewError> {
+        self.write(review_id, DIFF_FILE, diff.as_bytes()).await
+    }
+
+    pub async fn diff(&self, review_id: Uuid) -> Option<Vec<u8>> {
+        self.read(review_id, DIFF_FILE).await
+    }
+
+    pub async fn write_file(
+        &self,
+        review_id: Uuid,
+        hash: &str,
+        contents: &[u8],
+    ) -> Result<(), ReviewError> {
+        self.write(review_id, &format!("{FILES_DIR}/{hash}"), contents)
+            .await
+    }
+
+    pub async fn file(&self, review_id: Uuid, hash: &str) -> Option<Vec<u8>> {
+        if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.read(review_id, &format!("{FILES_DIR}/{hash}")).await
+    }
+
+    /// Removes the unpacked codebase, keeping the results.
+    pub async fn remove_repo(&self, review_id: Uuid) {
+        let repo_dir = self.repo_dir(review_id);
+        if let Err(e) = tokio::fs::remove_dir_all(&repo_dir).await
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!("Failed to remove {}: {}", repo_dir.display(), e);
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
@@ -15,6 +15,13 @@ fn generate_types_content() -> String {
         remote::routes::tasks::AssigneesQuery::decl(),
         remote::db::tasks::SharedTask::decl(),
         remote::db::users::UserData::decl(),
+        remote::db::task_comments::SharedTaskComment::decl(),
+        remote::db::task_activity::SharedTaskActivity::decl(),
+        remote::db::task_activity::SharedTaskActivityType::decl(),
+        remote::routes::task_comments::CreateSharedTaskCommentRequest::decl(),
+        remote::routes::task_comments::UpdateSharedTaskCommentRequest::decl(),
+        remote::routes::task_comments::ListSharedTaskCommentsResponse::decl(),
+        remote::routes::task_comments::ListSharedTaskActivityResponse::decl(),
         db::models::project::Project::decl(),
         db::models::project::CreateProject::decl(),
         db::models::project::UpdateProject::decl(),
@@ -24,6 +31,10 @@ fn generate_types_content() -> String {
         db::models::project_repo::ProjectRepo::decl(),
         db::models::project_repo::CreateProjectRepo::decl(),
         db::models::project_repo::UpdateProjectRepo::decl(),
+        db::models::project_secret::ProjectSecret::decl(),
+        db::models::project_secret::UpsertProjectSecret::decl(),
+        db::models::workspace_storage::WorkspaceStorageEntry::decl(),
+        db::models::workspace_storage::PinWorkspace::decl(),
         db::models::workspace_repo::WorkspaceRepo::decl(),
         db::models::workspace_repo::CreateWorkspaceRepo::decl(),
         db::models::workspace_repo::RepoWithTargetBranch::decl(),
@@ -36,6 +47,9 @@ fn generate_types_content() -> String {
         db::models::task::TaskRelationships::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
+        db::models::task_plan::TaskPlanStatus::decl(),
+        db::models::task_plan::TaskPlan::decl(),
+        db::models::task_plan::UpdateTaskPlan::decl(),
         db::models::scratch::DraftFollowUpData::decl(),
         db::models::scratch::ScratchPayload::decl(),
         db::models::scratch::ScratchType::decl(),
@@ -49,6 +63,7 @@ fn generate_types_content() -> String {
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
+        db::models::execution_process::ExecutionProcessFailureReason::decl(),
         db::models::execution_process_repo_state::ExecutionProcessRepoState::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
@@ -86,6 +101,8 @@ fn generate_types_content() -> String {
         utils::api::organizations::ListMembersResponse::decl(),
         utils::api::organizations::UpdateMemberRoleRequest::decl(),
         utils::api::organizations::UpdateMemberRoleResponse::decl(),
+        utils::api::organizations::MemberProjectAccess::decl(),
+        utils::api::organizations::UpdateMemberProjectAccessRequest::decl(),
         utils::api::projects::RemoteProject::decl(),
         utils::api::projects::ListProjectsResponse::decl(),
         utils::api::projects::RemoteProjectMembersResponse::decl(),
@@ -94,6 +111,8 @@ fn generate_types_content() -> String {
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
         server::routes::tags::TagSearchParams::decl(),
+        server::routes::workspaces::WorkspaceStorageSort::decl(),
+        server::routes::workspaces::WorkspaceStorageQuery::decl(),
         server::routes::oauth::TokenResponse::decl(),
         server::routes::config::UserSystemInfo::decl(),
         server::routes::config::Environment::decl(),
@@ -103,8 +122,11 @@ fn generate_types_content() -> String {
         server::routes::config::CheckEditorAvailabilityQuery::decl(),
         server::routes::config::CheckEditorAvailabilityResponse::decl(),
         server::routes::config::CheckAgentAvailabilityQuery::decl(),
+        server::routes::config::CheckAgentAvailabilityResponse::decl(),
         server::routes::oauth::CurrentUserResponse::decl(),
         server::routes::sessions::CreateFollowUpAttempt::decl(),
+        server::routes::execution_processes::SteerExecutionProcessRequest::decl(),
+        server::routes::execution_processes::ForkExecutionProcessResponse::decl(),
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchResponse::decl(),
         server::routes::task_attempts::MergeTaskAttemptRequest::decl(),
@@ -150,11 +172,16 @@ fn generate_types_content() -> String {
         services::services::config::SoundFile::decl(),
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
+        services::services::config::LogRedactionConfig::decl(),
+        services::services::config::WorkspaceCleanupConfig::decl(),
         services::services::git::GitBranch::decl(),
         services::services::share::SharedTaskDetails::decl(),
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
         services::services::git::ConflictOp::decl(),
+        services::services::transcript::TranscriptFormat::decl(),
+        services::services::transcript::Transcript::decl(),
+        services::services::transcript::TranscriptTurn::decl(),
         executors::actions::ExecutorAction::decl(),
         executors::mcp_config::McpConfig::decl(),
         executors::actions::ExecutorActionType::decl(),
@@ -164,7 +191,11 @@ fn generate_types_content() -> String {
         executors::executors::BaseCodingAgent::decl(),
         executors::executors::CodingAgent::decl(),
         executors::executors::AvailabilityInfo::decl(),
+        executors::version::AgentVersionInfo::decl(),
         executors::command::CommandBuilder::decl(),
+        executors::sandbox::SandboxConfig::decl(),
+        executors::watchdog::WatchdogConfig::decl(),
+        executors::failure::RetryPolicy::decl(),
         executors::profile::ExecutorProfileId::decl(),
         executors::profile::ExecutorConfig::decl(),
         executors::profile::ExecutorConfigs::decl(),
@@ -186,6 +217,8 @@ fn generate_types_content() -> String {
         executors::executors::droid::Autonomy::decl(),
         executors::executors::droid::ReasoningEffortLevel::decl(),
         executors::executors::custom_agent::CustomAgent::decl(),
+        executors::executors::replay::Replay::decl(),
+        executors::executors::openai_compatible::OpenaiCompatible::decl(),
         executors::executors::AppendPrompt::decl(),
         executors::actions::coding_agent_initial::CodingAgentInitialRequest::decl(),
         executors::actions::coding_agent_follow_up::CodingAgentFollowUpRequest::decl(),
@@ -291,6 +324,14 @@ fn generate_schemas() -> Result<HashMap<&'static str, String>, serde_json::Error
             "custom",
             generate_json_schema::<executors::executors::custom_agent::CustomAgent>()?,
         ),
+        (
+            "replay",
+            generate_json_schema::<executors::executors::replay::Replay>()?,
+        ),
+        (
+            "openai_compatible",
+            generate_json_schema::<executors::executors::openai_compatible::OpenaiCompatible>()?,
+        ),
     ]);
     println!(
         "✅ JSON schemas generated. {} schemas created.",
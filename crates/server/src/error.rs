@@ -13,6 +13,7 @@ use deployment::{DeploymentError, RemoteClientNotConfigured};
 use executors::executors::ExecutorError;
 use git2::Error as Git2Error;
 use services::services::{
+    approvals::ApprovalError,
     config::{ConfigError, EditorOpenError},
     container::ContainerError,
     git::GitServiceError,
@@ -20,6 +21,7 @@ use services::services::{
     image::ImageError,
     project::ProjectServiceError,
     remote_client::RemoteClientError,
+    replay_recorder::ReplayRecordError,
     repo::RepoError as RepoServiceError,
     share::ShareError,
     worktree_manager::WorktreeError,
@@ -68,10 +70,14 @@ pub enum ApiError {
     EditorOpen(#[from] EditorOpenError),
     #[error(transparent)]
     RemoteClient(#[from] RemoteClientError),
+    #[error(transparent)]
+    Approval(#[from] ApprovalError),
     #[error("Unauthorized")]
     Unauthorized,
     #[error("Bad request: {0}")]
     BadRequest(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
     #[error("Conflict: {0}")]
     Conflict(String),
     #[error("Forbidden: {0}")]
@@ -141,6 +147,11 @@ impl IntoResponse for ApiError {
                 _ => (StatusCode::BAD_REQUEST, "EditorOpenError"),
             },
             ApiError::Multipart(_) => (StatusCode::BAD_REQUEST, "MultipartError"),
+            ApiError::Approval(err) => match err {
+                ApprovalError::NotFound => (StatusCode::NOT_FOUND, "ApprovalError"),
+                ApprovalError::AlreadyCompleted => (StatusCode::CONFLICT, "ApprovalError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "ApprovalError"),
+            },
             ApiError::RemoteClient(err) => match err {
                 RemoteClientError::Auth => (StatusCode::UNAUTHORIZED, "RemoteClientError"),
                 RemoteClientError::Timeout => (StatusCode::GATEWAY_TIMEOUT, "RemoteClientError"),
@@ -175,6 +186,7 @@ impl IntoResponse for ApiError {
             },
             ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized"),
             ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BadRequest"),
+            ApiError::NotFound(_) => (StatusCode::NOT_FOUND, "NotFound"),
             ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
             ApiError::Forbidden(_) => (StatusCode::FORBIDDEN, "ForbiddenError"),
         };
@@ -251,6 +263,7 @@ impl IntoResponse for ApiError {
             },
             ApiError::Unauthorized => "Unauthorized. Please sign in again.".to_string(),
             ApiError::BadRequest(msg) => msg.clone(),
+            ApiError::NotFound(msg) => msg.clone(),
             ApiError::Conflict(msg) => msg.clone(),
             ApiError::Forbidden(msg) => msg.clone(),
             _ => format!("{}: {}", error_type, self),
@@ -348,6 +361,24 @@ impl From<ProjectServiceError> for ApiError {
     }
 }
 
+impl From<ReplayRecordError> for ApiError {
+    fn from(err: ReplayRecordError) -> Self {
+        match err {
+            ReplayRecordError::Sqlx(db_err) => ApiError::Database(db_err),
+            ReplayRecordError::Git(git_err) => ApiError::from(git_err),
+            ReplayRecordError::Json(json_err) => ApiError::BadRequest(format!(
+                "Failed to parse execution process logs: {json_err}"
+            )),
+            ReplayRecordError::ProcessNotFound => {
+                ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound)
+            }
+            ReplayRecordError::NotCodingAgent
+            | ReplayRecordError::StillRunning
+            | ReplayRecordError::AlreadyReplay => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
 impl From<RepoServiceError> for ApiError {
     fn from(err: RepoServiceError) -> Self {
         match err {
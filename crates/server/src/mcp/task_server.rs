@@ -3,6 +3,7 @@ use std::{future::Future, str::FromStr};
 use db::models::{
     project::Project,
     repo::Repo,
+    session::Session,
     tag::Tag,
     task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus, UpdateTask},
     workspace::{Workspace, WorkspaceContext},
@@ -24,6 +25,7 @@ use uuid::Uuid;
 use crate::routes::{
     containers::ContainerQuery,
     task_attempts::{CreateTaskAttemptBody, WorkspaceRepoInput},
+    tasks::CreateAndStartTaskRequest,
 };
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -34,11 +36,24 @@ pub struct CreateTaskRequest {
     pub title: String,
     #[schemars(description = "Optional description of the task")]
     pub description: Option<String>,
+    #[schemars(
+        description = "Start the task right away as a sub-task of the current workspace session, using the same repositories and target branches. You will get a follow-up message when it is ready for review or done"
+    )]
+    pub auto_start: Option<bool>,
+    #[schemars(
+        description = "Executor for an auto-started sub-task; defaults to the executor of the current session"
+    )]
+    pub executor: Option<String>,
+    #[schemars(description = "Optional executor variant for an auto-started sub-task")]
+    pub variant: Option<String>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct CreateTaskResponse {
     pub task_id: String,
+    #[schemars(description = "The workspace this task was created from, if any")]
+    pub parent_workspace_id: Option<String>,
+    pub started: bool,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -418,6 +433,48 @@ impl TaskServer {
             .ok_or_else(|| Self::err("VK API response missing data field", None).unwrap())
     }
 
+    fn parse_executor_profile_id(
+        executor: &str,
+        variant: Option<String>,
+    ) -> Result<ExecutorProfileId, CallToolResult> {
+        let executor_trimmed = executor.trim();
+        if executor_trimmed.is_empty() {
+            return Err(
+                Self::err("Executor must not be empty.".to_string(), None::<String>).unwrap(),
+            );
+        }
+
+        let normalized_executor = executor_trimmed.replace('-', "_").to_ascii_uppercase();
+        let base_executor = BaseCodingAgent::from_str(&normalized_executor).map_err(|_| {
+            Self::err(
+                format!("Unknown executor '{executor_trimmed}'."),
+                None::<String>,
+            )
+            .unwrap()
+        })?;
+
+        let variant = variant.and_then(|v| {
+            let trimmed = v.trim();
+            if trimmed.is_empty() {
+                None
+            } else {
+                Some(trimmed.to_string())
+            }
+        });
+
+        Ok(ExecutorProfileId {
+            executor: base_executor,
+            variant,
+        })
+    }
+
+    /// Executor of the latest session in a workspace, if recorded
+    async fn current_session_executor(&self, workspace_id: Uuid) -> Option<String> {
+        let url = self.url(&format!("/api/sessions?workspace_id={workspace_id}"));
+        let sessions: Vec<Session> = self.send_json(self.client.get(&url)).await.ok()?;
+        sessions.into_iter().find_map(|session| session.executor)
+    }
+
     fn url(&self, path: &str) -> String {
         format!(
             "{}/{}",
@@ -500,6 +557,9 @@ impl TaskServer {
             project_id,
             title,
             description,
+            auto_start,
+            executor,
+            variant,
         }): Parameters<CreateTaskRequest>,
     ) -> Result<CallToolResult, ErrorData> {
         // Expand @tagname references in description
@@ -508,26 +568,82 @@ impl TaskServer {
             None => None,
         };
 
-        let url = self.url("/api/tasks");
-
-        let task: Task = match self
-            .send_json(
-                self.client
-                    .post(&url)
-                    .json(&CreateTask::from_title_description(
-                        project_id,
-                        title,
-                        expanded_description,
-                    )),
-            )
-            .await
-        {
-            Ok(t) => t,
+        // Tasks created from inside a workspace session become its sub-tasks
+        let parent_context = self
+            .context
+            .as_ref()
+            .filter(|ctx| ctx.project_id == project_id);
+
+        let mut create_task =
+            CreateTask::from_title_description(project_id, title, expanded_description);
+        create_task.parent_workspace_id = parent_context.map(|ctx| ctx.workspace_id);
+
+        if !auto_start.unwrap_or(false) {
+            let url = self.url("/api/tasks");
+            let task: Task = match self
+                .send_json(self.client.post(&url).json(&create_task))
+                .await
+            {
+                Ok(t) => t,
+                Err(e) => return Ok(e),
+            };
+
+            return TaskServer::success(&CreateTaskResponse {
+                task_id: task.id.to_string(),
+                parent_workspace_id: task.parent_workspace_id.map(|id| id.to_string()),
+                started: false,
+            });
+        }
+
+        let Some(context) = parent_context else {
+            return Self::err(
+                "`auto_start` is only available for tasks in the project of the current workspace session. Use `start_workspace_session` instead.".to_string(),
+                None::<String>,
+            );
+        };
+
+        let executor = match executor {
+            Some(executor) => executor,
+            None => match self.current_session_executor(context.workspace_id).await {
+                Some(executor) => executor,
+                None => {
+                    return Self::err(
+                        "Could not determine the current executor; pass `executor`.".to_string(),
+                        None::<String>,
+                    );
+                }
+            },
+        };
+        let executor_profile_id = match Self::parse_executor_profile_id(&executor, variant) {
+            Ok(profile) => profile,
             Err(e) => return Ok(e),
         };
 
+        let payload = CreateAndStartTaskRequest {
+            task: create_task,
+            executor_profile_id,
+            repos: context
+                .workspace_repos
+                .iter()
+                .map(|r| WorkspaceRepoInput {
+                    repo_id: r.repo_id,
+                    target_branch: r.target_branch.clone(),
+                })
+                .collect(),
+            plan_first: false,
+        };
+
+        let url = self.url("/api/tasks/create-and-start");
+        let task: TaskWithAttemptStatus =
+            match self.send_json(self.client.post(&url).json(&payload)).await {
+                Ok(t) => t,
+                Err(e) => return Ok(e),
+            };
+
         TaskServer::success(&CreateTaskResponse {
-            task_id: task.id.to_string(),
+            task_id: task.task.id.to_string(),
+            parent_workspace_id: task.task.parent_workspace_id.map(|id| id.to_string()),
+            started: task.has_in_progress_attempt,
         })
     }
 
@@ -659,34 +775,9 @@ impl TaskServer {
             );
         }
 
-        let executor_trimmed = executor.trim();
-        if executor_trimmed.is_empty() {
-            return Self::err("Executor must not be empty.".to_string(), None::<String>);
-        }
-
-        let normalized_executor = executor_trimmed.replace('-', "_").to_ascii_uppercase();
-        let base_executor = match BaseCodingAgent::from_str(&normalized_executor) {
-            Ok(exec) => exec,
-            Err(_) => {
-                return Self::err(
-                    format!("Unknown executor '{executor_trimmed}'."),
-                    None::<String>,
-                );
-            }
-        };
-
-        let variant = variant.and_then(|v| {
-            let trimmed = v.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed.to_string())
-            }
-        });
-
-        let executor_profile_id = ExecutorProfileId {
-            executor: base_executor,
-            variant,
+        let executor_profile_id = match Self::parse_executor_profile_id(&executor, variant) {
+            Ok(profile) => profile,
+            Err(e) => return Ok(e),
         };
 
         let workspace_repos: Vec<WorkspaceRepoInput> = repos
@@ -701,6 +792,8 @@ impl TaskServer {
             task_id,
             executor_profile_id,
             repos: workspace_repos,
+            base_workspace_id: None,
+            plan_first: false,
         };
 
         let url = self.url("/api/task-attempts");
@@ -815,7 +908,7 @@ impl ServerHandler for TaskServer {
     fn get_info(&self) -> ServerInfo {
         let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`.. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_task', 'update_task', 'delete_task', 'list_repos'. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string();
         if self.context.is_some() {
-            let context_instruction = "Use 'get_context' to fetch project/task/workspace metadata for the active Vibe Kanban workspace session when available.";
+            let context_instruction = "Use 'get_context' to fetch project/task/workspace metadata for the active Vibe Kanban workspace session when available. Tasks you create in this project are tracked as sub-tasks of this workspace; pass `auto_start` to 'create_task' to start one right away and get a follow-up message with its result.";
             instruction = format!("{} {}", context_instruction, instruction);
         }
 
@@ -1,42 +1,50 @@
 use axum::{
     Json, Router,
     extract::{Path, State},
-    http::StatusCode,
     routing::post,
 };
 use deployment::Deployment;
 use utils::approvals::{ApprovalResponse, ApprovalStatus};
 
-use crate::DeploymentImpl;
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::plan::{handle_plan_approval, prepare_plan_answer},
+};
 
 pub async fn respond_to_approval(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<String>,
     Json(request): Json<ApprovalResponse>,
-) -> Result<Json<ApprovalStatus>, StatusCode> {
+) -> Result<Json<ApprovalStatus>, ApiError> {
     let service = deployment.approvals();
 
-    match service.respond(&deployment.db().pool, &id, request).await {
-        Ok((status, context)) => {
-            deployment
-                .track_if_analytics_allowed(
-                    "approval_responded",
-                    serde_json::json!({
-                        "approval_id": &id,
-                        "status": format!("{:?}", status),
-                        "tool_name": context.tool_name,
-                        "execution_process_id": context.execution_process_id.to_string(),
-                    }),
-                )
-                .await;
-
-            Ok(Json(status))
-        }
-        Err(e) => {
-            tracing::error!("Failed to respond to approval: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+    // Answering a plan starts a turn; refuse before the approval is consumed
+    // if that turn can't start
+    let plan_answer = prepare_plan_answer(&deployment, &id).await?;
+
+    let (status, context) = service
+        .respond(&deployment.db().pool, &id, request)
+        .await
+        .inspect_err(|e| tracing::error!("Failed to respond to approval: {:?}", e))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "approval_responded",
+            serde_json::json!({
+                "approval_id": &id,
+                "status": format!("{:?}", status),
+                "tool_name": context.tool_name,
+                "execution_process_id": context.execution_process_id.to_string(),
+            }),
+        )
+        .await;
+
+    if let Some(plan_answer) = plan_answer {
+        handle_plan_approval(&deployment, plan_answer, &status).await?;
     }
+
+    Ok(Json(status))
 }
 
 pub fn router() -> Router<DeploymentImpl> {
@@ -15,6 +15,7 @@ use executors::{
     },
     mcp_config::{McpConfig, read_agent_config, write_agent_config},
     profile::{ExecutorConfigs, ExecutorProfileId},
+    version::AgentVersionInfo,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -471,17 +472,29 @@ pub struct CheckAgentAvailabilityQuery {
     executor: BaseCodingAgent,
 }
 
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct CheckAgentAvailabilityResponse {
+    pub availability: AvailabilityInfo,
+    pub version: AgentVersionInfo,
+}
+
 async fn check_agent_availability(
     State(_deployment): State<DeploymentImpl>,
     Query(query): Query<CheckAgentAvailabilityQuery>,
-) -> ResponseJson<ApiResponse<AvailabilityInfo>> {
+) -> ResponseJson<ApiResponse<CheckAgentAvailabilityResponse>> {
     let profiles = ExecutorConfigs::get_cached();
     let profile_id = ExecutorProfileId::new(query.executor);
 
-    let info = match profiles.get_coding_agent(&profile_id) {
-        Some(agent) => agent.get_availability_info(),
-        None => AvailabilityInfo::NotFound,
+    let response = match profiles.get_coding_agent(&profile_id) {
+        Some(agent) => CheckAgentAvailabilityResponse {
+            availability: agent.get_availability_info(),
+            version: agent.get_version_info(),
+        },
+        None => CheckAgentAvailabilityResponse {
+            availability: AvailabilityInfo::NotFound,
+            version: AgentVersionInfo::default(),
+        },
     };
 
-    ResponseJson(ApiResponse::success(info))
+    ResponseJson(ApiResponse::success(response))
 }
@@ -1,22 +1,35 @@
 use anyhow;
 use axum::{
-    Extension, Router,
+    Extension, Json, Router,
     extract::{
         Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
+    http::header,
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::{get, post},
 };
 use db::models::{
-    execution_process::{ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus},
+    execution_process::{
+        ExecutionProcess, ExecutionProcessError, ExecutionProcessRunReason, ExecutionProcessStatus,
+    },
     execution_process_repo_state::ExecutionProcessRepoState,
+    session::{CreateSession, Session, SessionError},
+    workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
 use deployment::Deployment;
+use executors::executors::{BaseAgentCapability, replay::ReplayFixture};
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use serde::Deserialize;
-use services::services::container::ContainerService;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    replay_recorder,
+    transcript::{Transcript, TranscriptFormat},
+};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
 use utils::{log_msg::LogMsg, response::ApiResponse};
 use uuid::Uuid;
 
@@ -178,6 +191,40 @@ pub async fn stop_execution_process(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct SteerExecutionProcessRequest {
+    pub message: String,
+}
+
+/// Send a message to the agent while it is running, without waiting for a follow-up
+pub async fn steer_execution_process(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SteerExecutionProcessRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let message = payload.message.trim();
+    if message.is_empty() {
+        return Err(ApiError::BadRequest("Message cannot be empty".to_string()));
+    }
+    if execution_process.status != ExecutionProcessStatus::Running {
+        return Err(ApiError::Conflict(
+            "Execution process is not running".to_string(),
+        ));
+    }
+
+    if !deployment
+        .container()
+        .steer_execution(&execution_process, message.to_string())
+        .await
+    {
+        return Err(ApiError::Conflict(
+            "This agent does not accept messages while it is running".to_string(),
+        ));
+    }
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub async fn stream_execution_processes_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -243,11 +290,226 @@ pub async fn get_execution_process_repo_states(
     Ok(ResponseJson(ApiResponse::success(repo_states)))
 }
 
+/// Record this run as a fixture for the `REPLAY` executor
+pub async fn get_execution_process_replay_fixture(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ReplayFixture>>, ApiError> {
+    let fixture =
+        replay_recorder::record_fixture(&deployment.db().pool, execution_process.id).await?;
+    Ok(ResponseJson(ApiResponse::success(fixture)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TranscriptQuery {
+    #[serde(default)]
+    pub format: TranscriptFormat,
+}
+
+/// Serve a transcript as a file download
+pub fn transcript_response(
+    transcript: &Transcript,
+    format: TranscriptFormat,
+    name: &str,
+) -> Response {
+    let disposition = format!("attachment; filename=\"{name}.{}\"", format.extension());
+    (
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        transcript.render(format),
+    )
+        .into_response()
+}
+
+/// Export the conversation of this run as Markdown, HTML or JSON
+pub async fn get_execution_process_transcript(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TranscriptQuery>,
+) -> Result<Response, ApiError> {
+    let name = format!("transcript-{}", execution_process.id);
+    let transcript =
+        Transcript::for_execution_process(deployment.container(), execution_process).await?;
+    Ok(transcript_response(&transcript, query.format, &name))
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ForkExecutionProcessResponse {
+    pub workspace: Workspace,
+    pub session: Session,
+}
+
+/// Continue from the end of this coding agent turn in a new workspace and
+/// session, leaving the original workspace and its later history untouched
+pub async fn fork_execution_process(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ForkExecutionProcessResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if execution_process.run_reason != ExecutionProcessRunReason::CodingAgent {
+        return Err(ApiError::BadRequest(
+            "Only coding agent turns can be forked".to_string(),
+        ));
+    }
+    if execution_process.status == ExecutionProcessStatus::Running {
+        return Err(ApiError::Conflict(
+            "Wait for the turn to finish before forking from it".to_string(),
+        ));
+    }
+    let action = execution_process
+        .executor_action()
+        .map_err(|e| ExecutionProcessError::ValidationError(e.to_string()))?;
+    let can_fork = action.coding_agent().is_some_and(|agent| {
+        agent
+            .capabilities()
+            .contains(&BaseAgentCapability::SessionFork)
+    });
+    if !can_fork {
+        return Err(ApiError::BadRequest(
+            "This agent cannot fork its conversation".to_string(),
+        ));
+    }
+
+    let session = Session::find_by_id(pool, execution_process.session_id)
+        .await?
+        .ok_or(SessionError::NotFound)?;
+    let workspace = Workspace::find_by_id(pool, session.workspace_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let task = workspace
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    // Each repo's branch starts from the commit the turn left it at
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let mut fork_commits = Vec::with_capacity(repos.len());
+    for repo in &repos {
+        let commit = execution_process
+            .fork_commit(pool, repo.id)
+            .await?
+            .ok_or_else(|| {
+                ApiError::BadRequest(format!(
+                    "No commit was recorded for {} at this turn",
+                    repo.name
+                ))
+            })?;
+        fork_commits.push((repo, commit));
+    }
+    let workspace_repos: Vec<CreateWorkspaceRepo> =
+        WorkspaceRepo::find_by_workspace_id(pool, workspace.id)
+            .await?
+            .into_iter()
+            .map(|wr| CreateWorkspaceRepo {
+                repo_id: wr.repo_id,
+                target_branch: wr.target_branch,
+            })
+            .collect();
+
+    let forked_workspace_id = Uuid::new_v4();
+    let branch = deployment
+        .container()
+        .git_branch_from_workspace(&forked_workspace_id, &task.title)
+        .await;
+
+    // Create the rows, then the branches they refer to; undo both if any step fails
+    let mut created_branches = Vec::new();
+    let forked = async {
+        Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch: branch.clone(),
+                agent_working_dir: workspace.agent_working_dir.clone(),
+                base_workspace_id: workspace.base_workspace_id,
+            },
+            forked_workspace_id,
+            task.id,
+        )
+        .await?;
+        WorkspaceRepo::create_many(pool, forked_workspace_id, &workspace_repos).await?;
+        let forked_session = Session::create_fork(
+            pool,
+            &CreateSession {
+                executor: session.executor.clone(),
+            },
+            Uuid::new_v4(),
+            forked_workspace_id,
+            execution_process.id,
+        )
+        .await?;
+
+        for (repo, commit) in &fork_commits {
+            deployment
+                .git()
+                .create_branch_at_commit(&repo.path, &branch, commit)?;
+            created_branches.push(repo.path.clone());
+        }
+
+        let forked_workspace = Workspace::find_by_id(pool, forked_workspace_id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        deployment
+            .container()
+            .ensure_container_exists(&forked_workspace)
+            .await?;
+        Ok::<_, ApiError>((forked_workspace, forked_session))
+    }
+    .await;
+    let (forked_workspace, forked_session) = match forked {
+        Ok(forked) => forked,
+        Err(e) => {
+            for repo_path in &created_branches {
+                if let Err(err) = deployment.git().delete_branch(repo_path, &branch) {
+                    tracing::warn!(
+                        "Failed to delete branch {} after a failed fork: {}",
+                        branch,
+                        err
+                    );
+                }
+            }
+            if let Err(err) = Workspace::delete(pool, forked_workspace_id).await {
+                tracing::warn!(
+                    "Failed to delete workspace {} after a failed fork: {}",
+                    forked_workspace_id,
+                    err
+                );
+            }
+            return Err(e);
+        }
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_forked",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "workspace_id": forked_workspace_id.to_string(),
+                "forked_from_workspace_id": workspace.id.to_string(),
+                "repository_count": repos.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        ForkExecutionProcessResponse {
+            workspace: forked_workspace,
+            session: forked_session,
+        },
+    )))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let workspace_id_router = Router::new()
         .route("/", get(get_execution_process_by_id))
         .route("/stop", post(stop_execution_process))
+        .route("/steer", post(steer_execution_process))
+        .route("/fork", post(fork_execution_process))
         .route("/repo-states", get(get_execution_process_repo_states))
+        .route("/replay-fixture", get(get_execution_process_replay_fixture))
+        .route("/transcript", get(get_execution_process_transcript))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
         .layer(from_fn_with_state(
@@ -12,8 +12,9 @@ use utils::{
             AcceptInvitationResponse, CreateInvitationRequest, CreateInvitationResponse,
             CreateOrganizationRequest, CreateOrganizationResponse, GetInvitationResponse,
             GetOrganizationResponse, ListInvitationsResponse, ListMembersResponse,
-            ListOrganizationsResponse, Organization, RevokeInvitationRequest,
-            UpdateMemberRoleRequest, UpdateMemberRoleResponse, UpdateOrganizationRequest,
+            ListOrganizationsResponse, MemberProjectAccess, Organization, RevokeInvitationRequest,
+            UpdateMemberProjectAccessRequest, UpdateMemberRoleRequest, UpdateMemberRoleResponse,
+            UpdateOrganizationRequest,
         },
         projects::RemoteProject,
     },
@@ -54,6 +55,10 @@ pub fn router() -> Router<DeploymentImpl> {
             "/organizations/{org_id}/members/{user_id}/role",
             patch(update_member_role),
         )
+        .route(
+            "/organizations/{org_id}/members/{user_id}/projects",
+            get(get_member_project_access).patch(update_member_project_access),
+        )
 }
 
 async fn list_organization_projects(
@@ -234,3 +239,28 @@ async fn update_member_role(
 
     Ok(ResponseJson(ApiResponse::success(response)))
 }
+
+async fn get_member_project_access(
+    State(deployment): State<DeploymentImpl>,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<MemberProjectAccess>>, ApiError> {
+    let client = deployment.remote_client()?;
+
+    let response = client.get_member_project_access(org_id, user_id).await?;
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+async fn update_member_project_access(
+    State(deployment): State<DeploymentImpl>,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateMemberProjectAccessRequest>,
+) -> Result<ResponseJson<ApiResponse<MemberProjectAccess>>, ApiError> {
+    let client = deployment.remote_client()?;
+
+    let response = client
+        .update_member_project_access(org_id, user_id, &request)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
@@ -4,25 +4,26 @@ use anyhow;
 use axum::{
     Extension, Json, Router,
     extract::{
-        Path, Query, State,
+        Path, Query, Request, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
     http::StatusCode,
-    middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
-    routing::{get, post},
+    middleware::{Next, from_fn_with_state},
+    response::{IntoResponse, Json as ResponseJson, Response},
+    routing::{delete, get, post},
 };
 use db::models::{
     project::{CreateProject, Project, ProjectError, SearchResult, UpdateProject},
     project_repo::{CreateProjectRepo, ProjectRepo, UpdateProjectRepo},
+    project_secret::{ProjectSecret, UpsertProjectSecret},
     repo::Repo,
 };
-use deployment::Deployment;
+use deployment::{Deployment, DeploymentError};
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::Deserialize;
 use services::services::{
     file_search_cache::SearchQuery, project::ProjectServiceError,
-    remote_client::CreateRemoteProjectPayload,
+    remote_client::CreateRemoteProjectPayload, secrets::SecretsError,
 };
 use ts_rs::TS;
 use utils::{
@@ -582,6 +583,97 @@ pub async fn update_project_repository(
     }
 }
 
+pub async fn get_project_secrets(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectSecret>>>, ApiError> {
+    let secrets = deployment
+        .secrets()
+        .list(&deployment.db().pool, project.id)
+        .await
+        .map_err(DeploymentError::from)?;
+    Ok(ResponseJson(ApiResponse::success(secrets)))
+}
+
+pub async fn upsert_project_secret(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectSecret>,
+) -> Result<ResponseJson<ApiResponse<ProjectSecret>>, ApiError> {
+    match deployment
+        .secrets()
+        .set(
+            &deployment.db().pool,
+            project.id,
+            &payload.name,
+            &payload.value,
+        )
+        .await
+    {
+        Ok(secret) => {
+            deployment
+                .track_if_analytics_allowed(
+                    "project_secret_set",
+                    serde_json::json!({
+                        "project_id": project.id.to_string(),
+                    }),
+                )
+                .await;
+
+            Ok(ResponseJson(ApiResponse::success(secret)))
+        }
+        Err(SecretsError::InvalidName(name)) => Err(ApiError::BadRequest(format!(
+            "Invalid secret name '{name}': use letters, digits and underscores, not starting with a digit"
+        ))),
+        Err(e) => Err(DeploymentError::from(e).into()),
+    }
+}
+
+pub async fn delete_project_secret(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, name)): Path<(Uuid, String)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    match deployment
+        .secrets()
+        .delete(&deployment.db().pool, project.id, &name)
+        .await
+    {
+        Ok(()) => {
+            deployment
+                .track_if_analytics_allowed(
+                    "project_secret_deleted",
+                    serde_json::json!({
+                        "project_id": project.id.to_string(),
+                    }),
+                )
+                .await;
+
+            Ok(ResponseJson(ApiResponse::success(())))
+        }
+        Err(SecretsError::NotFound(name)) => {
+            Err(ApiError::NotFound(format!("Secret '{name}' not found")))
+        }
+        Err(e) => Err(DeploymentError::from(e).into()),
+    }
+}
+
+/// Middleware to load the Project for routes that also carry a secret name.
+async fn load_project_with_secret_name(
+    State(deployment): State<DeploymentImpl>,
+    Path((id, _name)): Path<(Uuid, String)>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let project = match Project::find_by_id(&deployment.db().pool, id).await {
+        Ok(Some(project)) => project,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    request.extensions_mut().insert(project);
+    Ok(next.run(request).await)
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let project_id_router = Router::new()
         .route(
@@ -600,11 +692,22 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/repositories",
             get(get_project_repositories).post(add_project_repository),
         )
+        .route(
+            "/secrets",
+            get(get_project_secrets).put(upsert_project_secret),
+        )
         .layer(from_fn_with_state(
             deployment.clone(),
             load_project_middleware,
         ));
 
+    let project_secret_router = Router::new()
+        .route("/secrets/{name}", delete(delete_project_secret))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_with_secret_name,
+        ));
+
     let projects_router = Router::new()
         .route("/", get(get_projects).post(create_project))
         .route(
@@ -614,7 +717,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
                 .delete(delete_project_repository),
         )
         .route("/stream/ws", get(stream_projects_ws))
-        .nest("/{id}", project_id_router);
+        .nest("/{id}", project_id_router.merge(project_secret_router));
 
     Router::new().nest("/projects", projects_router).route(
         "/remote-projects/{remote_project_id}",
@@ -4,7 +4,7 @@ use axum::{
     Extension, Json, Router,
     extract::{Query, State},
     middleware::from_fn_with_state,
-    response::Json as ResponseJson,
+    response::{Json as ResponseJson, Response},
     routing::{get, post},
 };
 use db::models::{
@@ -22,15 +22,20 @@ use executors::{
     profile::ExecutorProfileId,
 };
 use serde::Deserialize;
-use services::services::container::ContainerService;
+use services::services::{container::ContainerService, transcript::Transcript};
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_session_middleware,
-    routes::task_attempts::util::restore_worktrees_to_process,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_session_middleware,
+    routes::{
+        execution_processes::{TranscriptQuery, transcript_response},
+        task_attempts::util::restore_worktrees_to_process,
+    },
 };
 
 #[derive(Debug, Deserialize)]
@@ -193,6 +198,7 @@ pub async fn follow_up(
             session_id: agent_session_id,
             executor_profile_id: executor_profile_id.clone(),
             working_dir: working_dir.clone(),
+            instructions: None,
         })
     } else {
         ExecutorActionType::CodingAgentInitialRequest(
@@ -200,6 +206,7 @@ pub async fn follow_up(
                 prompt,
                 executor_profile_id: executor_profile_id.clone(),
                 working_dir,
+                instructions: None,
             },
         )
     };
@@ -230,10 +237,25 @@ pub async fn follow_up(
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
+/// Export the conversation of every run in this session as Markdown, HTML or JSON
+pub async fn get_session_transcript(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TranscriptQuery>,
+) -> Result<Response, ApiError> {
+    let transcript = Transcript::for_session(deployment.container(), &session).await?;
+    Ok(transcript_response(
+        &transcript,
+        query.format,
+        &format!("transcript-{}", session.id),
+    ))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let session_id_router = Router::new()
         .route("/", get(get_session))
         .route("/follow-up", post(follow_up))
+        .route("/transcript", get(get_session_transcript))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_session_middleware,
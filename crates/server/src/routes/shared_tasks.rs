@@ -2,11 +2,20 @@ use axum::{
     Json, Router,
     extract::{Path, State},
     response::Json as ResponseJson,
-    routing::{delete, post},
+    routing::{delete, get, patch, post},
 };
 use db::models::task::Task;
 use deployment::Deployment;
-use remote::routes::tasks::SharedTaskResponse;
+use remote::{
+    db::task_comments::SharedTaskComment,
+    routes::{
+        task_comments::{
+            CreateSharedTaskCommentRequest, ListSharedTaskActivityResponse,
+            ListSharedTaskCommentsResponse, UpdateSharedTaskCommentRequest,
+        },
+        tasks::SharedTaskResponse,
+    },
+};
 use serde::Deserialize;
 use services::services::share::{ShareError, SharedTaskDetails};
 use ts_rs::TS;
@@ -32,6 +41,18 @@ pub fn router() -> Router<DeploymentImpl> {
             "/shared-tasks/link-to-local",
             post(link_shared_task_to_local),
         )
+        .route(
+            "/shared-tasks/{shared_task_id}/comments",
+            get(list_shared_task_comments).post(create_shared_task_comment),
+        )
+        .route(
+            "/shared-tasks/{shared_task_id}/comments/{comment_id}",
+            patch(update_shared_task_comment).delete(delete_shared_task_comment),
+        )
+        .route(
+            "/shared-tasks/{shared_task_id}/activity",
+            get(list_shared_task_activity),
+        )
 }
 
 pub async fn assign_shared_task(
@@ -101,3 +122,74 @@ pub async fn link_shared_task_to_local(
 
     Ok(ResponseJson(ApiResponse::success(task)))
 }
+
+pub async fn list_shared_task_comments(
+    Path(shared_task_id): Path<Uuid>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ListSharedTaskCommentsResponse>>, ApiError> {
+    let client = deployment.remote_client()?;
+
+    let response = client.list_shared_task_comments(shared_task_id).await?;
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+pub async fn create_shared_task_comment(
+    Path(shared_task_id): Path<Uuid>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateSharedTaskCommentRequest>,
+) -> Result<ResponseJson<ApiResponse<SharedTaskComment>>, ApiError> {
+    let client = deployment.remote_client()?;
+
+    let comment = client
+        .create_shared_task_comment(shared_task_id, &payload)
+        .await?;
+
+    let props = serde_json::json!({
+        "shared_task_id": shared_task_id,
+        "comment_id": comment.id,
+    });
+    deployment
+        .track_if_analytics_allowed("shared_task_comment_created", props)
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+pub async fn update_shared_task_comment(
+    Path((shared_task_id, comment_id)): Path<(Uuid, Uuid)>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateSharedTaskCommentRequest>,
+) -> Result<ResponseJson<ApiResponse<SharedTaskComment>>, ApiError> {
+    let client = deployment.remote_client()?;
+
+    let comment = client
+        .update_shared_task_comment(shared_task_id, comment_id, &payload)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+pub async fn delete_shared_task_comment(
+    Path((shared_task_id, comment_id)): Path<(Uuid, Uuid)>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let client = deployment.remote_client()?;
+
+    client
+        .delete_shared_task_comment(shared_task_id, comment_id)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn list_shared_task_activity(
+    Path(shared_task_id): Path<Uuid>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ListSharedTaskActivityResponse>>, ApiError> {
+    let client = deployment.remote_client()?;
+
+    let response = client.list_shared_task_activity(shared_task_id).await?;
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
@@ -2,6 +2,7 @@ pub mod codex_setup;
 pub mod cursor_setup;
 pub mod gh_cli_setup;
 pub mod images;
+pub mod plan;
 pub mod pr;
 pub mod util;
 
@@ -18,7 +19,7 @@ use axum::{
     },
     http::StatusCode,
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::{get, post},
 };
 use db::models::{
@@ -28,8 +29,10 @@ use db::models::{
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskRelationships, TaskStatus},
+    task_plan::TaskPlan,
     workspace::{CreateWorkspace, Workspace, WorkspaceError},
     workspace_repo::{CreateWorkspaceRepo, RepoWithTargetBranch, WorkspaceRepo},
+    workspace_storage::{PinWorkspace, WorkspaceStorage},
 };
 use deployment::Deployment;
 use executors::{
@@ -46,6 +49,8 @@ use services::services::{
     container::ContainerService,
     git::{ConflictOp, GitCliError, GitServiceError},
     github::GitHubService,
+    stacked_workspace::StackedWorkspaceService,
+    transcript::Transcript,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
@@ -53,8 +58,13 @@ use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_workspace_middleware,
-    routes::task_attempts::gh_cli_setup::GhCliSetupError,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_workspace_middleware,
+    routes::{
+        execution_processes::{TranscriptQuery, transcript_response},
+        task_attempts::gh_cli_setup::GhCliSetupError,
+    },
 };
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -108,6 +118,16 @@ pub struct CreateTaskAttemptBody {
     pub task_id: Uuid,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    /// Stack the new attempt on another attempt's branch. Repos shared with
+    /// the base attempt target its branch instead of the requested one.
+    #[serde(default)]
+    #[ts(optional)]
+    pub base_workspace_id: Option<Uuid>,
+    /// Start with a read-only planning turn whose plan must be approved
+    /// before the agent implements it
+    #[serde(default)]
+    #[ts(optional)]
+    pub plan_first: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
@@ -153,6 +173,34 @@ pub async fn create_task_attempt(
         .filter(|dir| !dir.is_empty())
         .cloned();
 
+    // Stacked attempts target the base attempt's branch for every repo they share
+    let base_workspace = match payload.base_workspace_id {
+        Some(base_id) => {
+            let base = Workspace::find_by_id(pool, base_id)
+                .await?
+                .ok_or(ApiError::BadRequest("Base workspace not found".to_string()))?;
+            let base_task = base
+                .parent_task(pool)
+                .await?
+                .ok_or(ApiError::Workspace(WorkspaceError::TaskNotFound))?;
+            if base_task.project_id != task.project_id {
+                return Err(ApiError::BadRequest(
+                    "Base workspace must belong to the same project".to_string(),
+                ));
+            }
+            Some(base)
+        }
+        None => None,
+    };
+    let base_repo_ids: Vec<Uuid> = match &base_workspace {
+        Some(base) => WorkspaceRepo::find_by_workspace_id(pool, base.id)
+            .await?
+            .into_iter()
+            .map(|wr| wr.repo_id)
+            .collect(),
+        None => Vec::new(),
+    };
+
     let attempt_id = Uuid::new_v4();
     let git_branch_name = deployment
         .container()
@@ -164,6 +212,7 @@ pub async fn create_task_attempt(
         &CreateWorkspace {
             branch: git_branch_name.clone(),
             agent_working_dir,
+            base_workspace_id: base_workspace.as_ref().map(|base| base.id),
         },
         attempt_id,
         payload.task_id,
@@ -175,11 +224,47 @@ pub async fn create_task_attempt(
         .iter()
         .map(|r| CreateWorkspaceRepo {
             repo_id: r.repo_id,
-            target_branch: r.target_branch.clone(),
+            target_branch: match &base_workspace {
+                Some(base) if base_repo_ids.contains(&r.repo_id) => base.branch.clone(),
+                _ => r.target_branch.clone(),
+            },
         })
         .collect();
 
     WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
+    // Record where the stacked branch starts, so later rebases only replay its own commits
+    if let Some(base) = &base_workspace {
+        for repo_id in payload
+            .repos
+            .iter()
+            .map(|r| r.repo_id)
+            .filter(|id| base_repo_ids.contains(id))
+        {
+            let Some(repo) = Repo::find_by_id(pool, repo_id).await? else {
+                continue;
+            };
+            match deployment.git().get_branch_oid(&repo.path, &base.branch) {
+                Ok(base_commit) => {
+                    WorkspaceRepo::set_stacked_base_commit(
+                        pool,
+                        workspace.id,
+                        repo_id,
+                        Some(&base_commit),
+                    )
+                    .await?
+                }
+                Err(e) => tracing::warn!(
+                    "Failed to resolve base branch {} for stacked workspace {}: {}",
+                    base.branch,
+                    workspace.id,
+                    e
+                ),
+            }
+        }
+    }
+    if payload.plan_first {
+        TaskPlan::create(pool, task.id, workspace.id).await?;
+    }
     if let Err(err) = deployment
         .container()
         .start_workspace(&workspace, executor_profile_id.clone())
@@ -197,6 +282,8 @@ pub async fn create_task_attempt(
                 "executor": &executor_profile_id.executor,
                 "workspace_id": workspace.id.to_string(),
                 "repository_count": payload.repos.len(),
+                "stacked": workspace.base_workspace_id.is_some(),
+                "plan_first": payload.plan_first,
             }),
         )
         .await;
@@ -370,6 +457,30 @@ pub async fn merge_task_attempt(
     )
     .await?;
     Task::update_status(pool, task.id, TaskStatus::Done).await?;
+    deployment
+        .container()
+        .notify_parent_workspace(&workspace)
+        .await;
+
+    // Move workspaces stacked on this one over to the branch we just merged into
+    let stacked = StackedWorkspaceService::new(deployment.db().clone(), deployment.git().clone());
+    match stacked
+        .handle_base_merged(&workspace, workspace_repo.repo_id)
+        .await
+    {
+        Ok(conflicts) => {
+            for conflict in conflicts {
+                conflict
+                    .notify(deployment.container().notification_service())
+                    .await;
+            }
+        }
+        Err(e) => tracing::warn!(
+            "Failed to retarget stacked workspaces for {}: {}",
+            workspace.id,
+            e
+        ),
+    }
 
     // Stop any running dev servers for this workspace
     let dev_servers =
@@ -1055,6 +1166,22 @@ pub async fn rebase_task_attempt(
         };
     }
 
+    let stacked = StackedWorkspaceService::new(deployment.db().clone(), deployment.git().clone());
+    match stacked.sync_children(&workspace).await {
+        Ok(conflicts) => {
+            for conflict in conflicts {
+                conflict
+                    .notify(deployment.container().notification_service())
+                    .await;
+            }
+        }
+        Err(e) => tracing::warn!(
+            "Failed to sync stacked workspaces for {}: {}",
+            workspace.id,
+            e
+        ),
+    }
+
     deployment
         .track_if_analytics_allowed(
             "task_attempt_rebased",
@@ -1209,6 +1336,20 @@ pub async fn start_dev_server(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Export the conversation of every session in this attempt as Markdown, HTML or JSON
+pub async fn get_task_attempt_transcript(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TranscriptQuery>,
+) -> Result<Response, ApiError> {
+    let transcript = Transcript::for_workspace(deployment.container(), &workspace).await?;
+    Ok(transcript_response(
+        &transcript,
+        query.format,
+        &format!("transcript-{}", workspace.id),
+    ))
+}
+
 pub async fn get_task_attempt_children(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1482,6 +1623,16 @@ pub async fn get_task_attempt_repos(
     Ok(ResponseJson(ApiResponse::success(repos)))
 }
 
+/// Pinned workspaces are never removed by the automatic cleanup
+pub async fn pin_task_attempt(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<PinWorkspace>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    WorkspaceStorage::set_pinned(&deployment.db().pool, workspace.id, payload.pinned).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempt_id_router = Router::new()
         .route("/", get(get_task_attempt))
@@ -1500,12 +1651,18 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/pr", post(pr::create_github_pr))
         .route("/pr/attach", post(pr::attach_existing_pr))
         .route("/pr/comments", get(pr::get_pr_comments))
+        .route(
+            "/plan",
+            get(plan::get_task_plan).put(plan::update_task_plan),
+        )
         .route("/open-editor", post(open_task_attempt_in_editor))
         .route("/children", get(get_task_attempt_children))
+        .route("/transcript", get(get_task_attempt_transcript))
         .route("/stop", post(stop_task_attempt_execution))
         .route("/change-target-branch", post(change_target_branch))
         .route("/rename-branch", post(rename_branch))
         .route("/repos", get(get_task_attempt_repos))
+        .route("/pin", post(pin_task_attempt))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_workspace_middleware,
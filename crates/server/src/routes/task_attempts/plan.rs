@@ -0,0 +1,233 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    project_repo::ProjectRepo,
+    session::Session,
+    task_plan::{TaskPlan, TaskPlanStatus, UpdateTaskPlan},
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use executors::actions::{
+    ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
+    coding_agent_initial::CodingAgentInitialRequest,
+};
+use services::services::{container::ContainerService, task_plan};
+use sqlx::Error as SqlxError;
+use utils::{approvals::ApprovalStatus, response::ApiResponse};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The attempt's plan, if it was started plan-first
+pub async fn get_task_plan(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<TaskPlan>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut plan = TaskPlan::find_by_workspace_id(pool, workspace.id).await?;
+
+    if let Some(plan) = plan
+        .as_mut()
+        .filter(|plan| plan.status == TaskPlanStatus::PendingApproval)
+    {
+        task_plan::ensure_approval_pending(pool, deployment.approvals(), plan).await?;
+    }
+
+    Ok(ResponseJson(ApiResponse::success(plan)))
+}
+
+/// Edit the plan before approving it
+pub async fn update_task_plan(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateTaskPlan>,
+) -> Result<ResponseJson<ApiResponse<TaskPlan>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let plan = TaskPlan::find_by_workspace_id(pool, workspace.id)
+        .await?
+        .ok_or(ApiError::BadRequest(
+            "This attempt was not started plan-first".to_string(),
+        ))?;
+    if plan.status == TaskPlanStatus::Approved {
+        return Err(ApiError::Conflict(
+            "The plan was already approved".to_string(),
+        ));
+    }
+
+    TaskPlan::update_content(pool, plan.id, &payload.content).await?;
+    let plan = TaskPlan::find_by_workspace_id(pool, workspace.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    Ok(ResponseJson(ApiResponse::success(plan)))
+}
+
+/// A plan approval about to be answered, with the session its answer continues
+pub struct PlanAnswer {
+    plan: TaskPlan,
+    workspace: Workspace,
+    session: Session,
+}
+
+/// Check that the answer to a plan's approval can be acted on before the
+/// approval is consumed. Returns `None` if the approval isn't for a plan.
+pub async fn prepare_plan_answer(
+    deployment: &DeploymentImpl,
+    approval_id: &str,
+) -> Result<Option<PlanAnswer>, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(plan) = TaskPlan::find_by_approval_id(pool, approval_id).await? else {
+        return Ok(None);
+    };
+    if plan.status != TaskPlanStatus::PendingApproval {
+        return Ok(None);
+    }
+
+    let workspace = Workspace::find_by_id(pool, plan.workspace_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    // The answer starts a turn, so it has to wait for the agent
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "Wait for the running process to finish before answering the plan".to_string(),
+        ));
+    }
+
+    let session = match plan.execution_process_id {
+        Some(process_id) => match ExecutionProcess::find_by_id(pool, process_id).await? {
+            Some(process) => Session::find_by_id(pool, process.session_id).await?,
+            None => None,
+        },
+        None => None,
+    };
+    let session = match session {
+        Some(session) => session,
+        None => Session::find_latest_by_workspace_id(pool, workspace.id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?,
+    };
+
+    Ok(Some(PlanAnswer {
+        plan,
+        workspace,
+        session,
+    }))
+}
+
+/// Act on the answer to a plan's approval: approving starts the implementation
+/// turn with the plan as it is now, denying asks the agent for a revised plan.
+/// The plan only moves on once that turn has started; if it can't start, the
+/// plan is put back up for approval so the answer can be given again.
+pub async fn handle_plan_approval(
+    deployment: &DeploymentImpl,
+    answer: PlanAnswer,
+    status: &ApprovalStatus,
+) -> Result<(), ApiError> {
+    let pool = &deployment.db().pool;
+    let PlanAnswer {
+        mut plan,
+        workspace,
+        session,
+    } = answer;
+
+    let (next_status, prompt) = match status {
+        ApprovalStatus::Approved => (
+            TaskPlanStatus::Approved,
+            task_plan::implementation_prompt(&plan.content),
+        ),
+        ApprovalStatus::Denied { reason } => (
+            TaskPlanStatus::Planning,
+            task_plan::revision_prompt(reason.as_deref()),
+        ),
+        ApprovalStatus::Pending | ApprovalStatus::TimedOut => return Ok(()),
+    };
+
+    if let Err(e) = start_plan_follow_up(deployment, &workspace, &session, prompt).await {
+        // The answer consumed the approval, so ask again
+        if let Err(err) =
+            task_plan::ensure_approval_pending(pool, deployment.approvals(), &mut plan).await
+        {
+            tracing::error!("Failed to request plan approval again: {}", err);
+        }
+        return Err(e);
+    }
+    TaskPlan::update_status(pool, plan.id, next_status).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_plan_answered",
+            serde_json::json!({
+                "task_id": plan.task_id.to_string(),
+                "workspace_id": workspace.id.to_string(),
+                "approved": next_status == TaskPlanStatus::Approved,
+            }),
+        )
+        .await;
+
+    Ok(())
+}
+
+async fn start_plan_follow_up(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    session: &Session,
+    prompt: String,
+) -> Result<ExecutionProcess, ApiError> {
+    let pool = &deployment.db().pool;
+
+    deployment
+        .container()
+        .ensure_container_exists(workspace)
+        .await?;
+
+    let task = workspace
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let executor_profile_id =
+        ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?;
+    let latest_agent_session_id =
+        ExecutionProcess::find_latest_coding_agent_turn_session_id(pool, session.id).await?;
+
+    let project_repos = ProjectRepo::find_by_project_id_with_names(pool, task.project_id).await?;
+    let cleanup_action = deployment
+        .container()
+        .cleanup_actions_for_repos(&project_repos);
+
+    let working_dir = workspace
+        .agent_working_dir
+        .as_ref()
+        .filter(|dir| !dir.is_empty())
+        .cloned();
+
+    let action_type = if let Some(agent_session_id) = latest_agent_session_id {
+        ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+            prompt,
+            session_id: agent_session_id,
+            executor_profile_id,
+            working_dir,
+            instructions: None,
+        })
+    } else {
+        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt,
+            executor_profile_id,
+            working_dir,
+            instructions: None,
+        })
+    };
+
+    let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
+
+    Ok(deployment
+        .container()
+        .start_execution(
+            workspace,
+            session,
+            &action,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?)
+}
@@ -25,6 +25,7 @@ use services::services::{
     container::ContainerService,
     git::{GitCliError, GitServiceError},
     github::{CreatePrRequest, GitHubService, GitHubServiceError, UnifiedPrComment},
+    transcript::Transcript,
 };
 use ts_rs::TS;
 use utils::response::ApiResponse;
@@ -41,6 +42,9 @@ pub struct CreateGitHubPrRequest {
     pub repo_id: Uuid,
     #[serde(default)]
     pub auto_generate_description: bool,
+    /// Append a condensed transcript of the agent conversations to the body
+    #[serde(default)]
+    pub include_transcript: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -159,12 +163,14 @@ async fn trigger_pr_description_follow_up(
             session_id: agent_session_id,
             executor_profile_id: executor_profile_id.clone(),
             working_dir: working_dir.clone(),
+            instructions: None,
         })
     } else {
         ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
             prompt,
             executor_profile_id: executor_profile_id.clone(),
             working_dir,
+            instructions: None,
         })
     };
 
@@ -211,7 +217,30 @@ pub async fn create_github_pr(
         .ensure_container_exists(&workspace)
         .await?;
     let workspace_path = PathBuf::from(&container_ref);
-    let worktree_path = workspace_path.join(repo.name);
+    let worktree_path = workspace_path.join(&repo.name);
+
+    // Stacked PR: make sure the base workspace's branch exists on the remote
+    if let Some(base_id) = workspace.base_workspace_id
+        && let Some(base) = Workspace::find_by_id(pool, base_id).await?
+        && base.branch == target_branch
+    {
+        let base_container_ref = deployment
+            .container()
+            .ensure_container_exists(&base)
+            .await?;
+        let base_worktree_path = PathBuf::from(&base_container_ref).join(&repo.name);
+        if let Err(e) = deployment
+            .git()
+            .push_to_github(&base_worktree_path, &base.branch, false)
+        {
+            tracing::error!(
+                "Failed to push base branch {} to GitHub: {}",
+                base.branch,
+                e
+            );
+            return Err(ApiError::GitService(e));
+        }
+    }
 
     match deployment
         .git()
@@ -278,10 +307,15 @@ pub async fn create_github_pr(
     } else {
         target_branch
     };
+    let body = if request.include_transcript {
+        body_with_transcript(&deployment, &workspace, request.body.clone()).await
+    } else {
+        request.body.clone()
+    };
     // Create the PR using GitHub service
     let pr_request = CreatePrRequest {
         title: request.title.clone(),
-        body: request.body.clone(),
+        body,
         head_branch: workspace.branch.clone(),
         base_branch: norm_target_branch_name.clone(),
         draft: request.draft,
@@ -355,6 +389,27 @@ pub async fn create_github_pr(
     }
 }
 
+/// Append a condensed transcript of the attempt's agent conversations to a PR body
+async fn body_with_transcript(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    body: Option<String>,
+) -> Option<String> {
+    let transcript = match Transcript::for_workspace(deployment.container(), workspace).await {
+        Ok(transcript) if !transcript.turns.is_empty() => transcript,
+        Ok(_) => return body,
+        Err(e) => {
+            tracing::warn!("Failed to build transcript for PR body: {}", e);
+            return body;
+        }
+    };
+    let condensed = transcript.to_condensed_markdown();
+    match body.filter(|body| !body.trim().is_empty()) {
+        Some(body) => Some(format!("{body}\n\n{condensed}")),
+        None => Some(condensed),
+    }
+}
+
 pub async fn attach_existing_pr(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -422,6 +477,10 @@ pub async fn attach_existing_pr(
         // If PR is merged, mark task as done
         if matches!(pr_info.status, MergeStatus::Merged) {
             Task::update_status(pool, task.id, TaskStatus::Done).await?;
+            deployment
+                .container()
+                .notify_parent_workspace(&workspace)
+                .await;
 
             // Try broadcast update to other users in organization
             if let Ok(publisher) = deployment.share_publisher() {
@@ -17,6 +17,7 @@ use db::models::{
     project::{Project, ProjectError},
     repo::Repo,
     task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
+    task_plan::TaskPlan,
     workspace::{CreateWorkspace, Workspace},
     workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
@@ -140,11 +141,16 @@ pub async fn create_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS)]
 pub struct CreateAndStartTaskRequest {
     pub task: CreateTask,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    /// Start with a read-only planning turn whose plan must be approved
+    /// before the agent implements it
+    #[serde(default)]
+    #[ts(optional)]
+    pub plan_first: bool,
 }
 
 pub async fn create_task_and_start(
@@ -199,6 +205,7 @@ pub async fn create_task_and_start(
         &CreateWorkspace {
             branch: git_branch_name,
             agent_working_dir,
+            base_workspace_id: None,
         },
         attempt_id,
         task.id,
@@ -214,6 +221,9 @@ pub async fn create_task_and_start(
         })
         .collect();
     WorkspaceRepo::create_many(&deployment.db().pool, workspace.id, &workspace_repos).await?;
+    if payload.plan_first {
+        TaskPlan::create(pool, task.id, workspace.id).await?;
+    }
 
     let is_attempt_running = deployment
         .container()
@@ -229,6 +239,7 @@ pub async fn create_task_and_start(
                 "executor": &payload.executor_profile_id.executor,
                 "variant": &payload.executor_profile_id.variant,
                 "workspace_id": workspace.id.to_string(),
+                "plan_first": payload.plan_first,
             }),
         )
         .await;
@@ -0,0 +1,67 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::workspace_storage::{WorkspaceStorage, WorkspaceStorageEntry};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::workspace_storage;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkspaceStorageSort {
+    // Largest first, unmeasured workspaces last
+    #[default]
+    Size,
+    // Least recently active first
+    Age,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct WorkspaceStorageQuery {
+    #[serde(default)]
+    pub sort: WorkspaceStorageSort,
+}
+
+fn sort_entries(entries: &mut [WorkspaceStorageEntry], sort: WorkspaceStorageSort) {
+    match sort {
+        WorkspaceStorageSort::Size => {
+            entries.sort_by_key(|e| std::cmp::Reverse(e.total_bytes.unwrap_or(-1)))
+        }
+        WorkspaceStorageSort::Age => entries.sort_by_key(|e| e.last_activity_at),
+    }
+}
+
+pub async fn get_workspace_storage(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<WorkspaceStorageQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkspaceStorageEntry>>>, ApiError> {
+    let mut entries = WorkspaceStorage::find_all_entries(&deployment.db().pool).await?;
+    sort_entries(&mut entries, query.sort);
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
+/// Measure all workspaces now rather than waiting for the next cleanup run
+pub async fn refresh_workspace_storage(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<WorkspaceStorageQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkspaceStorageEntry>>>, ApiError> {
+    let mut entries = workspace_storage::refresh_disk_usage(&deployment.db().pool).await?;
+    sort_entries(&mut entries, query.sort);
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/workspaces/storage", get(get_workspace_storage))
+        .route(
+            "/workspaces/storage/refresh",
+            post(refresh_workspace_storage),
+        )
+}
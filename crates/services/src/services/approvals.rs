@@ -27,8 +27,8 @@ use uuid::Uuid;
 
 #[derive(Debug)]
 struct PendingApproval {
-    entry_index: usize,
-    entry: NormalizedEntry,
+    /// Tool use entry showing the approval in the conversation, with its index
+    entry: Option<(usize, NormalizedEntry)>,
     execution_process_id: Uuid,
     tool_name: String,
     response_tx: oneshot::Sender<ApprovalStatus>,
@@ -102,8 +102,7 @@ impl Approvals {
                 self.pending.insert(
                     req_id.clone(),
                     PendingApproval {
-                        entry_index: idx,
-                        entry: matching_tool,
+                        entry: Some((idx, matching_tool)),
                         execution_process_id: request.execution_process_id,
                         tool_name: request.tool_name.clone(),
                         response_tx: tx,
@@ -133,6 +132,31 @@ impl Approvals {
         Ok((request, waiter))
     }
 
+    /// Register an approval that is shown outside the agent's conversation,
+    /// such as the plan of a plan-first attempt
+    pub fn create_detached(&self, request: &ApprovalRequest) {
+        let (tx, rx) = oneshot::channel();
+        let waiter: ApprovalWaiter = rx
+            .map(|result| result.unwrap_or(ApprovalStatus::TimedOut))
+            .boxed()
+            .shared();
+
+        self.pending.insert(
+            request.id.clone(),
+            PendingApproval {
+                entry: None,
+                execution_process_id: request.execution_process_id,
+                tool_name: request.tool_name.clone(),
+                response_tx: tx,
+            },
+        );
+        self.spawn_timeout_watcher(request.id.clone(), request.timeout_at, waiter);
+    }
+
+    pub fn is_pending(&self, id: &str) -> bool {
+        self.pending.contains_key(id)
+    }
+
     #[tracing::instrument(skip(self, id, req))]
     pub async fn respond(
         &self,
@@ -144,17 +168,18 @@ impl Approvals {
             self.completed.insert(id.to_string(), req.status.clone());
             let _ = p.response_tx.send(req.status.clone());
 
-            if let Some(store) = self.msg_store_by_id(&p.execution_process_id).await {
+            if let Some((entry_index, entry)) = &p.entry
+                && let Some(store) = self.msg_store_by_id(&p.execution_process_id).await
+            {
                 let status = ToolStatus::from_approval_status(&req.status).ok_or(
                     ApprovalError::Custom(anyhow::anyhow!("Invalid approval status")),
                 )?;
-                let updated_entry = p
-                    .entry
+                let updated_entry = entry
                     .with_tool_status(status)
                     .ok_or(ApprovalError::NoToolUseEntry)?;
 
-                store.push_patch(ConversationPatch::replace(p.entry_index, updated_entry));
-            } else {
+                store.push_patch(ConversationPatch::replace(*entry_index, updated_entry));
+            } else if p.entry.is_some() {
                 tracing::warn!(
                     "No msg_store found for execution_process_id: {}",
                     p.execution_process_id
@@ -222,20 +247,18 @@ impl Approvals {
                     tracing::debug!("approval '{}' timeout notification receiver dropped", id);
                 }
 
+                let Some((entry_index, entry)) = pending_approval.entry else {
+                    return;
+                };
+
                 let store = {
                     let map = msg_stores.read().await;
                     map.get(&pending_approval.execution_process_id).cloned()
                 };
 
                 if let Some(store) = store {
-                    if let Some(updated_entry) = pending_approval
-                        .entry
-                        .with_tool_status(ToolStatus::TimedOut)
-                    {
-                        store.push_patch(ConversationPatch::replace(
-                            pending_approval.entry_index,
-                            updated_entry,
-                        ));
+                    if let Some(updated_entry) = entry.with_tool_status(ToolStatus::TimedOut) {
+                        store.push_patch(ConversationPatch::replace(entry_index, updated_entry));
                     } else {
                         tracing::warn!(
                             "Timed out approval '{}' but couldn't update tool status (no tool-use entry).",
@@ -26,6 +26,8 @@ pub type EditorType = versions::v8::EditorType;
 pub type GitHubConfig = versions::v8::GitHubConfig;
 pub type UiLanguage = versions::v8::UiLanguage;
 pub type ShowcaseState = versions::v8::ShowcaseState;
+pub type LogRedactionConfig = versions::v8::LogRedactionConfig;
+pub type WorkspaceCleanupConfig = versions::v8::WorkspaceCleanupConfig;
 
 /// Will always return config, trying old schemas or eventually returning default
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
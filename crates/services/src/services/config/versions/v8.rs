@@ -21,6 +21,64 @@ fn default_task_form_auto_start_by_default() -> bool {
     false
 }
 
+fn default_true() -> bool {
+    true
+}
+
+/// Redaction applied to process output before it is stored or streamed
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct LogRedactionConfig {
+    /// Redact AWS keys, GitHub tokens, JWTs and private keys
+    #[serde(default = "default_true")]
+    pub builtin_detectors: bool,
+    /// Redact long mixed-case tokens with high entropy; may catch non-secrets
+    #[serde(default)]
+    pub high_entropy: bool,
+    /// Additional regexes; when a pattern has a capture group only the group is redacted
+    #[serde(default)]
+    pub custom_patterns: Vec<String>,
+}
+
+impl Default for LogRedactionConfig {
+    fn default() -> Self {
+        Self {
+            builtin_detectors: true,
+            high_entropy: false,
+            custom_patterns: Vec::new(),
+        }
+    }
+}
+
+/// When workspace worktrees are removed from disk. Pinned workspaces and
+/// workspaces with running processes are always kept. Missing fields take
+/// their values from [`WorkspaceCleanupConfig::default`].
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct WorkspaceCleanupConfig {
+    /// Remove worktrees after this many hours without activity; `None` disables
+    pub inactive_hours: Option<u32>,
+    /// Remove worktrees as soon as their task is marked done
+    pub cleanup_on_done: bool,
+    /// Keep only the N most recently active workspaces of each task
+    pub keep_per_task: Option<u32>,
+    /// Evict least recently used workspaces while total usage exceeds this many MB
+    pub disk_quota_mb: Option<u32>,
+    /// Minutes between cleanup runs
+    pub interval_minutes: u32,
+}
+
+impl Default for WorkspaceCleanupConfig {
+    fn default() -> Self {
+        Self {
+            inactive_hours: Some(72),
+            cleanup_on_done: false,
+            keep_per_task: None,
+            disk_quota_mb: None,
+            interval_minutes: 30,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TS)]
 pub struct Config {
     pub config_version: String,
@@ -47,6 +105,10 @@ pub struct Config {
     pub pr_auto_description_prompt: Option<String>,
     #[serde(default = "default_task_form_auto_start_by_default")]
     pub task_form_auto_start_by_default: bool,
+    #[serde(default)]
+    pub log_redaction: LogRedactionConfig,
+    #[serde(default)]
+    pub workspace_cleanup: WorkspaceCleanupConfig,
 }
 
 impl Config {
@@ -73,6 +135,8 @@ impl Config {
             pr_auto_description_enabled: true,
             pr_auto_description_prompt: None,
             task_form_auto_start_by_default: false,
+            log_redaction: LogRedactionConfig::default(),
+            workspace_cleanup: WorkspaceCleanupConfig::default(),
         }
     }
 
@@ -124,6 +188,27 @@ impl Default for Config {
             pr_auto_description_enabled: true,
             pr_auto_description_prompt: None,
             task_form_auto_start_by_default: false,
+            log_redaction: LogRedactionConfig::default(),
+            workspace_cleanup: WorkspaceCleanupConfig::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_workspace_cleanup_config_keeps_defaults() {
+        let config: WorkspaceCleanupConfig =
+            serde_json::from_str(r#"{ "cleanup_on_done": true }"#).unwrap();
+        assert!(config.cleanup_on_done);
+        assert_eq!(config.inactive_hours, Some(72));
+        assert_eq!(config.interval_minutes, 30);
+
+        // An explicit null still disables the inactivity limit
+        let config: WorkspaceCleanupConfig =
+            serde_json::from_str(r#"{ "inactive_hours": null }"#).unwrap();
+        assert_eq!(config.inactive_hours, None);
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -23,6 +23,7 @@ use db::{
         repo::Repo,
         session::{CreateSession, Session, SessionError},
         task::{Task, TaskStatus},
+        task_plan::{TaskPlan, TaskPlanStatus},
         workspace::{Workspace, WorkspaceError},
         workspace_repo::WorkspaceRepo,
     },
@@ -52,6 +53,7 @@ use crate::services::{
     git::{GitService, GitServiceError},
     notification::NotificationService,
     share::SharePublisher,
+    task_plan,
     workspace_manager::WorkspaceError as WorkspaceManagerError,
     worktree_manager::WorktreeError,
 };
@@ -249,6 +251,7 @@ pub trait ContainerService {
                         ctx.task.id
                     );
                 }
+                self.notify_parent_workspace(&ctx.workspace).await;
             }
             Err(e) => {
                 tracing::error!("Failed to update task status to {:?}: {}", status_for_log, e);
@@ -488,6 +491,8 @@ pub trait ContainerService {
                                 } else {
                                     project.default_agent_working_dir.clone()
                                 },
+                                sandbox_config: None,
+                                agent_instructions: None,
                             },
                         )
                         .await?;
@@ -647,6 +652,10 @@ pub trait ContainerService {
 
     async fn is_container_clean(&self, workspace: &Workspace) -> Result<bool, ContainerError>;
 
+    /// Tell the workspace a sub-task was created from that the sub-task (whose
+    /// attempt is `workspace`) reached review or done. Best-effort.
+    async fn notify_parent_workspace(&self, workspace: &Workspace);
+
     async fn start_execution_inner(
         &self,
         workspace: &Workspace,
@@ -660,6 +669,10 @@ pub trait ContainerService {
         status: ExecutionProcessStatus,
     ) -> Result<(), ContainerError>;
 
+    /// Deliver a user message to a running execution process. Returns false if
+    /// it isn't running or its executor doesn't accept messages mid-run.
+    async fn steer_execution(&self, execution_process: &ExecutionProcess, message: String) -> bool;
+
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError>;
 
     async fn copy_project_files(
@@ -858,8 +871,10 @@ pub trait ContainerService {
                 ExecutorActionType::CodingAgentFollowUpRequest(request) => {
                     let executor = ExecutorConfigs::get_cached()
                         .get_coding_agent_or_default(&request.executor_profile_id);
-                    executor
-                        .normalize_logs(temp_store.clone(), &request.effective_dir(&current_dir));
+                    executor.normalize_follow_up_logs(
+                        temp_store.clone(),
+                        &request.effective_dir(&current_dir),
+                    );
                 }
                 _ => {
                     tracing::debug!(
@@ -993,7 +1008,15 @@ pub trait ContainerService {
         )
         .await?;
 
-        let prompt = task.to_prompt();
+        let mut prompt = task.to_prompt();
+
+        // Plan-first attempts start with a planning turn that leaves files untouched
+        if TaskPlan::find_by_workspace_id(&self.db().pool, workspace.id)
+            .await?
+            .is_some_and(|plan| plan.status == TaskPlanStatus::Planning)
+        {
+            prompt = task_plan::planning_prompt(&prompt);
+        }
 
         let repos_with_setup: Vec<_> = project_repos
             .iter()
@@ -1015,6 +1038,7 @@ pub trait ContainerService {
                 prompt,
                 executor_profile_id: executor_profile_id.clone(),
                 working_dir,
+                instructions: None,
             }),
             cleanup_action.map(Box::new),
         );
@@ -1057,6 +1081,39 @@ pub trait ContainerService {
         Ok(execution_process)
     }
 
+    /// Instructions for coding agents in the workspace: the project's, then
+    /// those of each repository in the workspace
+    async fn agent_instructions(
+        &self,
+        task: &Task,
+        workspace: &Workspace,
+    ) -> Result<Option<String>, ContainerError> {
+        let pool = &self.db().pool;
+        let project = task
+            .parent_project(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let workspace_repo_ids = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id)
+            .await?
+            .into_iter()
+            .map(|repo| repo.id)
+            .collect::<HashSet<_>>();
+
+        let mut sections = project.agent_instructions.into_iter().collect::<Vec<_>>();
+        for repo in ProjectRepo::find_by_project_id_with_names(pool, project.id).await? {
+            if let Some(instructions) = repo.agent_instructions
+                && workspace_repo_ids.contains(&repo.repo_id)
+            {
+                sections.push(format!(
+                    "In the `{}` repository:\n{instructions}",
+                    repo.repo_name
+                ));
+            }
+        }
+
+        Ok((!sections.is_empty()).then(|| sections.join("\n\n")))
+    }
+
     async fn start_execution(
         &self,
         workspace: &Workspace,
@@ -1069,6 +1126,14 @@ pub trait ContainerService {
             .parent_task(&self.db().pool)
             .await?
             .ok_or(SqlxError::RowNotFound)?;
+        // Project instructions are recorded on the action, so the conversation
+        // can show what the agent was told
+        let executor_action = &match executor_action.executor_profile_id() {
+            Some(_) => executor_action
+                .clone()
+                .with_instructions(self.agent_instructions(&task, workspace).await?),
+            None => executor_action.clone(),
+        };
         if task.status != TaskStatus::InProgress
             && run_reason != &ExecutionProcessRunReason::DevServer
         {
@@ -1195,15 +1260,23 @@ pub trait ContainerService {
             }
 
             // Emit NextAction with failure context for coding agent requests
-            if let ContainerError::ExecutorError(ExecutorError::ExecutableNotFound { program }) =
-                &start_error
-            {
-                let help_text = format!("The required executable `{program}` is not installed.");
+            let failure_entry = match &start_error {
+                ContainerError::ExecutorError(ExecutorError::ExecutableNotFound { program }) => {
+                    Some((
+                        NormalizedEntryError::SetupRequired,
+                        format!("The required executable `{program}` is not installed."),
+                    ))
+                }
+                ContainerError::ExecutorError(ExecutorError::SandboxUnavailable(reason)) => Some((
+                    NormalizedEntryError::SandboxViolation,
+                    format!("Sandboxing is enabled but unavailable: {reason}"),
+                )),
+                _ => None,
+            };
+            if let Some((error_type, help_text)) = failure_entry {
                 let error_message = NormalizedEntry {
                     timestamp: None,
-                    entry_type: NormalizedEntryType::ErrorMessage {
-                        error_type: NormalizedEntryError::SetupRequired,
-                    },
+                    entry_type: NormalizedEntryType::ErrorMessage { error_type },
                     content: help_text,
                     metadata: None,
                 };
@@ -1223,22 +1296,29 @@ pub trait ContainerService {
         // Start processing normalised logs for executor requests and follow ups
         let workspace_root = self.workspace_to_current_dir(workspace);
         if let Some(msg_store) = self.get_msg_store_by_id(&execution_process.id).await
-            && let Some((executor_profile_id, working_dir)) = match executor_action.typ() {
-                ExecutorActionType::CodingAgentInitialRequest(request) => Some((
-                    &request.executor_profile_id,
-                    request.effective_dir(&workspace_root),
-                )),
-                ExecutorActionType::CodingAgentFollowUpRequest(request) => Some((
-                    &request.executor_profile_id,
-                    request.effective_dir(&workspace_root),
-                )),
-                _ => None,
-            }
+            && let Some((executor_profile_id, working_dir, is_follow_up)) =
+                match executor_action.typ() {
+                    ExecutorActionType::CodingAgentInitialRequest(request) => Some((
+                        &request.executor_profile_id,
+                        request.effective_dir(&workspace_root),
+                        false,
+                    )),
+                    ExecutorActionType::CodingAgentFollowUpRequest(request) => Some((
+                        &request.executor_profile_id,
+                        request.effective_dir(&workspace_root),
+                        true,
+                    )),
+                    _ => None,
+                }
         {
             if let Some(executor) =
                 ExecutorConfigs::get_cached().get_coding_agent(executor_profile_id)
             {
-                executor.normalize_logs(msg_store, &working_dir);
+                if is_follow_up {
+                    executor.normalize_follow_up_logs(msg_store, &working_dir);
+                } else {
+                    executor.normalize_logs(msg_store, &working_dir);
+                }
             } else {
                 tracing::error!(
                     "Failed to resolve profile '{:?}' for normalization",
@@ -1054,6 +1054,32 @@ impl GitService {
         Ok(oid)
     }
 
+    /// Create a local branch pointing at the given commit
+    pub fn create_branch_at_commit(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        commit_sha: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let oid = git2::Oid::from_str(commit_sha)
+            .map_err(|_| GitServiceError::InvalidRepository("Invalid commit SHA".into()))?;
+        let commit = repo.find_commit(oid)?;
+        repo.branch(branch_name, &commit, false)?;
+        Ok(())
+    }
+
+    /// Delete a local branch
+    pub fn delete_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        repo.find_branch(branch_name, BranchType::Local)?.delete()?;
+        Ok(())
+    }
+
     /// Get the subject/summary line for a given commit OID
     pub fn get_commit_subject(
         &self,
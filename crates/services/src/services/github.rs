@@ -249,6 +249,28 @@ impl GitHubService {
         .await
     }
 
+    /// Retarget an open pull request to a different base branch
+    pub async fn update_pr_base(
+        &self,
+        pr_url: &str,
+        base_branch: &str,
+    ) -> Result<(), GitHubServiceError> {
+        let cli = self.gh_cli.clone();
+        let url = pr_url.to_string();
+        let base = base_branch.to_string();
+        task::spawn_blocking(move || cli.edit_pr_base(&url, &base))
+            .await
+            .map_err(|err| {
+                GitHubServiceError::PullRequest(format!(
+                    "Failed to execute GitHub CLI for editing PR at {pr_url}: {err}"
+                ))
+            })?
+            .map_err(GitHubServiceError::from)?;
+
+        info!("Retargeted GitHub PR {} to base {}", pr_url, base_branch);
+        Ok(())
+    }
+
     /// List all pull requests for a branch (including closed/merged)
     pub async fn list_all_prs_for_branch(
         &self,
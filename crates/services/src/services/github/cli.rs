@@ -218,6 +218,12 @@ impl GhCli {
         Self::parse_pr_view(&raw)
     }
 
+    /// Change the base branch of an existing pull request.
+    pub fn edit_pr_base(&self, pr_url: &str, base_branch: &str) -> Result<(), GhCliError> {
+        self.run(["pr", "edit", pr_url, "--base", base_branch], None)?;
+        Ok(())
+    }
+
     /// List pull requests for a branch (includes closed/merged).
     pub fn list_prs_for_branch(
         &self,
@@ -18,7 +18,13 @@ pub mod pr_monitor;
 pub mod project;
 pub mod queued_message;
 pub mod remote_client;
+pub mod replay_recorder;
 pub mod repo;
+pub mod secrets;
 pub mod share;
+pub mod stacked_workspace;
+pub mod task_plan;
+pub mod transcript;
 pub mod workspace_manager;
+pub mod workspace_storage;
 pub mod worktree_manager;
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use db::{
     DBService,
@@ -8,6 +8,7 @@ use db::{
         workspace::{Workspace, WorkspaceError},
     },
 };
+use futures::future::BoxFuture;
 use serde_json::json;
 use sqlx::error::Error as SqlxError;
 use thiserror::Error;
@@ -16,8 +17,11 @@ use tracing::{debug, error, info};
 
 use crate::services::{
     analytics::AnalyticsContext,
+    git::GitService,
     github::{GitHubService, GitHubServiceError},
+    notification::NotificationService,
     share::SharePublisher,
+    stacked_workspace::StackedWorkspaceService,
 };
 
 #[derive(Debug, Error)]
@@ -30,12 +34,17 @@ enum PrMonitorError {
     Sqlx(#[from] SqlxError),
 }
 
+/// Called with the workspace whose PR was merged, after its task is marked done
+pub type PrMergedHook = Arc<dyn Fn(Workspace) -> BoxFuture<'static, ()> + Send + Sync>;
+
 /// Service to monitor GitHub PRs and update task status when they are merged
 pub struct PrMonitorService {
     db: DBService,
     poll_interval: Duration,
     analytics: Option<AnalyticsContext>,
     publisher: Option<SharePublisher>,
+    notification_service: NotificationService,
+    on_merged: Option<PrMergedHook>,
 }
 
 impl PrMonitorService {
@@ -43,12 +52,16 @@ impl PrMonitorService {
         db: DBService,
         analytics: Option<AnalyticsContext>,
         publisher: Option<SharePublisher>,
+        notification_service: NotificationService,
+        on_merged: Option<PrMergedHook>,
     ) -> tokio::task::JoinHandle<()> {
         let service = Self {
             db,
             poll_interval: Duration::from_secs(60), // Check every minute
             analytics,
             publisher,
+            notification_service,
+            on_merged,
         };
         tokio::spawn(async move {
             service.start().await;
@@ -129,6 +142,23 @@ impl PrMonitorService {
                 );
                 Task::update_status(&self.db.pool, workspace.task_id, TaskStatus::Done).await?;
 
+                // Retarget workspaces stacked on the merged branch
+                let stacked = StackedWorkspaceService::new(self.db.clone(), GitService::new());
+                match stacked
+                    .handle_base_merged(&workspace, pr_merge.repo_id)
+                    .await
+                {
+                    Ok(conflicts) => {
+                        for conflict in conflicts {
+                            conflict.notify(&self.notification_service).await;
+                        }
+                    }
+                    Err(e) => error!(
+                        "Failed to retarget stacked workspaces for {}: {}",
+                        workspace.id, e
+                    ),
+                }
+
                 // Track analytics event
                 if let Some(analytics) = &self.analytics
                     && let Ok(Some(task)) = Task::find_by_id(&self.db.pool, workspace.task_id).await
@@ -153,6 +183,10 @@ impl PrMonitorService {
                         workspace.task_id
                     );
                 }
+
+                if let Some(on_merged) = &self.on_merged {
+                    on_merged(workspace).await;
+                }
             }
         }
 
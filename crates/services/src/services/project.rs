@@ -132,6 +132,8 @@ impl ProjectService {
                     dev_script: None,
                     dev_script_working_dir: None,
                     default_agent_working_dir: Some(repo.name),
+                    sandbox_config: None,
+                    agent_instructions: None,
                 },
             )
             .await?;
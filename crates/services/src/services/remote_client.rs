@@ -4,9 +4,18 @@ use std::time::Duration;
 
 use backon::{ExponentialBuilder, Retryable};
 use chrono::Duration as ChronoDuration;
-use remote::routes::tasks::{
-    AssignSharedTaskRequest, CheckTasksRequest, CreateSharedTaskRequest, SharedTaskResponse,
-    UpdateSharedTaskRequest,
+use remote::{
+    db::task_comments::SharedTaskComment,
+    routes::{
+        task_comments::{
+            CreateSharedTaskCommentRequest, ListSharedTaskActivityResponse,
+            ListSharedTaskCommentsResponse, UpdateSharedTaskCommentRequest,
+        },
+        tasks::{
+            AssignSharedTaskRequest, CheckTasksRequest, CreateSharedTaskRequest,
+            SharedTaskResponse, UpdateSharedTaskRequest,
+        },
+    },
 };
 use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
@@ -24,8 +33,9 @@ use utils::{
             AcceptInvitationResponse, CreateInvitationRequest, CreateInvitationResponse,
             CreateOrganizationRequest, CreateOrganizationResponse, GetInvitationResponse,
             GetOrganizationResponse, ListInvitationsResponse, ListMembersResponse,
-            ListOrganizationsResponse, Organization, RevokeInvitationRequest,
-            UpdateMemberRoleRequest, UpdateMemberRoleResponse, UpdateOrganizationRequest,
+            ListOrganizationsResponse, MemberProjectAccess, Organization, RevokeInvitationRequest,
+            UpdateMemberProjectAccessRequest, UpdateMemberRoleRequest, UpdateMemberRoleResponse,
+            UpdateOrganizationRequest,
         },
         projects::{ListProjectsResponse, RemoteProject},
     },
@@ -545,6 +555,32 @@ impl RemoteClient {
         .await
     }
 
+    /// Gets the projects a member is restricted to.
+    pub async fn get_member_project_access(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<MemberProjectAccess, RemoteClientError> {
+        self.get_authed(&format!(
+            "/v1/organizations/{org_id}/members/{user_id}/projects"
+        ))
+        .await
+    }
+
+    /// Restricts a member to specific projects, or lifts the restriction.
+    pub async fn update_member_project_access(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        request: &UpdateMemberProjectAccessRequest,
+    ) -> Result<MemberProjectAccess, RemoteClientError> {
+        self.patch_authed(
+            &format!("/v1/organizations/{org_id}/members/{user_id}/projects"),
+            request,
+        )
+        .await
+    }
+
     /// Creates a shared task.
     pub async fn create_shared_task(
         &self,
@@ -596,6 +632,67 @@ impl RemoteClient {
         let request = CheckTasksRequest { task_ids };
         self.post_authed("/v1/tasks/check", Some(&request)).await
     }
+
+    /// Lists the comments on a shared task.
+    pub async fn list_shared_task_comments(
+        &self,
+        task_id: Uuid,
+    ) -> Result<ListSharedTaskCommentsResponse, RemoteClientError> {
+        self.get_authed(&format!("/v1/tasks/{task_id}/comments"))
+            .await
+    }
+
+    /// Posts a comment on a shared task.
+    pub async fn create_shared_task_comment(
+        &self,
+        task_id: Uuid,
+        request: &CreateSharedTaskCommentRequest,
+    ) -> Result<SharedTaskComment, RemoteClientError> {
+        self.post_authed(&format!("/v1/tasks/{task_id}/comments"), Some(request))
+            .await
+    }
+
+    /// Edits a comment authored by the current user.
+    pub async fn update_shared_task_comment(
+        &self,
+        task_id: Uuid,
+        comment_id: Uuid,
+        request: &UpdateSharedTaskCommentRequest,
+    ) -> Result<SharedTaskComment, RemoteClientError> {
+        self.patch_authed(
+            &format!("/v1/tasks/{task_id}/comments/{comment_id}"),
+            request,
+        )
+        .await
+    }
+
+    /// Deletes a comment authored by the current user.
+    pub async fn delete_shared_task_comment(
+        &self,
+        task_id: Uuid,
+        comment_id: Uuid,
+    ) -> Result<SharedTaskComment, RemoteClientError> {
+        let res = self
+            .send(
+                reqwest::Method::DELETE,
+                &format!("/v1/tasks/{task_id}/comments/{comment_id}"),
+                true,
+                None::<&()>,
+            )
+            .await?;
+        res.json::<SharedTaskComment>()
+            .await
+            .map_err(|e| RemoteClientError::Serde(e.to_string()))
+    }
+
+    /// Lists status and assignee changes recorded for a shared task.
+    pub async fn list_shared_task_activity(
+        &self,
+        task_id: Uuid,
+    ) -> Result<ListSharedTaskActivityResponse, RemoteClientError> {
+        self.get_authed(&format!("/v1/tasks/{task_id}/activity"))
+            .await
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -0,0 +1,262 @@
+//! Captures replay fixtures from finished coding agent runs.
+//!
+//! The fixture holds the run's raw stdout/stderr, the agent configuration it
+//! ran with, the files it changed (from the before/after commits recorded for
+//! each repository, relative to the directory the agent ran in) and its exit
+//! code. Recorded edits are replayed after the output, as the order they were
+//! made in isn't kept.
+
+use std::path::Path;
+
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
+    execution_process_logs::ExecutionProcessLogs,
+    execution_process_repo_state::ExecutionProcessRepoState,
+    repo::Repo,
+};
+use executors::{
+    actions::ExecutorActionType,
+    executors::{
+        CodingAgent,
+        replay::{ReplayFixture, ReplayStep},
+    },
+    profile::ExecutorConfigs,
+};
+use git2::{Delta, Repository};
+use sqlx::{Error as SqlxError, SqlitePool};
+use thiserror::Error;
+use utils::log_msg::LogMsg;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ReplayRecordError {
+    #[error(transparent)]
+    Sqlx(#[from] SqlxError),
+    #[error(transparent)]
+    Git(#[from] git2::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("Execution process not found")]
+    ProcessNotFound,
+    #[error("Only coding agent runs can be recorded")]
+    NotCodingAgent,
+    #[error("Execution process is still running")]
+    StillRunning,
+    #[error("Replay runs can't be recorded again")]
+    AlreadyReplay,
+}
+
+/// Build a replay fixture for a finished coding agent execution process
+pub async fn record_fixture(
+    pool: &SqlitePool,
+    execution_process_id: Uuid,
+) -> Result<ReplayFixture, ReplayRecordError> {
+    let process = ExecutionProcess::find_by_id(pool, execution_process_id)
+        .await?
+        .ok_or(ReplayRecordError::ProcessNotFound)?;
+    if process.status == ExecutionProcessStatus::Running {
+        return Err(ReplayRecordError::StillRunning);
+    }
+
+    let action = process
+        .executor_action()
+        .map_err(|_| ReplayRecordError::NotCodingAgent)?;
+    let (profile, working_dir) = match action.typ() {
+        ExecutorActionType::CodingAgentInitialRequest(request) => {
+            (&request.executor_profile_id, request.working_dir.as_deref())
+        }
+        ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+            (&request.executor_profile_id, request.working_dir.as_deref())
+        }
+        ExecutorActionType::ScriptRequest(_) => return Err(ReplayRecordError::NotCodingAgent),
+    };
+    let agent = ExecutorConfigs::get_cached().get_coding_agent_or_default(profile);
+    if matches!(agent, CodingAgent::Replay(_)) {
+        return Err(ReplayRecordError::AlreadyReplay);
+    }
+
+    let records = ExecutionProcessLogs::find_by_execution_id(pool, execution_process_id).await?;
+    let mut steps = output_steps(ExecutionProcessLogs::parse_logs(&records)?);
+
+    for state in
+        ExecutionProcessRepoState::find_by_execution_process_id(pool, execution_process_id).await?
+    {
+        let (Some(before), Some(after)) = (&state.before_head_commit, &state.after_head_commit)
+        else {
+            continue;
+        };
+        if before == after {
+            continue;
+        }
+        let Some(repo) = Repo::find_by_id(pool, state.repo_id).await? else {
+            continue;
+        };
+        steps.extend(edit_steps(
+            &repo.path,
+            &repo.name,
+            working_dir,
+            before,
+            after,
+        )?);
+    }
+
+    Ok(ReplayFixture {
+        agent,
+        steps,
+        exit_code: process.exit_code.unwrap_or(0) as i32,
+    })
+}
+
+/// One step per line, keeping the interleaving of stdout and stderr
+fn output_steps(messages: Vec<LogMsg>) -> Vec<ReplayStep> {
+    let mut steps = Vec::new();
+    for message in messages {
+        let (text, is_stdout) = match message {
+            LogMsg::Stdout(text) => (text, true),
+            LogMsg::Stderr(text) => (text, false),
+            _ => continue,
+        };
+
+        for line in text.split_inclusive('\n') {
+            // Chunks can split lines; join a partial line with its continuation
+            match steps.last_mut() {
+                Some(ReplayStep::Stdout { text }) if is_stdout && !text.ends_with('\n') => {
+                    text.push_str(line)
+                }
+                Some(ReplayStep::Stderr { text }) if !is_stdout && !text.ends_with('\n') => {
+                    text.push_str(line)
+                }
+                _ if is_stdout => steps.push(ReplayStep::Stdout {
+                    text: line.to_string(),
+                }),
+                _ => steps.push(ReplayStep::Stderr {
+                    text: line.to_string(),
+                }),
+            }
+        }
+    }
+    steps
+}
+
+/// File writes and deletions taking `before` to `after`. Paths are relative
+/// to the directory the agent ran in, as replays run there too. Binary files
+/// and files outside that directory are skipped.
+fn edit_steps(
+    repo_path: &Path,
+    repo_name: &str,
+    working_dir: Option<&str>,
+    before: &str,
+    after: &str,
+) -> Result<Vec<ReplayStep>, ReplayRecordError> {
+    let repo = Repository::open(repo_path)?;
+    let before_tree = repo.revparse_single(before)?.peel_to_tree()?;
+    let after_tree = repo.revparse_single(after)?.peel_to_tree()?;
+    let diff = repo.diff_tree_to_tree(Some(&before_tree), Some(&after_tree), None)?;
+
+    let replay_path = |path: &Path| {
+        let replay_path = replay_edit_path(repo_name, working_dir, path);
+        if replay_path.is_none() {
+            tracing::debug!(
+                "Skipping {} outside the agent's working directory in replay fixture",
+                path.display()
+            );
+        }
+        replay_path
+    };
+
+    let mut steps = Vec::new();
+    for delta in diff.deltas() {
+        if matches!(delta.status(), Delta::Deleted | Delta::Renamed)
+            && let Some(path) = delta.old_file().path().and_then(replay_path)
+        {
+            steps.push(ReplayStep::DeleteFile { path });
+        }
+
+        if delta.status() == Delta::Deleted {
+            continue;
+        }
+        let Some(path) = delta.new_file().path().and_then(replay_path) else {
+            continue;
+        };
+        let blob = repo.find_blob(delta.new_file().id())?;
+        let Ok(content) = std::str::from_utf8(blob.content()) else {
+            tracing::debug!("Skipping binary file {} in replay fixture", path);
+            continue;
+        };
+        steps.push(ReplayStep::WriteFile {
+            path,
+            content: content.to_string(),
+        });
+    }
+
+    Ok(steps)
+}
+
+/// Path of a file in a repository, relative to the agent's working directory
+/// within the workspace (the workspace root when unset). `None` when the file
+/// is outside the working directory.
+fn replay_edit_path(repo_name: &str, working_dir: Option<&str>, path: &Path) -> Option<String> {
+    let workspace_path = Path::new(repo_name).join(path);
+    let relative = match working_dir {
+        Some(working_dir) => workspace_path.strip_prefix(working_dir).ok()?,
+        None => workspace_path.as_path(),
+    };
+    if relative.as_os_str().is_empty() {
+        return None;
+    }
+    Some(relative.to_string_lossy().replace('\\', "/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_edit_path() {
+        let path = Path::new("src/lib.rs");
+        // Single-repo projects run the agent in the repository
+        assert_eq!(
+            replay_edit_path("repo", Some("repo"), path).as_deref(),
+            Some("src/lib.rs")
+        );
+        // Multi-repo workspaces run it in the workspace root
+        assert_eq!(
+            replay_edit_path("repo", None, path).as_deref(),
+            Some("repo/src/lib.rs")
+        );
+        assert_eq!(
+            replay_edit_path("repo", Some("repo/src"), path).as_deref(),
+            Some("lib.rs")
+        );
+        assert_eq!(replay_edit_path("other", Some("repo"), path), None);
+    }
+
+    #[test]
+    fn test_output_steps() {
+        let steps = output_steps(vec![
+            LogMsg::Stdout("{\"type\":".to_string()),
+            LogMsg::Stdout("\"system\"}\n".to_string()),
+            LogMsg::Stderr("warning\n".to_string()),
+            LogMsg::Stdout("first\nsecond\n".to_string()),
+            LogMsg::Finished,
+        ]);
+
+        assert_eq!(
+            steps,
+            vec![
+                ReplayStep::Stdout {
+                    text: "{\"type\":\"system\"}\n".to_string()
+                },
+                ReplayStep::Stderr {
+                    text: "warning\n".to_string()
+                },
+                ReplayStep::Stdout {
+                    text: "first\n".to_string()
+                },
+                ReplayStep::Stdout {
+                    text: "second\n".to_string()
+                },
+            ]
+        );
+    }
+}
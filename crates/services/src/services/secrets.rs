@@ -0,0 +1,268 @@
+//! Per-project secret store.
+//!
+//! Secret values are encrypted with AES-256-GCM before they reach SQLite. The
+//! key is derived with Argon2id, salted with a random value stored next to the
+//! database, from a passphrase that comes from, in order of preference:
+//! 1. a master passphrase file named by `VK_SECRETS_PASSPHRASE_FILE`,
+//! 2. the OS keyring (macOS Keychain, Windows Credential Manager or the
+//!    Secret Service on Linux),
+//! 3. a generated key file in the asset directory (mode 0600).
+//!
+//! The key file is stored in plaintext, so the secrets are only as safe as its
+//! permissions; a warning is logged whenever it is used.
+
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use aes_gcm::{
+    Aes256Gcm, Key, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+};
+use argon2::Argon2;
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use db::models::project_secret::ProjectSecret;
+use sqlx::SqlitePool;
+use thiserror::Error;
+use utils::{assets::asset_dir, redact::Redactor};
+use uuid::Uuid;
+
+const NONCE_SIZE: usize = 12;
+
+#[derive(Debug, Error)]
+pub enum SecretsError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Invalid secret name: {0}")]
+    InvalidName(String),
+    #[error("Secret not found: {0}")]
+    NotFound(String),
+    #[error("Failed to load secrets key: {0}")]
+    Key(String),
+    #[error("Failed to encrypt or decrypt secret")]
+    Crypto,
+}
+
+/// Secrets resolved for an execution: env vars to inject and a redactor for their values
+pub struct ResolvedSecrets {
+    pub env: HashMap<String, String>,
+    pub redactor: Redactor,
+}
+
+#[derive(Clone)]
+pub struct SecretsService {
+    key: Arc<[u8; 32]>,
+}
+
+impl SecretsService {
+    pub fn new() -> Result<Self, SecretsError> {
+        Ok(Self {
+            key: Arc::new(load_key()?),
+        })
+    }
+
+    pub async fn list(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<ProjectSecret>, SecretsError> {
+        Ok(ProjectSecret::find_by_project_id(pool, project_id).await?)
+    }
+
+    pub async fn set(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+        value: &str,
+    ) -> Result<ProjectSecret, SecretsError> {
+        validate_name(name)?;
+        let encrypted = self.encrypt(value)?;
+        Ok(ProjectSecret::upsert(pool, project_id, name, &encrypted).await?)
+    }
+
+    pub async fn delete(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+    ) -> Result<(), SecretsError> {
+        if ProjectSecret::delete(pool, project_id, name).await? == 0 {
+            return Err(SecretsError::NotFound(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Decrypt all secrets of a project for injection into an execution environment.
+    /// Secrets that fail to decrypt (e.g. after a key change) are skipped.
+    pub async fn resolve(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<ResolvedSecrets, SecretsError> {
+        let mut env = HashMap::new();
+        let mut redactor = Redactor::new();
+        for secret in ProjectSecret::find_by_project_id(pool, project_id).await? {
+            match self.decrypt(&secret.encrypted_value) {
+                Ok(value) => {
+                    redactor.add_secret(&secret.name, &value);
+                    env.insert(secret.name, value);
+                }
+                Err(e) => tracing::warn!(
+                    "Skipping secret {} for project {}: {}",
+                    secret.name,
+                    project_id,
+                    e
+                ),
+            }
+        }
+        Ok(ResolvedSecrets { env, redactor })
+    }
+
+    fn encrypt(&self, value: &str) -> Result<String, SecretsError> {
+        let cipher = Aes256Gcm::new(&Key::<Aes256Gcm>::from(*self.key));
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, value.as_bytes())
+            .map_err(|_| SecretsError::Crypto)?;
+
+        let mut combined = nonce.to_vec();
+        combined.extend_from_slice(&ciphertext);
+        Ok(STANDARD.encode(combined))
+    }
+
+    fn decrypt(&self, encrypted: &str) -> Result<String, SecretsError> {
+        let decoded = STANDARD
+            .decode(encrypted)
+            .map_err(|_| SecretsError::Crypto)?;
+        if decoded.len() < NONCE_SIZE {
+            return Err(SecretsError::Crypto);
+        }
+        let (nonce_bytes, ciphertext) = decoded.split_at(NONCE_SIZE);
+        let nonce_bytes: [u8; NONCE_SIZE] =
+            nonce_bytes.try_into().map_err(|_| SecretsError::Crypto)?;
+
+        let cipher = Aes256Gcm::new(&Key::<Aes256Gcm>::from(*self.key));
+        let plaintext = cipher
+            .decrypt(&Nonce::from(nonce_bytes), ciphertext)
+            .map_err(|_| SecretsError::Crypto)?;
+        String::from_utf8(plaintext).map_err(|_| SecretsError::Crypto)
+    }
+}
+
+/// Secret names become environment variable names
+fn validate_name(name: &str) -> Result<(), SecretsError> {
+    let valid = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(SecretsError::InvalidName(name.to_string()))
+    }
+}
+
+fn load_key() -> Result<[u8; 32], SecretsError> {
+    let salt = load_or_create_random_file(&asset_dir().join("secrets.salt"))?;
+
+    if let Ok(path) = std::env::var("VK_SECRETS_PASSPHRASE_FILE") {
+        let passphrase = std::fs::read_to_string(&path)
+            .map_err(|e| SecretsError::Key(format!("cannot read {path}: {e}")))?;
+        return derive_key(passphrase.trim().as_bytes(), &salt);
+    }
+
+    let key_path = asset_dir().join("secrets.key");
+    match os_keyring::load_or_create() {
+        Ok(passphrase) => return derive_key(&passphrase, &salt),
+        Err(e) => tracing::warn!(
+            "OS keyring unavailable ({e}); the secrets key is stored in plaintext at {}. \
+             Set VK_SECRETS_PASSPHRASE_FILE to keep it on protected storage instead.",
+            key_path.display()
+        ),
+    }
+
+    let passphrase = load_or_create_random_file(&key_path)?;
+    derive_key(&passphrase, &salt)
+}
+
+fn derive_key(passphrase: &[u8], salt: &[u8]) -> Result<[u8; 32], SecretsError> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase, salt, &mut key)
+        .map_err(|e| SecretsError::Key(e.to_string()))?;
+    Ok(key)
+}
+
+fn random_passphrase() -> Vec<u8> {
+    STANDARD
+        .encode(Aes256Gcm::generate_key(&mut OsRng))
+        .into_bytes()
+}
+
+/// Reads a random value from `path`, generating it on first use
+fn load_or_create_random_file(path: &Path) -> Result<Vec<u8>, SecretsError> {
+    if path.exists() {
+        return Ok(std::fs::read_to_string(path)?.trim().as_bytes().to_vec());
+    }
+
+    let passphrase = random_passphrase();
+    let mut opts = std::fs::OpenOptions::new();
+    opts.create_new(true).write(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        opts.mode(0o600);
+    }
+    let mut file = opts.open(path)?;
+    std::io::Write::write_all(&mut file, &passphrase)?;
+    file.sync_all()?;
+    tracing::info!("Created {}", path.display());
+    Ok(passphrase)
+}
+
+mod os_keyring {
+    use keyring::Entry;
+
+    const SERVICE_NAME: &str = concat!(env!("CARGO_PKG_NAME"), ":secrets");
+    const ACCOUNT_NAME: &str = "default";
+
+    pub fn load_or_create() -> Result<Vec<u8>, keyring::Error> {
+        let entry = Entry::new(SERVICE_NAME, ACCOUNT_NAME)?;
+        match entry.get_secret() {
+            Ok(passphrase) => Ok(passphrase),
+            Err(keyring::Error::NoEntry) => {
+                let passphrase = super::random_passphrase();
+                entry.set_secret(&passphrase)?;
+                Ok(passphrase)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derived_key_depends_on_salt() {
+        let salt = random_passphrase();
+        let key = derive_key(b"passphrase", &salt).unwrap();
+        assert_eq!(key, derive_key(b"passphrase", &salt).unwrap());
+        assert_ne!(
+            key,
+            derive_key(b"passphrase", &random_passphrase()).unwrap()
+        );
+        assert_ne!(key, derive_key(b"other", &salt).unwrap());
+    }
+
+    #[test]
+    fn encrypted_values_round_trip() {
+        let service = SecretsService {
+            key: Arc::new(derive_key(b"passphrase", &random_passphrase()).unwrap()),
+        };
+        let encrypted = service.encrypt("hunter2").unwrap();
+        assert!(!encrypted.contains("hunter2"));
+        assert_eq!(service.decrypt(&encrypted).unwrap(), "hunter2");
+    }
+}
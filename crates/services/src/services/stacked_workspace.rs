@@ -0,0 +1,332 @@
+//! Keeps stacked workspaces in sync with the workspace they are based on.
+//!
+//! A stacked workspace targets its base workspace's branch for every repo the
+//! two share. When the base branch moves, children are rebased onto it. When
+//! the base is merged, children are retargeted to the base's own target branch
+//! and rebased with `--onto`, so squash merges don't replay the base commits.
+//!
+//! The base commit each child was last rebased onto is recorded, so rebases
+//! after the base branch was rewritten (amend, rebase, squash) only replay the
+//! child's own commits. A rebase that fails is aborted and the child is left as
+//! it was, for the user to rebase themselves.
+
+use std::path::{Path, PathBuf};
+
+use db::{
+    DBService,
+    models::{
+        execution_process::ExecutionProcess,
+        merge::{Merge, MergeStatus},
+        repo::Repo,
+        workspace::Workspace,
+        workspace_repo::WorkspaceRepo,
+    },
+};
+use sqlx::Error as SqlxError;
+use thiserror::Error;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::services::{
+    git::{GitService, GitServiceError},
+    github::{GitHubService, GitHubServiceError},
+    notification::NotificationService,
+};
+
+#[derive(Debug, Error)]
+pub enum StackedWorkspaceError {
+    #[error(transparent)]
+    Sqlx(#[from] SqlxError),
+    #[error(transparent)]
+    GitService(#[from] GitServiceError),
+    #[error(transparent)]
+    GitHubService(#[from] GitHubServiceError),
+}
+
+/// A stacked workspace that could not be rebased automatically. Its branch and
+/// target branch are left untouched.
+#[derive(Debug, Clone)]
+pub struct StackedRebaseConflict {
+    pub workspace_id: Uuid,
+    pub branch: String,
+    pub new_base: String,
+    pub reason: String,
+}
+
+impl StackedRebaseConflict {
+    /// Let the user know the workspace needs to be rebased by hand
+    pub async fn notify(&self, notification_service: &NotificationService) {
+        let title = format!("Rebase needed: {}", self.branch);
+        let message = format!(
+            "Could not rebase {} onto {}: {}",
+            self.branch, self.new_base, self.reason
+        );
+        notification_service.notify(&title, &message).await;
+    }
+}
+
+enum RebaseOutcome {
+    Rebased(PathBuf),
+    /// The workspace has no worktree for the repo, so there is nothing to rebase
+    NoWorktree,
+    /// An agent is running in the workspace
+    Busy,
+    Failed(String),
+}
+
+#[derive(Clone)]
+pub struct StackedWorkspaceService {
+    db: DBService,
+    git: GitService,
+}
+
+impl StackedWorkspaceService {
+    pub fn new(db: DBService, git: GitService) -> Self {
+        Self { db, git }
+    }
+
+    /// Rebase every child stacked on `base` onto the base's current branch tip.
+    /// Call after the base branch has moved (new commits or a rebase).
+    pub async fn sync_children(
+        &self,
+        base: &Workspace,
+    ) -> Result<Vec<StackedRebaseConflict>, StackedWorkspaceError> {
+        let pool = &self.db.pool;
+        let mut conflicts = Vec::new();
+        for child in Workspace::find_stacked_children(pool, base.id).await? {
+            for child_repo in WorkspaceRepo::find_by_workspace_id(pool, child.id).await? {
+                if child_repo.target_branch != base.branch {
+                    continue;
+                }
+                let Some(repo) = Repo::find_by_id(pool, child_repo.repo_id).await? else {
+                    continue;
+                };
+                let base_tip = match self.git.get_branch_oid(&repo.path, &base.branch) {
+                    Ok(oid) => oid,
+                    Err(e) => {
+                        warn!(
+                            "Failed to resolve base branch {} for stacked workspace {}: {}",
+                            base.branch, child.id, e
+                        );
+                        continue;
+                    }
+                };
+                let old_base =
+                    WorkspaceRepo::find_stacked_base_commit(pool, child.id, repo.id).await?;
+                if old_base.as_deref() == Some(base_tip.as_str()) {
+                    continue;
+                }
+                let old_base = old_base.unwrap_or_else(|| base.branch.clone());
+
+                match self
+                    .rebase_child(&child, &repo, &base.branch, &old_base)
+                    .await?
+                {
+                    RebaseOutcome::Rebased(_) => {
+                        WorkspaceRepo::set_stacked_base_commit(
+                            pool,
+                            child.id,
+                            repo.id,
+                            Some(&base_tip),
+                        )
+                        .await?;
+                    }
+                    // Rebased on a later sync, from the recorded base commit
+                    RebaseOutcome::NoWorktree | RebaseOutcome::Busy => {}
+                    RebaseOutcome::Failed(reason) => conflicts.push(StackedRebaseConflict {
+                        workspace_id: child.id,
+                        branch: child.branch.clone(),
+                        new_base: base.branch.clone(),
+                        reason,
+                    }),
+                }
+            }
+        }
+        Ok(conflicts)
+    }
+
+    /// Retarget children of `base` after the base was merged into its target
+    /// branch for `repo_id`, rebasing them and updating any open PRs. Children
+    /// that can't be rebased keep targeting the base branch.
+    pub async fn handle_base_merged(
+        &self,
+        base: &Workspace,
+        repo_id: Uuid,
+    ) -> Result<Vec<StackedRebaseConflict>, StackedWorkspaceError> {
+        let pool = &self.db.pool;
+        let Some(base_repo) =
+            WorkspaceRepo::find_by_workspace_and_repo_id(pool, base.id, repo_id).await?
+        else {
+            return Ok(Vec::new());
+        };
+        let Some(repo) = Repo::find_by_id(pool, repo_id).await? else {
+            return Ok(Vec::new());
+        };
+        let new_target = base_repo.target_branch;
+        let mut conflicts = Vec::new();
+
+        for child in Workspace::find_stacked_children(pool, base.id).await? {
+            let Some(child_repo) =
+                WorkspaceRepo::find_by_workspace_and_repo_id(pool, child.id, repo_id).await?
+            else {
+                continue;
+            };
+            if child_repo.target_branch != base.branch {
+                continue;
+            }
+
+            info!(
+                "Retargeting stacked workspace {} from {} to {}",
+                child.id, base.branch, new_target
+            );
+            let old_base = WorkspaceRepo::find_stacked_base_commit(pool, child.id, repo_id)
+                .await?
+                .unwrap_or_else(|| base.branch.clone());
+            let reason = match self
+                .rebase_child(&child, &repo, &new_target, &old_base)
+                .await?
+            {
+                RebaseOutcome::Rebased(worktree_path) => {
+                    self.retarget(&child, repo_id, &new_target).await?;
+                    if let Err(e) = self
+                        .retarget_open_prs(&child, repo_id, &worktree_path, &new_target)
+                        .await
+                    {
+                        warn!(
+                            "Failed to update PR base for stacked workspace {}: {}",
+                            child.id, e
+                        );
+                    }
+                    None
+                }
+                RebaseOutcome::NoWorktree => {
+                    self.retarget(&child, repo_id, &new_target).await?;
+                    None
+                }
+                RebaseOutcome::Busy => Some("an agent is running in the workspace".to_string()),
+                RebaseOutcome::Failed(reason) => Some(reason),
+            };
+            if let Some(reason) = reason {
+                conflicts.push(StackedRebaseConflict {
+                    workspace_id: child.id,
+                    branch: child.branch.clone(),
+                    new_base: new_target.clone(),
+                    reason,
+                });
+                continue;
+            }
+
+            // Once no repo targets the base branch anymore, the child is no longer stacked
+            let still_stacked = WorkspaceRepo::find_by_workspace_id(pool, child.id)
+                .await?
+                .iter()
+                .any(|wr| wr.target_branch == base.branch);
+            if !still_stacked {
+                Workspace::clear_base_workspace(pool, child.id).await?;
+            }
+        }
+        Ok(conflicts)
+    }
+
+    async fn retarget(
+        &self,
+        child: &Workspace,
+        repo_id: Uuid,
+        new_target: &str,
+    ) -> Result<(), StackedWorkspaceError> {
+        let pool = &self.db.pool;
+        WorkspaceRepo::update_target_branch(pool, child.id, repo_id, new_target).await?;
+        WorkspaceRepo::set_stacked_base_commit(pool, child.id, repo_id, None).await?;
+        Ok(())
+    }
+
+    /// Rebase a child's branch from `old_base` onto `new_base`. A rebase that
+    /// stops on conflicts is aborted, so the next agent turn never runs in a
+    /// half-rebased worktree.
+    async fn rebase_child(
+        &self,
+        child: &Workspace,
+        repo: &Repo,
+        new_base: &str,
+        old_base: &str,
+    ) -> Result<RebaseOutcome, StackedWorkspaceError> {
+        let Some(container_ref) = &child.container_ref else {
+            return Ok(RebaseOutcome::NoWorktree);
+        };
+        let worktree_path = Path::new(container_ref).join(&repo.name);
+        if !worktree_path.exists() {
+            return Ok(RebaseOutcome::NoWorktree);
+        }
+        // Never rewrite history underneath a running agent
+        if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+            &self.db.pool,
+            child.id,
+        )
+        .await?
+        {
+            info!(
+                "Skipping rebase of stacked workspace {} while it has running processes",
+                child.id
+            );
+            return Ok(RebaseOutcome::Busy);
+        }
+
+        match self.git.rebase_branch(
+            &repo.path,
+            &worktree_path,
+            new_base,
+            old_base,
+            &child.branch,
+        ) {
+            Ok(_) => Ok(RebaseOutcome::Rebased(worktree_path)),
+            // Someone else's rebase, leave it alone
+            Err(e @ GitServiceError::RebaseInProgress) => Ok(RebaseOutcome::Failed(e.to_string())),
+            Err(e) => {
+                warn!(
+                    "Failed to rebase stacked workspace {} onto {}: {}",
+                    child.id, new_base, e
+                );
+                if let Err(abort_err) = self.git.abort_rebase(&worktree_path) {
+                    warn!(
+                        "Failed to abort rebase of stacked workspace {}: {}",
+                        child.id, abort_err
+                    );
+                }
+                Ok(RebaseOutcome::Failed(e.to_string()))
+            }
+        }
+    }
+
+    /// Force-push the rebased branch and point open PRs at the new base
+    async fn retarget_open_prs(
+        &self,
+        child: &Workspace,
+        repo_id: Uuid,
+        worktree_path: &Path,
+        new_base: &str,
+    ) -> Result<(), StackedWorkspaceError> {
+        let open_prs: Vec<_> =
+            Merge::find_by_workspace_and_repo_id(&self.db.pool, child.id, repo_id)
+                .await?
+                .into_iter()
+                .filter_map(|merge| match merge {
+                    Merge::Pr(pr) if matches!(pr.pr_info.status, MergeStatus::Open) => Some(pr),
+                    _ => None,
+                })
+                .collect();
+        if open_prs.is_empty() {
+            return Ok(());
+        }
+
+        self.git
+            .push_to_github(worktree_path, &child.branch, true)?;
+
+        let github_service = GitHubService::new()?;
+        for pr in open_prs {
+            github_service
+                .update_pr_base(&pr.pr_info.url, new_base)
+                .await?;
+        }
+        Ok(())
+    }
+}
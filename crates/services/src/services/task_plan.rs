@@ -0,0 +1,186 @@
+//! Plan-first attempts, for any executor.
+//!
+//! The first turn of a plan-first attempt is told to investigate and propose
+//! a plan without changing files. When it finishes, the plan is stored on the
+//! attempt and waits for approval through the approvals API; it may be edited
+//! in the meantime. Approving starts the implementation turn with the approved
+//! plan, denying with feedback asks the agent for a revised plan.
+
+use db::models::task_plan::TaskPlan;
+use executors::logs::{
+    ActionType, NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch,
+};
+use sqlx::SqlitePool;
+use utils::{
+    approvals::{ApprovalRequest, CreateApprovalRequest},
+    log_msg::LogMsg,
+    msg_store::MsgStore,
+};
+use uuid::Uuid;
+
+use crate::services::approvals::Approvals;
+
+/// Tool name the plan's approval is reported under
+pub const PLAN_APPROVAL_TOOL_NAME: &str = "Plan";
+
+pub fn planning_prompt(task_prompt: &str) -> String {
+    format!(
+        "{task_prompt}\n\n\
+         ---\n\
+         Before making any changes, plan this work. Investigate the codebase as needed, \
+         but do not create, modify or delete any files and do not run commands that change \
+         the repository. Finish by replying with the complete implementation plan in \
+         Markdown: the approach, the files to change and how, and how to verify the result. \
+         The plan will be reviewed, and possibly edited, before you are asked to implement it."
+    )
+}
+
+pub fn revision_prompt(feedback: Option<&str>) -> String {
+    let feedback = feedback
+        .map(str::trim)
+        .filter(|feedback| !feedback.is_empty())
+        .unwrap_or("No reason was given.");
+    format!(
+        "The plan was not approved. Feedback from the reviewer:\n\n{feedback}\n\n\
+         Revise the plan accordingly. Still do not change any files; reply with the complete \
+         revised plan."
+    )
+}
+
+pub fn implementation_prompt(plan: &str) -> String {
+    format!(
+        "The plan below has been approved, possibly with edits by the reviewer. Implement it \
+         now, following it as written.\n\n{plan}"
+    )
+}
+
+/// The plan a planning turn ended with: a presented plan if the agent has a
+/// dedicated plan tool, otherwise its last message
+pub fn extract_plan(msg_store: &MsgStore) -> Option<String> {
+    let entries = msg_store
+        .get_history()
+        .iter()
+        .filter_map(|msg| match msg {
+            LogMsg::JsonPatch(patch) => extract_normalized_entry_from_patch(patch),
+            _ => None,
+        })
+        .map(|(_, entry)| entry)
+        .collect::<Vec<_>>();
+
+    let presented = entries
+        .iter()
+        .rev()
+        .find_map(|entry| match &entry.entry_type {
+            NormalizedEntryType::ToolUse {
+                action_type: ActionType::PlanPresentation { plan },
+                ..
+            } => Some(plan.clone()),
+            _ => None,
+        });
+
+    presented
+        .or_else(|| {
+            entries.iter().rev().find_map(|entry| {
+                matches!(entry.entry_type, NormalizedEntryType::AssistantMessage)
+                    .then(|| entry.content.clone())
+            })
+        })
+        .filter(|plan| !plan.trim().is_empty())
+}
+
+/// Ask for approval of a plan, returning the approval's id
+pub fn request_approval(
+    approvals: &Approvals,
+    plan: &TaskPlan,
+    content: &str,
+    execution_process_id: Uuid,
+) -> String {
+    let request = ApprovalRequest::from_create(
+        CreateApprovalRequest {
+            tool_name: PLAN_APPROVAL_TOOL_NAME.to_string(),
+            tool_input: serde_json::json!({ "plan": content }),
+            tool_call_id: plan.id.to_string(),
+        },
+        execution_process_id,
+    );
+    approvals.create_detached(&request);
+    request.id
+}
+
+/// Make sure a plan waiting for approval can still be answered. Approvals are
+/// held in memory, so they are lost on restart and expire after a while.
+pub async fn ensure_approval_pending(
+    pool: &SqlitePool,
+    approvals: &Approvals,
+    plan: &mut TaskPlan,
+) -> Result<(), sqlx::Error> {
+    let Some(execution_process_id) = plan.execution_process_id else {
+        return Ok(());
+    };
+    if plan
+        .approval_id
+        .as_deref()
+        .is_some_and(|id| approvals.is_pending(id))
+    {
+        return Ok(());
+    }
+
+    let approval_id = request_approval(approvals, plan, &plan.content, execution_process_id);
+    TaskPlan::update_approval_id(pool, plan.id, &approval_id).await?;
+    plan.approval_id = Some(approval_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::logs::{NormalizedEntry, ToolStatus, utils::patch::ConversationPatch};
+
+    use super::*;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_extract_plan() {
+        let store = MsgStore::new();
+        assert_eq!(extract_plan(&store), None);
+
+        store.push_patch(ConversationPatch::add_normalized_entry(
+            0,
+            entry(NormalizedEntryType::AssistantMessage, "Let me look around."),
+        ));
+        store.push_patch(ConversationPatch::add_normalized_entry(
+            1,
+            entry(NormalizedEntryType::AssistantMessage, "## Plan\n1. Do it"),
+        ));
+        assert_eq!(extract_plan(&store).as_deref(), Some("## Plan\n1. Do it"));
+
+        store.push_patch(ConversationPatch::add_normalized_entry(
+            2,
+            entry(
+                NormalizedEntryType::ToolUse {
+                    tool_name: "ExitPlanMode".to_string(),
+                    action_type: ActionType::PlanPresentation {
+                        plan: "Presented plan".to_string(),
+                    },
+                    status: ToolStatus::Created,
+                },
+                "",
+            ),
+        ));
+        store.push_patch(ConversationPatch::add_normalized_entry(
+            3,
+            entry(
+                NormalizedEntryType::AssistantMessage,
+                "Waiting for approval.",
+            ),
+        ));
+        assert_eq!(extract_plan(&store).as_deref(), Some("Presented plan"));
+    }
+}
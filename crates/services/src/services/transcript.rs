@@ -0,0 +1,768 @@
+//! Exports agent conversations as transcripts that can be shared outside the
+//! app: Markdown for PRs and reviews, a standalone HTML page and JSON.
+//!
+//! A transcript covers the coding agent runs of an execution process, session
+//! or workspace, each with the entries its normalizer produced. The JSON form
+//! carries a schema version, bumped whenever a field changes meaning.
+
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    coding_agent_turn::CodingAgentTurn,
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    session::Session,
+    task::Task,
+    workspace::Workspace,
+};
+use executors::{
+    actions::ExecutorActionType,
+    logs::{
+        ActionType, CommandExitStatus, FileChange, NormalizedEntry, NormalizedEntryType,
+        ToolResultValueType, ToolStatus, utils::patch::collect_normalized_entries,
+    },
+};
+use futures::future;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::container::ContainerService;
+
+pub const TRANSCRIPT_SCHEMA_VERSION: u32 = 1;
+
+/// Shown in place of the entries of a run that hasn't finished
+const RUNNING_NOTE: &str = "Still running; its output is not included.";
+
+/// GitHub rejects PR bodies over 65536 characters; leave room for the rest
+const MAX_CONDENSED_LENGTH: usize = 50_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptFormat {
+    #[default]
+    Markdown,
+    Html,
+    Json,
+}
+
+impl TranscriptFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Markdown => "text/markdown; charset=utf-8",
+            Self::Html => "text/html; charset=utf-8",
+            Self::Json => "application/json",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct Transcript {
+    pub schema_version: u32,
+    pub title: String,
+    pub exported_at: DateTime<Utc>,
+    pub turns: Vec<TranscriptTurn>,
+}
+
+/// One coding agent run
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TranscriptTurn {
+    pub execution_process_id: Uuid,
+    pub session_id: Uuid,
+    /// Executor profile, e.g. `CLAUDE_CODE:PLAN`
+    pub executor: Option<String>,
+    pub prompt: Option<String>,
+    pub status: ExecutionProcessStatus,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub entries: Vec<NormalizedEntry>,
+}
+
+impl Transcript {
+    pub async fn for_execution_process<C: ContainerService + Sync + ?Sized>(
+        container: &C,
+        process: ExecutionProcess,
+    ) -> Result<Self, sqlx::Error> {
+        let pool = &container.db().pool;
+        let title = match Session::find_by_id(pool, process.session_id).await? {
+            Some(session) => task_title(pool, session.workspace_id).await?,
+            None => None,
+        };
+        Self::build(container, title, vec![process]).await
+    }
+
+    pub async fn for_session<C: ContainerService + Sync + ?Sized>(
+        container: &C,
+        session: &Session,
+    ) -> Result<Self, sqlx::Error> {
+        let pool = &container.db().pool;
+        let title = task_title(pool, session.workspace_id).await?;
+        let processes = ExecutionProcess::find_by_session_id(pool, session.id, false).await?;
+        Self::build(container, title, processes).await
+    }
+
+    /// All sessions of the workspace, oldest first
+    pub async fn for_workspace<C: ContainerService + Sync + ?Sized>(
+        container: &C,
+        workspace: &Workspace,
+    ) -> Result<Self, sqlx::Error> {
+        let pool = &container.db().pool;
+        let title = Task::find_by_id(pool, workspace.task_id)
+            .await?
+            .map(|task| task.title);
+        let mut processes = Vec::new();
+        for session in Session::find_by_workspace_id(pool, workspace.id)
+            .await?
+            .into_iter()
+            .rev()
+        {
+            processes.extend(ExecutionProcess::find_by_session_id(pool, session.id, false).await?);
+        }
+        Self::build(container, title, processes).await
+    }
+
+    async fn build<C: ContainerService + Sync + ?Sized>(
+        container: &C,
+        title: Option<String>,
+        processes: Vec<ExecutionProcess>,
+    ) -> Result<Self, sqlx::Error> {
+        let pool = &container.db().pool;
+        let mut turns = Vec::new();
+        for process in processes {
+            if process.run_reason != ExecutionProcessRunReason::CodingAgent {
+                continue;
+            }
+            let (executor, action_prompt) = match process.executor_action().map(|a| a.typ()) {
+                Ok(ExecutorActionType::CodingAgentInitialRequest(request)) => (
+                    Some(request.executor_profile_id.to_string()),
+                    Some(request.prompt.clone()),
+                ),
+                Ok(ExecutorActionType::CodingAgentFollowUpRequest(request)) => (
+                    Some(request.executor_profile_id.to_string()),
+                    Some(request.prompt.clone()),
+                ),
+                _ => (None, None),
+            };
+            let prompt = CodingAgentTurn::find_by_execution_process_id(pool, process.id)
+                .await?
+                .and_then(|turn| turn.prompt)
+                .or(action_prompt);
+
+            turns.push(TranscriptTurn {
+                execution_process_id: process.id,
+                session_id: process.session_id,
+                executor,
+                prompt,
+                status: process.status.clone(),
+                started_at: process.started_at,
+                completed_at: process.completed_at,
+                entries: Vec::new(),
+            });
+        }
+
+        // A running process's normalizer is still producing entries, so only
+        // finished runs are read, all at once
+        let entries = future::join_all(turns.iter().map(|turn| async move {
+            if matches!(turn.status, ExecutionProcessStatus::Running) {
+                Vec::new()
+            } else {
+                collect_entries(container, turn.execution_process_id).await
+            }
+        }))
+        .await;
+        for (turn, entries) in turns.iter_mut().zip(entries) {
+            turn.entries = entries;
+        }
+
+        Ok(Self {
+            schema_version: TRANSCRIPT_SCHEMA_VERSION,
+            title: title.unwrap_or_else(|| "Agent transcript".to_string()),
+            exported_at: Utc::now(),
+            turns,
+        })
+    }
+
+    pub fn render(&self, format: TranscriptFormat) -> String {
+        match format {
+            TranscriptFormat::Markdown => self.to_markdown(),
+            TranscriptFormat::Html => self.to_html(),
+            TranscriptFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+        }
+    }
+
+    /// Markdown with tool calls collapsed and file edits shown as diffs
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n", self.title);
+        for (number, turn) in self.turns.iter().enumerate() {
+            let _ = write!(out, "\n## Turn {}", number + 1);
+            if let Some(executor) = &turn.executor {
+                let _ = write!(out, " · {executor}");
+            }
+            let _ = write!(
+                out,
+                "\n\n*{} · started {}*\n",
+                status_label(&turn.status),
+                turn.started_at.format("%Y-%m-%d %H:%M UTC")
+            );
+            if let Some(prompt) = &turn.prompt {
+                let _ = write!(out, "\n**User**\n\n{}\n", quote(prompt));
+            }
+            if matches!(turn.status, ExecutionProcessStatus::Running) {
+                let _ = write!(out, "\n*{RUNNING_NOTE}*\n");
+            }
+            for entry in &turn.entries {
+                if let Some(block) = entry_markdown(entry) {
+                    let _ = write!(out, "\n{block}\n");
+                }
+            }
+        }
+        out
+    }
+
+    /// Prompts, replies and a one-line list of tool calls per turn, folded
+    /// into a single section for a PR body. Past the length limit whole
+    /// messages are dropped, so a cut never lands inside a code block.
+    pub fn to_condensed_markdown(&self) -> String {
+        let mut blocks = Vec::new();
+        for (number, turn) in self.turns.iter().enumerate() {
+            let mut header = format!("\n**Turn {}**", number + 1);
+            if let Some(executor) = &turn.executor {
+                let _ = write!(header, " · {executor}");
+            }
+            header.push('\n');
+            blocks.push(header);
+            if let Some(prompt) = &turn.prompt {
+                blocks.push(format!("\n{}\n", quote(prompt)));
+            }
+            let mut tools = Vec::new();
+            for entry in &turn.entries {
+                match &entry.entry_type {
+                    NormalizedEntryType::UserMessage => {
+                        blocks.push(format!("\n{}\n", quote(&entry.content)));
+                    }
+                    NormalizedEntryType::AssistantMessage => {
+                        blocks.push(format!("\n{}\n", entry.content.trim()));
+                    }
+                    NormalizedEntryType::ToolUse { tool_name, .. } => tools.push(tool_name.clone()),
+                    _ => {}
+                }
+            }
+            if !tools.is_empty() {
+                blocks.push(format!("\n*Tool calls: {}*\n", summarize_tools(&tools)));
+            }
+        }
+
+        let mut body = String::new();
+        for block in blocks {
+            if body.len() + block.len() > MAX_CONDENSED_LENGTH {
+                body.push_str("\n\n*Transcript truncated.*\n");
+                break;
+            }
+            body.push_str(&block);
+        }
+        format!("<details>\n<summary>Agent transcript</summary>\n{body}\n</details>")
+    }
+
+    /// A standalone page; messages are shown as plain text
+    pub fn to_html(&self) -> String {
+        let mut body = String::new();
+        for (number, turn) in self.turns.iter().enumerate() {
+            let mut heading = format!("Turn {}", number + 1);
+            if let Some(executor) = &turn.executor {
+                let _ = write!(heading, " · {executor}");
+            }
+            let _ = write!(
+                body,
+                "<section>\n<h2>{}</h2>\n<p class=\"meta\">{} · started {}</p>\n",
+                escape_html(&heading),
+                status_label(&turn.status),
+                turn.started_at.format("%Y-%m-%d %H:%M UTC")
+            );
+            if let Some(prompt) = &turn.prompt {
+                let _ = writeln!(
+                    body,
+                    "<div class=\"user\"><strong>User</strong><p>{}</p></div>",
+                    escape_html(prompt)
+                );
+            }
+            if matches!(turn.status, ExecutionProcessStatus::Running) {
+                let _ = writeln!(body, "<p class=\"system\">{RUNNING_NOTE}</p>");
+            }
+            for entry in &turn.entries {
+                if let Some(block) = entry_html(entry) {
+                    body.push_str(&block);
+                    body.push('\n');
+                }
+            }
+            body.push_str("</section>\n");
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n{body}</body>\n</html>\n",
+            title = escape_html(&self.title),
+        )
+    }
+}
+
+async fn task_title(pool: &SqlitePool, workspace_id: Uuid) -> Result<Option<String>, sqlx::Error> {
+    let Some(workspace) = Workspace::find_by_id(pool, workspace_id).await? else {
+        return Ok(None);
+    };
+    Ok(Task::find_by_id(pool, workspace.task_id)
+        .await?
+        .map(|task| task.title))
+}
+
+/// Current state of each entry of a run, in conversation order
+async fn collect_entries<C: ContainerService + Sync + ?Sized>(
+    container: &C,
+    execution_process_id: Uuid,
+) -> Vec<NormalizedEntry> {
+    let Some(stream) = container
+        .stream_normalized_logs(&execution_process_id)
+        .await
+    else {
+        return Vec::new();
+    };
+
+    collect_normalized_entries(stream)
+        .await
+        .into_iter()
+        .filter(|entry| {
+            !matches!(
+                entry.entry_type,
+                NormalizedEntryType::Loading | NormalizedEntryType::NextAction { .. }
+            )
+        })
+        .collect()
+}
+
+fn status_label(status: &ExecutionProcessStatus) -> &'static str {
+    match status {
+        ExecutionProcessStatus::Running => "Running",
+        ExecutionProcessStatus::Completed => "Completed",
+        ExecutionProcessStatus::Failed => "Failed",
+        ExecutionProcessStatus::Killed => "Stopped",
+    }
+}
+
+fn tool_status_suffix(status: &ToolStatus) -> &'static str {
+    match status {
+        ToolStatus::Created | ToolStatus::Success => "",
+        ToolStatus::Failed => " (failed)",
+        ToolStatus::Denied { .. } => " (denied)",
+        ToolStatus::PendingApproval { .. } => " (awaiting approval)",
+        ToolStatus::TimedOut => " (timed out)",
+    }
+}
+
+/// "Read ×3, Edit, Bash ×2", keeping the order tools were first used in
+fn summarize_tools(tools: &[String]) -> String {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for tool in tools {
+        match counts.iter_mut().find(|(name, _)| name == tool) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((tool, 1)),
+        }
+    }
+    counts
+        .into_iter()
+        .map(|(name, count)| match count {
+            1 => name.to_string(),
+            n => format!("{name} ×{n}"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn quote(text: &str) -> String {
+    text.trim()
+        .lines()
+        .map(|line| format!("> {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A fenced code block whose fence can't be closed by the content
+fn fenced(lang: &str, content: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in content.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{fence}{lang}\n{}\n{fence}", content.trim_end_matches('\n'))
+}
+
+fn first_line(text: &str) -> &str {
+    text.trim().lines().next().unwrap_or_default()
+}
+
+fn entry_markdown(entry: &NormalizedEntry) -> Option<String> {
+    let content = entry.content.trim();
+    let block = match &entry.entry_type {
+        NormalizedEntryType::UserMessage => format!("**User**\n\n{}", quote(content)),
+        NormalizedEntryType::UserFeedback { denied_tool } => {
+            format!("**User** denied `{denied_tool}`\n\n{}", quote(content))
+        }
+        NormalizedEntryType::AssistantMessage => format!("**Assistant**\n\n{content}"),
+        NormalizedEntryType::Thinking => {
+            format!("<details>\n<summary>Thinking</summary>\n\n{content}\n\n</details>")
+        }
+        NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            status,
+        } => {
+            let summary = format!(
+                "{}: {}{}",
+                tool_name,
+                first_line(content),
+                tool_status_suffix(status)
+            );
+            let details = action_markdown(action_type, content);
+            format!(
+                "<details>\n<summary>{}</summary>\n\n{}\n\n</details>",
+                escape_html(&summary),
+                details
+            )
+        }
+        NormalizedEntryType::SystemMessage => quote(content),
+        NormalizedEntryType::ErrorMessage { .. } => format!("> **Error:** {content}"),
+        NormalizedEntryType::Loading | NormalizedEntryType::NextAction { .. } => return None,
+    };
+    Some(block)
+}
+
+fn action_markdown(action_type: &ActionType, content: &str) -> String {
+    match action_type {
+        ActionType::FileEdit { path, changes } => changes
+            .iter()
+            .map(|change| match change {
+                FileChange::Write { content } => {
+                    format!("Wrote `{path}`\n\n{}", fenced("", content))
+                }
+                FileChange::Delete => format!("Deleted `{path}`"),
+                FileChange::Rename { new_path } => format!("Renamed `{path}` to `{new_path}`"),
+                FileChange::Edit { unified_diff, .. } => fenced("diff", unified_diff),
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        ActionType::CommandRun { command, result } => {
+            let mut out = fenced("sh", command);
+            if let Some(result) = result {
+                if let Some(output) = result.output.as_deref().filter(|o| !o.trim().is_empty()) {
+                    let _ = write!(out, "\n\n{}", fenced("", output));
+                }
+                match &result.exit_status {
+                    Some(CommandExitStatus::ExitCode { code }) => {
+                        let _ = write!(out, "\n\nExit code {code}");
+                    }
+                    Some(CommandExitStatus::Success { success: false }) => {
+                        out.push_str("\n\nFailed");
+                    }
+                    _ => {}
+                }
+            }
+            out
+        }
+        ActionType::Tool {
+            arguments, result, ..
+        } => {
+            let mut parts = Vec::new();
+            if let Some(arguments) = arguments {
+                parts.push(fenced(
+                    "json",
+                    &serde_json::to_string_pretty(arguments).unwrap_or_default(),
+                ));
+            }
+            if let Some(result) = result {
+                parts.push(match (&result.r#type, &result.value) {
+                    (ToolResultValueType::Markdown, serde_json::Value::String(markdown)) => {
+                        markdown.clone()
+                    }
+                    (_, value) => fenced(
+                        "json",
+                        &serde_json::to_string_pretty(value).unwrap_or_default(),
+                    ),
+                });
+            }
+            if parts.is_empty() {
+                content.to_string()
+            } else {
+                parts.join("\n\n")
+            }
+        }
+        ActionType::PlanPresentation { plan } => plan.clone(),
+        ActionType::TodoManagement { todos, .. } => todos
+            .iter()
+            .map(|todo| {
+                let checked = if todo.status == "completed" { "x" } else { " " };
+                format!("- [{checked}] {}", todo.content)
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        ActionType::FileRead { .. }
+        | ActionType::Search { .. }
+        | ActionType::WebFetch { .. }
+        | ActionType::TaskCreate { .. }
+        | ActionType::Other { .. } => fenced("", content),
+    }
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;line-height:1.5;color:#1f2328}\
+section{border-top:1px solid #d0d7de;margin-top:2rem}\
+.meta{color:#656d76;font-size:.9rem}\
+p{white-space:pre-wrap}\
+.user{border-left:3px solid #0969da;padding-left:.75rem}\
+.error{color:#cf222e}\
+.system{color:#656d76}\
+details{margin:.5rem 0;border:1px solid #d0d7de;border-radius:6px;padding:.25rem .75rem}\
+summary{cursor:pointer;font-family:ui-monospace,monospace;font-size:.9rem}\
+pre{background:#f6f8fa;padding:.75rem;overflow-x:auto;border-radius:6px}\
+.add{color:#116329}.del{color:#82071e}";
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn pre(content: &str) -> String {
+    format!("<pre><code>{}</code></pre>", escape_html(content))
+}
+
+/// Diff lines coloured by whether they add or remove
+fn diff_pre(diff: &str) -> String {
+    let lines = diff
+        .lines()
+        .map(|line| {
+            let class = match line.chars().next() {
+                Some('+') if !line.starts_with("+++") => Some("add"),
+                Some('-') if !line.starts_with("---") => Some("del"),
+                _ => None,
+            };
+            match class {
+                Some(class) => format!("<span class=\"{class}\">{}</span>", escape_html(line)),
+                None => escape_html(line),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("<pre><code>{lines}</code></pre>")
+}
+
+fn entry_html(entry: &NormalizedEntry) -> Option<String> {
+    let content = entry.content.trim();
+    let block = match &entry.entry_type {
+        NormalizedEntryType::UserMessage => format!(
+            "<div class=\"user\"><strong>User</strong><p>{}</p></div>",
+            escape_html(content)
+        ),
+        NormalizedEntryType::UserFeedback { denied_tool } => format!(
+            "<div class=\"user\"><strong>User</strong> denied <code>{}</code><p>{}</p></div>",
+            escape_html(denied_tool),
+            escape_html(content)
+        ),
+        NormalizedEntryType::AssistantMessage => format!(
+            "<div class=\"assistant\"><strong>Assistant</strong><p>{}</p></div>",
+            escape_html(content)
+        ),
+        NormalizedEntryType::Thinking => format!(
+            "<details><summary>Thinking</summary><p>{}</p></details>",
+            escape_html(content)
+        ),
+        NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            status,
+        } => {
+            let summary = format!(
+                "{}: {}{}",
+                tool_name,
+                first_line(content),
+                tool_status_suffix(status)
+            );
+            format!(
+                "<details><summary>{}</summary>{}</details>",
+                escape_html(&summary),
+                action_html(action_type, content)
+            )
+        }
+        NormalizedEntryType::SystemMessage => {
+            format!("<p class=\"system\">{}</p>", escape_html(content))
+        }
+        NormalizedEntryType::ErrorMessage { .. } => {
+            format!("<p class=\"error\">{}</p>", escape_html(content))
+        }
+        NormalizedEntryType::Loading | NormalizedEntryType::NextAction { .. } => return None,
+    };
+    Some(block)
+}
+
+fn action_html(action_type: &ActionType, content: &str) -> String {
+    match action_type {
+        ActionType::FileEdit { path, changes } => changes
+            .iter()
+            .map(|change| match change {
+                FileChange::Write { content } => {
+                    format!(
+                        "<p>Wrote <code>{}</code></p>{}",
+                        escape_html(path),
+                        pre(content)
+                    )
+                }
+                FileChange::Delete => format!("<p>Deleted <code>{}</code></p>", escape_html(path)),
+                FileChange::Rename { new_path } => format!(
+                    "<p>Renamed <code>{}</code> to <code>{}</code></p>",
+                    escape_html(path),
+                    escape_html(new_path)
+                ),
+                FileChange::Edit { unified_diff, .. } => diff_pre(unified_diff),
+            })
+            .collect(),
+        ActionType::CommandRun { command, result } => {
+            let mut out = pre(command);
+            if let Some(output) = result
+                .as_ref()
+                .and_then(|result| result.output.as_deref())
+                .filter(|output| !output.trim().is_empty())
+            {
+                out.push_str(&pre(output));
+            }
+            out
+        }
+        ActionType::Tool {
+            arguments, result, ..
+        } => {
+            let mut out = String::new();
+            if let Some(arguments) = arguments {
+                out.push_str(&pre(
+                    &serde_json::to_string_pretty(arguments).unwrap_or_default()
+                ));
+            }
+            if let Some(result) = result {
+                out.push_str(&match &result.value {
+                    serde_json::Value::String(text) => pre(text),
+                    value => pre(&serde_json::to_string_pretty(value).unwrap_or_default()),
+                });
+            }
+            if out.is_empty() { pre(content) } else { out }
+        }
+        ActionType::PlanPresentation { plan } => format!("<p>{}</p>", escape_html(plan)),
+        _ => pre(content),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    fn transcript() -> Transcript {
+        Transcript {
+            schema_version: TRANSCRIPT_SCHEMA_VERSION,
+            title: "Fix <login> bug".to_string(),
+            exported_at: Utc::now(),
+            turns: vec![TranscriptTurn {
+                execution_process_id: Uuid::new_v4(),
+                session_id: Uuid::new_v4(),
+                executor: Some("CLAUDE_CODE".to_string()),
+                prompt: Some("Fix the login bug".to_string()),
+                status: ExecutionProcessStatus::Completed,
+                started_at: Utc::now(),
+                completed_at: None,
+                entries: vec![
+                    entry(
+                        NormalizedEntryType::ToolUse {
+                            tool_name: "Edit".to_string(),
+                            action_type: ActionType::FileEdit {
+                                path: "src/login.rs".to_string(),
+                                changes: vec![FileChange::Edit {
+                                    unified_diff: "--- a/src/login.rs\n+++ b/src/login.rs\n@@ -1 +1 @@\n-let ok = false;\n+let ok = true;\n".to_string(),
+                                    has_line_numbers: true,
+                                }],
+                            },
+                            status: ToolStatus::Success,
+                        },
+                        "src/login.rs",
+                    ),
+                    entry(NormalizedEntryType::AssistantMessage, "Fixed the check."),
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_markdown_collapses_tool_calls_with_diffs() {
+        let markdown = transcript().to_markdown();
+        assert!(markdown.starts_with("# Fix <login> bug\n"));
+        assert!(markdown.contains("> Fix the login bug"));
+        assert!(markdown.contains("<summary>Edit: src/login.rs</summary>"));
+        assert!(markdown.contains("```diff\n--- a/src/login.rs"));
+        assert!(markdown.contains("**Assistant**\n\nFixed the check."));
+
+        let condensed = transcript().to_condensed_markdown();
+        assert!(condensed.contains("*Tool calls: Edit*"));
+        assert!(!condensed.contains("```diff"));
+    }
+
+    #[test]
+    fn test_html_escapes_content() {
+        let html = transcript().to_html();
+        assert!(html.contains("<title>Fix &lt;login&gt; bug</title>"));
+        assert!(html.contains("<span class=\"add\">+let ok = true;</span>"));
+    }
+
+    #[test]
+    fn test_running_turn_is_flagged() {
+        let mut transcript = transcript();
+        transcript.turns[0].status = ExecutionProcessStatus::Running;
+        transcript.turns[0].entries.clear();
+        assert!(transcript.to_markdown().contains(RUNNING_NOTE));
+        assert!(transcript.to_html().contains(RUNNING_NOTE));
+    }
+
+    #[test]
+    fn test_condensed_markdown_truncates_between_messages() {
+        let code = format!("```rust\n{}```", "let x = 1;\n".repeat(1_000));
+        let mut transcript = transcript();
+        transcript.turns[0].entries = (0..10)
+            .map(|_| entry(NormalizedEntryType::AssistantMessage, &code))
+            .collect();
+
+        let condensed = transcript.to_condensed_markdown();
+        assert!(condensed.contains("*Transcript truncated.*"));
+        assert!(condensed.len() < MAX_CONDENSED_LENGTH + 200);
+        // Every code block that was kept is closed
+        assert_eq!(condensed.matches("```").count() % 2, 0);
+        assert!(condensed.ends_with("*Transcript truncated.*\n\n</details>"));
+    }
+}
@@ -0,0 +1,325 @@
+//! Disk usage accounting and cleanup policies for workspace worktrees.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Duration, Utc};
+use db::models::{
+    task::TaskStatus,
+    workspace_storage::{WorkspaceDiskUsage, WorkspaceStorage, WorkspaceStorageEntry},
+};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::services::config::WorkspaceCleanupConfig;
+
+/// Directories holding installed dependencies
+const DEPENDENCY_DIRS: &[&str] = &["node_modules", ".venv", "venv", "vendor", ".pnpm-store"];
+
+/// Directories holding build output
+const BUILD_DIRS: &[&str] = &["target", "dist", "build", ".next", "out", ".turbo"];
+
+/// Why [`plan_cleanup`] selected a workspace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanupReason {
+    TaskDone,
+    Inactive,
+    ExceedsPerTaskLimit,
+    DiskQuota,
+}
+
+#[derive(Clone, Copy)]
+enum DirKind {
+    Other,
+    Dependency,
+    Build,
+}
+
+/// Measure the size of everything under `dir`. Symlinks are not followed, so
+/// shared caches linked into a worktree aren't counted against it.
+///
+/// This walks the whole tree and should be run on a blocking thread.
+pub fn measure_workspace(dir: &Path) -> WorkspaceDiskUsage {
+    let mut usage = WorkspaceDiskUsage::default();
+    measure_dir(dir, DirKind::Other, &mut usage);
+    usage
+}
+
+fn measure_dir(dir: &Path, kind: DirKind, usage: &mut WorkspaceDiskUsage) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            // The outermost match decides the kind, e.g. `node_modules/**/dist`
+            // is a dependency
+            let child_kind = match kind {
+                DirKind::Other => {
+                    let name = entry.file_name();
+                    let name = name.to_string_lossy();
+                    if DEPENDENCY_DIRS.contains(&name.as_ref()) {
+                        DirKind::Dependency
+                    } else if BUILD_DIRS.contains(&name.as_ref()) {
+                        DirKind::Build
+                    } else {
+                        DirKind::Other
+                    }
+                }
+                kind => kind,
+            };
+            measure_dir(&entry.path(), child_kind, usage);
+        } else if file_type.is_file() {
+            let len = entry.metadata().map(|m| m.len() as i64).unwrap_or(0);
+            usage.total_bytes += len;
+            match kind {
+                DirKind::Dependency => usage.dependency_bytes += len,
+                DirKind::Build => usage.build_bytes += len,
+                DirKind::Other => {}
+            }
+        }
+    }
+}
+
+/// Measurements older than this are refreshed even without new activity, to
+/// pick up changes made outside of execution processes
+const MEASUREMENT_MAX_AGE_HOURS: i64 = 24;
+
+/// Whether a workspace's recorded usage may be out of date: it was never
+/// measured, has been active since, or the measurement is older than
+/// [`MEASUREMENT_MAX_AGE_HOURS`]
+pub fn needs_measuring(entry: &WorkspaceStorageEntry, now: DateTime<Utc>) -> bool {
+    match entry.measured_at {
+        None => true,
+        Some(measured_at) => {
+            measured_at < entry.last_activity_at
+                || now - measured_at > Duration::hours(MEASUREMENT_MAX_AGE_HOURS)
+        }
+    }
+}
+
+/// Measure every workspace that still has worktrees on disk, record the
+/// results and return the refreshed entries
+pub async fn refresh_disk_usage(
+    pool: &SqlitePool,
+) -> Result<Vec<WorkspaceStorageEntry>, sqlx::Error> {
+    refresh_entries(pool, |_| true).await
+}
+
+/// Like [`refresh_disk_usage`], but only walks workspaces whose recorded usage
+/// may be out of date. Used by the periodic cleanup so unchanged worktrees
+/// aren't walked on every run.
+pub async fn refresh_stale_disk_usage(
+    pool: &SqlitePool,
+    now: DateTime<Utc>,
+) -> Result<Vec<WorkspaceStorageEntry>, sqlx::Error> {
+    refresh_entries(pool, |entry| needs_measuring(entry, now)).await
+}
+
+async fn refresh_entries(
+    pool: &SqlitePool,
+    should_measure: impl Fn(&WorkspaceStorageEntry) -> bool,
+) -> Result<Vec<WorkspaceStorageEntry>, sqlx::Error> {
+    let entries = WorkspaceStorage::find_all_entries(pool).await?;
+
+    for entry in entries.iter().filter(|entry| should_measure(entry)) {
+        let Some(container_ref) = &entry.container_ref else {
+            continue;
+        };
+        let dir = PathBuf::from(container_ref);
+        let usage = match tokio::task::spawn_blocking(move || measure_workspace(&dir)).await {
+            Ok(usage) => usage,
+            Err(e) => {
+                tracing::warn!("Failed to measure workspace {}: {}", entry.workspace_id, e);
+                continue;
+            }
+        };
+        WorkspaceStorage::record_usage(pool, entry.workspace_id, &usage).await?;
+    }
+
+    WorkspaceStorage::find_all_entries(pool).await
+}
+
+/// Decide which workspaces to clean up, in the order the rules apply: done
+/// tasks, inactivity, the per-task limit and finally least recently used
+/// eviction until the disk quota is met.
+///
+/// Workspaces without worktrees, pinned workspaces and workspaces with a
+/// running process are never selected, and don't count towards the per-task
+/// limit.
+pub fn plan_cleanup(
+    entries: &[WorkspaceStorageEntry],
+    config: &WorkspaceCleanupConfig,
+    now: DateTime<Utc>,
+) -> Vec<(Uuid, CleanupReason)> {
+    let mut candidates: Vec<&WorkspaceStorageEntry> = entries
+        .iter()
+        .filter(|e| e.container_ref.is_some() && !e.pinned && !e.has_running_process)
+        .collect();
+    // Least recently used first
+    candidates.sort_by_key(|e| e.last_activity_at);
+
+    let mut planned: Vec<(Uuid, CleanupReason)> = Vec::new();
+    let select = |planned: &mut Vec<(Uuid, CleanupReason)>, id: Uuid, reason: CleanupReason| {
+        if !planned.iter().any(|(planned_id, _)| *planned_id == id) {
+            planned.push((id, reason));
+        }
+    };
+
+    if config.cleanup_on_done {
+        for entry in &candidates {
+            if entry.task_status == TaskStatus::Done {
+                select(&mut planned, entry.workspace_id, CleanupReason::TaskDone);
+            }
+        }
+    }
+
+    if let Some(hours) = config.inactive_hours {
+        let cutoff = now - Duration::hours(i64::from(hours));
+        for entry in &candidates {
+            if entry.last_activity_at < cutoff {
+                select(&mut planned, entry.workspace_id, CleanupReason::Inactive);
+            }
+        }
+    }
+
+    if let Some(keep) = config.keep_per_task {
+        let mut kept_per_task: HashMap<Uuid, u32> = HashMap::new();
+        for entry in candidates.iter().rev() {
+            let kept = kept_per_task.entry(entry.task_id).or_default();
+            if *kept < keep {
+                *kept += 1;
+            } else {
+                select(
+                    &mut planned,
+                    entry.workspace_id,
+                    CleanupReason::ExceedsPerTaskLimit,
+                );
+            }
+        }
+    }
+
+    if let Some(quota_mb) = config.disk_quota_mb {
+        let quota_bytes = i64::from(quota_mb) * 1024 * 1024;
+        let is_planned = |planned: &[(Uuid, CleanupReason)], id: Uuid| {
+            planned.iter().any(|(planned_id, _)| *planned_id == id)
+        };
+        let mut remaining: i64 = entries
+            .iter()
+            .filter(|e| e.container_ref.is_some() && !is_planned(&planned, e.workspace_id))
+            .filter_map(|e| e.total_bytes)
+            .sum();
+
+        for entry in &candidates {
+            if remaining <= quota_bytes {
+                break;
+            }
+            if is_planned(&planned, entry.workspace_id) {
+                continue;
+            }
+            remaining -= entry.total_bytes.unwrap_or(0);
+            select(&mut planned, entry.workspace_id, CleanupReason::DiskQuota);
+        }
+    }
+
+    planned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(task_id: Uuid, hours_ago: i64, mb: i64, now: DateTime<Utc>) -> WorkspaceStorageEntry {
+        WorkspaceStorageEntry {
+            workspace_id: Uuid::new_v4(),
+            task_id,
+            project_id: Uuid::nil(),
+            task_title: "task".to_string(),
+            task_status: TaskStatus::InProgress,
+            branch: "vk/branch".to_string(),
+            container_ref: Some("/tmp/ws".to_string()),
+            pinned: false,
+            total_bytes: Some(mb * 1024 * 1024),
+            dependency_bytes: None,
+            build_bytes: None,
+            measured_at: Some(now),
+            last_activity_at: now - Duration::hours(hours_ago),
+            has_running_process: false,
+            created_at: now - Duration::hours(hours_ago),
+        }
+    }
+
+    #[test]
+    fn test_plan_cleanup() {
+        let now = Utc::now();
+        let task = Uuid::new_v4();
+
+        let mut done = entry(Uuid::new_v4(), 1, 10, now);
+        done.task_status = TaskStatus::Done;
+        let mut pinned_old = entry(task, 200, 500, now);
+        pinned_old.pinned = true;
+        let old = entry(task, 100, 10, now);
+        let older_sibling = entry(task, 20, 10, now);
+        let recent = entry(task, 2, 10, now);
+        let large = entry(Uuid::new_v4(), 10, 300, now);
+        let entries = vec![
+            done.clone(),
+            pinned_old.clone(),
+            old.clone(),
+            older_sibling.clone(),
+            recent.clone(),
+            large.clone(),
+        ];
+
+        let config = WorkspaceCleanupConfig {
+            inactive_hours: Some(72),
+            cleanup_on_done: true,
+            keep_per_task: Some(1),
+            disk_quota_mb: Some(600),
+            interval_minutes: 30,
+        };
+        let planned = plan_cleanup(&entries, &config, now);
+
+        assert_eq!(
+            planned,
+            vec![
+                (done.workspace_id, CleanupReason::TaskDone),
+                (old.workspace_id, CleanupReason::Inactive),
+                (
+                    older_sibling.workspace_id,
+                    CleanupReason::ExceedsPerTaskLimit
+                ),
+                // 500 (pinned) + 10 (recent) + 300 (large) is over quota
+                (large.workspace_id, CleanupReason::DiskQuota),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_needs_measuring() {
+        let now = Utc::now();
+
+        let mut unmeasured = entry(Uuid::new_v4(), 1, 10, now);
+        unmeasured.measured_at = None;
+        assert!(needs_measuring(&unmeasured, now));
+
+        // Measured after its last activity
+        let fresh = entry(Uuid::new_v4(), 1, 10, now);
+        assert!(!needs_measuring(&fresh, now));
+
+        let mut active_since = entry(Uuid::new_v4(), 1, 10, now);
+        active_since.measured_at = Some(now - Duration::hours(2));
+        assert!(needs_measuring(&active_since, now));
+
+        let mut expired = entry(Uuid::new_v4(), 100, 10, now);
+        expired.measured_at = Some(now - Duration::hours(MEASUREMENT_MAX_AGE_HOURS + 1));
+        assert!(needs_measuring(&expired, now));
+    }
+}
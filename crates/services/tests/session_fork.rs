@@ -0,0 +1,223 @@
+//! Integration tests for forking a session from a coding agent turn:
+//! - a fork starts each repo from where the turn left it, falling back to
+//!   the commit it started from and then to the previous turn's
+//! - a forked session resumes the agent session of the turn it was forked from
+//!   until it has turns of its own
+
+use std::path::Path;
+
+use db::{
+    DBService,
+    models::{
+        coding_agent_turn::{CodingAgentTurn, CreateCodingAgentTurn},
+        execution_process::{CreateExecutionProcess, ExecutionProcess, ExecutionProcessRunReason},
+        execution_process_repo_state::CreateExecutionProcessRepoState,
+        project::{CreateProject, Project},
+        repo::Repo,
+        session::{CreateSession, Session},
+        task::{CreateTask, Task},
+        workspace::{CreateWorkspace, Workspace},
+    },
+};
+use executors::actions::{
+    ExecutorAction, ExecutorActionType,
+    script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+};
+use sqlx::sqlite::SqlitePoolOptions;
+use uuid::Uuid;
+
+async fn setup_db() -> DBService {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+    DBService { pool }
+}
+
+async fn create_workspace(db: &DBService) -> Workspace {
+    let project = Project::create(
+        &db.pool,
+        &CreateProject {
+            name: "project".to_string(),
+            repositories: Vec::new(),
+        },
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+    let task = Task::create(
+        &db.pool,
+        &CreateTask::from_title_description(project.id, "task".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+    Workspace::create(
+        &db.pool,
+        &CreateWorkspace {
+            branch: format!("task-{}", Uuid::new_v4()),
+            agent_working_dir: None,
+            base_workspace_id: None,
+        },
+        Uuid::new_v4(),
+        task.id,
+    )
+    .await
+    .unwrap()
+}
+
+async fn create_session(db: &DBService, workspace: &Workspace) -> Session {
+    Session::create(
+        &db.pool,
+        &CreateSession { executor: None },
+        Uuid::new_v4(),
+        workspace.id,
+    )
+    .await
+    .unwrap()
+}
+
+async fn create_turn(
+    db: &DBService,
+    session: &Session,
+    repo_states: &[CreateExecutionProcessRepoState],
+    agent_session_id: Option<&str>,
+) -> ExecutionProcess {
+    let action = ExecutorAction::new(
+        ExecutorActionType::ScriptRequest(ScriptRequest {
+            script: "true".to_string(),
+            language: ScriptRequestLanguage::Bash,
+            context: ScriptContext::SetupScript,
+            working_dir: None,
+        }),
+        None,
+    );
+    let process = ExecutionProcess::create(
+        &db.pool,
+        &CreateExecutionProcess {
+            session_id: session.id,
+            executor_action: action,
+            run_reason: ExecutionProcessRunReason::CodingAgent,
+        },
+        Uuid::new_v4(),
+        repo_states,
+    )
+    .await
+    .unwrap();
+    CodingAgentTurn::create(
+        &db.pool,
+        &CreateCodingAgentTurn {
+            execution_process_id: process.id,
+            prompt: None,
+        },
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+    if let Some(agent_session_id) = agent_session_id {
+        CodingAgentTurn::update_agent_session_id(&db.pool, process.id, agent_session_id)
+            .await
+            .unwrap();
+    }
+    process
+}
+
+fn repo_state(
+    repo: &Repo,
+    before_head_commit: Option<&str>,
+    after_head_commit: Option<&str>,
+) -> CreateExecutionProcessRepoState {
+    CreateExecutionProcessRepoState {
+        repo_id: repo.id,
+        before_head_commit: before_head_commit.map(str::to_string),
+        after_head_commit: after_head_commit.map(str::to_string),
+        merge_commit: None,
+        had_conflicts_before: false,
+    }
+}
+
+#[tokio::test]
+async fn fork_commit_prefers_where_the_turn_left_the_repo() {
+    let db = setup_db().await;
+    let workspace = create_workspace(&db).await;
+    let session = create_session(&db, &workspace).await;
+    let repo = Repo::find_or_create(&db.pool, Path::new("/tmp/fork-repo"), "repo")
+        .await
+        .unwrap();
+
+    let first = create_turn(
+        &db,
+        &session,
+        &[repo_state(&repo, Some("c1"), Some("c2"))],
+        None,
+    )
+    .await;
+    // Nothing recorded for the repo: continue from the previous turn
+    let second = create_turn(&db, &session, &[], None).await;
+    // Interrupted before its end state was captured
+    let third = create_turn(&db, &session, &[repo_state(&repo, Some("c3"), None)], None).await;
+
+    assert_eq!(
+        first.fork_commit(&db.pool, repo.id).await.unwrap(),
+        Some("c2".to_string())
+    );
+    assert_eq!(
+        second.fork_commit(&db.pool, repo.id).await.unwrap(),
+        Some("c2".to_string())
+    );
+    assert_eq!(
+        third.fork_commit(&db.pool, repo.id).await.unwrap(),
+        Some("c3".to_string())
+    );
+    assert_eq!(
+        first.fork_commit(&db.pool, Uuid::new_v4()).await.unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn forked_session_resumes_the_forked_turn() {
+    let db = setup_db().await;
+    let workspace = create_workspace(&db).await;
+    let session = create_session(&db, &workspace).await;
+    let first = create_turn(&db, &session, &[], Some("agent-1")).await;
+    create_turn(&db, &session, &[], Some("agent-2")).await;
+
+    let forked_workspace = create_workspace(&db).await;
+    let forked = Session::create_fork(
+        &db.pool,
+        &CreateSession { executor: None },
+        Uuid::new_v4(),
+        forked_workspace.id,
+        first.id,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        ExecutionProcess::find_latest_coding_agent_turn_session_id(&db.pool, forked.id)
+            .await
+            .unwrap()
+            .as_deref(),
+        Some("agent-1")
+    );
+
+    // Once the fork has its own turns, those take over
+    create_turn(&db, &forked, &[], Some("agent-3")).await;
+    assert_eq!(
+        ExecutionProcess::find_latest_coding_agent_turn_session_id(&db.pool, forked.id)
+            .await
+            .unwrap()
+            .as_deref(),
+        Some("agent-3")
+    );
+
+    let unrelated = create_session(&db, &forked_workspace).await;
+    assert_eq!(
+        ExecutionProcess::find_latest_coding_agent_turn_session_id(&db.pool, unrelated.id)
+            .await
+            .unwrap(),
+        None
+    );
+}
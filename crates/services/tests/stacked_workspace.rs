@@ -0,0 +1,359 @@
+//! Integration tests for keeping stacked workspaces in sync with their base:
+//! - base branch rewritten -> child keeps only its own commits on the new tip
+//! - base squash-merged -> child retargeted to main and rebased onto the squash
+//! - rebase conflicts -> rebase aborted, child and its target left untouched
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use db::{
+    DBService,
+    models::{
+        project::{CreateProject, Project},
+        repo::Repo,
+        task::{CreateTask, Task},
+        workspace::{CreateWorkspace, Workspace},
+        workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+    },
+};
+use services::services::{
+    git::{GitCli, GitService},
+    stacked_workspace::StackedWorkspaceService,
+};
+use sqlx::sqlite::SqlitePoolOptions;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+const BASE_BRANCH: &str = "base";
+const CHILD_BRANCH: &str = "child";
+
+fn git(path: &Path, args: &[&str]) -> String {
+    GitCli::new().git(path, args).unwrap().trim().to_string()
+}
+
+fn commit_file(path: &Path, rel: &str, content: &str, message: &str) {
+    fs::write(path.join(rel), content).unwrap();
+    git(path, &["add", rel]);
+    git(path, &["commit", "-m", message]);
+}
+
+struct Fixture {
+    _root: TempDir,
+    db: DBService,
+    repo_path: PathBuf,
+    child_worktree: PathBuf,
+    repo: Repo,
+    base: Workspace,
+    child: Workspace,
+}
+
+impl Fixture {
+    /// `main` <- `base` (adds base.txt) <- `child` (runs `child_change` in its worktree)
+    async fn new(child_change: impl FnOnce(&Path)) -> Self {
+        let root = TempDir::new().unwrap();
+        let repo_path = root.path().join("repo");
+        GitService::new()
+            .initialize_repo_with_main_branch(&repo_path)
+            .unwrap();
+        git(&repo_path, &["config", "user.name", "Test User"]);
+        git(&repo_path, &["config", "user.email", "test@example.com"]);
+
+        git(&repo_path, &["checkout", "-b", BASE_BRANCH]);
+        commit_file(&repo_path, "base.txt", "base\n", "base change");
+        git(&repo_path, &["checkout", "main"]);
+
+        let child_container = root.path().join("child");
+        let child_worktree = child_container.join("repo");
+        git(
+            &repo_path,
+            &[
+                "worktree",
+                "add",
+                "-b",
+                CHILD_BRANCH,
+                child_worktree.to_str().unwrap(),
+                BASE_BRANCH,
+            ],
+        );
+        child_change(&child_worktree);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+        let db = DBService { pool };
+
+        let project = Project::create(
+            &db.pool,
+            &CreateProject {
+                name: "project".to_string(),
+                repositories: Vec::new(),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let repo = Repo::find_or_create(&db.pool, &repo_path, "repo")
+            .await
+            .unwrap();
+
+        let base = create_workspace(&db, project.id, repo.id, BASE_BRANCH, None, "main").await;
+        let child = create_workspace(
+            &db,
+            project.id,
+            repo.id,
+            CHILD_BRANCH,
+            Some(base.id),
+            BASE_BRANCH,
+        )
+        .await;
+        Workspace::update_container_ref(&db.pool, child.id, child_container.to_str().unwrap())
+            .await
+            .unwrap();
+        let base_commit = git(&repo_path, &["rev-parse", BASE_BRANCH]);
+        WorkspaceRepo::set_stacked_base_commit(&db.pool, child.id, repo.id, Some(&base_commit))
+            .await
+            .unwrap();
+        let child = Workspace::find_by_id(&db.pool, child.id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        Self {
+            _root: root,
+            db,
+            repo_path,
+            child_worktree,
+            repo,
+            base,
+            child,
+        }
+    }
+
+    fn service(&self) -> StackedWorkspaceService {
+        StackedWorkspaceService::new(self.db.clone(), GitService::new())
+    }
+
+    /// Commits on the child branch that aren't on `upstream`
+    fn child_commits_over(&self, upstream: &str) -> Vec<String> {
+        let range = format!("{upstream}..{CHILD_BRANCH}");
+        git(&self.repo_path, &["log", "--format=%s", &range])
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Replace the base branch's commit, as an amend or a rebase would
+    fn rewrite_base(&self, content: &str) {
+        git(&self.repo_path, &["checkout", BASE_BRANCH]);
+        fs::write(self.repo_path.join("base.txt"), content).unwrap();
+        git(&self.repo_path, &["add", "base.txt"]);
+        git(
+            &self.repo_path,
+            &["commit", "--amend", "-m", "base change (amended)"],
+        );
+        git(&self.repo_path, &["checkout", "main"]);
+    }
+
+    /// Land the base branch on main as a single new commit
+    fn squash_merge_base(&self, content: &str) {
+        commit_file(&self.repo_path, "base.txt", content, "squashed base");
+    }
+
+    async fn child_target(&self) -> String {
+        WorkspaceRepo::find_by_workspace_and_repo_id(&self.db.pool, self.child.id, self.repo.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .target_branch
+    }
+
+    async fn recorded_base_commit(&self) -> Option<String> {
+        WorkspaceRepo::find_stacked_base_commit(&self.db.pool, self.child.id, self.repo.id)
+            .await
+            .unwrap()
+    }
+}
+
+async fn create_workspace(
+    db: &DBService,
+    project_id: Uuid,
+    repo_id: Uuid,
+    branch: &str,
+    base_workspace_id: Option<Uuid>,
+    target_branch: &str,
+) -> Workspace {
+    let task = Task::create(
+        &db.pool,
+        &CreateTask::from_title_description(project_id, branch.to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+    let workspace = Workspace::create(
+        &db.pool,
+        &CreateWorkspace {
+            branch: branch.to_string(),
+            agent_working_dir: None,
+            base_workspace_id,
+        },
+        Uuid::new_v4(),
+        task.id,
+    )
+    .await
+    .unwrap();
+    WorkspaceRepo::create_many(
+        &db.pool,
+        workspace.id,
+        &[CreateWorkspaceRepo {
+            repo_id,
+            target_branch: target_branch.to_string(),
+        }],
+    )
+    .await
+    .unwrap();
+    workspace
+}
+
+#[tokio::test]
+async fn sync_after_base_rewrite_replays_only_child_commits() {
+    let fixture = Fixture::new(|worktree| {
+        commit_file(worktree, "child.txt", "child\n", "child change");
+    })
+    .await;
+    fixture.rewrite_base("base, amended\n");
+
+    let conflicts = fixture
+        .service()
+        .sync_children(&fixture.base)
+        .await
+        .unwrap();
+
+    assert!(conflicts.is_empty(), "unexpected conflicts: {conflicts:?}");
+    assert_eq!(
+        fixture.child_commits_over(BASE_BRANCH),
+        vec!["child change"]
+    );
+    let base_tip = git(&fixture.repo_path, &["rev-parse", BASE_BRANCH]);
+    assert_eq!(fixture.recorded_base_commit().await, Some(base_tip));
+    assert_eq!(
+        fs::read_to_string(fixture.child_worktree.join("base.txt")).unwrap(),
+        "base, amended\n"
+    );
+}
+
+#[tokio::test]
+async fn sync_is_a_no_op_when_base_has_not_moved() {
+    let fixture = Fixture::new(|worktree| {
+        commit_file(worktree, "child.txt", "child\n", "child change");
+    })
+    .await;
+    let child_tip = git(&fixture.repo_path, &["rev-parse", CHILD_BRANCH]);
+
+    let conflicts = fixture
+        .service()
+        .sync_children(&fixture.base)
+        .await
+        .unwrap();
+
+    assert!(conflicts.is_empty());
+    assert_eq!(
+        git(&fixture.repo_path, &["rev-parse", CHILD_BRANCH]),
+        child_tip
+    );
+}
+
+#[tokio::test]
+async fn base_merge_retargets_child_onto_squash_commit() {
+    let fixture = Fixture::new(|worktree| {
+        commit_file(worktree, "child.txt", "child\n", "child change");
+    })
+    .await;
+    fixture.squash_merge_base("base\n");
+
+    let conflicts = fixture
+        .service()
+        .handle_base_merged(&fixture.base, fixture.repo.id)
+        .await
+        .unwrap();
+
+    assert!(conflicts.is_empty(), "unexpected conflicts: {conflicts:?}");
+    assert_eq!(fixture.child_target().await, "main");
+    assert_eq!(fixture.child_commits_over("main"), vec!["child change"]);
+    assert_eq!(fixture.recorded_base_commit().await, None);
+    let child = Workspace::find_by_id(&fixture.db.pool, fixture.child.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(child.base_workspace_id, None);
+}
+
+#[tokio::test]
+async fn base_merge_conflict_aborts_rebase_and_keeps_target() {
+    let fixture = Fixture::new(|worktree| {
+        commit_file(worktree, "base.txt", "child edit\n", "child change");
+    })
+    .await;
+    // main lands a different version of the base change, so the child's edit conflicts
+    fixture.squash_merge_base("base, reworked in review\n");
+    let child_tip = git(&fixture.repo_path, &["rev-parse", CHILD_BRANCH]);
+
+    let conflicts = fixture
+        .service()
+        .handle_base_merged(&fixture.base, fixture.repo.id)
+        .await
+        .unwrap();
+
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].workspace_id, fixture.child.id);
+    assert_eq!(conflicts[0].new_base, "main");
+    assert!(
+        !GitCli::new()
+            .is_rebase_in_progress(&fixture.child_worktree)
+            .unwrap()
+    );
+    assert_eq!(
+        git(&fixture.repo_path, &["rev-parse", CHILD_BRANCH]),
+        child_tip
+    );
+    assert_eq!(fixture.child_target().await, BASE_BRANCH);
+    assert!(fixture.recorded_base_commit().await.is_some());
+    let child = Workspace::find_by_id(&fixture.db.pool, fixture.child.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(child.base_workspace_id, Some(fixture.base.id));
+}
+
+#[tokio::test]
+async fn sync_conflict_aborts_rebase() {
+    let fixture = Fixture::new(|worktree| {
+        commit_file(worktree, "base.txt", "child edit\n", "child change");
+    })
+    .await;
+    fixture.rewrite_base("base, amended\n");
+    let child_tip = git(&fixture.repo_path, &["rev-parse", CHILD_BRANCH]);
+    let recorded = fixture.recorded_base_commit().await;
+
+    let conflicts = fixture
+        .service()
+        .sync_children(&fixture.base)
+        .await
+        .unwrap();
+
+    assert_eq!(conflicts.len(), 1);
+    assert!(
+        !GitCli::new()
+            .is_rebase_in_progress(&fixture.child_worktree)
+            .unwrap()
+    );
+    assert_eq!(
+        git(&fixture.repo_path, &["rev-parse", CHILD_BRANCH]),
+        child_tip
+    );
+    assert_eq!(fixture.recorded_base_commit().await, recorded);
+}
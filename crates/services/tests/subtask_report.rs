@@ -0,0 +1,137 @@
+//! Integration tests for de-duplicating sub-task reports to the parent workspace:
+//! - each status is claimed once, however often the sub-task reaches it
+//! - reaching a new status (review -> done) is reported again
+//! - claims are tracked per sub-task workspace
+//! - a released claim, after a failed delivery, is reported again
+
+use db::{
+    DBService,
+    models::{
+        project::{CreateProject, Project},
+        task::{CreateTask, Task, TaskStatus},
+        workspace::{CreateWorkspace, Workspace},
+    },
+};
+use sqlx::sqlite::SqlitePoolOptions;
+use uuid::Uuid;
+
+async fn setup_db() -> DBService {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+    DBService { pool }
+}
+
+async fn create_subtask_workspace(db: &DBService) -> Workspace {
+    let project = Project::create(
+        &db.pool,
+        &CreateProject {
+            name: "project".to_string(),
+            repositories: Vec::new(),
+        },
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+    let task = Task::create(
+        &db.pool,
+        &CreateTask::from_title_description(project.id, "sub-task".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+    Workspace::create(
+        &db.pool,
+        &CreateWorkspace {
+            branch: format!("sub-task-{}", Uuid::new_v4()),
+            agent_working_dir: None,
+            base_workspace_id: None,
+        },
+        Uuid::new_v4(),
+        task.id,
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn each_status_is_reported_once() {
+    let db = setup_db().await;
+    let workspace = create_subtask_workspace(&db).await;
+
+    assert!(
+        Workspace::claim_parent_report(&db.pool, workspace.id, &TaskStatus::InReview)
+            .await
+            .unwrap()
+    );
+    // A follow-up turn finishing puts the task back in review
+    assert!(
+        !Workspace::claim_parent_report(&db.pool, workspace.id, &TaskStatus::InReview)
+            .await
+            .unwrap()
+    );
+
+    // Merging marks it done, from the merge route and again from finalization
+    assert!(
+        Workspace::claim_parent_report(&db.pool, workspace.id, &TaskStatus::Done)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !Workspace::claim_parent_report(&db.pool, workspace.id, &TaskStatus::Done)
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn reports_are_tracked_per_workspace() {
+    let db = setup_db().await;
+    let first = create_subtask_workspace(&db).await;
+    let second = create_subtask_workspace(&db).await;
+
+    assert!(
+        Workspace::claim_parent_report(&db.pool, first.id, &TaskStatus::InReview)
+            .await
+            .unwrap()
+    );
+    assert!(
+        Workspace::claim_parent_report(&db.pool, second.id, &TaskStatus::InReview)
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn released_claim_is_reported_again() {
+    let db = setup_db().await;
+    let workspace = create_subtask_workspace(&db).await;
+
+    assert!(
+        Workspace::claim_parent_report(&db.pool, workspace.id, &TaskStatus::InReview)
+            .await
+            .unwrap()
+    );
+    // Starting the follow-up failed
+    Workspace::release_parent_report(&db.pool, workspace.id, &TaskStatus::InReview)
+        .await
+        .unwrap();
+    assert!(
+        Workspace::claim_parent_report(&db.pool, workspace.id, &TaskStatus::InReview)
+            .await
+            .unwrap()
+    );
+
+    // Releasing a status other than the claimed one leaves the claim alone
+    Workspace::release_parent_report(&db.pool, workspace.id, &TaskStatus::Done)
+        .await
+        .unwrap();
+    assert!(
+        !Workspace::claim_parent_report(&db.pool, workspace.id, &TaskStatus::InReview)
+            .await
+            .unwrap()
+    );
+}
@@ -13,6 +13,8 @@ use uuid::Uuid;
 pub enum MemberRole {
     Admin,
     Member,
+    /// Read-only access to projects and shared tasks
+    Viewer,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
@@ -180,3 +182,18 @@ pub struct UpdateMemberRoleResponse {
     pub user_id: Uuid,
     pub role: MemberRole,
 }
+
+/// `project_ids` is `None` when the member can see every project of the
+/// organization, otherwise the projects they are restricted to.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MemberProjectAccess {
+    pub user_id: Uuid,
+    pub project_ids: Option<Vec<Uuid>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UpdateMemberProjectAccessRequest {
+    pub project_ids: Option<Vec<Uuid>>,
+}
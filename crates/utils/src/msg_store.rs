@@ -1,6 +1,6 @@
 use std::{
     collections::VecDeque,
-    sync::{Arc, RwLock},
+    sync::{Arc, RwLock, atomic::AtomicUsize},
 };
 
 use axum::response::sse::Event;
@@ -8,7 +8,7 @@ use futures::{StreamExt, TryStreamExt, future};
 use tokio::{sync::broadcast, task::JoinHandle};
 use tokio_stream::wrappers::BroadcastStream;
 
-use crate::{log_msg::LogMsg, stream_lines::LinesStreamExt};
+use crate::{log_msg::LogMsg, redact::Redactor, stream_lines::LinesStreamExt};
 
 // 100 MB Limit
 const HISTORY_BYTES: usize = 100000 * 1024;
@@ -27,6 +27,12 @@ struct Inner {
 pub struct MsgStore {
     inner: RwLock<Inner>,
     sender: broadcast::Sender<LogMsg>,
+    /// Applied to JSON patches (normalized entries) before they are stored or
+    /// broadcast. Raw stdout/stderr is redacted upstream, on line boundaries.
+    redactor: Option<Arc<Redactor>>,
+    /// Next normalized-entry index, shared by everything that adds entries to
+    /// this store so they never overwrite each other.
+    entry_index: Arc<AtomicUsize>,
 }
 
 impl Default for MsgStore {
@@ -44,10 +50,27 @@ impl MsgStore {
                 total_bytes: 0,
             }),
             sender,
+            redactor: None,
+            entry_index: Arc::new(AtomicUsize::new(0)),
         }
     }
 
-    pub fn push(&self, msg: LogMsg) {
+    pub fn with_redactor(redactor: Arc<Redactor>) -> Self {
+        Self {
+            redactor: Some(redactor),
+            ..Self::new()
+        }
+    }
+
+    /// Counter handing out normalized-entry indices for this store
+    pub fn entry_index(&self) -> Arc<AtomicUsize> {
+        self.entry_index.clone()
+    }
+
+    pub fn push(&self, mut msg: LogMsg) {
+        if let (Some(redactor), LogMsg::JsonPatch(patch)) = (&self.redactor, &mut msg) {
+            redactor.redact_patch(patch);
+        }
         let _ = self.sender.send(msg.clone()); // live listeners
         let bytes = msg.approx_bytes();
 
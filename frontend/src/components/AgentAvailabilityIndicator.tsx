@@ -62,6 +62,24 @@ export function AgentAvailabilityIndicator({
           </p>
         </>
       )}
+      {availability.status !== 'checking' && availability.version.installed && (
+        <p className="text-xs text-muted-foreground pl-6">
+          {t('settings.agents.availability.installedVersion', {
+            version: availability.version.installed,
+          })}
+        </p>
+      )}
+      {availability.status !== 'checking' && availability.version.warning && (
+        <div className="flex items-center gap-2">
+          <AlertCircle className="h-4 w-4 text-warning" />
+          <span className="text-xs text-warning">
+            {t('settings.agents.availability.untestedVersion', {
+              version: availability.version.installed,
+              range: availability.version.tested_range,
+            })}
+          </span>
+        </div>
+      )}
     </div>
   );
 }
@@ -153,6 +153,9 @@ const InviteMemberDialogImpl = NiceModal.create<InviteMemberDialogProps>(
                   <SelectItem value={MemberRole.MEMBER}>
                     {t('roles.member')}
                   </SelectItem>
+                  <SelectItem value={MemberRole.VIEWER}>
+                    {t('roles.viewer')}
+                  </SelectItem>
                   <SelectItem value={MemberRole.ADMIN}>
                     {t('roles.admin')}
                   </SelectItem>
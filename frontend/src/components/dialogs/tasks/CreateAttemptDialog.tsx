@@ -9,6 +9,8 @@ import {
   DialogTitle,
 } from '@/components/ui/dialog';
 import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Label } from '@/components/ui/label';
 import RepoBranchSelector from '@/components/tasks/RepoBranchSelector';
 import { ExecutorProfileSelector } from '@/components/settings';
 import { useAttemptCreation } from '@/hooks/useAttemptCreation';
@@ -50,6 +52,7 @@ const CreateAttemptDialogImpl = NiceModal.create<CreateAttemptDialogProps>(
 
     const [userSelectedProfile, setUserSelectedProfile] =
       useState<ExecutorProfileId | null>(null);
+    const [planFirst, setPlanFirst] = useState(false);
 
     const { data: attempts = [], isLoading: isLoadingAttempts } =
       useTaskAttemptsWithSessions(taskId, {
@@ -94,6 +97,7 @@ const CreateAttemptDialogImpl = NiceModal.create<CreateAttemptDialogProps>(
     useEffect(() => {
       if (!modal.visible) {
         setUserSelectedProfile(null);
+        setPlanFirst(false);
         resetBranchSelection();
       }
     }, [modal.visible, resetBranchSelection]);
@@ -152,6 +156,7 @@ const CreateAttemptDialogImpl = NiceModal.create<CreateAttemptDialogProps>(
         await createAttempt({
           profile: effectiveProfile,
           repos,
+          planFirst,
         });
 
         modal.hide();
@@ -199,6 +204,26 @@ const CreateAttemptDialogImpl = NiceModal.create<CreateAttemptDialogProps>(
               className="space-y-2"
             />
 
+            <div className="flex items-start space-x-2">
+              <Checkbox
+                id="attempt-plan-first"
+                checked={planFirst}
+                onCheckedChange={setPlanFirst}
+                className="h-5 w-5"
+              />
+              <div className="space-y-1">
+                <Label
+                  htmlFor="attempt-plan-first"
+                  className="cursor-pointer text-sm"
+                >
+                  {t('createAttemptDialog.planFirstLabel')}
+                </Label>
+                <p className="text-xs text-muted-foreground">
+                  {t('createAttemptDialog.planFirstHelp')}
+                </p>
+              </div>
+            </div>
+
             {error && (
               <div className="text-sm text-destructive">
                 {t('createAttemptDialog.error')}
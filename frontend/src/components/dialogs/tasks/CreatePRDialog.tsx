@@ -56,6 +56,7 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
       null
     );
     const [isDraft, setIsDraft] = useState(false);
+    const [includeTranscript, setIncludeTranscript] = useState(false);
     const [autoGenerateDescription, setAutoGenerateDescription] = useState(
       config?.pr_auto_description_enabled ?? false
     );
@@ -140,6 +141,7 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
         target_branch: prBaseBranch || null,
         draft: isDraft,
         auto_generate_description: autoGenerateDescription,
+        include_transcript: includeTranscript,
         repo_id: repoId,
       });
 
@@ -148,6 +150,7 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
         setPrBody('');
         setPrBaseBranch('');
         setIsDraft(false);
+        setIncludeTranscript(false);
         setAutoGenerateDescription(
           config?.pr_auto_description_enabled ?? false
         );
@@ -223,6 +226,7 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
       prBody,
       prTitle,
       isDraft,
+      includeTranscript,
       autoGenerateDescription,
       config?.pr_auto_description_enabled,
       modal,
@@ -237,6 +241,7 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
       setPrBody('');
       setPrBaseBranch('');
       setIsDraft(false);
+      setIncludeTranscript(false);
       setAutoGenerateDescription(config?.pr_auto_description_enabled ?? false);
     }, [modal, config?.pr_auto_description_enabled]);
 
@@ -337,6 +342,20 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
                     {t('createPrDialog.draftLabel')}
                   </Label>
                 </div>
+                <div className="flex items-center space-x-2">
+                  <Checkbox
+                    id="pr-include-transcript"
+                    checked={includeTranscript}
+                    onCheckedChange={setIncludeTranscript}
+                    className="h-5 w-5"
+                  />
+                  <Label
+                    htmlFor="pr-include-transcript"
+                    className="cursor-pointer text-sm"
+                  >
+                    {t('createPrDialog.includeTranscriptLabel')}
+                  </Label>
+                </div>
                 {ghCliHelp?.variant && (
                   <Alert variant="default">
                     <AlertTitle>
@@ -2,6 +2,7 @@ import type { TaskWithAttemptStatus } from 'shared/types';
 import type { WorkspaceWithSession } from '@/types/attempt';
 import VirtualizedList from '@/components/logs/VirtualizedList';
 import { TaskFollowUpSection } from '@/components/tasks/TaskFollowUpSection';
+import { TaskPlanCard } from '@/components/tasks/TaskPlanCard';
 import { EntriesProvider } from '@/contexts/EntriesContext';
 import { RetryUiProvider } from '@/contexts/RetryUiContext';
 import type { ReactNode } from 'react';
@@ -33,7 +34,10 @@ const TaskAttemptPanel = ({
             <VirtualizedList key={attempt.id} attempt={attempt} task={task} />
           ),
           followUp: (
-            <TaskFollowUpSection task={task} session={attempt.session} />
+            <>
+              <TaskPlanCard attemptId={attempt.id} />
+              <TaskFollowUpSection task={task} session={attempt.session} />
+            </>
           ),
         })}
       </RetryUiProvider>
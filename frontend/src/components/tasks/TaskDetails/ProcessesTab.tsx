@@ -231,6 +231,13 @@ function ProcessesTab({ attemptId }: ProcessesTabProps) {
                                 }
                               />
                             ) : null}
+                            {process.executor_version && (
+                              <span className="ml-1 text-xs">
+                                {t('processes.version', {
+                                  version: process.executor_version,
+                                })}
+                              </span>
+                            )}
                           </p>
                         }
                       </div>
@@ -0,0 +1,172 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useQueryClient } from '@tanstack/react-query';
+import { Check, ClipboardList, Loader2, MessageSquare } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { Textarea } from '@/components/ui/textarea';
+import { useTaskPlan } from '@/hooks/useTaskPlan';
+import { approvalsApi, attemptsApi } from '@/lib/api';
+import type { TaskPlan } from 'shared/types';
+
+interface TaskPlanCardProps {
+  attemptId: string;
+}
+
+/**
+ * Shows the plan of a plan-first attempt while it waits for approval.
+ * The plan can be edited before approving it, and the approved text is what
+ * the agent is asked to implement.
+ */
+export function TaskPlanCard({ attemptId }: TaskPlanCardProps) {
+  const { data: plan } = useTaskPlan(attemptId);
+
+  if (
+    !plan ||
+    plan.status !== 'pending_approval' ||
+    !plan.approval_id ||
+    !plan.execution_process_id
+  ) {
+    return null;
+  }
+
+  return (
+    <PendingPlan key={plan.approval_id} attemptId={attemptId} plan={plan} />
+  );
+}
+
+function PendingPlan({
+  attemptId,
+  plan,
+}: {
+  attemptId: string;
+  plan: TaskPlan;
+}) {
+  const { t } = useTranslation('tasks');
+  const queryClient = useQueryClient();
+  const [content, setContent] = useState(plan.content);
+  const [feedback, setFeedback] = useState('');
+  const [isEnteringFeedback, setIsEnteringFeedback] = useState(false);
+  const [isSubmitting, setIsSubmitting] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setContent(plan.content);
+  }, [plan.content]);
+
+  const isEdited = content !== plan.content;
+
+  const run = async (action: () => Promise<unknown>) => {
+    setIsSubmitting(true);
+    setError(null);
+    try {
+      await action();
+      await queryClient.invalidateQueries({
+        queryKey: ['taskPlan', attemptId],
+      });
+    } catch (e: unknown) {
+      setError(e instanceof Error ? e.message : t('taskPlan.error'));
+    } finally {
+      setIsSubmitting(false);
+    }
+  };
+
+  const saveEdits = () =>
+    attemptsApi.updatePlan(attemptId, { content: content.trim() });
+
+  const respond = (approved: boolean) =>
+    run(async () => {
+      if (approved && isEdited) {
+        await saveEdits();
+      }
+      await approvalsApi.respond(plan.approval_id!, {
+        execution_process_id: plan.execution_process_id!,
+        status: approved
+          ? { status: 'approved' }
+          : { status: 'denied', reason: feedback.trim() || undefined },
+      });
+    });
+
+  return (
+    <div className="border-b bg-background px-4 py-3 space-y-2">
+      <div className="flex items-center gap-2 text-sm font-medium">
+        <ClipboardList className="h-4 w-4" />
+        {t('taskPlan.title')}
+      </div>
+      <p className="text-xs text-muted-foreground">
+        {t('taskPlan.description')}
+      </p>
+      <Textarea
+        value={content}
+        onChange={(e) => setContent(e.target.value)}
+        disabled={isSubmitting}
+        className="min-h-[160px] max-h-[40vh] font-mono text-xs"
+      />
+      {isEnteringFeedback && (
+        <Textarea
+          value={feedback}
+          onChange={(e) => setFeedback(e.target.value)}
+          placeholder={t('taskPlan.feedbackPlaceholder')}
+          disabled={isSubmitting}
+          className="min-h-[60px] text-sm"
+          autoFocus
+        />
+      )}
+      {error && <div className="text-xs text-destructive">{error}</div>}
+      <div className="flex items-center justify-end gap-2">
+        {isEnteringFeedback ? (
+          <>
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => setIsEnteringFeedback(false)}
+              disabled={isSubmitting}
+            >
+              {t('common:buttons.cancel')}
+            </Button>
+            <Button
+              size="sm"
+              onClick={() => respond(false)}
+              disabled={isSubmitting}
+            >
+              {t('taskPlan.sendFeedback')}
+            </Button>
+          </>
+        ) : (
+          <>
+            {isEdited && (
+              <Button
+                variant="outline"
+                size="sm"
+                onClick={() => run(saveEdits)}
+                disabled={isSubmitting}
+              >
+                {t('taskPlan.saveEdits')}
+              </Button>
+            )}
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => setIsEnteringFeedback(true)}
+              disabled={isSubmitting}
+            >
+              <MessageSquare className="h-4 w-4 mr-1" />
+              {t('taskPlan.requestChanges')}
+            </Button>
+            <Button
+              size="sm"
+              onClick={() => respond(true)}
+              disabled={isSubmitting || !content.trim()}
+            >
+              {isSubmitting ? (
+                <Loader2 className="h-4 w-4 mr-1 animate-spin" />
+              ) : (
+                <Check className="h-4 w-4 mr-1" />
+              )}
+              {t('taskPlan.approve')}
+            </Button>
+          </>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -1,12 +1,12 @@
 import { useEffect, useState } from 'react';
-import { BaseCodingAgent } from 'shared/types';
+import { AgentVersionInfo, BaseCodingAgent } from 'shared/types';
 import { configApi } from '../lib/api';
 
 export type AgentAvailabilityState =
   | { status: 'checking' }
-  | { status: 'login_detected' }
-  | { status: 'installation_found' }
-  | { status: 'not_found' }
+  | { status: 'login_detected'; version: AgentVersionInfo }
+  | { status: 'installation_found'; version: AgentVersionInfo }
+  | { status: 'not_found'; version: AgentVersionInfo }
   | null;
 
 export function useAgentAvailability(
@@ -24,18 +24,19 @@ export function useAgentAvailability(
     const checkAvailability = async () => {
       setAvailability({ status: 'checking' });
       try {
-        const info = await configApi.checkAgentAvailability(agent);
+        const { availability: info, version } =
+          await configApi.checkAgentAvailability(agent);
 
         // Map backend enum to frontend state
         switch (info.type) {
           case 'LOGIN_DETECTED':
-            setAvailability({ status: 'login_detected' });
+            setAvailability({ status: 'login_detected', version });
             break;
           case 'INSTALLATION_FOUND':
-            setAvailability({ status: 'installation_found' });
+            setAvailability({ status: 'installation_found', version });
             break;
           case 'NOT_FOUND':
-            setAvailability({ status: 'not_found' });
+            setAvailability({ status: 'not_found', version });
             break;
         }
       } catch (error) {
@@ -9,6 +9,7 @@ import type {
 type CreateAttemptArgs = {
   profile: ExecutorProfileId;
   repos: WorkspaceRepoInput[];
+  planFirst?: boolean;
 };
 
 type UseAttemptCreationArgs = {
@@ -23,11 +24,12 @@ export function useAttemptCreation({
   const queryClient = useQueryClient();
 
   const mutation = useMutation({
-    mutationFn: ({ profile, repos }: CreateAttemptArgs) =>
+    mutationFn: ({ profile, repos, planFirst }: CreateAttemptArgs) =>
       attemptsApi.create({
         task_id: taskId,
         executor_profile_id: profile,
         repos,
+        plan_first: planFirst,
       }),
     onSuccess: (newAttempt: Workspace) => {
       queryClient.setQueryData(
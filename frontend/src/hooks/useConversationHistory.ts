@@ -161,7 +161,7 @@ export const useConversationHistory = ({
   const patchWithKey = (
     patch: PatchType,
     executionProcessId: string,
-    index: number | 'user'
+    index: number | 'user' | 'instructions'
   ) => {
     return {
       ...patch,
@@ -209,6 +209,7 @@ export const useConversationHistory = ({
       let lastProcessFailedOrKilled = false;
       let needsSetup = false;
       let setupHelpText: string | undefined;
+      let lastInstructions: string | undefined;
 
       // Create user messages + tool calls for setup/cleanup scripts
       const allEntries = Object.values(executionProcessState)
@@ -229,6 +230,31 @@ export const useConversationHistory = ({
             p.executionProcess.executor_action.typ.type ===
               'CodingAgentFollowUpRequest'
           ) {
+            // Project instructions, shown when the agent is first given them
+            // and whenever they change
+            const instructions =
+              p.executionProcess.executor_action.typ.instructions;
+            if (instructions && instructions !== lastInstructions) {
+              const instructionsPatch: PatchType = {
+                type: 'NORMALIZED_ENTRY',
+                content: {
+                  entry_type: {
+                    type: 'system_message',
+                  },
+                  content: instructions,
+                  timestamp: null,
+                },
+              };
+              entries.push(
+                patchWithKey(
+                  instructionsPatch,
+                  p.executionProcess.id,
+                  'instructions'
+                )
+              );
+            }
+            lastInstructions = instructions ?? lastInstructions;
+
             // New user message
             const userNormalizedEntry: NormalizedEntry = {
               entry_type: {
@@ -0,0 +1,11 @@
+import { useQuery } from '@tanstack/react-query';
+import { attemptsApi } from '@/lib/api';
+
+export function useTaskPlan(attemptId?: string) {
+  return useQuery({
+    queryKey: ['taskPlan', attemptId],
+    queryFn: () => attemptsApi.getPlan(attemptId!),
+    enabled: !!attemptId,
+    refetchInterval: 5000,
+  });
+}
@@ -15,6 +15,7 @@ import {
   DirectoryEntry,
   ExecutionProcess,
   ExecutionProcessRepoState,
+  ForkExecutionProcessResponse,
   GitBranch,
   Project,
   ProjectRepo,
@@ -46,7 +47,7 @@ import {
   RenameBranchRequest,
   RenameBranchResponse,
   CheckEditorAvailabilityResponse,
-  AvailabilityInfo,
+  CheckAgentAvailabilityResponse,
   BaseCodingAgent,
   RunAgentSetupRequest,
   RunAgentSetupResponse,
@@ -66,6 +67,8 @@ import {
   CreateRemoteProjectRequest,
   LinkToExistingRequest,
   UpdateMemberRoleResponse,
+  MemberProjectAccess,
+  UpdateMemberProjectAccessRequest,
   Invitation,
   RemoteProject,
   ListInvitationsResponse,
@@ -88,6 +91,8 @@ import {
   RepoBranchStatus,
   AbortConflictsRequest,
   Session,
+  TaskPlan,
+  UpdateTaskPlan,
   Workspace,
 } from 'shared/types';
 import type { WorkspaceWithSession } from '@/types/attempt';
@@ -587,6 +592,25 @@ export const attemptsApi = {
     return handleApiResponse<RepoWithTargetBranch[]>(response);
   },
 
+  getPlan: async (attemptId: string): Promise<TaskPlan | null> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/plan`);
+    return handleApiResponse<TaskPlan | null>(response);
+  },
+
+  updatePlan: async (
+    attemptId: string,
+    data: UpdateTaskPlan
+  ): Promise<TaskPlan> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/plan`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskPlan>(response);
+  },
+
   merge: async (
     attemptId: string,
     data: MergeTaskAttemptRequest
@@ -780,6 +804,16 @@ export const executionProcessesApi = {
     );
     return handleApiResponse<void>(response);
   },
+
+  fork: async (processId: string): Promise<ForkExecutionProcessResponse> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/fork`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<ForkExecutionProcessResponse>(response);
+  },
 };
 
 // File System APIs
@@ -854,11 +888,11 @@ export const configApi = {
   },
   checkAgentAvailability: async (
     agent: BaseCodingAgent
-  ): Promise<AvailabilityInfo> => {
+  ): Promise<CheckAgentAvailabilityResponse> => {
     const response = await makeRequest(
       `/api/agents/check-availability?executor=${encodeURIComponent(agent)}`
     );
-    return handleApiResponse<AvailabilityInfo>(response);
+    return handleApiResponse<CheckAgentAvailabilityResponse>(response);
   },
 };
 
@@ -1182,6 +1216,32 @@ export const organizationsApi = {
     return handleApiResponse<UpdateMemberRoleResponse>(response);
   },
 
+  getMemberProjectAccess: async (
+    orgId: string,
+    userId: string
+  ): Promise<MemberProjectAccess> => {
+    const response = await makeRequest(
+      `/api/organizations/${orgId}/members/${userId}/projects`
+    );
+    return handleApiResponse<MemberProjectAccess>(response);
+  },
+
+  updateMemberProjectAccess: async (
+    orgId: string,
+    userId: string,
+    data: UpdateMemberProjectAccessRequest
+  ): Promise<MemberProjectAccess> => {
+    const response = await makeRequest(
+      `/api/organizations/${orgId}/members/${userId}/projects`,
+      {
+        method: 'PATCH',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<MemberProjectAccess>(response);
+  },
+
   listInvitations: async (orgId: string): Promise<Invitation[]> => {
     const response = await makeRequest(
       `/api/organizations/${orgId}/invitations`
@@ -38,6 +38,7 @@ interface ProjectFormState {
   dev_script: string;
   dev_script_working_dir: string;
   default_agent_working_dir: string;
+  agent_instructions: string;
 }
 
 interface RepoScriptsFormState {
@@ -45,6 +46,7 @@ interface RepoScriptsFormState {
   parallel_setup_script: boolean;
   cleanup_script: string;
   copy_files: string;
+  agent_instructions: string;
 }
 
 function projectToFormState(project: Project): ProjectFormState {
@@ -53,6 +55,7 @@ function projectToFormState(project: Project): ProjectFormState {
     dev_script: project.dev_script ?? '',
     dev_script_working_dir: project.dev_script_working_dir ?? '',
     default_agent_working_dir: project.default_agent_working_dir ?? '',
+    agent_instructions: project.agent_instructions ?? '',
   };
 }
 
@@ -64,6 +67,7 @@ function projectRepoToScriptsFormState(
     parallel_setup_script: projectRepo?.parallel_setup_script ?? false,
     cleanup_script: projectRepo?.cleanup_script ?? '',
     copy_files: projectRepo?.copy_files ?? '',
+    agent_instructions: projectRepo?.agent_instructions ?? '',
   };
 }
 
@@ -397,6 +401,7 @@ export function ProjectSettings() {
         dev_script_working_dir: draft.dev_script_working_dir.trim() || null,
         default_agent_working_dir:
           draft.default_agent_working_dir.trim() || null,
+        agent_instructions: draft.agent_instructions.trim(),
       };
 
       updateProject.mutate({
@@ -426,6 +431,7 @@ export function ProjectSettings() {
           cleanup_script: scriptsDraft.cleanup_script.trim() || null,
           copy_files: scriptsDraft.copy_files.trim() || null,
           parallel_setup_script: scriptsDraft.parallel_setup_script,
+          agent_instructions: scriptsDraft.agent_instructions.trim(),
         }
       );
       setSelectedProjectRepo(updatedRepo);
@@ -630,6 +636,27 @@ export function ProjectSettings() {
                 </p>
               </div>
 
+              <div className="space-y-2">
+                <Label htmlFor="agent-instructions">
+                  {t('settings.projects.scripts.agentInstructions.label')}
+                </Label>
+                <AutoExpandingTextarea
+                  id="agent-instructions"
+                  value={draft.agent_instructions}
+                  onChange={(e) =>
+                    updateDraft({ agent_instructions: e.target.value })
+                  }
+                  placeholder={t(
+                    'settings.projects.scripts.agentInstructions.placeholder'
+                  )}
+                  maxRows={12}
+                  className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.scripts.agentInstructions.helper')}
+                </p>
+              </div>
+
               {/* Save Button */}
               <div className="flex items-center justify-between pt-4 border-t">
                 {hasUnsavedProjectChanges ? (
@@ -891,6 +918,33 @@ export function ProjectSettings() {
                         </p>
                       </div>
 
+                      <div className="space-y-2">
+                        <Label htmlFor="repo-agent-instructions">
+                          {t(
+                            'settings.projects.scripts.repoAgentInstructions.label'
+                          )}
+                        </Label>
+                        <AutoExpandingTextarea
+                          id="repo-agent-instructions"
+                          value={scriptsDraft.agent_instructions}
+                          onChange={(e) =>
+                            updateScriptsDraft({
+                              agent_instructions: e.target.value,
+                            })
+                          }
+                          placeholder={t(
+                            'settings.projects.scripts.repoAgentInstructions.placeholder'
+                          )}
+                          maxRows={12}
+                          className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring"
+                        />
+                        <p className="text-sm text-muted-foreground">
+                          {t(
+                            'settings.projects.scripts.repoAgentInstructions.helper'
+                          )}
+                        </p>
+                      </div>
+
                       {/* Scripts Save Buttons */}
                       <div className="flex items-center justify-between pt-4 border-t">
                         {hasUnsavedScriptsChanges ? (
@@ -12,11 +12,41 @@ export type SharedTask = { id: string, organization_id: string, project_id: stri
 
 export type UserData = { user_id: string, first_name: string | null, last_name: string | null, username: string | null, };
 
-export type Project = { id: string, name: string, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type SharedTaskComment = { id: string, task_id: string, organization_id: string, project_id: string, author_user_id: string | null, 
+/**
+ * Markdown body
+ */
+body: string, edited_at: string | null, deleted_at: string | null, created_at: string, updated_at: string, };
+
+export type SharedTaskActivity = { id: string, task_id: string, organization_id: string, project_id: string, actor_user_id: string | null, event_type: SharedTaskActivityType, from_value: string | null, to_value: string | null, created_at: string, };
+
+export type SharedTaskActivityType = "created" | "status_changed" | "assignee_changed" | "deleted";
+
+export type CreateSharedTaskCommentRequest = { body: string, };
+
+export type UpdateSharedTaskCommentRequest = { body: string, };
+
+export type ListSharedTaskCommentsResponse = { comments: Array<SharedTaskComment>, };
+
+export type ListSharedTaskActivityResponse = { activity: Array<SharedTaskActivity>, };
+
+export type Project = { id: string, name: string, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, remote_project_id: string | null, sandbox_config: SandboxConfig | null, 
+/**
+ * Instructions given to every coding agent run in the project
+ */
+agent_instructions: string | null, created_at: Date, updated_at: Date, };
 
 export type CreateProject = { name: string, repositories: Array<CreateProjectRepo>, };
 
-export type UpdateProject = { name: string | null, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, };
+export type UpdateProject = { name: string | null, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, 
+/**
+ * Leaves the existing sandbox settings untouched when omitted
+ */
+sandbox_config?: SandboxConfig, 
+/**
+ * Leaves the existing instructions untouched when omitted; empty clears them
+ */
+agent_instructions?: string, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, };
 
@@ -24,11 +54,27 @@ export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
 export type Repo = { id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
 
-export type ProjectRepo = { id: string, project_id: string, repo_id: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, };
+export type ProjectRepo = { id: string, project_id: string, repo_id: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, 
+/**
+ * Instructions for coding agents working in this repository
+ */
+agent_instructions: string | null, };
 
 export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
 
-export type UpdateProjectRepo = { setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean | null, };
+export type UpdateProjectRepo = { setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean | null, 
+/**
+ * Leaves the existing instructions untouched when omitted; empty clears them
+ */
+agent_instructions?: string, };
+
+export type ProjectSecret = { id: string, project_id: string, name: string, created_at: string, updated_at: string, };
+
+export type UpsertProjectSecret = { name: string, value: string, };
+
+export type WorkspaceStorageEntry = { workspace_id: string, task_id: string, project_id: string, task_title: string, task_status: TaskStatus, branch: string, container_ref: string | null, pinned: boolean, total_bytes: number | null, dependency_bytes: number | null, build_bytes: number | null, measured_at: Date | null, last_activity_at: Date, has_running_process: boolean, created_at: Date, };
+
+export type PinWorkspace = { pinned: boolean, };
 
 export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
 
@@ -54,6 +100,23 @@ export type CreateTask = { project_id: string, title: string, description: strin
 
 export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, };
 
+export type TaskPlanStatus = "planning" | "pending_approval" | "approved";
+
+/**
+ * Plan produced by the planning turn of a plan-first attempt
+ */
+export type TaskPlan = { id: string, task_id: string, workspace_id: string, 
+/**
+ * Planning turn that produced the current content
+ */
+execution_process_id: string | null, content: string, status: TaskPlanStatus, 
+/**
+ * Approval the plan is waiting on, answered through the approvals API
+ */
+approval_id: string | null, approved_at: string | null, created_at: string, updated_at: string, };
+
+export type UpdateTaskPlan = { content: string, };
+
 export type DraftFollowUpData = { message: string, variant: string | null, };
 
 export type ScratchPayload = { "type": "DRAFT_TASK", "data": string } | { "type": "DRAFT_FOLLOW_UP", "data": DraftFollowUpData };
@@ -70,11 +133,23 @@ export type Image = { id: string, file_path: string, original_name: string, mime
 
 export type CreateImage = { file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, };
 
-export type Workspace = { id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, };
+export type Workspace = { id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, 
+/**
+ * Workspace whose branch this workspace is stacked on, if any
+ */
+base_workspace_id: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, };
 
-export type Session = { id: string, workspace_id: string, executor: string | null, created_at: string, updated_at: string, };
+export type Session = { id: string, workspace_id: string, executor: string | null, 
+/**
+ * Coding agent turn this session's conversation was forked from
+ */
+forked_from_process_id: string | null, created_at: string, updated_at: string, };
 
-export type ExecutionProcess = { id: string, session_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, 
+export type ExecutionProcess = { id: string, session_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, failure_reason: ExecutionProcessFailureReason | null, 
+/**
+ * Version of the coding agent CLI, detected when the process started
+ */
+executor_version: string | null, 
 /**
  * dropped: true if this process is excluded from the current
  * history view (due to restore/trimming). Hidden from logs/timeline;
@@ -86,6 +161,11 @@ export enum ExecutionProcessStatus { running = "running", completed = "completed
 
 export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
 
+/**
+ * Why a process failed, when the cause is known
+ */
+export enum ExecutionProcessFailureReason { time_limit_exceeded = "time_limit_exceeded", stalled = "stalled", repeated_tool_calls = "repeated_tool_calls", rate_limited = "rate_limited", overloaded = "overloaded", auth_expired = "auth_expired", network = "network", context_length_exceeded = "context_length_exceeded" }
+
 export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
 
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
@@ -126,7 +206,7 @@ export type ProviderProfile = { provider: string, username: string | null, displ
 
 export type StatusResponse = { logged_in: boolean, profile: ProfileResponse | null, degraded: boolean | null, };
 
-export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER" }
+export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER", VIEWER = "VIEWER" }
 
 export enum InvitationStatus { PENDING = "PENDING", ACCEPTED = "ACCEPTED", DECLINED = "DECLINED", EXPIRED = "EXPIRED" }
 
@@ -168,6 +248,10 @@ export type UpdateMemberRoleRequest = { role: MemberRole, };
 
 export type UpdateMemberRoleResponse = { user_id: string, role: MemberRole, };
 
+export type MemberProjectAccess = { user_id: string, project_ids: Array<string> | null, };
+
+export type UpdateMemberProjectAccessRequest = { project_ids: Array<string> | null, };
+
 export type RemoteProject = { id: string, organization_id: string, name: string, metadata: Record<string, unknown>, created_at: string, };
 
 export type ListProjectsResponse = { projects: Array<RemoteProject>, };
@@ -184,6 +268,10 @@ export type InitRepoRequest = { parent_path: string, folder_name: string, };
 
 export type TagSearchParams = { search: string | null, };
 
+export type WorkspaceStorageSort = "size" | "age";
+
+export type WorkspaceStorageQuery = { sort: WorkspaceStorageSort, };
+
 export type TokenResponse = { access_token: string, expires_at: string | null, };
 
 export type UserSystemInfo = { config: Config, analytics_user_id: string, login_status: LoginStatus, environment: Environment, 
@@ -206,10 +294,16 @@ export type CheckEditorAvailabilityResponse = { available: boolean, };
 
 export type CheckAgentAvailabilityQuery = { executor: BaseCodingAgent, };
 
+export type CheckAgentAvailabilityResponse = { availability: AvailabilityInfo, version: AgentVersionInfo, };
+
 export type CurrentUserResponse = { user_id: string, };
 
 export type CreateFollowUpAttempt = { prompt: string, variant: string | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
 
+export type SteerExecutionProcessRequest = { message: string, };
+
+export type ForkExecutionProcessResponse = { workspace: Workspace, session: Session, };
+
 export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: string, };
 
 export type ChangeTargetBranchResponse = { repo_id: string, new_target_branch: string, status: [number, number], };
@@ -230,15 +324,34 @@ export type AssignSharedTaskRequest = { new_assignee_user_id: string | null, };
 
 export type ShareTaskResponse = { shared_task_id: string, };
 
-export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
+/**
+ * Start with a read-only planning turn whose plan must be approved
+ * before the agent implements it
+ */
+plan_first?: boolean, };
 
-export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
+export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, 
+/**
+ * Append a condensed transcript of the agent conversations to the body
+ */
+include_transcript: boolean, };
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
 export type ImageMetadata = { exists: boolean, file_name: string | null, path: string | null, size_bytes: bigint | null, format: string | null, proxy_url: string | null, };
 
-export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
+/**
+ * Stack the new attempt on another attempt's branch. Repos shared with
+ * the base attempt target its branch instead of the requested one.
+ */
+base_workspace_id?: string, 
+/**
+ * Start with a read-only planning turn whose plan must be approved
+ * before the agent implements it
+ */
+plan_first?: boolean, };
 
 export type WorkspaceRepoInput = { repo_id: string, target_branch: string, };
 
@@ -304,7 +417,7 @@ export type DirectoryEntry = { name: string, path: string, is_directory: boolean
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, task_form_auto_start_by_default: boolean, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, task_form_auto_start_by_default: boolean, log_redaction: LogRedactionConfig, workspace_cleanup: WorkspaceCleanupConfig, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -324,6 +437,42 @@ export type UiLanguage = "BROWSER" | "EN" | "JA" | "ES" | "KO" | "ZH_HANS" | "ZH
 
 export type ShowcaseState = { seen_features: Array<string>, };
 
+export type LogRedactionConfig = { 
+/**
+ * Redact AWS keys, GitHub tokens, JWTs and private keys
+ */
+builtin_detectors: boolean, 
+/**
+ * Redact long mixed-case tokens with high entropy; may catch non-secrets
+ */
+high_entropy: boolean, 
+/**
+ * Additional regexes; when a pattern has a capture group only the group is redacted
+ */
+custom_patterns: Array<string>, };
+
+export type WorkspaceCleanupConfig = { 
+/**
+ * Remove worktrees after this many hours without activity; `None` disables
+ */
+inactive_hours: number | null, 
+/**
+ * Remove worktrees as soon as their task is marked done
+ */
+cleanup_on_done: boolean, 
+/**
+ * Keep only the N most recently active workspaces of each task
+ */
+keep_per_task: number | null, 
+/**
+ * Evict least recently used workspaces while total usage exceeds this many MB
+ */
+disk_quota_mb: number | null, 
+/**
+ * Minutes between cleanup runs
+ */
+interval_minutes: number, };
+
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
 export type SharedTaskDetails = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, };
@@ -346,6 +495,19 @@ export type QueueStatus = { "status": "empty" } | { "status": "queued", message:
 
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
+export type TranscriptFormat = "markdown" | "html" | "json";
+
+export type Transcript = { schema_version: number, title: string, exported_at: string, turns: Array<TranscriptTurn>, };
+
+/**
+ * One coding agent run
+ */
+export type TranscriptTurn = { execution_process_id: string, session_id: string, 
+/**
+ * Executor profile, e.g. `CLAUDE_CODE:PLAN`
+ */
+executor: string | null, prompt: string | null, status: ExecutionProcessStatus, started_at: string, completed_at: string | null, entries: Array<NormalizedEntry>, };
+
 export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
 
 export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path: Array<string>, template: JsonValue, preconfigured: JsonValue, is_toml_config: boolean, };
@@ -363,12 +525,26 @@ working_dir: string | null, };
 
 export type ScriptRequestLanguage = "Bash";
 
-export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", DROID = "DROID", CUSTOM = "CUSTOM" }
+export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", DROID = "DROID", CUSTOM = "CUSTOM", REPLAY = "REPLAY", OPENAI_COMPATIBLE = "OPENAI_COMPATIBLE" }
 
-export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } | { "CUSTOM": CustomAgent };
+export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } | { "CUSTOM": CustomAgent } | { "REPLAY": Replay } | { "OPENAI_COMPATIBLE": OpenaiCompatible };
 
 export type AvailabilityInfo = { "type": "LOGIN_DETECTED", last_auth_timestamp: bigint, } | { "type": "INSTALLATION_FOUND" } | { "type": "NOT_FOUND" };
 
+export type AgentVersionInfo = { 
+/**
+ * Version the CLI reported, if it could be run
+ */
+installed: string | null, 
+/**
+ * Versions the executor was tested with, e.g. ">=2.0.0, <2.1.0"
+ */
+tested_range: string | null, 
+/**
+ * Set when the installed version is outside the tested range
+ */
+warning: string | null, };
+
 export type CommandBuilder = { 
 /**
  * Base executable command (e.g., "npx -y @anthropic-ai/claude-code@latest")
@@ -379,6 +555,23 @@ base: string,
  */
 params: Array<string> | null, };
 
+/**
+ * Sandbox settings. Configured per project and overridable per executor
+ * profile; unset fields fall through to the lower-precedence layer.
+ */
+export type SandboxConfig = { enabled?: boolean | null, allow_network?: boolean | null, writable_paths?: Array<string> | null, };
+
+/**
+ * Watchdog limits for an executor profile. Unset limits are not enforced.
+ */
+export type WatchdogConfig = { max_duration_secs?: bigint | null, idle_timeout_secs?: bigint | null, max_repeated_tool_calls?: number | null, };
+
+/**
+ * Retries for runs that fail for transient reasons such as rate limits.
+ * Unset fields use the defaults; set `max_attempts` to 0 to disable.
+ */
+export type RetryPolicy = { max_attempts?: number | null, initial_backoff_secs?: bigint | null, max_backoff_secs?: bigint | null, };
+
 export type ExecutorProfileId = { 
 /**
  * The executor type (e.g., "CLAUDE_CODE", "AMP")
@@ -393,15 +586,15 @@ export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } |
 
 export type ExecutorConfigs = { executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
 
-export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER" }
+export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER", STEER = "STEER" }
 
-export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, process_sandbox?: SandboxConfig | null, watchdog?: WatchdogConfig | null, retry?: RetryPolicy | null, };
 
-export type Gemini = { append_prompt: AppendPrompt, model?: string | null, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Gemini = { append_prompt: AppendPrompt, model?: string | null, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, process_sandbox?: SandboxConfig | null, watchdog?: WatchdogConfig | null, retry?: RetryPolicy | null, };
 
-export type Amp = { append_prompt: AppendPrompt, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Amp = { append_prompt: AppendPrompt, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, process_sandbox?: SandboxConfig | null, watchdog?: WatchdogConfig | null, retry?: RetryPolicy | null, };
 
-export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_apply_patch_tool?: boolean | null, model_provider?: string | null, compact_prompt?: string | null, developer_instructions?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_apply_patch_tool?: boolean | null, model_provider?: string | null, compact_prompt?: string | null, developer_instructions?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, process_sandbox?: SandboxConfig | null, watchdog?: WatchdogConfig | null, retry?: RetryPolicy | null, };
 
 export type SandboxMode = "auto" | "read-only" | "workspace-write" | "danger-full-access";
 
@@ -413,19 +606,19 @@ export type ReasoningSummary = "auto" | "concise" | "detailed" | "none";
 
 export type ReasoningSummaryFormat = "none" | "experimental";
 
-export type CursorAgent = { append_prompt: AppendPrompt, force?: boolean | null, model?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type CursorAgent = { append_prompt: AppendPrompt, force?: boolean | null, model?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, process_sandbox?: SandboxConfig | null, watchdog?: WatchdogConfig | null, retry?: RetryPolicy | null, };
 
-export type Copilot = { append_prompt: AppendPrompt, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Copilot = { append_prompt: AppendPrompt, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, process_sandbox?: SandboxConfig | null, watchdog?: WatchdogConfig | null, retry?: RetryPolicy | null, };
 
 export type Opencode = { append_prompt: AppendPrompt, model?: string | null, mode?: string | null, 
 /**
  * Auto-approve agent actions
  */
-auto_approve: boolean, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+auto_approve: boolean, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, process_sandbox?: SandboxConfig | null, watchdog?: WatchdogConfig | null, retry?: RetryPolicy | null, };
 
-export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, process_sandbox?: SandboxConfig | null, watchdog?: WatchdogConfig | null, retry?: RetryPolicy | null, };
 
-export type Droid = { append_prompt: AppendPrompt, autonomy: Autonomy, model?: string | null, reasoning_effort?: DroidReasoningEffort | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Droid = { append_prompt: AppendPrompt, autonomy: Autonomy, model?: string | null, reasoning_effort?: DroidReasoningEffort | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, process_sandbox?: SandboxConfig | null, watchdog?: WatchdogConfig | null, retry?: RetryPolicy | null, };
 
 export type Autonomy = "normal" | "low" | "medium" | "high" | "skip-permissions-unsafe";
 
@@ -440,7 +633,43 @@ name?: string | null,
  * Which agent type to base this custom agent on
  * Uses CustomAgentBaseAgentSchema for schema generation to provide better form UX
  */
-base_agent?: CodingAgent | null, append_prompt: AppendPrompt, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+base_agent?: CodingAgent | null, append_prompt: AppendPrompt, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, process_sandbox?: SandboxConfig | null, watchdog?: WatchdogConfig | null, retry?: RetryPolicy | null, };
+
+export type Replay = { 
+/**
+ * Path to the fixture replayed for the initial request
+ */
+fixture: string, 
+/**
+ * Fixture replayed for follow-ups; defaults to `fixture`
+ */
+follow_up_fixture?: string | null, 
+/**
+ * Honor recorded `wait` steps instead of replaying as fast as possible
+ */
+honor_timing: boolean, };
+
+export type OpenaiCompatible = { append_prompt: AppendPrompt, 
+/**
+ * Base URL of the API, including the version prefix, e.g. `http://localhost:11434/v1`
+ */
+base_url: string, model?: string | null, 
+/**
+ * Environment variable holding the API key, for endpoints that require one
+ */
+api_key_env?: string | null, 
+/**
+ * Model requests per run before giving up, defaults to 50
+ */
+max_turns?: number | null, 
+/**
+ * Timeout for commands the agent runs, defaults to 300 seconds
+ */
+command_timeout_secs?: number | null, 
+/**
+ * Auto-approve file edits and commands
+ */
+auto_approve: boolean, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, process_sandbox?: SandboxConfig | null, watchdog?: WatchdogConfig | null, retry?: RetryPolicy | null, };
 
 export type AppendPrompt = string | null;
 
@@ -453,7 +682,11 @@ executor_profile_id: ExecutorProfileId,
  * Optional relative path to execute the agent in (relative to container_ref).
  * If None, uses the container_ref directory directly.
  */
-working_dir: string | null, };
+working_dir: string | null, 
+/**
+ * Project instructions given to the agent, filled in when the run starts
+ */
+instructions?: string, };
 
 export type CodingAgentFollowUpRequest = { prompt: string, session_id: string, 
 /**
@@ -464,7 +697,11 @@ executor_profile_id: ExecutorProfileId,
  * Optional relative path to execute the agent in (relative to container_ref).
  * If None, uses the container_ref directory directly.
  */
-working_dir: string | null, };
+working_dir: string | null, 
+/**
+ * Project instructions given to the agent, filled in when the run starts
+ */
+instructions?: string, };
 
 export type CommandExitStatus = { "type": "exit_code", code: number, } | { "type": "success", success: boolean, };
 
@@ -488,7 +725,7 @@ export type ActionType = { "action": "file_read", path: string, } | { "action":
 
 export type TodoItem = { content: string, status: string, priority: string | null, };
 
-export type NormalizedEntryError = { "type": "setup_required" } | { "type": "other" };
+export type NormalizedEntryError = { "type": "setup_required" } | { "type": "sandbox_violation" } | { "type": "other" };
 
 export type ToolResult = { type: ToolResultValueType, 
 /**